@@ -21,6 +21,7 @@
 use bp_parachains::SubmitParachainHeadsInfo;
 use bp_relayers::ExplicitOrAccountParams;
 use bp_runtime::Parachain;
+use pallet_bridge_beefy::{CallSubType as BeefyCallSubType, SubmitCommitmentHelper};
 use pallet_bridge_grandpa::{
 	BridgedBlockNumber, CallSubType as GrandpaCallSubType, SubmitFinalityProofHelper,
 };
@@ -116,6 +117,68 @@ where
 	}
 }
 
+/// Wrapper for the bridge BEEFY pallet that checks calls for obsolete submissions
+/// and also boosts transaction priority if it has submitted by registered relayer.
+/// The boost is computed as
+/// `(BundledCommitmentBlockNumber - 1 - BestCommitmentBlockNumber) * Priority::get()`.
+/// The boost is only applied if submitter has active registration in the relayers
+/// pallet.
+pub struct CheckAndBoostBridgeBeefyTransactions<T, I, Priority, SlashAccount>(
+	PhantomData<(T, I, Priority, SlashAccount)>,
+);
+
+impl<T, I: 'static, Priority: Get<TransactionPriority>, SlashAccount: Get<T::AccountId>>
+	BridgeRuntimeFilterCall<T::AccountId, T::RuntimeCall>
+	for CheckAndBoostBridgeBeefyTransactions<T, I, Priority, SlashAccount>
+where
+	T: pallet_bridge_relayers::Config + pallet_bridge_beefy::Config<I>,
+	T::RuntimeCall: BeefyCallSubType<T, I>,
+{
+	// bridged block number of the commitment, bundled in transaction
+	type ToPostDispatch = Option<pallet_bridge_beefy::BridgedBlockNumber<T, I>>;
+
+	fn validate(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+	) -> (Self::ToPostDispatch, TransactionValidity) {
+		match BeefyCallSubType::<T, I>::check_obsolete_submit_commitment(call) {
+			Ok(Some(our_tx)) => {
+				let to_post_dispatch = Some(our_tx.base.block_number);
+				let total_priority_boost =
+					compute_priority_boost::<T, _, Priority>(who, our_tx.improved_by);
+				(
+					to_post_dispatch,
+					ValidTransactionBuilder::default().priority(total_priority_boost).build(),
+				)
+			},
+			Ok(None) => (None, ValidTransactionBuilder::default().build()),
+			Err(e) => (None, Err(e)),
+		}
+	}
+
+	fn post_dispatch(
+		relayer: &T::AccountId,
+		has_failed: bool,
+		bundled_block_number: Self::ToPostDispatch,
+	) {
+		// we are only interested in associated pallet submissions
+		let Some(bundled_block_number) = bundled_block_number else { return };
+		// we are only interested in failed or unneeded transactions
+		let has_failed =
+			has_failed || !SubmitCommitmentHelper::<T, I>::was_successful(bundled_block_number);
+
+		if !has_failed {
+			return
+		}
+
+		// let's slash registered relayer
+		RelayersPallet::<T>::slash_and_deregister(
+			relayer,
+			ExplicitOrAccountParams::Explicit::<_, ()>(SlashAccount::get()),
+		);
+	}
+}
+
 /// Wrapper for the bridge parachains pallet that checks calls for obsolete submissions
 /// and also boosts transaction priority if it has submitted by registered relayer.
 /// The boost is computed as
@@ -203,6 +266,22 @@ where
 	}
 }
 
+impl<T, I: 'static> BridgeRuntimeFilterCall<T::AccountId, T::RuntimeCall>
+	for pallet_bridge_beefy::Pallet<T, I>
+where
+	T: pallet_bridge_beefy::Config<I>,
+	T::RuntimeCall: BeefyCallSubType<T, I>,
+{
+	type ToPostDispatch = ();
+	fn validate(_who: &T::AccountId, call: &T::RuntimeCall) -> ((), TransactionValidity) {
+		(
+			(),
+			BeefyCallSubType::<T, I>::check_obsolete_submit_commitment(call)
+				.and_then(|_| ValidTransactionBuilder::default().build()),
+		)
+	}
+}
+
 impl<T, I: 'static> BridgeRuntimeFilterCall<T::AccountId, T::RuntimeCall>
 	for pallet_bridge_parachains::Pallet<T, I>
 where
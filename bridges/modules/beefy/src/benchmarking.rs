@@ -0,0 +1,119 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the BEEFY Pallet.
+//!
+//! The main dispatchable for the BEEFY pallet is `submit_commitment`. There are two main
+//! factors which affect commitment verification:
+//!
+//! 1. The number of validators in the BEEFY authority set. We need to compute the merkle root
+//!    of all validator keys and verify signatures of 2/3+1 of them;
+//!
+//! 2. The number of items in the MMR leaf proof.
+//!
+//! The worst case is a commitment that enacts the next BEEFY authority set and prunes the
+//! oldest imported commitment.
+
+use crate::{
+	BridgedBeefyAuthoritySet, BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedMmrProof,
+	Call, CurrentAuthoritySetInfo, ImportedBlockNumbers, ImportedCommitment, ImportedCommitments,
+	ImportedCommitmentsInfo, InitializationDataOf,
+};
+
+use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Zero;
+use sp_std::boxed::Box;
+
+/// The maximal number of validators in the BEEFY authority set that we are benchmarking.
+const MAX_VALIDATORS: u32 = 1024;
+
+/// The maximal number of items in the MMR leaf proof that we are benchmarking.
+///
+/// The MMR proof has at most `2 * log2(leaf_count)` items, so it is large enough for
+/// any practical MMR size.
+const MAX_MMR_PROOF_ITEMS: u32 = 32;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config<I>, I: 'static = ()>(crate::Pallet<T, I>);
+
+/// Everything that is required to initialize the pallet and to submit a commitment.
+pub struct SubmitCommitmentData<T: crate::Config<I>, I: 'static> {
+	/// Data to initialize the pallet with.
+	pub init_data: InitializationDataOf<T, I>,
+	/// Signed commitment for some bridged chain block, better than the one from `init_data`.
+	///
+	/// The MMR leaf of this block must enact the next authority set.
+	pub commitment: BridgedBeefySignedCommitment<T, I>,
+	/// Validator set that has signed the commitment.
+	pub validator_set: BridgedBeefyAuthoritySet<T, I>,
+	/// MMR leaf of the block the commitment is signed for.
+	pub mmr_leaf: BridgedBeefyMmrLeaf<T, I>,
+	/// MMR proof of the `mmr_leaf`.
+	pub mmr_proof: BridgedMmrProof<T, I>,
+}
+
+/// Trait that must be implemented by runtime to benchmark the BEEFY pallet.
+pub trait Config<I: 'static>: crate::Config<I> {
+	/// Generate valid commitment, signed by the set of `validators` validators, that enacts the
+	/// next authority set, and the MMR proof that has (roughly) `proof_items` items.
+	fn prepare_submit_commitment(validators: u32, proof_items: u32)
+		-> SubmitCommitmentData<Self, I>;
+}
+
+benchmarks_instance_pallet! {
+	where_clause {
+		where
+			crate::BridgedMmrHashing<T, I>: 'static + Send + Sync,
+			BridgedBeefySignedCommitment<T, I>: Clone,
+	}
+
+	submit_commitment {
+		let v in 1..MAX_VALIDATORS;
+		let p in 0..MAX_MMR_PROOF_ITEMS;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let data = T::prepare_submit_commitment(v, p);
+		let block_number = data.commitment.commitment.block_number;
+		let next_authority_set_id = data.mmr_leaf.beefy_next_authority_set.id;
+		let pruned_block_number = data.init_data.best_block_number;
+		crate::initialize::<T, I>(data.init_data).expect("benchmark data is valid");
+
+		// fill the ring buffer slot, so that the oldest commitment is pruned
+		ImportedBlockNumbers::<T, I>::insert(0, pruned_block_number);
+		ImportedCommitments::<T, I>::insert(
+			pruned_block_number,
+			ImportedCommitment::<T, I> {
+				parent_number_and_hash: (Zero::zero(), Default::default()),
+				mmr_root: Default::default(),
+			},
+		);
+	}: submit_commitment(
+		RawOrigin::Signed(caller),
+		data.commitment,
+		data.validator_set,
+		Box::new(data.mmr_leaf),
+		data.mmr_proof
+	)
+	verify {
+		assert_eq!(ImportedCommitmentsInfo::<T, I>::get().unwrap().best_block_number, block_number);
+		assert!(ImportedCommitments::<T, I>::contains_key(block_number));
+		assert!(!ImportedCommitments::<T, I>::contains_key(pruned_block_number));
+		assert_eq!(CurrentAuthoritySetInfo::<T, I>::get().id, next_authority_set_id);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
+}
@@ -0,0 +1,341 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	BridgedBlockNumber, Config, CurrentAuthoritySetInfo, Error, FreeCommitmentsRemaining,
+	ImportedCommitmentsInfo, Pallet,
+};
+use bp_beefy::ValidatorSetId;
+use bp_runtime::OwnedBridgeModule;
+use frame_support::{
+	dispatch::CallableCallFor,
+	traits::{Get, IsSubType},
+};
+use sp_runtime::{
+	traits::{CheckedSub, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	RuntimeDebug,
+};
+use sp_std::fmt::Debug;
+
+/// Info about a `SubmitCommitment` call which tries to update a single commitment.
+#[derive(Copy, Clone, PartialEq, RuntimeDebug)]
+pub struct SubmitCommitmentInfo<N: Debug> {
+	/// Number of the block, the commitment is signed for.
+	pub block_number: N,
+	/// Id of the validator set that has signed the commitment.
+	pub validator_set_id: ValidatorSetId,
+	/// Id of the next validator set from the bundled MMR leaf.
+	pub next_validator_set_id: ValidatorSetId,
+}
+
+/// Verified `SubmitCommitmentInfo<N>`.
+#[derive(Copy, Clone, PartialEq, RuntimeDebug)]
+pub struct VerifiedSubmitCommitmentInfo<N: Debug> {
+	/// Base call information.
+	pub base: SubmitCommitmentInfo<N>,
+	/// A difference between the commitment block and the best commitment block known to us
+	/// before the call.
+	pub improved_by: N,
+	/// If `true`, the commitment enacts the next BEEFY authority set. The pallet can't go
+	/// further without importing such commitments.
+	pub is_mandatory: bool,
+}
+
+/// Helper struct that provides methods for working with the `SubmitCommitment` call.
+pub struct SubmitCommitmentHelper<T: Config<I>, I: 'static> {
+	_phantom_data: sp_std::marker::PhantomData<(T, I)>,
+}
+
+impl<T: Config<I>, I: 'static> SubmitCommitmentHelper<T, I> {
+	/// Returns `true` if we may fit more free commitments into the current block.
+	pub fn has_free_commitment_slots() -> bool {
+		// `unwrap_or(u32::MAX)` means that if `FreeCommitmentsRemaining` is `None`, we may
+		// accept this commitment for free. It is `None` only if executed outside of transaction
+		// (e.g. during block initialization).
+		FreeCommitmentsRemaining::<T, I>::get().unwrap_or(u32::MAX) > 0
+	}
+
+	/// Check that the commitment is better than the best one we know and that it is signed
+	/// by the current authority set.
+	///
+	/// Returns number of blocks between the best commitment block, known to the pallet and
+	/// the bundled commitment block.
+	pub fn check_obsolete(
+		block_number: BridgedBlockNumber<T, I>,
+		validator_set_id: ValidatorSetId,
+	) -> Result<BridgedBlockNumber<T, I>, Error<T, I>> {
+		let commitments_info = ImportedCommitmentsInfo::<T, I>::get().ok_or_else(|| {
+			log::trace!(
+				target: crate::LOG_TARGET,
+				"Cannot import commitment for block {:?} because pallet is not yet initialized",
+				block_number,
+			);
+			<Error<T, I>>::NotInitialized
+		})?;
+
+		let improved_by = match block_number.checked_sub(&commitments_info.best_block_number) {
+			Some(improved_by) if improved_by > Zero::zero() => improved_by,
+			_ => {
+				log::trace!(
+					target: crate::LOG_TARGET,
+					"Cannot import obsolete commitment: bundled {:?}, best {:?}",
+					block_number,
+					commitments_info.best_block_number,
+				);
+
+				return Err(Error::<T, I>::OldCommitment)
+			},
+		};
+
+		let actual_set_id = CurrentAuthoritySetInfo::<T, I>::get().id;
+		if validator_set_id != actual_set_id {
+			log::trace!(
+				target: crate::LOG_TARGET,
+				"Cannot import commitment signed by unknown validator set: bundled {:?}, best {:?}",
+				validator_set_id,
+				actual_set_id,
+			);
+
+			return Err(Error::<T, I>::InvalidCommitmentValidatorSetId)
+		}
+
+		Ok(improved_by)
+	}
+
+	/// Returns `true` if the commitment with given next validator set id enacts the new
+	/// BEEFY authority set.
+	pub fn is_mandatory(next_validator_set_id: ValidatorSetId) -> bool {
+		next_validator_set_id > CurrentAuthoritySetInfo::<T, I>::get().id
+	}
+
+	/// Return true if we may refund transaction cost to the submitter. In other words,
+	/// this transaction is considered as common good deed w.r.t to pallet configuration.
+	pub fn may_refund_call_fee(
+		is_mandatory: bool,
+		improved_by: BridgedBlockNumber<T, I>,
+	) -> bool {
+		// if we have refunded too much at this block => not refunding
+		if FreeCommitmentsRemaining::<T, I>::get().unwrap_or(0) == 0 {
+			return false
+		}
+
+		// if that's a mandatory commitment => refund
+		if is_mandatory {
+			return true
+		}
+
+		// if configuration allows free non-mandatory commitments and the commitment
+		// matches criteria => refund
+		if let Some(free_commitments_interval) = T::FreeCommitmentsInterval::get() {
+			if improved_by >= free_commitments_interval.into() {
+				return true
+			}
+		}
+
+		false
+	}
+
+	/// Check if the `SubmitCommitment` was successfully executed.
+	pub fn was_successful(block_number: BridgedBlockNumber<T, I>) -> bool {
+		match ImportedCommitmentsInfo::<T, I>::get() {
+			Some(commitments_info) => commitments_info.best_block_number == block_number,
+			None => false,
+		}
+	}
+}
+
+/// Trait representing a call that is a sub type of this pallet's call.
+pub trait CallSubType<T: Config<I, RuntimeCall = Self>, I: 'static>:
+	IsSubType<CallableCallFor<Pallet<T, I>, T>>
+{
+	/// Extract commitment info from a runtime call.
+	fn submit_commitment_info(&self) -> Option<SubmitCommitmentInfo<BridgedBlockNumber<T, I>>> {
+		if let Some(crate::Call::<T, I>::submit_commitment { commitment, mmr_leaf, .. }) =
+			self.is_sub_type()
+		{
+			return Some(SubmitCommitmentInfo {
+				block_number: commitment.commitment.block_number,
+				validator_set_id: commitment.commitment.validator_set_id,
+				next_validator_set_id: mmr_leaf.beefy_next_authority_set.id,
+			})
+		}
+
+		None
+	}
+
+	/// Validate BEEFY commitments in order to avoid "mining" transactions that provide outdated
+	/// bridged chain commitments. Without this validation, even honest relayers may lose their
+	/// funds if there are multiple relays running and submitting the same information.
+	///
+	/// Returns `Ok(None)` if the call is not the `submit_commitment` call of our pallet.
+	/// Returns `Ok(Some(_))` if the call is the `submit_commitment` call of our pallet and
+	/// we believe the call brings commitment that improves the pallet state.
+	/// Returns `Err(_)` if the call is the `submit_commitment` call of our pallet and we
+	/// believe that the call will fail.
+	fn check_obsolete_submit_commitment(
+		&self,
+	) -> Result<Option<VerifiedSubmitCommitmentInfo<BridgedBlockNumber<T, I>>>, TransactionValidityError>
+	where
+		Self: Sized,
+	{
+		let call_info = match self.submit_commitment_info() {
+			Some(call_info) => call_info,
+			_ => return Ok(None),
+		};
+
+		if Pallet::<T, I>::ensure_not_halted().is_err() {
+			return Err(InvalidTransaction::Call.into())
+		}
+
+		let result = SubmitCommitmentHelper::<T, I>::check_obsolete(
+			call_info.block_number,
+			call_info.validator_set_id,
+		);
+		match result {
+			Ok(improved_by) => Ok(Some(VerifiedSubmitCommitmentInfo {
+				base: call_info,
+				improved_by,
+				is_mandatory: SubmitCommitmentHelper::<T, I>::is_mandatory(
+					call_info.next_validator_set_id,
+				),
+			})),
+			Err(Error::<T, I>::OldCommitment) => Err(InvalidTransaction::Stale.into()),
+			Err(_) => Err(InvalidTransaction::Call.into()),
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> CallSubType<T, I> for T::RuntimeCall where
+	T::RuntimeCall: IsSubType<CallableCallFor<Pallet<T, I>, T>>
+{
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		call_ext::CallSubType,
+		mock::{run_test_with_initialize, RuntimeCall, TestBridgedBlockNumber, TestRuntime},
+		mock_chain::{ChainBuilder, HeaderAndCommitment},
+		ImportedCommitmentsInfo, ImportedCommitmentsInfoData, PalletOperatingMode,
+	};
+	use bp_runtime::BasicOperatingMode;
+
+	fn submit_commitment_call(header: HeaderAndCommitment) -> RuntimeCall {
+		RuntimeCall::Beefy(crate::Call::<TestRuntime, ()>::submit_commitment {
+			commitment: header.commitment.expect("header is finalized"),
+			validator_set: header.validator_set,
+			mmr_leaf: Box::new(header.leaf),
+			mmr_proof: header.leaf_proof,
+		})
+	}
+
+	fn validate_commitment_submit(header: HeaderAndCommitment) -> bool {
+		submit_commitment_call(header).check_obsolete_submit_commitment().is_ok()
+	}
+
+	fn sync_to_block_10() {
+		ImportedCommitmentsInfo::<TestRuntime, ()>::put(ImportedCommitmentsInfoData {
+			best_block_number: 10,
+			next_block_number_index: 0,
+		});
+	}
+
+	fn chain_header(number: TestBridgedBlockNumber) -> HeaderAndCommitment {
+		ChainBuilder::new(3)
+			.append_default_headers(number as usize - 1)
+			.append_finalized_header()
+			.header(number)
+	}
+
+	#[test]
+	fn extension_rejects_obsolete_commitment() {
+		run_test_with_initialize(3, || {
+			// when current best commitment is #10 and we're trying to import commitment#5 => tx is
+			// rejected
+			sync_to_block_10();
+			assert!(!validate_commitment_submit(chain_header(5)));
+		});
+	}
+
+	#[test]
+	fn extension_rejects_same_commitment() {
+		run_test_with_initialize(3, || {
+			// when current best commitment is #10 and we're trying to import commitment#10 => tx
+			// is rejected
+			sync_to_block_10();
+			assert!(!validate_commitment_submit(chain_header(10)));
+		});
+	}
+
+	#[test]
+	fn extension_rejects_new_commitment_if_pallet_is_halted() {
+		run_test_with_initialize(3, || {
+			// when pallet is halted => tx is rejected
+			sync_to_block_10();
+			PalletOperatingMode::<TestRuntime, ()>::put(BasicOperatingMode::Halted);
+
+			assert!(!validate_commitment_submit(chain_header(15)));
+		});
+	}
+
+	#[test]
+	fn extension_rejects_new_commitment_if_set_id_is_invalid() {
+		run_test_with_initialize(3, || {
+			// when commitment is signed by the unknown set => tx is rejected
+			sync_to_block_10();
+			let mut header = chain_header(15);
+			header.customize_commitment(
+				|commitment| commitment.validator_set_id += 1,
+				&crate::mock::validator_pairs(0, 3),
+				3,
+			);
+
+			assert!(!validate_commitment_submit(header));
+		});
+	}
+
+	#[test]
+	fn extension_accepts_new_commitment() {
+		run_test_with_initialize(3, || {
+			// when current best commitment is #10 and we're trying to import commitment#15 => tx
+			// is accepted
+			sync_to_block_10();
+			assert!(validate_commitment_submit(chain_header(15)));
+		});
+	}
+
+	#[test]
+	fn extension_marks_handoff_commitment_as_mandatory() {
+		run_test_with_initialize(3, || {
+			sync_to_block_10();
+			let chain = ChainBuilder::new(3).append_default_headers(14).append_handoff_header(5);
+
+			let verified = submit_commitment_call(chain.header(15))
+				.check_obsolete_submit_commitment()
+				.unwrap()
+				.unwrap();
+			assert!(verified.is_mandatory);
+			assert_eq!(verified.improved_by, 5);
+
+			let verified = submit_commitment_call(chain_header(15))
+				.check_obsolete_submit_commitment()
+				.unwrap()
+				.unwrap();
+			assert!(!verified.is_mandatory);
+		});
+	}
+}
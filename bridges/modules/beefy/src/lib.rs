@@ -32,14 +32,26 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bp_beefy::{ChainWithBeefy, InitializationData};
+use bp_beefy::{BeefyFinalityProof, ChainWithBeefy, InitializationData};
+use frame_support::dispatch::PostDispatchInfo;
+use sp_runtime::SaturatedConversion;
 use sp_std::{boxed::Box, prelude::*};
 
+pub use bp_beefy::ImportedCommitmentsInfoData;
 // Re-export in crate namespace for `construct_runtime!`
+pub use call_ext::*;
 pub use pallet::*;
+pub use weights::WeightInfo;
 
+mod call_ext;
 mod utils;
 
+/// Module, containing weights for this pallet.
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -85,17 +97,10 @@ pub type ImportedCommitment<T, I> = bp_beefy::ImportedCommitment<
 	BridgedMmrHash<T, I>,
 >;
 
-/// Some high level info about the imported commitments.
-#[derive(codec::Encode, codec::Decode, scale_info::TypeInfo)]
-pub struct ImportedCommitmentsInfoData<BlockNumber> {
-	/// Best known block number, provided in a BEEFY commitment. However this is not
-	/// the best proven block. The best proven block is this block's parent.
-	best_block_number: BlockNumber,
-	/// The head of the `ImportedBlockNumbers` ring buffer.
-	next_block_number_index: u32,
-}
+/// BEEFY-related info of the imported commitment, stored in the pallet events.
+pub type StoredHeaderBeefyInfoOf<T, I> = bp_beefy::StoredHeaderBeefyInfo<BridgedChain<T, I>>;
 
-#[frame_support::pallet(dev_mode)]
+#[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use bp_runtime::{BasicOperatingMode, OwnedBridgeModule};
@@ -104,6 +109,10 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		/// The upper bound on the number of requests allowed by the pallet.
 		///
 		/// A request refers to an action which writes a header to storage.
@@ -121,8 +130,34 @@ pub mod pallet {
 		#[pallet::constant]
 		type CommitmentsToKeep: Get<u32>;
 
+		/// Maximal number of "free" commitment transactions per block.
+		///
+		/// To be able to track the bridged chain, the pallet requires all commitments that are
+		/// enacting new BEEFY authority sets at the bridged chain (we call them mandatory).
+		/// So it is a common good deed to submit mandatory commitments to the pallet.
+		///
+		/// The pallet may be configured (see `[Self::FreeCommitmentsInterval]`) to import some
+		/// non-mandatory commitments for free as well.
+		///
+		/// However, if the bridged chain gets compromised, its validators may generate as many
+		/// "free" commitments as they want. This constant limits number of calls that we may
+		/// refund in a single block. All calls above this limit are accepted, but are not
+		/// refunded.
+		#[pallet::constant]
+		type MaxFreeCommitmentsPerBlock: Get<u32>;
+
+		/// The distance between bridged chain blocks, commitments for which may be submitted
+		/// for free. In other words, the difference between the block of the submitted
+		/// commitment and the best block known to the pallet should be at least
+		/// `FreeCommitmentsInterval` for the non-mandatory commitment to be refunded.
+		#[pallet::constant]
+		type FreeCommitmentsInterval: Get<Option<u32>>;
+
 		/// The chain we are bridging to here.
 		type BridgedChain: ChainWithBeefy;
+
+		/// Weights gathered through benchmarking.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
@@ -133,11 +168,17 @@ pub mod pallet {
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
 		fn on_initialize(_n: BlockNumberFor<T>) -> frame_support::weights::Weight {
 			<RequestCount<T, I>>::mutate(|count| *count = count.saturating_sub(1));
+			FreeCommitmentsRemaining::<T, I>::put(T::MaxFreeCommitmentsPerBlock::get());
 
+			// `FreeCommitmentsRemaining` is whitelisted, so we only pay for `RequestCount`
 			Weight::from_parts(0, 0)
 				.saturating_add(T::DbWeight::get().reads(1))
 				.saturating_add(T::DbWeight::get().writes(1))
 		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			FreeCommitmentsRemaining::<T, I>::kill();
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> OwnedBridgeModule<T> for Pallet<T, I> {
@@ -197,15 +238,22 @@ pub mod pallet {
 		///
 		/// If successful in verification, it will update the underlying storage with the data
 		/// provided in the newly submitted commitment.
+		///
+		/// Commitments that are enacting new BEEFY authority sets (and, depending on the
+		/// pallet configuration, some other commitments) are imported for free, unless we
+		/// have already refunded `MaxFreeCommitmentsPerBlock` calls in the current block.
 		#[pallet::call_index(3)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::submit_commitment(
+			validator_set.len().saturated_into(),
+			mmr_proof.items.len().saturated_into(),
+		))]
 		pub fn submit_commitment(
 			origin: OriginFor<T>,
 			commitment: BridgedBeefySignedCommitment<T, I>,
 			validator_set: BridgedBeefyAuthoritySet<T, I>,
 			mmr_leaf: Box<BridgedBeefyMmrLeaf<T, I>>,
 			mmr_proof: BridgedMmrProof<T, I>,
-		) -> DispatchResult
+		) -> DispatchResultWithPostInfo
 		where
 			BridgedBeefySignedCommitment<T, I>: Clone,
 		{
@@ -214,14 +262,18 @@ pub mod pallet {
 
 			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
 
-			// Ensure that the commitment is for a better block.
-			let commitments_info =
-				ImportedCommitmentsInfo::<T, I>::get().ok_or(Error::<T, I>::NotInitialized)?;
-			ensure!(
-				commitment.commitment.block_number > commitments_info.best_block_number,
-				Error::<T, I>::OldCommitment
+			let declared_weight = T::WeightInfo::submit_commitment(
+				validator_set.len().saturated_into(),
+				mmr_proof.items.len().saturated_into(),
 			);
 
+			// Ensure that the commitment is for a better block and is signed by the current
+			// authority set.
+			let improved_by = SubmitCommitmentHelper::<T, I>::check_obsolete(
+				commitment.commitment.block_number,
+				commitment.commitment.validator_set_id,
+			)?;
+
 			// Verify commitment and mmr leaf.
 			let current_authority_set_info = CurrentAuthoritySetInfo::<T, I>::get();
 			let mmr_root = utils::verify_commitment::<T, I>(
@@ -229,52 +281,82 @@ pub mod pallet {
 				&current_authority_set_info,
 				&validator_set,
 			)?;
-			utils::verify_beefy_mmr_leaf::<T, I>(&mmr_leaf, mmr_proof, mmr_root)?;
+			utils::verify_beefy_mmr_leaf::<T, I>(&mmr_leaf, mmr_proof.clone(), mmr_root)?;
 
 			// Update request count.
 			RequestCount::<T, I>::mutate(|count| *count += 1);
 			// Update authority set if needed.
-			if mmr_leaf.beefy_next_authority_set.id > current_authority_set_info.id {
-				CurrentAuthoritySetInfo::<T, I>::put(mmr_leaf.beefy_next_authority_set);
+			let is_mandatory =
+				SubmitCommitmentHelper::<T, I>::is_mandatory(mmr_leaf.beefy_next_authority_set.id);
+			let new_authority_set_info = if is_mandatory {
+				CurrentAuthoritySetInfo::<T, I>::put(mmr_leaf.beefy_next_authority_set.clone());
+				Some(mmr_leaf.beefy_next_authority_set.clone())
+			} else {
+				None
+			};
+
+			let may_refund_call_fee =
+				SubmitCommitmentHelper::<T, I>::may_refund_call_fee(is_mandatory, improved_by);
+			if may_refund_call_fee {
+				on_free_commitment_imported::<T, I>();
 			}
 
 			// Import commitment.
-			let block_number_index = commitments_info.next_block_number_index;
-			let to_prune = ImportedBlockNumbers::<T, I>::try_get(block_number_index);
-			ImportedCommitments::<T, I>::insert(
+			let is_pruned = insert_commitment::<T, I>(
 				commitment.commitment.block_number,
 				ImportedCommitment::<T, I> {
 					parent_number_and_hash: mmr_leaf.parent_number_and_hash,
 					mmr_root,
 				},
 			);
-			ImportedBlockNumbers::<T, I>::insert(
-				block_number_index,
-				commitment.commitment.block_number,
-			);
-			ImportedCommitmentsInfo::<T, I>::put(ImportedCommitmentsInfoData {
-				best_block_number: commitment.commitment.block_number,
-				next_block_number_index: (block_number_index + 1) % T::CommitmentsToKeep::get(),
-			});
-			if let Ok(old_block_number) = to_prune {
-				log::debug!(
-					target: LOG_TARGET,
-					"Pruning commitment for old block: {:?}.",
-					old_block_number
-				);
-				ImportedCommitments::<T, I>::remove(old_block_number);
-			}
+
+			// We don't want to charge extra costs for mandatory operations. So relayer is not
+			// paying fee for commitments that are enacting new authority sets.
+			let pays_fee = if may_refund_call_fee { Pays::No } else { Pays::Yes };
 
 			log::info!(
 				target: LOG_TARGET,
-				"Successfully imported commitment for block {:?}",
+				"Successfully imported commitment for block {:?}! Free: {}",
 				commitment.commitment.block_number,
+				if may_refund_call_fee { "Yes" } else { "No" },
 			);
 
-			Ok(())
+			Self::deposit_event(Event::UpdatedBestCommitment {
+				block_number: commitment.commitment.block_number,
+				beefy_info: StoredHeaderBeefyInfoOf::<T, I> {
+					finality_proof: BeefyFinalityProof {
+						commitment,
+						validator_set,
+						mmr_leaf: *mmr_leaf,
+						mmr_proof,
+					},
+					new_verification_context: new_authority_set_info,
+				},
+			});
+
+			// The declared weight is computed for a commitment that enacts the next authority set
+			// and prunes the oldest imported commitment. Refund the writes we haven't made.
+			let unused_writes = u64::from(!is_mandatory) + u64::from(!is_pruned);
+			let actual_weight =
+				declared_weight.saturating_sub(T::DbWeight::get().writes(unused_writes));
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
 		}
 	}
 
+	/// Number of free commitment submissions that we may yet accept in the current block.
+	///
+	/// If the `FreeCommitmentsRemaining` hits zero, all following mandatory commitments in the
+	/// current block are accepted with fee (`Pays::Yes` is returned).
+	///
+	/// The `FreeCommitmentsRemaining` is an ephemeral value that is set to
+	/// `MaxFreeCommitmentsPerBlock` at each block initialization and is killed on block
+	/// finalization. So it never ends up in the storage trie.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type FreeCommitmentsRemaining<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, OptionQuery>;
+
 	/// The current number of requests which have written to storage.
 	///
 	/// If the `RequestCount` hits `MaxRequests`, no more calls will be allowed to the pallet until
@@ -354,6 +436,18 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Best known commitment has been updated to the commitment for the given block.
+		UpdatedBestCommitment {
+			/// Number of the block, the commitment is signed for.
+			block_number: BridgedBlockNumber<T, I>,
+			/// The BEEFY info associated with the commitment.
+			beefy_info: StoredHeaderBeefyInfoOf<T, I>,
+		},
+	}
+
 	#[pallet::error]
 	pub enum Error<T, I = ()> {
 		/// The pallet has not been initialized yet.
@@ -404,6 +498,50 @@ pub mod pallet {
 		Ok(())
 	}
 
+	/// Called when new free commitment is imported.
+	pub fn on_free_commitment_imported<T: Config<I>, I: 'static>() {
+		FreeCommitmentsRemaining::<T, I>::mutate(|count| {
+			*count = match *count {
+				None => None,
+				// the transaction extension expects that `None` means outside of block
+				// execution - i.e. when transaction is validated from the transaction pool,
+				// so use `saturating_sub` and don't go from `Some(0)`->`None`
+				Some(count) => Some(count.saturating_sub(1)),
+			}
+		});
+	}
+
+	/// Import commitment to the storage, pruning the oldest imported commitment if the
+	/// `ImportedBlockNumbers` ring buffer is full.
+	///
+	/// Returns `true` if the oldest imported commitment has been pruned.
+	pub(crate) fn insert_commitment<T: Config<I>, I: 'static>(
+		block_number: BridgedBlockNumber<T, I>,
+		imported_commitment: ImportedCommitment<T, I>,
+	) -> bool {
+		let block_number_index = ImportedCommitmentsInfo::<T, I>::get()
+			.map(|commitments_info| commitments_info.next_block_number_index)
+			.unwrap_or(0);
+		let to_prune = ImportedBlockNumbers::<T, I>::try_get(block_number_index);
+		ImportedCommitments::<T, I>::insert(block_number, imported_commitment);
+		ImportedBlockNumbers::<T, I>::insert(block_number_index, block_number);
+		ImportedCommitmentsInfo::<T, I>::put(ImportedCommitmentsInfoData {
+			best_block_number: block_number,
+			next_block_number_index: (block_number_index + 1) % T::CommitmentsToKeep::get(),
+		});
+		let is_pruned = to_prune.is_ok();
+		if let Ok(old_block_number) = to_prune {
+			log::debug!(
+				target: LOG_TARGET,
+				"Pruning commitment for old block: {:?}.",
+				old_block_number
+			);
+			ImportedCommitments::<T, I>::remove(old_block_number);
+		}
+
+		is_pruned
+	}
+
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// The current number of requests which have written to storage.
 		pub fn request_count() -> u32 {
@@ -412,12 +550,28 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config<I>, I: 'static> Pallet<T, I>
+where
+	<T as frame_system::Config>::RuntimeEvent: TryInto<Event<T, I>>,
+{
+	/// Get the BEEFY commitments accepted in the current block.
+	pub fn synced_headers_beefy_info() -> Vec<StoredHeaderBeefyInfoOf<T, I>> {
+		frame_system::Pallet::<T>::read_events_no_consensus()
+			.filter_map(|event| {
+				let Event::<T, I>::UpdatedBestCommitment { beefy_info, .. } =
+					event.event.try_into().ok()?;
+				Some(beefy_info)
+			})
+			.collect()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use bp_runtime::{BasicOperatingMode, OwnedBridgeModuleError};
 	use bp_test_utils::generate_owned_bridge_module_tests;
-	use frame_support::{assert_noop, assert_ok, traits::Get};
+	use frame_support::{assert_noop, assert_ok, dispatch::Pays, traits::Get};
 	use mock::*;
 	use mock_chain::*;
 	use sp_consensus_beefy::mmr::BeefyAuthoritySet;
@@ -573,6 +727,154 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn submit_commitment_deposits_beefy_info() {
+		run_test_with_initialize(3, || {
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			let chain = ChainBuilder::new(3).append_handoff_header(5).append_finalized_header();
+
+			let handoff_header = chain.header(1);
+			assert_ok!(import_commitment(handoff_header.clone()));
+			let handoff_info = StoredHeaderBeefyInfoOf::<TestRuntime, ()> {
+				finality_proof: BeefyFinalityProof {
+					commitment: handoff_header.commitment.unwrap(),
+					validator_set: handoff_header.validator_set,
+					mmr_leaf: handoff_header.leaf.clone(),
+					mmr_proof: handoff_header.leaf_proof,
+				},
+				new_verification_context: Some(handoff_header.leaf.beefy_next_authority_set),
+			};
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events().last().unwrap().event,
+				RuntimeEvent::Beefy(Event::UpdatedBestCommitment {
+					block_number: 1,
+					beefy_info: handoff_info.clone(),
+				}),
+			);
+
+			let header = chain.header(2);
+			assert_ok!(import_commitment(header.clone()));
+			assert_eq!(
+				Pallet::<TestRuntime>::synced_headers_beefy_info(),
+				vec![
+					handoff_info,
+					StoredHeaderBeefyInfoOf::<TestRuntime, ()> {
+						finality_proof: BeefyFinalityProof {
+							commitment: header.commitment.unwrap(),
+							validator_set: header.validator_set,
+							mmr_leaf: header.leaf,
+							mmr_proof: header.leaf_proof,
+						},
+						new_verification_context: None,
+					},
+				],
+			);
+		})
+	}
+
+	#[test]
+	fn mandatory_commitments_are_free() {
+		run_test_with_initialize(3, || {
+			next_block();
+			let chain = ChainBuilder::new(3)
+				.append_handoff_header(3)
+				.append_finalized_header()
+				.append_handoff_header(3);
+
+			// commitment that enacts new authority set is free
+			let result = import_commitment(chain.header(1));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get().id, 1);
+
+			// regular commitment that improves best block by 1 is not free
+			let result = import_commitment(chain.header(2));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+
+			// next commitment that enacts new authority set is free
+			let result = import_commitment(chain.header(3));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get().id, 2);
+		})
+	}
+
+	#[test]
+	fn submit_commitment_refunds_unused_writes() {
+		run_test_with_initialize(3, || {
+			let chain = ChainBuilder::new(3).append_finalized_header().append_handoff_header(3);
+			let db_weight = <TestRuntime as frame_system::Config>::DbWeight::get();
+			let declared_weight = |header: &HeaderAndCommitment| {
+				<TestRuntime as Config>::WeightInfo::submit_commitment(
+					header.validator_set.len() as u32,
+					header.leaf_proof.items.len() as u32,
+				)
+			};
+
+			// regular commitment doesn't change the authority set and there's nothing to prune
+			let header = chain.header(1);
+			let expected_weight = declared_weight(&header) - db_weight.writes(2);
+			let result = import_commitment(header);
+			assert_eq!(result.expect("call failed").actual_weight, Some(expected_weight));
+
+			// commitment that enacts new authority set still has nothing to prune
+			let header = chain.header(2);
+			let expected_weight = declared_weight(&header) - db_weight.writes(1);
+			let result = import_commitment(header);
+			assert_eq!(result.expect("call failed").actual_weight, Some(expected_weight));
+		})
+	}
+
+	#[test]
+	fn free_commitments_are_limited_per_block() {
+		run_test_with_initialize(3, || {
+			let max_free_commitments =
+				<TestRuntime as Config>::MaxFreeCommitmentsPerBlock::get() as u64;
+			let mut chain = ChainBuilder::new(3);
+			for _ in 0..max_free_commitments + 2 {
+				chain = chain.append_handoff_header(3);
+			}
+
+			// `MaxFreeCommitmentsPerBlock` mandatory commitments are free
+			next_block();
+			for i in 0..max_free_commitments {
+				let result = import_commitment(chain.header(i + 1));
+				assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+			}
+
+			// next mandatory commitment in the same block is not free
+			let result = import_commitment(chain.header(max_free_commitments + 1));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+
+			// but it is free again in the next block
+			next_block();
+			let result = import_commitment(chain.header(max_free_commitments + 2));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+		})
+	}
+
+	#[test]
+	fn commitments_are_free_if_they_improve_best_block_by_free_interval() {
+		run_test_with_initialize(3, || {
+			next_block();
+			let free_commitments_interval = FreeCommitmentsInterval::get() as usize;
+			let chain = ChainBuilder::new(3)
+				.append_default_headers(free_commitments_interval - 2)
+				.append_finalized_header()
+				.append_default_headers(free_commitments_interval - 1)
+				.append_finalized_header();
+
+			// commitment that improves best block by less than `FreeCommitmentsInterval`
+			// is not free
+			let number = free_commitments_interval as TestBridgedBlockNumber - 1;
+			let result = import_commitment(chain.header(number));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::Yes);
+
+			// commitment that improves best block by `FreeCommitmentsInterval` is free
+			let number = number + free_commitments_interval as TestBridgedBlockNumber;
+			let result = import_commitment(chain.header(number));
+			assert_eq!(result.expect("call failed").pays_fee, Pays::No);
+		})
+	}
+
 	#[test]
 	fn commitment_pruning_works() {
 		run_test_with_initialize(3, || {
@@ -24,7 +24,7 @@ use crate::{
 use bp_beefy::{BeefyValidatorSignatureOf, ChainWithBeefy, Commitment, MmrDataOrHash};
 use bp_runtime::{BasicOperatingMode, Chain, ChainId};
 use codec::Encode;
-use frame_support::{construct_runtime, derive_impl, weights::Weight};
+use frame_support::{construct_runtime, derive_impl, parameter_types, weights::Weight};
 use sp_core::{sr25519::Signature, Pair};
 use sp_runtime::{
 	testing::{Header, H256},
@@ -63,19 +63,56 @@ construct_runtime! {
 	pub enum TestRuntime
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
-		Beefy: beefy::{Pallet},
+		Beefy: beefy::{Pallet, Call, Event<T>},
 	}
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for TestRuntime {
 	type Block = Block;
+	type DbWeight = frame_support::weights::constants::RocksDbWeight;
+}
+
+parameter_types! {
+	pub const FreeCommitmentsInterval: u32 = 32;
 }
 
 impl beefy::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
 	type MaxRequests = frame_support::traits::ConstU32<16>;
 	type BridgedChain = TestBridgedChain;
 	type CommitmentsToKeep = frame_support::traits::ConstU32<16>;
+	type MaxFreeCommitmentsPerBlock = frame_support::traits::ConstU32<2>;
+	type FreeCommitmentsInterval = FreeCommitmentsInterval;
+	type WeightInfo = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl beefy::benchmarking::Config<()> for TestRuntime {
+	fn prepare_submit_commitment(
+		validators: u32,
+		proof_items: u32,
+	) -> beefy::benchmarking::SubmitCommitmentData<TestRuntime, ()> {
+		// in mock run we only care about benchmarks correctness, not the benchmark results
+		// => use the proof of whatever size the MMR gives us for `proof_items` leaves
+		let header = crate::mock_chain::ChainBuilder::new(validators)
+			.append_default_headers(proof_items as usize)
+			.append_handoff_header(validators)
+			.header(proof_items as TestBridgedBlockNumber + 1);
+		let validator_ids = validator_ids(0, validators);
+
+		beefy::benchmarking::SubmitCommitmentData {
+			init_data: bp_beefy::InitializationData {
+				operating_mode: BasicOperatingMode::Normal,
+				best_block_number: 0,
+				authority_set: authority_set_info(0, &validator_ids),
+			},
+			commitment: header.commitment.expect("header is finalized"),
+			validator_set: header.validator_set,
+			mmr_leaf: header.leaf,
+			mmr_proof: header.leaf_proof,
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -105,6 +142,8 @@ impl Chain for TestBridgedChain {
 }
 
 impl ChainWithBeefy for TestBridgedChain {
+	const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str = "Beefy";
+
 	type CommitmentHasher = Keccak256;
 	type MmrHashing = Keccak256;
 	type MmrHash = <Keccak256 as Hash>::Output;
@@ -113,9 +152,14 @@ impl ChainWithBeefy for TestBridgedChain {
 	type AuthorityIdToMerkleLeaf = pallet_beefy_mmr::BeefyEcdsaToEthereum;
 }
 
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	sp_io::TestExternalities::new(Default::default())
+}
+
 /// Run test within test runtime.
 pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
-	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+	new_test_ext().execute_with(test)
 }
 
 /// Initialize pallet and run test.
@@ -141,7 +185,7 @@ pub fn run_test_with_initialize<T>(initial_validators_count: u32, test: impl FnO
 /// Import given commitment.
 pub fn import_commitment(
 	header: crate::mock_chain::HeaderAndCommitment,
-) -> sp_runtime::DispatchResult {
+) -> frame_support::dispatch::DispatchResultWithPostInfo {
 	crate::Pallet::<TestRuntime>::submit_commitment(
 		RuntimeOrigin::signed(1),
 		header
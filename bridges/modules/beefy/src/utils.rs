@@ -3,7 +3,9 @@ use crate::{
 	BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment, BridgedChain, BridgedMmrHash,
 	BridgedMmrHashing, BridgedMmrProof, Config, Error, LOG_TARGET,
 };
-use bp_beefy::{merkle_root, verify_mmr_leaves_proof, BeefyAuthorityId, MmrDataOrHash};
+use bp_beefy::{
+	merkle_root, signatures_required, verify_mmr_leaves_proof, BeefyAuthorityId, MmrDataOrHash,
+};
 use codec::Encode;
 use frame_support::ensure;
 use sp_runtime::traits::{Convert, Hash};
@@ -47,15 +49,6 @@ fn verify_authority_set<T: Config<I>, I: 'static>(
 	Ok(())
 }
 
-/// Number of correct signatures, required from given validators set to accept signed
-/// commitment.
-///
-/// We're using 'conservative' approach here, where signatures of `2/3+1` validators are
-/// required..
-pub(crate) fn signatures_required(validators_len: usize) -> usize {
-	validators_len - validators_len.saturating_sub(1) / 3
-}
-
 fn verify_signatures<T: Config<I>, I: 'static>(
 	commitment: &BridgedBeefySignedCommitment<T, I>,
 	authority_set: &BridgedBeefyAuthoritySet<T, I>,
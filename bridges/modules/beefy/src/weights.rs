@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_bridge_beefy
+//!
+//! These weights are estimated from the storage accesses of `submit_commitment` and the cost of
+//! verifying ECDSA signatures and keccak merkle proofs, for a commitment that enacts the next
+//! authority set and prunes the oldest imported commitment, the worst case set up by its
+//! benchmark. They have not been measured yet and should be regenerated with the pallet's
+//! benchmarks:
+//!
+//! target/release/unknown-bridge-node benchmark pallet --chain=dev --steps=50 --repeat=20
+//! --pallet=pallet_bridge_beefy --extrinsic=* --wasm-execution=Compiled --heap-pages=4096
+//! --output=./modules/beefy/src/weights.rs --template=./.maintain/bridge-weight-template.hbs
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge_beefy.
+pub trait WeightInfo {
+	fn submit_commitment(v: u32, p: u32) -> Weight;
+}
+
+/// Estimated weights for `pallet_bridge_beefy`.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: `BridgeUnknownBeefy::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BridgeUnknownBeefy::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::RequestCount` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::RequestCount` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedCommitmentsInfo` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`:
+	/// None, mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::CurrentAuthoritySetInfo` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`:
+	/// None, mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedBlockNumbers` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::ImportedBlockNumbers` (`max_values`: None, `max_size`: None,
+	/// mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedCommitments` (r:0 w:2)
+	/// Proof: `BridgeUnknownBeefy::ImportedCommitments` (`max_values`: None, `max_size`: None,
+	/// mode: `Measured`)
+	///
+	/// The range of component `v` is `[1, 1024]`.
+	///
+	/// The range of component `p` is `[0, 32]`.
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		Weight::from_parts(61_370_417, 3809)
+			.saturating_add(Weight::from_parts(38_410_925, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(1_742_308, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: `BridgeUnknownBeefy::PalletOperatingMode` (r:1 w:0)
+	/// Proof: `BridgeUnknownBeefy::PalletOperatingMode` (`max_values`: Some(1), `max_size`: None,
+	/// mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::RequestCount` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::RequestCount` (`max_values`: Some(1), `max_size`: None, mode:
+	/// `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedCommitmentsInfo` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::ImportedCommitmentsInfo` (`max_values`: Some(1), `max_size`:
+	/// None, mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::CurrentAuthoritySetInfo` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::CurrentAuthoritySetInfo` (`max_values`: Some(1), `max_size`:
+	/// None, mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedBlockNumbers` (r:1 w:1)
+	/// Proof: `BridgeUnknownBeefy::ImportedBlockNumbers` (`max_values`: None, `max_size`: None,
+	/// mode: `Measured`)
+	/// Storage: `BridgeUnknownBeefy::ImportedCommitments` (r:0 w:2)
+	/// Proof: `BridgeUnknownBeefy::ImportedCommitments` (`max_values`: None, `max_size`: None,
+	/// mode: `Measured`)
+	///
+	/// The range of component `v` is `[1, 1024]`.
+	///
+	/// The range of component `p` is `[0, 32]`.
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		Weight::from_parts(61_370_417, 3809)
+			.saturating_add(Weight::from_parts(38_410_925, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(1_742_308, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+}
@@ -0,0 +1,194 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Adapter that allows using `pallet-bridge-relayers` as a signed extension in the
+//! bridge with remote BEEFY chain.
+
+use crate::{
+	extension::verify_messages_call_succeeded, Config as BridgeRelayersConfig, LOG_TARGET,
+};
+
+use bp_header_chain::SubmitFinalityProofInfo;
+use bp_relayers::{BatchCallUnpacker, ExtensionCallData, ExtensionCallInfo, ExtensionConfig};
+use bp_runtime::{Chain, StaticStrProvider};
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	weights::Weight,
+};
+use frame_system::Config as SystemConfig;
+use pallet_bridge_beefy::{
+	CallSubType as BridgeBeefyCallSubtype, Config as BridgeBeefyConfig, SubmitCommitmentHelper,
+};
+use pallet_bridge_messages::{
+	CallSubType as BridgeMessagesCallSubType, Config as BridgeMessagesConfig, LaneIdOf,
+};
+use sp_runtime::{
+	traits::{Dispatchable, Get},
+	transaction_validity::{TransactionPriority, TransactionValidityError},
+};
+
+/// Adapter to be used in signed extension configuration, when bridging with remote
+/// chains that are using BEEFY finality.
+pub struct WithBeefyChainExtensionConfig<
+	// signed extension identifier
+	IdProvider,
+	// runtime that implements `BridgeMessagesConfig<BridgeMessagesPalletInstance>`, which
+	// uses `BridgeBeefyConfig<BridgeBeefyPalletInstance>` to receive messages and
+	// confirmations from the remote chain.
+	Runtime,
+	// batch call unpacker
+	BatchCallUnpacker,
+	// instance of the `pallet-bridge-beefy`, tracked by this extension
+	BridgeBeefyPalletInstance,
+	// instance of BridgedChain `pallet-bridge-messages`, tracked by this extension
+	BridgeMessagesPalletInstance,
+	// instance of `pallet-bridge-relayers`, tracked by this extension
+	BridgeRelayersPalletInstance,
+	// message delivery transaction priority boost for every additional message
+	PriorityBoostPerMessage,
+>(
+	PhantomData<(
+		IdProvider,
+		Runtime,
+		BatchCallUnpacker,
+		BridgeBeefyPalletInstance,
+		BridgeMessagesPalletInstance,
+		BridgeRelayersPalletInstance,
+		PriorityBoostPerMessage,
+	)>,
+);
+
+impl<ID, R, BCU, BI, MI, RI, P> ExtensionConfig
+	for WithBeefyChainExtensionConfig<ID, R, BCU, BI, MI, RI, P>
+where
+	ID: StaticStrProvider,
+	R: BridgeRelayersConfig<RI>
+		+ BridgeMessagesConfig<MI, BridgedChain = pallet_bridge_beefy::BridgedChain<R, BI>>
+		+ BridgeBeefyConfig<BI>,
+	BCU: BatchCallUnpacker<R>,
+	BI: 'static,
+	MI: 'static,
+	RI: 'static,
+	P: Get<TransactionPriority>,
+	R::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>
+		+ BridgeBeefyCallSubtype<R, BI>
+		+ BridgeMessagesCallSubType<R, MI>,
+{
+	type IdProvider = ID;
+	type Runtime = R;
+	type BridgeMessagesPalletInstance = MI;
+	type BridgeRelayersPalletInstance = RI;
+	type PriorityBoostPerMessage = P;
+	type RemoteGrandpaChainBlockNumber = pallet_bridge_beefy::BridgedBlockNumber<R, BI>;
+	type LaneId = LaneIdOf<R, Self::BridgeMessagesPalletInstance>;
+
+	fn parse_and_check_for_obsolete_call(
+		call: &R::RuntimeCall,
+	) -> Result<
+		Option<ExtensionCallInfo<Self::RemoteGrandpaChainBlockNumber, Self::LaneId>>,
+		TransactionValidityError,
+	> {
+		let calls = BCU::unpack(call, 2);
+		let total_calls = calls.len();
+		let mut calls = calls.into_iter().map(Self::check_obsolete_parsed_call).rev();
+
+		let msgs_call = calls.next().transpose()?.and_then(|c| c.call_info());
+		let relay_finality_call =
+			calls.next().transpose()?.and_then(submit_finality_proof_info::<R, BI>);
+
+		Ok(match (total_calls, relay_finality_call, msgs_call) {
+			(2, Some(relay_finality_call), Some(msgs_call)) =>
+				Some(ExtensionCallInfo::RelayFinalityAndMsgs(relay_finality_call, msgs_call)),
+			(1, None, Some(msgs_call)) => Some(ExtensionCallInfo::Msgs(msgs_call)),
+			_ => None,
+		})
+	}
+
+	fn check_obsolete_parsed_call(
+		call: &R::RuntimeCall,
+	) -> Result<&R::RuntimeCall, TransactionValidityError> {
+		call.check_obsolete_submit_commitment()?;
+		call.check_obsolete_call()?;
+		Ok(call)
+	}
+
+	fn check_call_result(
+		call_info: &ExtensionCallInfo<Self::RemoteGrandpaChainBlockNumber, Self::LaneId>,
+		call_data: &mut ExtensionCallData,
+		relayer: &R::AccountId,
+	) -> bool {
+		verify_submit_commitment_succeeded::<Self, BI>(call_info, call_data, relayer) &&
+			verify_messages_call_succeeded::<Self>(call_info, call_data, relayer)
+	}
+}
+
+/// Returns the info of the BEEFY `submit_commitment` call in the form that is used by the
+/// extension for all finality calls.
+///
+/// BEEFY commitments have no size or weight limits, so the extra weight and size are always
+/// zero.
+fn submit_finality_proof_info<R, BI>(
+	call: &R::RuntimeCall,
+) -> Option<SubmitFinalityProofInfo<pallet_bridge_beefy::BridgedBlockNumber<R, BI>>>
+where
+	R: BridgeBeefyConfig<BI>,
+	BI: 'static,
+	R::RuntimeCall: BridgeBeefyCallSubtype<R, BI>,
+{
+	let info = call.submit_commitment_info()?;
+	Some(SubmitFinalityProofInfo {
+		block_number: info.block_number,
+		current_set_id: Some(info.validator_set_id),
+		is_mandatory: SubmitCommitmentHelper::<R, BI>::is_mandatory(info.next_validator_set_id),
+		is_free_execution_expected: false,
+		extra_weight: Weight::zero(),
+		extra_size: 0,
+	})
+}
+
+/// If the batch call contains the BEEFY chain state update call, verify that it
+/// has been successful.
+///
+/// Only returns false when BEEFY chain state update call has failed.
+fn verify_submit_commitment_succeeded<C, BI>(
+	call_info: &ExtensionCallInfo<C::RemoteGrandpaChainBlockNumber, C::LaneId>,
+	_call_data: &mut ExtensionCallData,
+	relayer: &<C::Runtime as SystemConfig>::AccountId,
+) -> bool
+where
+	C: ExtensionConfig,
+	BI: 'static,
+	C::Runtime: BridgeBeefyConfig<BI>,
+	<C::Runtime as BridgeBeefyConfig<BI>>::BridgedChain:
+		Chain<BlockNumber = C::RemoteGrandpaChainBlockNumber>,
+{
+	let Some(finality_proof_info) = call_info.submit_finality_proof_info() else { return true };
+
+	if !SubmitCommitmentHelper::<C::Runtime, BI>::was_successful(finality_proof_info.block_number) {
+		// we only refund relayer if all calls have updated chain state
+		log::trace!(
+			target: LOG_TARGET,
+			"{}.{:?}: relayer {:?} has submitted invalid BEEFY commitment",
+			C::IdProvider::STR,
+			call_info.messages_call_info().lane_id(),
+			relayer,
+		);
+		return false
+	}
+
+	true
+}
@@ -55,11 +55,13 @@ use sp_runtime::{
 	DispatchResult, RuntimeDebug,
 };
 
+pub use beefy_adapter::WithBeefyChainExtensionConfig;
 pub use grandpa_adapter::WithGrandpaChainExtensionConfig;
 pub use messages_adapter::WithMessagesExtensionConfig;
 pub use parachain_adapter::WithParachainExtensionConfig;
 pub use priority::*;
 
+mod beefy_adapter;
 mod grandpa_adapter;
 mod messages_adapter;
 mod parachain_adapter;
@@ -0,0 +1,468 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY finality proofs of the bridged chain headers and equivocations in them.
+
+use crate::{
+	authority_set_info, signatures_required, BeefyAuthorityIdOf, BeefyAuthorityIdToMerkleLeafOf,
+	BeefyAuthoritySetInfoOf, BeefyAuthoritySetOf, BeefyMmrLeafOf, BeefySignedCommitmentOf,
+	BeefyValidatorSignatureOf, ChainWithBeefy, Commitment, MmrHashOf, MmrHashingOf, MmrProofOf,
+};
+
+use bp_header_chain::{FinalityProof, FindEquivocations, HeaderFinalityInfo};
+use bp_runtime::{BlockNumberOf, HashOf};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::{CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use scale_info::TypeInfo;
+use sp_consensus_beefy::{check_double_voting_proof, DoubleVotingProof, VoteMessage};
+use sp_runtime::traits::Convert;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// BEEFY finality proof of the bridged chain header.
+///
+/// The commitment is signed for some block `N` and its payload contains the MMR root at this
+/// block. The last leaf of this MMR is added at block `N` and contains the number and hash of its
+/// parent, so the header that is proved to be finalized is the header `N - 1`.
+#[derive(
+	CloneNoBound,
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct BeefyFinalityProof<C: ChainWithBeefy> {
+	/// Commitment, signed by the BEEFY validators.
+	pub commitment: BeefySignedCommitmentOf<C>,
+	/// Validator set that has signed the `commitment`.
+	pub validator_set: BeefyAuthoritySetOf<C>,
+	/// MMR leaf, added to the MMR at the `commitment` block.
+	pub mmr_leaf: BeefyMmrLeafOf<C>,
+	/// Proof of the `mmr_leaf` inclusion into the MMR with the root from the `commitment` payload.
+	pub mmr_proof: MmrProofOf<C>,
+}
+
+impl<C: ChainWithBeefy> BeefyFinalityProof<C> {
+	/// Removes signatures that are not required to verify the commitment.
+	///
+	/// The bridge pallet stops verifying signatures once it has seen [`signatures_required`]
+	/// correct signatures, so the rest of them only make the call larger.
+	pub fn optimize(&mut self) {
+		let mut required = signatures_required(self.validator_set.len());
+		for signature in self.commitment.signatures.iter_mut().filter(|s| s.is_some()) {
+			if required == 0 {
+				*signature = None;
+			} else {
+				required -= 1;
+			}
+		}
+	}
+
+	/// Converts the proof into its compact form.
+	pub fn into_compact(self) -> CompactBeefyFinalityProof<C> {
+		let merkle_leafs = self
+			.validator_set
+			.validators()
+			.iter()
+			.cloned()
+			.map(BeefyAuthorityIdToMerkleLeafOf::<C>::convert)
+			.collect::<Vec<_>>();
+		let signatures = self
+			.validator_set
+			.validators()
+			.iter()
+			.zip(self.commitment.signatures)
+			.enumerate()
+			.filter_map(|(index, (validator, signature))| {
+				Some(CompactBeefySignature {
+					validator_index: index as u32,
+					validator: validator.clone(),
+					signature: signature?,
+					validator_proof: binary_merkle_tree::merkle_proof::<MmrHashingOf<C>, _, _>(
+						merkle_leafs.iter(),
+						index as u32,
+					)
+					.proof,
+				})
+			})
+			.take(signatures_required(merkle_leafs.len()))
+			.collect();
+
+		CompactBeefyFinalityProof {
+			commitment: self.commitment.commitment,
+			validator_set_len: merkle_leafs.len() as u32,
+			signatures,
+			mmr_leaf: self.mmr_leaf,
+			mmr_proof: self.mmr_proof,
+		}
+	}
+}
+
+impl<C: ChainWithBeefy> FinalityProof<HashOf<C>, BlockNumberOf<C>> for BeefyFinalityProof<C>
+where
+	Self: Send + Sync,
+{
+	fn target_header_hash(&self) -> HashOf<C> {
+		self.mmr_leaf.parent_number_and_hash.1
+	}
+
+	fn target_header_number(&self) -> BlockNumberOf<C> {
+		self.mmr_leaf.parent_number_and_hash.0
+	}
+}
+
+/// Signature of the BEEFY validator in the [`CompactBeefyFinalityProof`].
+#[derive(
+	CloneNoBound, Decode, Encode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct CompactBeefySignature<C: ChainWithBeefy> {
+	/// Index of the validator in the validator set.
+	pub validator_index: u32,
+	/// Identifier of the validator.
+	pub validator: BeefyAuthorityIdOf<C>,
+	/// Signature of the commitment, produced by the validator.
+	pub signature: BeefyValidatorSignatureOf<C>,
+	/// Proof of the validator membership in the `keyset_commitment` of the validator set.
+	pub validator_proof: Vec<MmrHashOf<C>>,
+}
+
+/// Compact form of the [`BeefyFinalityProof`].
+///
+/// Light clients of non-Substrate chains only track the `keyset_commitment` of the BEEFY
+/// authority set. So instead of the whole validator set, the compact proof contains just
+/// [`signatures_required`] signatures, each with a proof of the validator membership in the
+/// authority set.
+#[derive(
+	CloneNoBound, Decode, Encode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(C))]
+pub struct CompactBeefyFinalityProof<C: ChainWithBeefy> {
+	/// Commitment, signed by the BEEFY validators.
+	pub commitment: Commitment<BlockNumberOf<C>>,
+	/// Number of validators in the validator set that has signed the `commitment`.
+	pub validator_set_len: u32,
+	/// Signatures of the `commitment`, ordered by the validator index.
+	pub signatures: Vec<CompactBeefySignature<C>>,
+	/// MMR leaf, added to the MMR at the `commitment` block.
+	pub mmr_leaf: BeefyMmrLeafOf<C>,
+	/// Proof of the `mmr_leaf` inclusion into the MMR with the root from the `commitment` payload.
+	pub mmr_proof: MmrProofOf<C>,
+}
+
+/// BEEFY-related info associated to a header, that is stored by the bridge pallet.
+pub type StoredHeaderBeefyInfo<C> =
+	HeaderFinalityInfo<BeefyFinalityProof<C>, BeefyAuthoritySetInfoOf<C>>;
+
+/// BEEFY equivocation proof of the given chain.
+pub type BeefyEquivocationProofOf<C> =
+	DoubleVotingProof<BlockNumberOf<C>, BeefyAuthorityIdOf<C>, BeefyValidatorSignatureOf<C>>;
+
+/// Errors that may happen when looking for BEEFY equivocations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BeefyEquivocationsFinderError {
+	/// The synced proof is signed by the validator set that doesn't match the verification
+	/// context.
+	InvalidValidatorSet,
+}
+
+/// BEEFY equivocations finder.
+///
+/// Reports validators that have signed both the synced commitment and a different commitment
+/// for the same block.
+pub struct BeefyEquivocationsFinder<C>(PhantomData<C>);
+
+impl<C: ChainWithBeefy>
+	FindEquivocations<
+		BeefyFinalityProof<C>,
+		BeefyAuthoritySetInfoOf<C>,
+		BeefyEquivocationProofOf<C>,
+	> for BeefyEquivocationsFinder<C>
+{
+	type Error = BeefyEquivocationsFinderError;
+
+	fn find_equivocations(
+		verification_context: &BeefyAuthoritySetInfoOf<C>,
+		synced_proof: &BeefyFinalityProof<C>,
+		source_proofs: &[BeefyFinalityProof<C>],
+	) -> Result<Vec<BeefyEquivocationProofOf<C>>, Self::Error> {
+		if authority_set_info::<C>(&synced_proof.validator_set) != *verification_context {
+			return Err(BeefyEquivocationsFinderError::InvalidValidatorSet)
+		}
+
+		let synced = &synced_proof.commitment;
+		let mut equivocations = Vec::new();
+		for source in source_proofs.iter().map(|proof| &proof.commitment) {
+			if source.commitment.block_number != synced.commitment.block_number ||
+				source.commitment.validator_set_id != synced.commitment.validator_set_id ||
+				source.commitment == synced.commitment
+			{
+				continue
+			}
+
+			let votes = synced_proof
+				.validator_set
+				.validators()
+				.iter()
+				.zip(synced.signatures.iter().zip(source.signatures.iter()));
+			for (validator, (first, second)) in votes {
+				let (Some(first), Some(second)) = (first, second) else { continue };
+				let equivocation = DoubleVotingProof {
+					first: VoteMessage {
+						commitment: synced.commitment.clone(),
+						id: validator.clone(),
+						signature: first.clone(),
+					},
+					second: VoteMessage {
+						commitment: source.commitment.clone(),
+						id: validator.clone(),
+						signature: second.clone(),
+					},
+				};
+				if !equivocations.contains(&equivocation) &&
+					check_double_voting_proof::<_, _, C::CommitmentHasher>(&equivocation)
+				{
+					equivocations.push(equivocation);
+				}
+			}
+		}
+
+		Ok(equivocations)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		BeefyAuthoritySet, BeefyPayload, EcdsaValidatorId, MmrLeafVersion, MmrProof,
+		SignedCommitment, ValidatorSet, MMR_ROOT_PAYLOAD_ID,
+	};
+	use bp_runtime::{Chain, ChainId};
+	use frame_support::weights::Weight;
+	use sp_core::{ecdsa, Pair, H256};
+	use sp_runtime::{
+		testing::Header,
+		traits::{BlakeTwo256, Hash, Keccak256},
+		StateVersion,
+	};
+
+	#[derive(Debug)]
+	struct TestChain;
+
+	impl Chain for TestChain {
+		const ID: ChainId = *b"test";
+
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hasher = BlakeTwo256;
+		type Header = Header;
+
+		type AccountId = u64;
+		type Balance = u64;
+		type Nonce = u64;
+		type Signature = sp_runtime::testing::TestSignature;
+
+		const STATE_VERSION: StateVersion = StateVersion::V1;
+
+		fn max_extrinsic_size() -> u32 {
+			unreachable!()
+		}
+		fn max_extrinsic_weight() -> Weight {
+			unreachable!()
+		}
+	}
+
+	impl ChainWithBeefy for TestChain {
+		const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str = "Beefy";
+
+		type CommitmentHasher = Keccak256;
+		type MmrHashing = Keccak256;
+		type MmrHash = H256;
+		type BeefyMmrLeafExtra = ();
+		type AuthorityId = EcdsaValidatorId;
+		type AuthorityIdToMerkleLeaf = crate::BeefyEcdsaToEthereum;
+	}
+
+	const VALIDATORS: u8 = 4;
+
+	fn validator_pairs() -> Vec<ecdsa::Pair> {
+		(0..VALIDATORS).map(|index| ecdsa::Pair::from_seed(&[index + 1; 32])).collect()
+	}
+
+	fn validator_set(id: u64) -> BeefyAuthoritySetOf<TestChain> {
+		let validators: Vec<EcdsaValidatorId> =
+			validator_pairs().iter().map(|pair| pair.public().into()).collect();
+		ValidatorSet::new(validators, id).expect("validator set is not empty")
+	}
+
+	fn commitment(block_number: u64, mmr_root: H256) -> Commitment<u64> {
+		Commitment {
+			payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+			block_number,
+			validator_set_id: 0,
+		}
+	}
+
+	fn finality_proof(
+		commitment: Commitment<u64>,
+		signers: &[usize],
+	) -> BeefyFinalityProof<TestChain> {
+		let commitment_hash = Keccak256::hash(&commitment.encode());
+		let signatures: Vec<Option<BeefyValidatorSignatureOf<TestChain>>> = validator_pairs()
+			.iter()
+			.enumerate()
+			.map(|(index, pair)| {
+				signers
+					.contains(&index)
+					.then(|| pair.sign_prehashed(commitment_hash.as_fixed_bytes()).into())
+			})
+			.collect();
+		let validator_set = validator_set(0);
+		let next_authority_set = authority_set_info::<TestChain>(&validator_set);
+
+		BeefyFinalityProof {
+			commitment: SignedCommitment { commitment: commitment.clone(), signatures },
+			validator_set,
+			mmr_leaf: BeefyMmrLeafOf::<TestChain> {
+				version: MmrLeafVersion::new(0, 0),
+				parent_number_and_hash: (commitment.block_number - 1, H256::repeat_byte(1)),
+				beefy_next_authority_set: BeefyAuthoritySet { id: 1, ..next_authority_set },
+				leaf_extra: (),
+			},
+			mmr_proof: MmrProof { leaf_indices: vec![0], leaf_count: 1, items: vec![] },
+		}
+	}
+
+	fn signers(proof: &BeefyFinalityProof<TestChain>) -> Vec<usize> {
+		proof
+			.commitment
+			.signatures
+			.iter()
+			.enumerate()
+			.filter_map(|(index, signature)| signature.as_ref().map(|_| index))
+			.collect()
+	}
+
+	#[test]
+	fn optimize_removes_extra_signatures() {
+		let mut proof = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 1, 2, 3]);
+		proof.optimize();
+		assert_eq!(signers(&proof), vec![0, 1, 2]);
+
+		// if there are not enough signatures, nothing is removed
+		let mut proof = finality_proof(commitment(10, H256::repeat_byte(2)), &[1, 3]);
+		proof.optimize();
+		assert_eq!(signers(&proof), vec![1, 3]);
+	}
+
+	#[test]
+	fn into_compact_keeps_required_signatures_with_membership_proofs() {
+		let proof = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 2, 3]);
+		let authority_set = authority_set_info::<TestChain>(&proof.validator_set);
+		let compact = proof.clone().into_compact();
+
+		assert_eq!(compact.commitment, proof.commitment.commitment);
+		assert_eq!(compact.validator_set_len, VALIDATORS as u32);
+		assert_eq!(compact.mmr_leaf, proof.mmr_leaf);
+		assert_eq!(compact.mmr_proof, proof.mmr_proof);
+		assert_eq!(
+			compact.signatures.iter().map(|s| s.validator_index).collect::<Vec<_>>(),
+			vec![0, 2, 3],
+		);
+		for signature in compact.signatures {
+			let index = signature.validator_index as usize;
+			assert_eq!(signature.validator, proof.validator_set.validators()[index]);
+			assert_eq!(Some(signature.signature), proof.commitment.signatures[index]);
+
+			let leaf = BeefyAuthorityIdToMerkleLeafOf::<TestChain>::convert(signature.validator);
+			assert!(binary_merkle_tree::verify_proof::<Keccak256, _, _>(
+				&authority_set.keyset_commitment,
+				signature.validator_proof,
+				VALIDATORS as u32,
+				index as u32,
+				&leaf,
+			));
+		}
+	}
+
+	#[test]
+	fn into_compact_drops_extra_signatures() {
+		let proof = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 1, 2, 3]);
+		let compact = proof.into_compact();
+		assert_eq!(
+			compact.signatures.iter().map(|s| s.validator_index).collect::<Vec<_>>(),
+			vec![0, 1, 2],
+		);
+	}
+
+	#[test]
+	fn equivocations_finder_reports_validators_that_signed_both_commitments() {
+		let synced = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 1, 2]);
+		let context = authority_set_info::<TestChain>(&synced.validator_set);
+		let sources = vec![
+			// same commitment is not an equivocation
+			synced.clone(),
+			// commitment for other block is not an equivocation
+			finality_proof(commitment(11, H256::repeat_byte(3)), &[0, 1, 2, 3]),
+			// validators 1 and 2 have signed both commitments
+			finality_proof(commitment(10, H256::repeat_byte(3)), &[1, 2, 3]),
+			// duplicate equivocations are reported once
+			finality_proof(commitment(10, H256::repeat_byte(3)), &[1]),
+		];
+
+		let equivocations =
+			BeefyEquivocationsFinder::<TestChain>::find_equivocations(&context, &synced, &sources)
+				.unwrap();
+		assert_eq!(
+			equivocations.iter().map(|e| e.offender_id().clone()).collect::<Vec<_>>(),
+			vec![
+				synced.validator_set.validators()[1].clone(),
+				synced.validator_set.validators()[2].clone(),
+			],
+		);
+		assert!(equivocations.iter().all(|e| e.first.commitment == synced.commitment.commitment));
+	}
+
+	#[test]
+	fn equivocations_finder_ignores_invalid_signatures() {
+		let synced = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 1, 2]);
+		let context = authority_set_info::<TestChain>(&synced.validator_set);
+		let mut source = finality_proof(commitment(10, H256::repeat_byte(3)), &[1, 2]);
+		source.commitment.signatures[2] = synced.commitment.signatures[2].clone();
+
+		let equivocations =
+			BeefyEquivocationsFinder::<TestChain>::find_equivocations(&context, &synced, &[source])
+				.unwrap();
+		assert_eq!(equivocations.len(), 1);
+		assert_eq!(equivocations[0].offender_id(), &synced.validator_set.validators()[1]);
+	}
+
+	#[test]
+	fn equivocations_finder_rejects_unknown_validator_set() {
+		let synced = finality_proof(commitment(10, H256::repeat_byte(2)), &[0, 1, 2]);
+		let mut context = authority_set_info::<TestChain>(&synced.validator_set);
+		context.id += 1;
+
+		assert_eq!(
+			BeefyEquivocationsFinder::<TestChain>::find_equivocations(&context, &synced, &[]),
+			Err(BeefyEquivocationsFinderError::InvalidValidatorSet),
+		);
+	}
+}
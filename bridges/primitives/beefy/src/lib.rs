@@ -20,9 +20,16 @@
 #![warn(missing_docs)]
 
 pub use binary_merkle_tree::merkle_root;
+pub use finality_proof::{
+	BeefyEquivocationProofOf, BeefyEquivocationsFinder, BeefyEquivocationsFinderError,
+	BeefyFinalityProof, CompactBeefyFinalityProof, CompactBeefySignature, StoredHeaderBeefyInfo,
+};
 pub use pallet_beefy_mmr::BeefyEcdsaToEthereum;
 pub use pallet_mmr::{
-	primitives::{DataOrHash as MmrDataOrHash, LeafProof as MmrProof},
+	primitives::{
+		DataOrHash as MmrDataOrHash, EncodableOpaqueLeaf as MmrEncodableOpaqueLeaf,
+		Error as MmrError, LeafProof as MmrProof,
+	},
 	verify_leaves_proof as verify_mmr_leaves_proof,
 };
 pub use sp_consensus_beefy::{
@@ -31,20 +38,26 @@ pub use sp_consensus_beefy::{
 	},
 	known_payloads::MMR_ROOT_ID as MMR_ROOT_PAYLOAD_ID,
 	mmr::{BeefyAuthoritySet, MmrLeafVersion},
-	BeefyAuthorityId, Commitment, Payload as BeefyPayload, SignedCommitment, ValidatorSet,
-	ValidatorSetId, BEEFY_ENGINE_ID,
+	BeefyAuthorityId, Commitment, ConsensusLog as BeefyConsensusLog, OpaqueKeyOwnershipProof,
+	Payload as BeefyPayload, SignedCommitment, ValidatorSet, ValidatorSetId,
+	VersionedFinalityProof, BEEFY_ENGINE_ID,
 };
 
+mod finality_proof;
+pub mod storage_keys;
+
+use bp_header_chain::ConsensusLogReader;
 use bp_runtime::{BasicOperatingMode, BlockNumberOf, Chain, HashOf};
-use codec::{Decode, Encode};
+use codec::{Codec, Decode, Encode};
 use frame_support::Parameter;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
+	generic::OpaqueDigestItemId,
 	traits::{Convert, MaybeSerializeDeserialize},
-	RuntimeAppPublic, RuntimeDebug,
+	Digest, RuntimeAppPublic, RuntimeDebug,
 };
-use sp_std::prelude::*;
+use sp_std::{marker::PhantomData, prelude::*};
 
 /// Substrate-based chain with BEEFY && MMR pallets deployed.
 ///
@@ -52,6 +65,13 @@ use sp_std::prelude::*;
 /// primitives. Some of types can be configured in low-level pallets, but are constrained
 /// when BEEFY+MMR bundle is used.
 pub trait ChainWithBeefy: Chain {
+	/// Name of the bridge BEEFY pallet (used in `construct_runtime` macro call) that is deployed
+	/// at some other chain to bridge with this `ChainWithBeefy`.
+	///
+	/// We assume that all chains that are bridging with this `ChainWithBeefy` are using
+	/// the same name.
+	const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str;
+
 	/// The hashing algorithm used to compute the digest of the BEEFY commitment.
 	///
 	/// Corresponds to the hashing algorithm, used by `sc_consensus_beefy::BeefyKeystore`.
@@ -149,3 +169,68 @@ pub struct ImportedCommitment<BlockNumber, BlockHash, MmrHash> {
 	/// MMR root at the imported block.
 	pub mmr_root: MmrHash,
 }
+
+/// High level info about the imported commitments.
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, Clone, TypeInfo)]
+pub struct ImportedCommitmentsInfoData<BlockNumber> {
+	/// Best known block number, provided in a BEEFY commitment. However this is not
+	/// the best proven block. The best proven block is this block's parent.
+	pub best_block_number: BlockNumber,
+	/// The head of the `ImportedBlockNumbers` ring buffer.
+	pub next_block_number_index: u32,
+}
+
+/// Number of correct signatures, required from given validators set to accept signed
+/// commitment.
+///
+/// We're using 'conservative' approach here, where signatures of `2/3+1` validators are
+/// required.
+pub fn signatures_required(validators_len: usize) -> usize {
+	validators_len - validators_len.saturating_sub(1) / 3
+}
+
+/// Returns BEEFY authority set info of the given validator set.
+pub fn authority_set_info<C: ChainWithBeefy>(
+	validator_set: &BeefyAuthoritySetOf<C>,
+) -> BeefyAuthoritySetInfoOf<C> {
+	let merkle_leafs = validator_set
+		.validators()
+		.iter()
+		.cloned()
+		.map(BeefyAuthorityIdToMerkleLeafOf::<C>::convert)
+		.collect::<Vec<_>>();
+	BeefyAuthoritySet {
+		id: validator_set.id(),
+		len: merkle_leafs.len() as u32,
+		keyset_commitment: merkle_root::<MmrHashingOf<C>, _>(merkle_leafs),
+	}
+}
+
+/// A struct that provides helper methods for querying the BEEFY consensus log.
+///
+/// The runtime deposits the `AuthoritiesChange` log in the first block of every BEEFY session.
+/// The commitment for this block is mandatory and the bridge pallet only accepts commitments,
+/// signed by its current authority set, so these headers must be relayed.
+pub struct BeefyConsensusLogReader<AuthorityId>(PhantomData<AuthorityId>);
+
+impl<AuthorityId: Codec> BeefyConsensusLogReader<AuthorityId> {
+	/// Find and return the authorities change digest item.
+	pub fn find_authorities_change(digest: &Digest) -> Option<ValidatorSet<AuthorityId>> {
+		let id = OpaqueDigestItemId::Consensus(&BEEFY_ENGINE_ID);
+
+		let filter_log = |log: BeefyConsensusLog<AuthorityId>| match log {
+			BeefyConsensusLog::AuthoritiesChange(validator_set) => Some(validator_set),
+			_ => None,
+		};
+
+		// find the first consensus digest with the right ID which converts to
+		// the right kind of consensus log.
+		digest.convert_first(|l| l.try_to(id).and_then(filter_log))
+	}
+}
+
+impl<AuthorityId: Codec> ConsensusLogReader for BeefyConsensusLogReader<AuthorityId> {
+	fn schedules_authorities_change(digest: &Digest) -> bool {
+		BeefyConsensusLogReader::<AuthorityId>::find_authorities_change(digest).is_some()
+	}
+}
@@ -0,0 +1,78 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives for exposing the compact BEEFY finality proofs in the CLI.
+
+use crate::{
+	cli::{bridge::*, chain_schema::*},
+	finality_base::engine::{Beefy, Engine},
+};
+
+use async_trait::async_trait;
+use clap::Parser;
+use codec::Encode;
+use relay_substrate_client::{
+	BlockNumberOf, BlockWithJustification, Chain, ChainWithBeefy, Client,
+};
+use relay_utils::UniqueSaturatedInto;
+
+/// Compact BEEFY finality proof exporting params.
+#[derive(Parser)]
+pub struct ExportBeefyProofParams {
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	/// Number of the source chain header that we want to prove. Its child must have a persistent
+	/// BEEFY justification at the [`Self::source`] node, otherwise the command will fail.
+	#[arg(long)]
+	number: u128,
+}
+
+/// Trait used for exporting compact BEEFY finality proofs of the source chain headers.
+///
+/// Compact proofs are meant for light clients of non-Substrate chains, which only track the
+/// BEEFY authority set commitment.
+#[async_trait]
+pub trait BeefyProofExporter: CliBridgeBase
+where
+	Self::Source: ChainWithBeefy,
+{
+	/// Print the hex-encoded compact BEEFY finality proof of the source chain header.
+	async fn export_beefy_proof(data: ExportBeefyProofParams) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let number: BlockNumberOf<Self::Source> = data.number.unique_saturated_into();
+
+		let justification_block_number = Beefy::<Self::Source>::justification_block_number(number);
+		let justification_block = source_client
+			.block_by_hash(source_client.header_hash_by_number(justification_block_number).await?)
+			.await?;
+		let justification = justification_block
+			.justification(<Beefy<Self::Source> as Engine<Self::Source>>::ID)
+			.ok_or_else(|| {
+				anyhow::format_err!(
+					"{} block #{} has no persistent BEEFY justification",
+					Self::Source::NAME,
+					justification_block_number,
+				)
+			})?;
+
+		let proof =
+			Beefy::<Self::Source>::finality_proof_from_justification(&source_client, justification)
+				.await?;
+		println!("0x{}", hex::encode(proof.into_compact().encode()));
+
+		Ok(())
+	}
+}
@@ -24,6 +24,7 @@ use std::str::FromStr;
 pub mod bridge;
 pub mod chain_schema;
 pub mod detect_equivocations;
+pub mod export_beefy_proof;
 pub mod init_bridge;
 pub mod relay_headers;
 pub mod relay_headers_and_messages;
@@ -32,10 +32,13 @@ use crate::{
 use relay_substrate_client::Client;
 
 /// Chain headers relaying params.
+///
+/// Works with any finality engine of the bridge. For BEEFY bridges, signed commitments are
+/// relayed to the bridge BEEFY pallet and there are no mandatory headers.
 #[derive(Parser)]
 pub struct RelayHeadersParams {
-	/// If passed, only mandatory headers (headers that are changing the GRANDPA authorities set)
-	/// are relayed.
+	/// If passed, only mandatory headers (headers that are changing the finality authorities
+	/// set) are relayed.
 	#[arg(long)]
 	only_mandatory_headers: bool,
 	/// If passed, only free headers (mandatory and every Nth header, if configured in runtime)
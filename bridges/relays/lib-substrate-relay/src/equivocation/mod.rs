@@ -27,11 +27,15 @@ use crate::{
 };
 
 use async_trait::async_trait;
+use bp_beefy::BeefyEquivocationProofOf;
 use bp_runtime::{AccountIdOf, BlockNumberOf, HashOf};
 use equivocation_detector::EquivocationDetectionPipeline;
 use finality_relay::FinalityPipeline;
+use pallet_beefy::{Call as BeefyCall, Config as BeefyConfig};
 use pallet_grandpa::{Call as GrandpaCall, Config as GrandpaConfig};
-use relay_substrate_client::{AccountKeyPairOf, CallOf, Chain, ChainWithTransactions, Client};
+use relay_substrate_client::{
+	AccountKeyPairOf, CallOf, Chain, ChainWithBeefy, ChainWithTransactions, Client,
+};
 use relay_utils::metrics::MetricsParams;
 use sp_core::Pair;
 use sp_runtime::traits::{Block, Header};
@@ -169,6 +173,38 @@ where
 	}
 }
 
+/// Building the BEEFY `report_double_voting` call when having direct access to the source chain
+/// runtime.
+pub struct DirectReportBeefyEquivocationCallBuilder<P, R> {
+	_phantom: PhantomData<(P, R)>,
+}
+
+impl<P, R> ReportEquivocationCallBuilder<P> for DirectReportBeefyEquivocationCallBuilder<P, R>
+where
+	P: SubstrateEquivocationDetectionPipeline,
+	P::SourceChain: ChainWithBeefy,
+	P::FinalityEngine:
+		Engine<P::SourceChain, EquivocationProof = BeefyEquivocationProofOf<P::SourceChain>>,
+	R: frame_system::Config
+		+ BeefyConfig<
+			BeefyId = bp_beefy::BeefyAuthorityIdOf<P::SourceChain>,
+			KeyOwnerProof = KeyOwnerProofOf<P>,
+		>,
+	<R::Block as Block>::Header: Header<Number = BlockNumberOf<P::SourceChain>>,
+	CallOf<P::SourceChain>: From<BeefyCall<R>>,
+{
+	fn build_report_equivocation_call(
+		equivocation_proof: EquivocationProofOf<P>,
+		key_owner_proof: KeyOwnerProofOf<P>,
+	) -> CallOf<P::SourceChain> {
+		BeefyCall::<R>::report_double_voting {
+			equivocation_proof: Box::new(equivocation_proof),
+			key_owner_proof,
+		}
+		.into()
+	}
+}
+
 /// Macro that generates `ReportEquivocationCallBuilder` implementation for the case where
 /// we only have access to the mocked version of the source chain runtime.
 #[rustfmt::skip]
@@ -24,14 +24,18 @@ use crate::{
 };
 
 use async_trait::async_trait;
+use bp_beefy::{
+	BeefyAuthorityIdOf, BeefyAuthoritySetInfoOf, BeefyFinalityProof, BeefyMmrLeafExtraOf, MmrHashOf,
+};
 use bp_header_chain::justification::{GrandpaJustification, JustificationVerificationContext};
 use finality_relay::{
 	FinalityPipeline, FinalitySyncPipeline, HeadersToRelay, SourceClient, TargetClient,
 };
+use pallet_bridge_beefy::{Call as BridgeBeefyCall, Config as BridgeBeefyConfig};
 use pallet_bridge_grandpa::{Call as BridgeGrandpaCall, Config as BridgeGrandpaConfig};
 use relay_substrate_client::{
 	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain,
-	ChainWithTransactions, Client, HashOf, HeaderOf, SyncHeader,
+	ChainWithBeefy, ChainWithTransactions, Client, HashOf, HeaderOf, SyncHeader,
 };
 use relay_utils::{metrics::MetricsParams, TrackedTransactionStatus, TransactionTracker};
 use sp_core::Pair;
@@ -238,6 +242,91 @@ macro_rules! generate_submit_finality_proof_ex_call_builder {
 	};
 }
 
+/// Building `submit_commitment` call of the bridge BEEFY pallet when you have direct access to
+/// the target chain runtime.
+pub struct DirectSubmitBeefyCommitmentCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitFinalityProofCallBuilder<P> for DirectSubmitBeefyCommitmentCallBuilder<P, R, I>
+where
+	P: SubstrateFinalitySyncPipeline,
+	P::SourceChain: ChainWithBeefy,
+	R: BridgeBeefyConfig<I>,
+	I: 'static,
+	R::BridgedChain: bp_beefy::ChainWithBeefy<
+			AuthorityId = BeefyAuthorityIdOf<P::SourceChain>,
+			MmrHash = MmrHashOf<P::SourceChain>,
+			BeefyMmrLeafExtra = BeefyMmrLeafExtraOf<P::SourceChain>,
+		> + bp_runtime::Chain<
+			BlockNumber = BlockNumberOf<P::SourceChain>,
+			Hash = HashOf<P::SourceChain>,
+		>,
+	CallOf<P::TargetChain>: From<BridgeBeefyCall<R, I>>,
+	P::FinalityEngine: Engine<
+		P::SourceChain,
+		FinalityProof = BeefyFinalityProof<P::SourceChain>,
+		FinalityVerificationContext = BeefyAuthoritySetInfoOf<P::SourceChain>,
+	>,
+{
+	fn build_submit_finality_proof_call(
+		_header: SyncHeader<HeaderOf<P::SourceChain>>,
+		proof: BeefyFinalityProof<P::SourceChain>,
+		_is_free_execution_expected: bool,
+		_context: BeefyAuthoritySetInfoOf<P::SourceChain>,
+	) -> CallOf<P::TargetChain> {
+		BridgeBeefyCall::<R, I>::submit_commitment {
+			commitment: proof.commitment,
+			validator_set: proof.validator_set,
+			mmr_leaf: Box::new(proof.mmr_leaf),
+			mmr_proof: proof.mmr_proof,
+		}
+		.into()
+	}
+}
+
+/// Macro that generates `SubmitFinalityProofCallBuilder` implementation for the case when
+/// you only have an access to the mocked version of target chain runtime. In this case you
+/// should provide "name" of the call variant for the bridge BEEFY calls and the "name" of
+/// the variant for the `submit_commitment` call within that first option.
+#[rustfmt::skip]
+#[macro_export]
+macro_rules! generate_submit_beefy_commitment_call_builder {
+	($pipeline:ident, $mocked_builder:ident, $bridge_beefy:path, $submit_commitment:path) => {
+		pub struct $mocked_builder;
+
+		impl $crate::finality::SubmitFinalityProofCallBuilder<$pipeline>
+			for $mocked_builder
+		{
+			fn build_submit_finality_proof_call(
+				_header: relay_substrate_client::SyncHeader<
+					relay_substrate_client::HeaderOf<
+						<$pipeline as $crate::finality_base::SubstrateFinalityPipeline>::SourceChain
+					>
+				>,
+				proof: bp_beefy::BeefyFinalityProof<
+					<$pipeline as $crate::finality_base::SubstrateFinalityPipeline>::SourceChain
+				>,
+				_is_free_execution_expected: bool,
+				_context: bp_beefy::BeefyAuthoritySetInfoOf<
+					<$pipeline as $crate::finality_base::SubstrateFinalityPipeline>::SourceChain
+				>,
+			) -> relay_substrate_client::CallOf<
+				<$pipeline as $crate::finality_base::SubstrateFinalityPipeline>::TargetChain
+			> {
+				bp_runtime::paste::item! {
+					$bridge_beefy($submit_commitment {
+						commitment: proof.commitment,
+						validator_set: proof.validator_set,
+						mmr_leaf: Box::new(proof.mmr_leaf),
+						mmr_proof: proof.mmr_proof,
+					})
+				}
+			}
+		}
+	};
+}
+
 /// Run Substrate-to-Substrate finality sync loop.
 pub async fn run<P: SubstrateFinalitySyncPipeline>(
 	source_client: impl Client<P::SourceChain>,
@@ -26,7 +26,6 @@ use crate::{
 use async_std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use bp_header_chain::FinalityProof;
-use codec::Decode;
 use finality_relay::{SourceClient, SourceClientBase};
 use futures::{
 	select,
@@ -253,13 +252,30 @@ async fn header_and_finality_proof<P: SubstrateFinalitySyncPipeline>(
 	let header_hash = client.header_hash_by_number(number).await?;
 	let signed_block = client.block_by_hash(header_hash).await?;
 
-	let justification = signed_block
-		.justification(P::FinalityEngine::ID)
-		.map(|raw_justification| {
-			SubstrateFinalityProof::<P>::decode(&mut raw_justification.as_slice())
-		})
-		.transpose()
-		.map_err(Error::ResponseParseFailed)?;
+	// finality of the header may be proved by the justification of some other block
+	let justification_block_number = P::FinalityEngine::justification_block_number(number);
+	let raw_justification = if justification_block_number == number {
+		signed_block.justification(P::FinalityEngine::ID).cloned()
+	} else if justification_block_number <= client.best_finalized_header_number().await? {
+		let justification_block_hash =
+			client.header_hash_by_number(justification_block_number).await?;
+		client
+			.block_by_hash(justification_block_hash)
+			.await?
+			.justification(P::FinalityEngine::ID)
+			.cloned()
+	} else {
+		None
+	};
+
+	let justification = match raw_justification {
+		Some(raw_justification) => Some(
+			P::FinalityEngine::finality_proof_from_justification(client, &raw_justification)
+				.await?,
+		),
+		None => None,
+	}
+	.filter(|justification| justification.target_header_number() == number);
 
 	Ok((signed_block.header().into(), justification))
 }
@@ -18,6 +18,13 @@
 
 use crate::error::Error;
 use async_trait::async_trait;
+use bp_beefy::{
+	authority_set_info, BeefyAuthorityIdOf, BeefyAuthoritySetInfoOf, BeefyAuthoritySetOf,
+	BeefyConsensusLogReader, BeefyEquivocationProofOf, BeefyEquivocationsFinder,
+	BeefyFinalityProof, BeefyMmrLeafOf, ImportedCommitmentsInfoData, MmrEncodableOpaqueLeaf,
+	MmrError, MmrProofOf, OpaqueKeyOwnershipProof, StoredHeaderBeefyInfo, VersionedFinalityProof,
+	BEEFY_ENGINE_ID,
+};
 use bp_header_chain::{
 	justification::{
 		verify_and_optimize_justification, GrandpaEquivocationsFinder, GrandpaJustification,
@@ -31,8 +38,8 @@ use codec::{Decode, Encode};
 use futures::stream::StreamExt;
 use num_traits::{One, Zero};
 use relay_substrate_client::{
-	BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf, HeaderOf,
-	Subscription,
+	BlockNumberOf, Chain, ChainWithBeefy, ChainWithGrandpa, Client, Error as SubstrateError,
+	HashOf, HeaderOf, Subscription,
 };
 use sp_consensus_grandpa::{AuthorityList as GrandpaAuthoritiesSet, GRANDPA_ENGINE_ID};
 use sp_core::{storage::StorageKey, Bytes};
@@ -104,6 +111,24 @@ pub trait Engine<C: Chain>: Send {
 		source_client: &impl Client<C>,
 	) -> Result<Subscription<Bytes>, SubstrateError>;
 
+	/// Returns number of the source block, which justification may prove finality of the
+	/// header with given number.
+	fn justification_block_number(header_number: BlockNumberOf<C>) -> BlockNumberOf<C> {
+		header_number
+	}
+
+	/// Build finality proof from the encoded justification, received from the source node.
+	///
+	/// By default, the justification is just decoded. But some engines need to read additional
+	/// data from the source chain to build the proof, which is verifiable at the target chain.
+	async fn finality_proof_from_justification(
+		_source_client: &impl Client<C>,
+		justification: &[u8],
+	) -> Result<Self::FinalityProof, SubstrateError> {
+		Self::FinalityProof::decode(&mut &justification[..])
+			.map_err(SubstrateError::ResponseParseFailed)
+	}
+
 	/// Verify and optimize finality proof before sending it to the target node.
 	///
 	/// Apart from optimization, we expect this method to perform all required checks
@@ -438,3 +463,346 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		Ok(key_owner_proof)
 	}
 }
+
+/// BEEFY finality engine.
+///
+/// The commitment, signed at block `N`, is proving finality of its parent (`N - 1`), because
+/// the MMR leaf that is added at block `N` only contains the hash of the parent block. So the
+/// BEEFY finality proof for the header is read from the justification of its child.
+///
+/// The bridge pallet only accepts commitments, signed by its current authority set. So the
+/// relayer must submit at least one commitment per BEEFY session, or the bridge stalls.
+pub struct Beefy<C>(PhantomData<C>);
+
+impl<C: ChainWithBeefy> Beefy<C> {
+	/// Read BEEFY validator set at given header.
+	async fn source_validator_set(
+		source_client: &impl Client<C>,
+		header_hash: C::Hash,
+	) -> Result<BeefyAuthoritySetOf<C>, SubstrateError> {
+		const SUB_API_BEEFY_VALIDATOR_SET: &str = "BeefyApi_validator_set";
+
+		source_client
+			.state_call::<_, Option<BeefyAuthoritySetOf<C>>>(
+				header_hash,
+				SUB_API_BEEFY_VALIDATOR_SET.to_string(),
+				(),
+			)
+			.await?
+			.ok_or_else(|| {
+				SubstrateError::Custom(format!(
+					"{} BEEFY validator set is missing at block {header_hash}",
+					C::NAME,
+				))
+			})
+	}
+
+	/// Read the MMR leaf, added at given header, and generate its proof.
+	async fn source_mmr_leaf_and_proof(
+		source_client: &impl Client<C>,
+		header_hash: C::Hash,
+		header_number: BlockNumberOf<C>,
+	) -> Result<(BeefyMmrLeafOf<C>, MmrProofOf<C>), SubstrateError> {
+		const SUB_API_MMR_GENERATE_PROOF: &str = "MmrApi_generate_proof";
+
+		let (leaves, mmr_proof) = source_client
+			.state_call::<_, Result<(Vec<MmrEncodableOpaqueLeaf>, MmrProofOf<C>), MmrError>>(
+				header_hash,
+				SUB_API_MMR_GENERATE_PROOF.to_string(),
+				(vec![header_number], Some(header_number)),
+			)
+			.await?
+			.map_err(|e| {
+				SubstrateError::Custom(format!(
+					"Failed to generate {} MMR proof at block {header_hash}: {e:?}",
+					C::NAME,
+				))
+			})?;
+		let mmr_leaf = leaves.into_iter().next().ok_or_else(|| {
+			SubstrateError::Custom(format!(
+				"{} MMR proof at block {header_hash} is missing the leaf",
+				C::NAME,
+			))
+		})?;
+		let mmr_leaf = BeefyMmrLeafOf::<C>::decode(&mut &mmr_leaf.0[..])?;
+
+		Ok((mmr_leaf, mmr_proof))
+	}
+}
+
+#[async_trait]
+impl<C: ChainWithBeefy> Engine<C> for Beefy<C> {
+	const ID: ConsensusEngineId = BEEFY_ENGINE_ID;
+	type ConsensusLogReader = BeefyConsensusLogReader<BeefyAuthorityIdOf<C>>;
+	type FinalityProof = BeefyFinalityProof<C>;
+	type FinalityVerificationContext = BeefyAuthoritySetInfoOf<C>;
+	type EquivocationProof = BeefyEquivocationProofOf<C>;
+	type EquivocationsFinder = BeefyEquivocationsFinder<C>;
+	type KeyOwnerProof = C::KeyOwnerProof;
+	type InitializationData =
+		bp_beefy::InitializationData<BlockNumberOf<C>, bp_beefy::MmrHashOf<C>>;
+	type OperatingMode = BasicOperatingMode;
+
+	fn is_initialized_key() -> StorageKey {
+		bp_beefy::storage_keys::imported_commitments_info_key(C::WITH_CHAIN_BEEFY_PALLET_NAME)
+	}
+
+	fn pallet_operating_mode_key() -> StorageKey {
+		bp_beefy::storage_keys::pallet_operating_mode_key(C::WITH_CHAIN_BEEFY_PALLET_NAME)
+	}
+
+	async fn source_finality_proofs(
+		client: &impl Client<C>,
+	) -> Result<Subscription<Bytes>, SubstrateError> {
+		client.subscribe_beefy_finality_justifications().await
+	}
+
+	fn justification_block_number(header_number: BlockNumberOf<C>) -> BlockNumberOf<C> {
+		header_number + One::one()
+	}
+
+	async fn finality_proof_from_justification(
+		source_client: &impl Client<C>,
+		justification: &[u8],
+	) -> Result<Self::FinalityProof, SubstrateError> {
+		let VersionedFinalityProof::V1(commitment) =
+			VersionedFinalityProof::<BlockNumberOf<C>, _>::decode(&mut &justification[..])?;
+
+		let commitment_block_number = commitment.commitment.block_number;
+		let commitment_block_hash =
+			source_client.header_hash_by_number(commitment_block_number).await?;
+		let validator_set =
+			Self::source_validator_set(source_client, commitment_block_hash).await?;
+		if validator_set.id() != commitment.commitment.validator_set_id {
+			return Err(SubstrateError::Custom(format!(
+				"{} BEEFY commitment at block {commitment_block_hash} is signed by the validator \
+				set {}, but the current set is {}",
+				C::NAME,
+				commitment.commitment.validator_set_id,
+				validator_set.id(),
+			)))
+		}
+		let (mmr_leaf, mmr_proof) = Self::source_mmr_leaf_and_proof(
+			source_client,
+			commitment_block_hash,
+			commitment_block_number,
+		)
+		.await?;
+
+		Ok(BeefyFinalityProof { commitment, validator_set, mmr_leaf, mmr_proof })
+	}
+
+	async fn verify_and_optimize_proof<TargetChain: Chain>(
+		target_client: &impl Client<TargetChain>,
+		header: &C::Header,
+		proof: &mut Self::FinalityProof,
+	) -> Result<Self::FinalityVerificationContext, SubstrateError> {
+		let at = target_client.best_header().await?.hash();
+		let verification_context =
+			Beefy::<C>::finality_verification_context(target_client, at).await?;
+		if proof.target_header_hash() != header.hash() {
+			return Err(SubstrateError::Custom(format!(
+				"{} BEEFY commitment doesn't prove finality of header {:?}",
+				C::NAME,
+				header.id(),
+			)))
+		}
+		if authority_set_info::<C>(&proof.validator_set) != verification_context {
+			return Err(SubstrateError::Custom(format!(
+				"{} BEEFY commitment for header {:?} is signed by the validator set {}, \
+				unknown to {}",
+				C::NAME,
+				header.id(),
+				proof.validator_set.id(),
+				TargetChain::NAME,
+			)))
+		}
+
+		let imported_commitments_info: Option<ImportedCommitmentsInfoData<BlockNumberOf<C>>> =
+			target_client
+				.storage_value(
+					at,
+					bp_beefy::storage_keys::imported_commitments_info_key(
+						C::WITH_CHAIN_BEEFY_PALLET_NAME,
+					),
+				)
+				.await?;
+		if let Some(imported_commitments_info) = imported_commitments_info {
+			if proof.commitment.commitment.block_number <=
+				imported_commitments_info.best_block_number
+			{
+				return Err(SubstrateError::Custom(format!(
+					"{} BEEFY commitment for header {:?} is older than the best commitment \
+					known to {}",
+					C::NAME,
+					header.id(),
+					TargetChain::NAME,
+				)))
+			}
+		}
+
+		proof.optimize();
+		Ok(verification_context)
+	}
+
+	fn check_max_expected_call_limits(
+		_header: &C::Header,
+		_proof: &Self::FinalityProof,
+	) -> SubmitFinalityProofCallExtras {
+		// BEEFY commitments are never submitted for free, so we don't need any limits here
+		SubmitFinalityProofCallExtras {
+			is_weight_limit_exceeded: false,
+			extra_size: 0,
+			is_mandatory_finality_target: false,
+		}
+	}
+
+	/// Prepare initialization data for the BEEFY light client pallet.
+	async fn prepare_initialization_data(
+		source_client: impl Client<C>,
+	) -> Result<Self::InitializationData, Error<HashOf<C>, BlockNumberOf<C>>> {
+		let mut justifications = Self::source_finality_proofs(&source_client)
+			.await
+			.map_err(|err| Error::Subscribe(C::NAME, err))?;
+		// Read next justification - the block where it is signed will be used as initial block.
+		let justification = justifications
+			.next()
+			.await
+			.ok_or(Error::ReadJustificationStreamEnded(C::NAME))?;
+		let proof = Self::finality_proof_from_justification(&source_client, &justification.0)
+			.await
+			.map_err(|err| Error::ReadJustification(C::NAME, err))?;
+
+		let initial_block_number = proof.commitment.commitment.block_number;
+		log::trace!(target: "bridge", "Selected {} initial BEEFY block: {}, validator set: {:?}",
+			C::NAME,
+			initial_block_number,
+			proof.validator_set,
+		);
+
+		Ok(bp_beefy::InitializationData {
+			operating_mode: BasicOperatingMode::Normal,
+			best_block_number: initial_block_number,
+			authority_set: authority_set_info::<C>(&proof.validator_set),
+		})
+	}
+
+	async fn finality_verification_context<TargetChain: Chain>(
+		target_client: &impl Client<TargetChain>,
+		at: HashOf<TargetChain>,
+	) -> Result<Self::FinalityVerificationContext, SubstrateError> {
+		let current_authority_set_info_key =
+			bp_beefy::storage_keys::current_authority_set_info_key(C::WITH_CHAIN_BEEFY_PALLET_NAME);
+		target_client
+			.storage_value(at, current_authority_set_info_key)
+			.await?
+			.map(Ok)
+			.unwrap_or(Err(SubstrateError::Custom(format!(
+				"{} `CurrentAuthoritySetInfo` is missing from the {} storage",
+				C::NAME,
+				TargetChain::NAME,
+			))))
+	}
+
+	async fn synced_headers_finality_info<TargetChain: Chain>(
+		target_client: &impl Client<TargetChain>,
+		at: TargetChain::Hash,
+	) -> Result<Vec<StoredHeaderBeefyInfo<C>>, SubstrateError> {
+		target_client
+			.state_call(at, C::SYNCED_HEADERS_BEEFY_INFO_METHOD.to_string(), ())
+			.await
+	}
+
+	async fn generate_source_key_ownership_proof(
+		source_client: &impl Client<C>,
+		at: C::Hash,
+		equivocation: &Self::EquivocationProof,
+	) -> Result<Self::KeyOwnerProof, SubstrateError> {
+		const SUB_API_BEEFY_GENERATE_KEY_OWNERSHIP_PROOF: &str =
+			"BeefyApi_generate_key_ownership_proof";
+
+		let set_id = equivocation.set_id();
+		let offender = equivocation.offender_id();
+
+		let opaque_key_owner_proof = source_client
+			.state_call::<_, Option<OpaqueKeyOwnershipProof>>(
+				at,
+				SUB_API_BEEFY_GENERATE_KEY_OWNERSHIP_PROOF.to_string(),
+				(set_id, offender.clone()),
+			)
+			.await?
+			.ok_or(SubstrateError::Custom(format!(
+				"Couldn't get BEEFY key ownership proof from {} at block: {at} \
+				for offender: {:?}, set_id: {set_id} ",
+				C::NAME,
+				offender.clone(),
+			)))?;
+
+		let key_owner_proof =
+			opaque_key_owner_proof.decode().ok_or(SubstrateError::Custom(format!(
+				"Couldn't decode BEEFY `OpaqueKeyOwnershipProof` from {} at block: {at} \
+				to `{:?}` for offender: {:?}, set_id: {set_id}",
+				C::NAME,
+				<C::KeyOwnerProof as TypeInfo>::type_info().path,
+				offender.clone(),
+			)))?;
+
+		Ok(key_owner_proof)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_beefy::{BeefyConsensusLog, EcdsaValidatorId, ValidatorSet};
+	use relay_substrate_client::test_chain::TestChain;
+	use sp_core::{ecdsa, Pair};
+	use sp_runtime::DigestItem;
+
+	type BeefyLogReader = <Beefy<TestChain> as Engine<TestChain>>::ConsensusLogReader;
+
+	fn beefy_log(log: BeefyConsensusLog<EcdsaValidatorId>) -> DigestItem {
+		DigestItem::Consensus(BEEFY_ENGINE_ID, log.encode())
+	}
+
+	fn validator_set(id: u64) -> ValidatorSet<EcdsaValidatorId> {
+		let validator = ecdsa::Pair::from_seed(&[1u8; 32]).public().into();
+		ValidatorSet::new(vec![validator], id).expect("validator set is not empty")
+	}
+
+	#[test]
+	fn beefy_engine_detects_authorities_change() {
+		let digest = sp_runtime::Digest {
+			logs: vec![
+				beefy_log(BeefyConsensusLog::OnDisabled(0)),
+				beefy_log(BeefyConsensusLog::AuthoritiesChange(validator_set(1))),
+			],
+		};
+
+		assert!(BeefyLogReader::schedules_authorities_change(&digest));
+		assert_eq!(
+			BeefyConsensusLogReader::<EcdsaValidatorId>::find_authorities_change(&digest),
+			Some(validator_set(1)),
+		);
+	}
+
+	#[test]
+	fn beefy_engine_ignores_other_logs() {
+		let digest = sp_runtime::Digest {
+			logs: vec![
+				beefy_log(BeefyConsensusLog::OnDisabled(0)),
+				DigestItem::Consensus(
+					GRANDPA_ENGINE_ID,
+					BeefyConsensusLog::AuthoritiesChange(validator_set(1)).encode(),
+				),
+			],
+		};
+
+		assert!(!BeefyLogReader::schedules_authorities_change(&digest));
+	}
+
+	#[test]
+	fn beefy_engine_proves_parent_of_the_commitment_block() {
+		assert_eq!(Beefy::<TestChain>::justification_block_number(10), 11);
+	}
+}
@@ -23,7 +23,6 @@ use crate::finality_base::engine::Engine;
 
 use async_trait::async_trait;
 use bp_runtime::{HashOf, HeaderIdOf};
-use codec::Decode;
 use futures::{stream::unfold, Stream, StreamExt};
 use relay_substrate_client::{Chain, Client, Error};
 use std::{fmt::Debug, pin::Pin};
@@ -53,8 +52,8 @@ pub async fn finality_proofs<P: SubstrateFinalityPipeline>(
 	client: &impl Client<P::SourceChain>,
 ) -> Result<SubstrateFinalityProofsStream<P>, Error> {
 	Ok(unfold(
-		P::FinalityEngine::source_finality_proofs(client).await?,
-		move |mut subscription| async move {
+		(client.clone(), P::FinalityEngine::source_finality_proofs(client).await?),
+		move |(client, mut subscription)| async move {
 			loop {
 				let log_error = |err| {
 					log::error!(
@@ -67,20 +66,20 @@ pub async fn finality_proofs<P: SubstrateFinalityPipeline>(
 
 				let next_justification = subscription.next().await?;
 
-				let decoded_justification =
-					<P::FinalityEngine as Engine<P::SourceChain>>::FinalityProof::decode(
-						&mut &next_justification[..],
-					);
-
-				let justification = match decoded_justification {
+				let justification = match P::FinalityEngine::finality_proof_from_justification(
+					&client,
+					&next_justification,
+				)
+				.await
+				{
 					Ok(j) => j,
 					Err(err) => {
-						log_error(format!("decode failed with error {err:?}"));
+						log_error(format!("failed to build finality proof: {err:?}"));
 						continue
 					},
 				};
 
-				return Some((justification, subscription))
+				return Some((justification, (client, subscription)))
 			}
 		},
 	)
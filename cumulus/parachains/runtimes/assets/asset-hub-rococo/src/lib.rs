@@ -438,6 +438,7 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type MaxPriceObservations = ConstU32<256>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -941,6 +942,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type AssetId = <Self as pallet_assets::Config<TrustBackedAssetsInstance>>::AssetId;
 	type Assets = Assets;
 	type Nfts = Nfts;
+	type RoyaltyBearingNfts = pallet_nfts::RoyaltyBearing<Runtime>;
 	type PalletId = NftFractionalizationPalletId;
 	type WeightInfo = weights::pallet_nft_fractionalization::WeightInfo<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -980,6 +982,7 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<5>;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1151,6 +1154,8 @@ pub type UncheckedExtrinsic =
 pub type Migrations = (
 	InitStorageVersions,
 	// unreleased
+	pallet_nfts::migration::v2::MigrateToV2<Runtime>,
+	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v5::MigrateV4ToV5<Runtime>,
 	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
@@ -1208,7 +1213,8 @@ impl frame_support::traits::OnRuntimeUpgrade for InitStorageVersions {
 		}
 
 		if Nfts::on_chain_storage_version() == StorageVersion::new(0) {
-			Nfts::in_code_storage_version().put::<Nfts>();
+			// `MigrateToV2` takes it from here.
+			StorageVersion::new(1).put::<Nfts>();
 			writes.saturating_inc();
 		}
 
@@ -1455,6 +1461,15 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: xcm::v5::Location, asset2: xcm::v5::Location) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn quote_price_time_weighted(
+			asset1: xcm::v5::Location,
+			asset2: xcm::v5::Location,
+			amount: Balance,
+			window: u32,
+		) -> Option<Balance> {
+			AssetConversion::quote_price_time_weighted(asset1, asset2, amount, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
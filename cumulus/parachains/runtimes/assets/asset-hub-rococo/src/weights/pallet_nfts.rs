@@ -190,15 +190,16 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::ItemAttributesApprovalsOf` (`max_values`: None, `max_size`: Some(1001), added: 3476, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn burn() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `491`
-		//  Estimated: `4326`
-		// Minimum execution time: 58_855_000 picoseconds.
-		Weight::from_parts(60_025_000, 0)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(63_025_000, 0)
 			.saturating_add(Weight::from_parts(0, 4326))
 			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes(9))
 	}
 	/// Storage: `Nfts::Collection` (r:1 w:0)
 	/// Proof: `Nfts::Collection` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
@@ -216,15 +217,16 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn transfer() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `520`
-		//  Estimated: `4326`
-		// Minimum execution time: 46_689_000 picoseconds.
-		Weight::from_parts(48_831_000, 0)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(51_831_000, 0)
 			.saturating_add(Weight::from_parts(0, 4326))
 			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	/// Storage: `Nfts::Collection` (r:1 w:0)
 	/// Proof: `Nfts::Collection` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
@@ -613,15 +615,18 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::ItemConfigOf` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::ItemPriceOf` (r:0 w:1)
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn set_price() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `445`
-		//  Estimated: `4326`
-		// Minimum execution time: 23_836_000 picoseconds.
-		Weight::from_parts(24_978_000, 0)
-			.saturating_add(Weight::from_parts(0, 4326))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(1))
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(30_978_000, 0)
+			.saturating_add(Weight::from_parts(0, 9678))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `Nfts::Item` (r:1 w:1)
 	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
@@ -639,15 +644,22 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:1 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:7 w:7)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	fn buy_item() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `632`
-		//  Estimated: `4326`
-		// Minimum execution time: 56_280_000 picoseconds.
-		Weight::from_parts(57_527_000, 0)
-			.saturating_add(Weight::from_parts(0, 4326))
-			.saturating_add(T::DbWeight::get().reads(6))
-			.saturating_add(T::DbWeight::get().writes(5))
+		// Not benchmarked: estimated for `MaxRoyaltyRecipients` (5) royalty recipients.
+		Weight::from_parts(317_527_000, 0)
+			.saturating_add(Weight::from_parts(0, 30416))
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().writes(14))
 	}
 	/// The range of component `n` is `[0, 10]`.
 	fn pay_tips(n: u32, ) -> Weight {
@@ -664,29 +676,31 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn create_swap() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `421`
-		//  Estimated: `7662`
-		// Minimum execution time: 20_957_000 picoseconds.
-		Weight::from_parts(21_845_000, 0)
-			.saturating_add(Weight::from_parts(0, 7662))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(27_845_000, 0)
+			.saturating_add(Weight::from_parts(0, 13014))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `Nfts::PendingSwapOf` (r:1 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::Item` (r:1 w:0)
 	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn cancel_swap() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `440`
-		//  Estimated: `4326`
-		// Minimum execution time: 24_881_000 picoseconds.
-		Weight::from_parts(25_701_000, 0)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(27_701_000, 0)
 			.saturating_add(Weight::from_parts(0, 4326))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes(2))
 	}
 	/// Storage: `Nfts::Item` (r:2 w:2)
 	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
@@ -704,15 +718,22 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::ItemPriceOf` (r:0 w:2)
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:1 w:2)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:2)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:7 w:7)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	fn claim_swap() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `761`
-		//  Estimated: `7662`
-		// Minimum execution time: 90_772_000 picoseconds.
-		Weight::from_parts(92_780_000, 0)
-			.saturating_add(Weight::from_parts(0, 7662))
-			.saturating_add(T::DbWeight::get().reads(9))
-			.saturating_add(T::DbWeight::get().writes(10))
+		// Not benchmarked: estimated for `MaxRoyaltyRecipients` (5) royalty recipients.
+		Weight::from_parts(352_780_000, 0)
+			.saturating_add(Weight::from_parts(0, 33752))
+			.saturating_add(T::DbWeight::get().reads(19))
+			.saturating_add(T::DbWeight::get().writes(21))
 	}
 	/// Storage: `Nfts::CollectionRoleOf` (r:2 w:0)
 	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -776,4 +797,44 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Storage: `Nfts::Collection` (r:1 w:0)
+	/// Proof: `Nfts::Collection` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:0 w:1)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	fn set_collection_royalties(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `341`
+		//  Estimated: `3549`
+		// Minimum execution time: 15_214_000 picoseconds.
+		Weight::from_parts(16_033_418, 0)
+			.saturating_add(Weight::from_parts(0, 3549))
+			// Standard Error: 9_871
+			.saturating_add(Weight::from_parts(88_519, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::Collection` (r:1 w:0)
+	/// Proof: `Nfts::Collection` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 5]`.
+	fn set_item_royalties(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `463`
+		//  Estimated: `4326`
+		// Minimum execution time: 18_902_000 picoseconds.
+		Weight::from_parts(19_877_205, 0)
+			.saturating_add(Weight::from_parts(0, 4326))
+			// Standard Error: 11_246
+			.saturating_add(Weight::from_parts(93_160, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
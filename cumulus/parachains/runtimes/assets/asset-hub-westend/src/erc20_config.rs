@@ -0,0 +1,132 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompiles exposing `Assets`, `ForeignAssets` and `PoolAssets` to `pallet-revive`.
+//!
+//! Trust-backed and pool assets have `u32` ids which are encoded in the precompile address as is.
+//! Foreign assets are identified by a [`Location`], so they are assigned a `u32` index when they
+//! are created, see [`ForeignAssetsErc20Index`].
+
+use crate::{
+	AccountId, ForeignAssetsInstance, PoolAssetsInstance, Runtime, TrustBackedAssetsInstance,
+};
+use frame_support::{
+	pallet_prelude::ValueQuery,
+	parameter_types, storage_alias,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+	Blake2_128Concat, Twox64Concat,
+};
+use pallet_assets::AssetsCallback;
+use pallet_revive::precompiles::{Erc20, InlineAssetId};
+use sp_runtime::traits::MaybeEquivalence;
+use xcm::v5::Location;
+
+parameter_types! {
+	pub const TrustBackedAssetsErc20Prefix: u16 = 0x0120;
+	pub const ForeignAssetsErc20Prefix: u16 = 0x0220;
+	pub const PoolAssetsErc20Prefix: u16 = 0x0320;
+}
+
+/// The ERC-20 precompiles of this runtime, see [`pallet_revive::precompiles::erc20_address`].
+///
+/// Declared in [`pallet_revive::Config::Precompiles`] next to the XCM precompile.
+pub type Erc20Precompiles = (
+	Erc20<TrustBackedAssetsErc20Prefix, TrustBackedAssetsInstance, InlineAssetId>,
+	Erc20<ForeignAssetsErc20Prefix, ForeignAssetsInstance, ForeignAssetsErc20Index>,
+	Erc20<PoolAssetsErc20Prefix, PoolAssetsInstance, InlineAssetId>,
+);
+
+#[storage_alias(verbatim)]
+type ForeignAssetByIndex = StorageMap<ForeignAssetsErc20, Twox64Concat, u32, Location>;
+
+#[storage_alias(verbatim)]
+type ForeignAssetIndex = StorageMap<ForeignAssetsErc20, Blake2_128Concat, Location, u32>;
+
+#[storage_alias(verbatim)]
+type NextForeignAssetIndex = StorageValue<ForeignAssetsErc20, u32, ValueQuery>;
+
+/// Whether [`IndexForeignAssets`] already ran.
+#[storage_alias(verbatim)]
+type ForeignAssetsIndexed = StorageValue<ForeignAssetsErc20, bool, ValueQuery>;
+
+/// Assigns every foreign asset a `u32` index to be used in its ERC-20 precompile address.
+///
+/// Indices are handed out on creation and are never reused, even after the asset is destroyed.
+pub struct ForeignAssetsErc20Index;
+
+impl ForeignAssetsErc20Index {
+	/// Index `id` unless it already is. Return whether a new index was assigned.
+	fn index(id: &Location) -> bool {
+		if ForeignAssetIndex::contains_key(id) {
+			return false
+		}
+		let index = NextForeignAssetIndex::get();
+		NextForeignAssetIndex::put(index.saturating_add(1));
+		ForeignAssetByIndex::insert(index, id);
+		ForeignAssetIndex::insert(id, index);
+		true
+	}
+}
+
+impl MaybeEquivalence<u32, Location> for ForeignAssetsErc20Index {
+	fn convert(index: &u32) -> Option<Location> {
+		ForeignAssetByIndex::get(index)
+	}
+
+	fn convert_back(id: &Location) -> Option<u32> {
+		ForeignAssetIndex::get(id)
+	}
+}
+
+impl AssetsCallback<Location, AccountId> for ForeignAssetsErc20Index {
+	fn created(id: &Location, _owner: &AccountId) -> Result<(), ()> {
+		Self::index(id);
+		Ok(())
+	}
+
+	fn destroyed(id: &Location) -> Result<(), ()> {
+		if let Some(index) = ForeignAssetIndex::take(id) {
+			ForeignAssetByIndex::remove(index);
+		}
+		Ok(())
+	}
+}
+
+/// Index the foreign assets that were created before [`ForeignAssetsErc20Index`] was in place.
+///
+/// Only runs once, later upgrades only check [`ForeignAssetsIndexed`].
+pub struct IndexForeignAssets;
+
+impl OnRuntimeUpgrade for IndexForeignAssets {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if ForeignAssetsIndexed::get() {
+			log::info!(target: "runtime::erc20", "foreign assets already indexed, skipping");
+			return db_weight.reads(1)
+		}
+
+		let (mut reads, mut indexed) = (1, 0);
+		for id in pallet_assets::Asset::<Runtime, ForeignAssetsInstance>::iter_keys() {
+			reads += 2;
+			if ForeignAssetsErc20Index::index(&id) {
+				indexed += 1;
+			}
+		}
+		ForeignAssetsIndexed::put(true);
+		log::info!(target: "runtime::erc20", "indexed {indexed} foreign assets");
+		db_weight.reads_writes(reads, indexed * 3 + 1)
+	}
+}
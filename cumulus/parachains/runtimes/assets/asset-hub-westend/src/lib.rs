@@ -25,6 +25,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 mod bridge_to_ethereum_config;
+pub mod erc20_config;
 mod genesis_config_presets;
 mod weights;
 pub mod xcm_config;
@@ -442,6 +443,7 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LPFee = ConstU32<3>;
 	type PalletId = AssetConversionPalletId;
 	type MaxSwapPathLength = ConstU32<3>;
+	type MaxPriceObservations = ConstU32<256>;
 	type MintMinLiquidity = ConstU128<100>;
 	type WeightInfo = weights::pallet_asset_conversion::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -554,7 +556,7 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type Freezer = ForeignAssetsFreezer;
 	type Extra = ();
 	type WeightInfo = weights::pallet_assets_foreign::WeightInfo<Runtime>;
-	type CallbackHandle = ();
+	type CallbackHandle = erc20_config::ForeignAssetsErc20Index;
 	type AssetAccountDeposit = ForeignAssetsAssetAccountDeposit;
 	type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -989,6 +991,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type AssetId = <Self as pallet_assets::Config<TrustBackedAssetsInstance>>::AssetId;
 	type Assets = Assets;
 	type Nfts = Nfts;
+	type RoyaltyBearingNfts = pallet_nfts::RoyaltyBearing<Runtime>;
 	type PalletId = NftFractionalizationPalletId;
 	type WeightInfo = weights::pallet_nft_fractionalization::WeightInfo<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -1028,6 +1031,7 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<5>;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1066,6 +1070,9 @@ parameter_types! {
 	pub const DepositPerItem: Balance = deposit(1, 0);
 	pub const DepositPerByte: Balance = deposit(0, 1);
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
+	pub XcmFeeSwapWeight: Weight = <
+		<Runtime as pallet_asset_conversion::Config>::WeightInfo as pallet_asset_conversion::WeightInfo
+	>::swap_tokens_for_exact_tokens(2).saturating_add(AssetConversion::price_oracle_weight(1));
 }
 
 impl pallet_revive::Config for Runtime {
@@ -1088,6 +1095,14 @@ impl pallet_revive::Config for Runtime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Xcm = pallet_xcm::Pallet<Self>;
+	type Precompiles =
+		(pallet_revive::precompiles::XcmPrecompile<Self>, erc20_config::Erc20Precompiles);
+	type XcmFeeSwap = pallet_revive::xcm_fees::SwapXcmFees<
+		AssetConversion,
+		xcm_config::WestendLocation,
+		xcm_config::WestendLocation,
+		XcmFeeSwapWeight,
+	>;
 	type ChainId = ConstU64<420_420_421>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
@@ -1252,6 +1267,8 @@ pub type Migrations = (
 	// unreleased
 	InitStorageVersions,
 	// unreleased
+	pallet_nfts::migration::v2::MigrateToV2<Runtime>,
+	// unreleased
 	DeleteUndecodableStorage,
 	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
@@ -1262,6 +1279,7 @@ pub type Migrations = (
 		Runtime,
 		TrustBackedAssetsInstance,
 	>,
+	erc20_config::IndexForeignAssets,
 	pallet_session::migrations::v1::MigrateV0ToV1<
 		Runtime,
 		pallet_session::migrations::v1::InitOffenceSeverity<Runtime>,
@@ -1655,6 +1673,15 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: xcm::v5::Location, asset2: xcm::v5::Location) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn quote_price_time_weighted(
+			asset1: xcm::v5::Location,
+			asset2: xcm::v5::Location,
+			amount: Balance,
+			window: u32,
+		) -> Option<Balance> {
+			AssetConversion::quote_price_time_weighted(asset1, asset2, amount, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
@@ -1776,6 +1803,22 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl xcm_runtime_apis::swaps::SwapAndTransferApi<Block> for Runtime {
+		fn build_swap_and_transfer(
+			origin: VersionedLocation,
+			give: VersionedAsset,
+			want: VersionedAsset,
+			route: xcm_runtime_apis::swaps::SwapRoute,
+			dest: VersionedLocation,
+			beneficiary: VersionedLocation,
+		) -> Result<
+			xcm_runtime_apis::swaps::SwapAndTransferXcm,
+			xcm_runtime_apis::swaps::Error
+		> {
+			PolkadotXcm::build_swap_and_transfer(origin, give, want, route, dest, beneficiary)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
@@ -2424,9 +2467,9 @@ impl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: pallet_revive::evm::TracerConfig
-		) -> Vec<(u32, pallet_revive::evm::CallTrace)> {
+		) -> Vec<(u32, pallet_revive::evm::Trace)> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let mut traces = vec![];
 			let (header, extrinsics) = block.deconstruct();
 
@@ -2436,7 +2479,7 @@ impl_runtime_apis! {
 					let _ = Executive::apply_extrinsic(ext);
 				});
 
-				if let Some(tx_trace) = tracer.collect_traces().pop() {
+				if let Some(tx_trace) = tracer.collect_trace() {
 					traces.push((index as u32, tx_trace));
 				}
 			}
@@ -2448,9 +2491,9 @@ impl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: pallet_revive::evm::TracerConfig
-		) -> Option<pallet_revive::evm::CallTrace> {
+		) -> Option<pallet_revive::evm::Trace> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
@@ -2465,19 +2508,19 @@ impl_runtime_apis! {
 				}
 			}
 
-			tracer.collect_traces().pop()
+			tracer.collect_trace()
 		}
 
 		fn trace_call(
 			tx: pallet_revive::evm::GenericTransaction,
 			config: pallet_revive::evm::TracerConfig)
-			-> Result<pallet_revive::evm::CallTrace, pallet_revive::EthTransactError>
+			-> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError>
 		{
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let result = trace(&mut tracer, || Self::eth_transact(tx));
 
-			if let Some(trace) = tracer.collect_traces().pop() {
+			if let Some(trace) = tracer.collect_trace() {
 				Ok(trace)
 			} else if let Err(err) = result {
 				Err(err)
@@ -2485,6 +2528,15 @@ impl_runtime_apis! {
 				Ok(Default::default())
 			}
 		}
+
+		fn quote_xcm_send_fee(
+			address: H160,
+			dest: xcm::VersionedLocation,
+			message: xcm::VersionedXcm<()>,
+			fee_asset: Vec<u8>,
+		) -> Result<u128, sp_runtime::DispatchError> {
+			Revive::quote_xcm_send_fee(address, dest, message, fee_asset)
+		}
 	}
 }
 
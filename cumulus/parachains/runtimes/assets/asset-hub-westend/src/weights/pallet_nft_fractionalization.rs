@@ -70,14 +70,17 @@ impl<T: frame_system::Config> pallet_nft_fractionalization::WeightInfo for Weigh
 	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(140), added: 2615, mode: `MaxEncodedLen`)
 	/// Storage: `NftFractionalization::NftToAsset` (r:0 w:1)
 	/// Proof: `NftFractionalization::NftToAsset` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
 	fn fractionalize() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `459`
-		//  Estimated: `4326`
-		// Minimum execution time: 183_512_000 picoseconds.
-		Weight::from_parts(187_719_000, 0)
-			.saturating_add(Weight::from_parts(0, 4326))
-			.saturating_add(T::DbWeight::get().reads(9))
+		// Not benchmarked: estimated as the last run plus the royalty checks.
+		Weight::from_parts(195_719_000, 0)
+			.saturating_add(Weight::from_parts(0, 12228))
+			.saturating_add(T::DbWeight::get().reads(12))
 			.saturating_add(T::DbWeight::get().writes(8))
 	}
 	/// Storage: `NftFractionalization::NftToAsset` (r:1 w:1)
@@ -108,14 +111,15 @@ impl<T: frame_system::Config> pallet_nft_fractionalization::WeightInfo for Weigh
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn unify() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1284`
-		//  Estimated: `6114`
-		// Minimum execution time: 162_307_000 picoseconds.
-		Weight::from_parts(164_950_000, 0)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(167_950_000, 0)
 			.saturating_add(Weight::from_parts(0, 6114))
 			.saturating_add(T::DbWeight::get().reads(12))
-			.saturating_add(T::DbWeight::get().writes(12))
+			.saturating_add(T::DbWeight::get().writes(14))
 	}
 }
@@ -125,7 +125,9 @@ impl<Call> XcmWeightInfo<Call> for AssetHubWestendXcmWeight<Call> {
 		assets.weigh_assets(XcmFungibleWeight::<Runtime>::deposit_reserve_asset())
 	}
 	fn exchange_asset(give: &AssetFilter, receive: &Assets, _maximal: &bool) -> Weight {
-		let base_weight = XcmGeneric::<Runtime>::exchange_asset();
+		// The exchange swaps through a single pool, updating its price oracle.
+		let base_weight = XcmGeneric::<Runtime>::exchange_asset()
+			.saturating_add(crate::AssetConversion::price_oracle_weight(1));
 		let give_weight = give.weigh_assets(base_weight);
 		let receive_weight = receive.weigh_assets(base_weight);
 		give_weight.max(receive_weight)
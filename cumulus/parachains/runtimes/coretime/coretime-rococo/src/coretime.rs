@@ -112,6 +112,8 @@ enum CoretimeProviderCalls {
 parameter_types! {
 	pub const BrokerPalletId: PalletId = PalletId(*b"py/broke");
 	pub const MinimumCreditPurchase: Balance =  UNITS / 10;
+	pub const MarketOrderDeposit: Balance = UNITS / 10;
+	pub const MinimumBid: Balance = UNITS / 10;
 	pub RevenueAccumulationAccount: AccountId = BrokerPalletId::get().into_sub_account_truncating(b"burnstash");
 }
 
@@ -308,6 +310,7 @@ impl MaybeConvert<TaskId, AccountId> for SovereignAccountOf {
 impl pallet_broker::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type OnRevenue = BurnCoretimeRevenue;
 	type TimeslicePeriod = ConstU32<{ coretime::TIMESLICE_PERIOD }>;
 	type MaxLeasedCores = ConstU32<50>;
@@ -321,4 +324,8 @@ impl pallet_broker::Config for Runtime {
 	type MaxAutoRenewals = ConstU32<100>;
 	type PriceAdapter = pallet_broker::CenterTargetPrice<Balance>;
 	type MinimumCreditPurchase = MinimumCreditPurchase;
+	type MaxMarketOrders = ConstU32<20>;
+	type MarketOrderDeposit = MarketOrderDeposit;
+	type MinimumBid = MinimumBid;
+	type MaxBidsPerAccount = ConstU32<4>;
 }
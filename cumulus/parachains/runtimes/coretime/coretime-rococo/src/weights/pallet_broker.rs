@@ -647,4 +647,106 @@ impl<T: frame_system::Config> pallet_broker::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Broker::Status` (r:1 w:0)
+	/// Proof: `Broker::Status` (`max_values`: Some(1), `max_size`: Some(18), added: 513, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Regions` (r:4 w:4)
+	/// Proof: `Broker::Regions` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Listings` (r:1 w:1)
+	/// Proof: `Broker::Listings` (`max_values`: None, `max_size`: Some(2201), added: 4676, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Bids` (r:1 w:1)
+	/// Proof: `Broker::Bids` (`max_values`: None, `max_size`: Some(1681), added: 4156, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn list_region() -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(160_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 30599))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	/// Storage: `Broker::Listings` (r:1 w:1)
+	/// Proof: `Broker::Listings` (`max_values`: None, `max_size`: Some(2201), added: 4676, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Regions` (r:1 w:1)
+	/// Proof: `Broker::Regions` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn delist_region() -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 12742))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `Broker::Listings` (r:1 w:1)
+	/// Proof: `Broker::Listings` (`max_values`: None, `max_size`: Some(2201), added: 4676, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Regions` (r:1 w:4)
+	/// Proof: `Broker::Regions` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn purchase_listed() -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 15345))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// Storage: `Broker::Status` (r:1 w:0)
+	/// Proof: `Broker::Status` (`max_values`: Some(1), `max_size`: Some(18), added: 513, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::SaleInfo` (r:1 w:0)
+	/// Proof: `Broker::SaleInfo` (`max_values`: Some(1), `max_size`: Some(57), added: 552, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Listings` (r:1 w:0)
+	/// Proof: `Broker::Listings` (`max_values`: None, `max_size`: Some(2201), added: 4676, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Bids` (r:1 w:1)
+	/// Proof: `Broker::Bids` (`max_values`: None, `max_size`: Some(1681), added: 4156, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn place_bid() -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(90_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 15402))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Broker::Bids` (r:1 w:1)
+	/// Proof: `Broker::Bids` (`max_values`: None, `max_size`: Some(1681), added: 4156, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn cancel_bid() -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 9661))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Broker::Listings` (r:1 w:1)
+	/// Proof: `Broker::Listings` (`max_values`: None, `max_size`: Some(2201), added: 4676, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Bids` (r:1 w:1)
+	/// Proof: `Broker::Bids` (`max_values`: None, `max_size`: Some(1681), added: 4156, mode: `MaxEncodedLen`)
+	/// Storage: `Broker::Regions` (r:n w:2n)
+	/// Proof: `Broker::Regions` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2n w:2n)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	fn process_market(n: u32, ) -> Weight {
+		// Not benchmarked: estimated for `MaxMarketOrders` (20) open listings and bids.
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8832))
+			.saturating_add(Weight::from_parts(80_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10669).saturating_mul(n.into()))
+	}
 }
@@ -18,24 +18,120 @@ use crate::common::{
 	rpc::BuildRpcExtensions as BuildRpcExtensionsT,
 	spec::{BaseNodeSpec, BuildImportQueue, ClientBlockImport, NodeSpec as NodeSpecT},
 	types::{Hash, ParachainBlockImport, ParachainClient},
+	ConstructNodeRuntimeApi, NodeBlock,
 };
-use codec::Encode;
+use codec::{Decode, Encode};
 use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
 use cumulus_primitives_aura::AuraUnincludedSegmentApi;
 use cumulus_primitives_core::{CollectCollationInfo, ParaId};
 use futures::FutureExt;
 use polkadot_primitives::UpgradeGoAhead;
-use sc_client_api::Backend;
-use sc_consensus::{DefaultImportQueue, LongestChain};
-use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+use sc_client_api::{Backend, CallExecutor, ExecutorProvider, StorageProvider};
+use sc_consensus::{BlockImportParams, DefaultImportQueue, LongestChain};
+use sc_consensus_manual_seal::{
+	rpc::{ManualSeal, ManualSealApiServer},
+	ConsensusDataProvider, TimestampOverride,
+};
 use sc_network::NetworkBackend;
 use sc_service::{Configuration, PartialComponents, TaskManager};
 use sc_telemetry::TelemetryHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
-use sp_runtime::traits::Header;
+use sp_consensus_aura::{Slot, SlotDuration, AURA_ENGINE_ID};
+use sp_core::traits::CallContext;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	traits::{Block as BlockT, Header},
+	Digest, DigestItem,
+};
+use sp_timestamp::TimestampInherentData;
 use std::{marker::PhantomData, sync::Arc};
 
+/// Duration of the slots of the mocked relay chain.
+const RELAY_CHAIN_SLOT_DURATION_MILLIS: u64 = 6_000;
+
+/// Provides the aura pre-runtime digest whenever the slot derived from the block timestamp
+/// differs from the current aura slot, e.g. after the timestamp has been adjusted with
+/// `engine_setNextBlockTimestamp`. Otherwise the slot is left untouched, so that runtimes which
+/// require a new slot for every block keep accepting blocks with the same timestamp.
+struct AuraSlotDigestProvider<Block: BlockT, RuntimeApi> {
+	client: Arc<ParachainClient<Block, RuntimeApi>>,
+}
+
+impl<Block, RuntimeApi> AuraSlotDigestProvider<Block, RuntimeApi>
+where
+	Block: NodeBlock,
+	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
+{
+	/// Returns the aura slot duration and the current slot of the `Aura` pallet at the given
+	/// block, or `None` if the runtime doesn't use aura.
+	fn aura_slot_at(
+		&self,
+		at: Block::Hash,
+	) -> Result<Option<(SlotDuration, Slot)>, sc_consensus_manual_seal::Error> {
+		// The slot duration doesn't depend on the type of the aura authority id, which this node
+		// doesn't know, so the runtime api is called directly.
+		let Ok(slot_duration) =
+			self.client
+				.executor()
+				.call(at, "AuraApi_slot_duration", &[], CallContext::Offchain)
+		else {
+			return Ok(None)
+		};
+		let slot_duration = SlotDuration::decode(&mut &slot_duration[..])
+			.map_err(|e| sc_consensus_manual_seal::Error::Other(Box::new(e)))?;
+
+		let key = [sp_core::twox_128(b"Aura"), sp_core::twox_128(b"CurrentSlot")].concat();
+		let current_slot = self
+			.client
+			.storage(at, &sp_core::storage::StorageKey(key))?
+			.map(|slot| Slot::decode(&mut &slot.0[..]))
+			.transpose()
+			.map_err(|e| sc_consensus_manual_seal::Error::Other(Box::new(e)))?
+			.unwrap_or_default();
+
+		Ok(Some((slot_duration, current_slot)))
+	}
+}
+
+impl<Block, RuntimeApi> ConsensusDataProvider<Block> for AuraSlotDigestProvider<Block, RuntimeApi>
+where
+	Block: NodeBlock,
+	RuntimeApi: ConstructNodeRuntimeApi<Block, ParachainClient<Block, RuntimeApi>>,
+{
+	type Proof = ();
+
+	fn create_digest(
+		&self,
+		parent: &Block::Header,
+		inherents: &InherentData,
+	) -> Result<Digest, sc_consensus_manual_seal::Error> {
+		let Some((slot_duration, current_slot)) = self.aura_slot_at(parent.hash())? else {
+			return Ok(Default::default())
+		};
+		let Some(timestamp) = inherents.timestamp_inherent_data()? else {
+			return Ok(Default::default())
+		};
+
+		let slot = Slot::from_timestamp(timestamp, slot_duration);
+		if slot == current_slot {
+			return Ok(Default::default())
+		}
+
+		Ok(Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())] })
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &Block::Header,
+		_params: &mut BlockImportParams<Block>,
+		_inherents: &InherentData,
+		_proof: Self::Proof,
+	) -> Result<(), sc_consensus_manual_seal::Error> {
+		Ok(())
+	}
+}
+
 pub struct ManualSealNode<NodeSpec>(PhantomData<NodeSpec>);
 
 impl<NodeSpec: NodeSpecT>
@@ -168,6 +264,8 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 				}
 			});
 
+		let timestamp_override = TimestampOverride::default();
+		let timestamp_override_for_cidp = timestamp_override.clone();
 		let client_for_cidp = client.clone();
 		let params = sc_consensus_manual_seal::ManualSealParams {
 			block_import: client.clone(),
@@ -176,7 +274,9 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 			pool: transaction_pool.clone(),
 			select_chain,
 			commands_stream: Box::pin(manual_seal_stream),
-			consensus_data_provider: None,
+			consensus_data_provider: Some(Box::new(AuraSlotDigestProvider {
+				client: client.clone(),
+			})),
 			create_inherent_data_providers: move |block: Hash, ()| {
 				let current_para_head = client_for_cidp
 					.header(block)
@@ -202,6 +302,13 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					.ok()
 					.unwrap_or_default();
 
+				// The parachain slot follows the timestamp, which may be adjusted with
+				// `engine_setNextBlockTimestamp`, and the aura-ext consensus hook rejects
+				// parachain slots that are ahead of the relay chain slot.
+				let relay_offset = timestamp_override_for_cidp
+					.adjust(0)
+					.div_ceil(RELAY_CHAIN_SLOT_DURATION_MILLIS);
+
 				let current_para_block_head =
 					Some(polkadot_primitives::HeadData(current_para_head.encode()));
 				let client_for_xcm = client_for_cidp.clone();
@@ -216,7 +323,9 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 						),
 						para_id,
 						current_para_block_head,
-						relay_offset: 0,
+						relay_offset: UniqueSaturatedInto::<u32>::unique_saturated_into(
+							relay_offset,
+						),
 						relay_blocks_per_para_block: requires_relay_progress
 							.then(|| 1)
 							.unwrap_or_default(),
@@ -234,16 +343,21 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 						}),
 					};
 					Ok((
-						// This is intentional, as the runtime that we expect to run against this
-						// will never receive the aura-related inherents/digests, and providing
-						// real timestamps would cause aura <> timestamp checking to fail.
+						// This is intentional, as providing real timestamps would move the aura
+						// slot ahead of the mocked relay chain. Tests that need to travel in time
+						// may still adjust it with the `engine_setNextBlockTimestamp` RPC, in
+						// which case the aura slot and the relay chain slot follow the
+						// timestamp.
 						sp_timestamp::InherentDataProvider::new(sp_timestamp::Timestamp::new(0)),
 						mocked_parachain,
 					))
 				}
 			},
 		};
-		let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
+		let authorship_future = sc_consensus_manual_seal::run_manual_seal_with_timestamp_override(
+			params,
+			timestamp_override,
+		);
 		task_manager.spawn_essential_handle().spawn_blocking(
 			"manual-seal",
 			None,
@@ -42,6 +42,10 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Refuse to serve availability chunks and data after storing them.
+	WithholdAvailability(WithholdAvailabilityOptions),
+	/// Dispute candidates after approving them.
+	ApprovalEquivocation(ApprovalEquivocationOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +109,16 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::WithholdAvailability(opts) => {
+				let WithholdAvailabilityOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, WithholdAvailability { percentage }, finality_delay)?
+			},
+			NemesisVariant::ApprovalEquivocation(opts) => {
+				let ApprovalEquivocationOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, ApprovalEquivocation { percentage }, finality_delay)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +258,40 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn withhold_availability_percentage_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-availability",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdAvailability(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 50);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn approval_equivocation_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"approval-equivocation",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::ApprovalEquivocation(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 100);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
@@ -0,0 +1,298 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that equivocates on its approval votes.
+//!
+//! This malus variant checks candidates honestly, both for approval voting and when participating
+//! in disputes, and passes the result on. Once a candidate has been checked, it additionally
+//! signs both an explicit `valid` and an explicit `invalid` dispute statement for the very same
+//! candidate and distributes them as a dispute. The node thereby votes on both sides of the
+//! dispute.
+//!
+//! The dispute coordinator never signs a second statement for a validator that already voted,
+//! so the conflicting statements are signed with the validator key directly.
+//!
+//! Disputes on valid candidates conclude valid, disputes on invalid candidates conclude invalid.
+//! In both cases the malus node is on the losing side, but only losers of disputes concluding
+//! invalid are slashed by the relay chain.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use futures::channel::{mpsc, oneshot};
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Handle, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_primitives::{DisputeMessage, SignedDisputeStatement};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_node_subsystem_util::signing_key_and_index;
+use polkadot_primitives::vstaging::CandidateReceiptV2 as CandidateReceipt;
+use rand::distributions::{Bernoulli, Distribution};
+use sp_core::traits::SpawnNamed;
+use sp_keystore::KeystorePtr;
+
+// Filter wrapping related types.
+use crate::{
+	interceptor::*,
+	shared::{launch_processing_task, MALUS},
+};
+
+use std::sync::Arc;
+
+/// Wraps around the candidate validation subsystem and replaces it.
+/// Reports every candidate checked by this node to the equivocation task.
+#[derive(Clone)]
+struct ApprovalEquivocator<Spawner> {
+	spawner: Spawner,
+	distribution: Bernoulli,
+	/// Candidates that were checked and have to be equivocated on.
+	checked_candidates: mpsc::UnboundedSender<CandidateReceipt>,
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for ApprovalEquivocator<Spawner>
+where
+	Sender: overseer::CandidateValidationSenderTrait + Clone + Send + 'static,
+	Spawner: SpawnNamed + Clone + 'static,
+{
+	type Message = CandidateValidationMessage;
+
+	/// Intercept approval checks and dispute participations and observe their outcome, pass the
+	/// rest as normal.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg:
+					CandidateValidationMessage::ValidateFromExhaustive {
+						validation_data,
+						validation_code,
+						candidate_receipt,
+						pov,
+						executor_params,
+						exec_kind: exec_kind @ (PvfExecKind::Approval | PvfExecKind::Dispute),
+						response_sender,
+					},
+			} if self.distribution.sample(&mut rand::thread_rng()) => {
+				let (tx, rx) = oneshot::channel();
+				let checked_candidates = self.checked_candidates.clone();
+				let receipt = candidate_receipt.clone();
+				self.spawner.spawn(
+					"malus-approval-equivocation-observer",
+					Some("malus"),
+					Box::pin(async move {
+						let Ok(result) = rx.await else { return };
+						// The honest result is passed on first, so approval voting casts its
+						// vote before we contradict it.
+						let _ = response_sender.send(result);
+						let _ = checked_candidates.unbounded_send(receipt);
+					}),
+				);
+
+				Some(FromOrchestra::Communication {
+					msg: CandidateValidationMessage::ValidateFromExhaustive {
+						validation_data,
+						validation_code,
+						candidate_receipt,
+						pov,
+						executor_params,
+						exec_kind,
+						response_sender: tx,
+					},
+				})
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+/// Signs both an explicit `valid` and an explicit `invalid` statement for a checked candidate
+/// and disputes it with them.
+async fn equivocate(
+	mut handle: Handle,
+	keystore: KeystorePtr,
+	candidate_receipt: CandidateReceipt,
+) {
+	let candidate_hash = candidate_receipt.hash();
+	let relay_parent = candidate_receipt.descriptor.relay_parent();
+	let (tx, rx) = oneshot::channel();
+	handle
+		.send_msg(
+			RuntimeApiMessage::Request(relay_parent, RuntimeApiRequest::SessionIndexForChild(tx)),
+			MALUS,
+		)
+		.await;
+	let session_index = match rx.await {
+		Ok(Ok(session_index)) => session_index,
+		_ => {
+			gum::error!(target: MALUS, ?candidate_hash, "😈 Failed to fetch session index for candidate.");
+			return
+		},
+	};
+
+	let (tx, rx) = oneshot::channel();
+	handle
+		.send_msg(
+			RuntimeApiMessage::Request(
+				relay_parent,
+				RuntimeApiRequest::SessionInfo(session_index, tx),
+			),
+			MALUS,
+		)
+		.await;
+	let session_info = match rx.await {
+		Ok(Ok(Some(session_info))) => session_info,
+		_ => {
+			gum::error!(target: MALUS, ?session_index, "😈 Failed to fetch session info.");
+			return
+		},
+	};
+
+	let Some((validator_id, validator_index)) =
+		signing_key_and_index(&session_info.validators, &keystore)
+	else {
+		gum::error!(target: MALUS, ?session_index, "😈 Not a validator in this session.");
+		return
+	};
+
+	let sign = |valid| {
+		SignedDisputeStatement::sign_explicit(
+			&keystore,
+			valid,
+			candidate_hash,
+			session_index,
+			validator_id.clone(),
+		)
+	};
+	let (Ok(Some(valid_statement)), Ok(Some(invalid_statement))) = (sign(true), sign(false)) else {
+		gum::error!(target: MALUS, ?candidate_hash, "😈 Failed to sign dispute statements.");
+		return
+	};
+
+	let dispute_message = match DisputeMessage::from_signed_statements(
+		valid_statement.clone(),
+		validator_index,
+		invalid_statement.clone(),
+		validator_index,
+		candidate_receipt.clone(),
+		&session_info,
+	) {
+		Ok(dispute_message) => dispute_message,
+		Err(err) => {
+			gum::error!(target: MALUS, ?candidate_hash, ?err, "😈 Failed to build dispute message.");
+			return
+		},
+	};
+
+	gum::info!(
+		target: MALUS,
+		?candidate_hash,
+		?session_index,
+		?validator_index,
+		"😈 Equivocating: voting both valid and invalid on a candidate we checked.",
+	);
+	handle
+		.send_msg(
+			DisputeCoordinatorMessage::ImportStatements {
+				candidate_receipt,
+				session: session_index,
+				statements: vec![
+					(valid_statement, validator_index),
+					(invalid_statement, validator_index),
+				],
+				pending_confirmation: None,
+			},
+			MALUS,
+		)
+		.await;
+	handle
+		.send_msg(DisputeDistributionMessage::SendDispute(dispute_message), MALUS)
+		.await;
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ApprovalEquivocationOptions {
+	/// Determines the percentage of checked candidates that are equivocated on. Value must
+	/// be in the range [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// ApprovalEquivocation implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct ApprovalEquivocation {
+	/// The probability of equivocating on a checked candidate.
+	pub percentage: u8,
+}
+
+impl OverseerGen for ApprovalEquivocation {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that equivocates on {:?}% of the candidates it checks.",
+			&self.percentage,
+		);
+
+		let spawner = args.spawner.clone();
+		let (checked_candidates, checked_candidates_rx) = mpsc::unbounded();
+		let equivocator = ApprovalEquivocator {
+			spawner: spawner.clone(),
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+			checked_candidates,
+		};
+
+		let ext_args =
+			ext_args.expect("Extended arguments required to build validator overseer are provided");
+		let keystore: KeystorePtr = ext_args.keystore.clone();
+
+		let (overseer, overseer_handle) = validator_overseer_builder(args, ext_args)?
+			.replace_candidate_validation(move |cv_subsystem| {
+				InterceptedSubsystem::new(cv_subsystem, equivocator)
+			})
+			.build_with_connector(connector)?;
+
+		let handle = Handle::new(overseer_handle.clone());
+		launch_processing_task(&spawner, checked_candidates_rx, move |candidate_receipt| {
+			equivocate(handle.clone(), keystore.clone(), candidate_receipt)
+		});
+
+		Ok((overseer, overseer_handle))
+	}
+}
@@ -16,6 +16,7 @@
 
 //! Collection of behavior variants.
 
+mod approval_equivocation;
 mod back_garbage_candidate;
 mod common;
 mod dispute_finalized_candidates;
@@ -23,13 +24,16 @@ mod dispute_valid_candidates;
 mod spam_statement_requests;
 mod suggest_garbage_candidate;
 mod support_disabled;
+mod withhold_availability;
 
 pub(crate) use self::{
+	approval_equivocation::{ApprovalEquivocation, ApprovalEquivocationOptions},
 	back_garbage_candidate::{BackGarbageCandidateOptions, BackGarbageCandidates},
 	dispute_finalized_candidates::{DisputeFinalizedCandidates, DisputeFinalizedCandidatesOptions},
 	dispute_valid_candidates::{DisputeAncestorOptions, DisputeValidCandidates},
 	spam_statement_requests::{SpamStatementRequests, SpamStatementRequestsOptions},
 	suggest_garbage_candidate::{SuggestGarbageCandidateOptions, SuggestGarbageCandidates},
 	support_disabled::{SupportDisabled, SupportDisabledOptions},
+	withhold_availability::{WithholdAvailability, WithholdAvailabilityOptions},
 };
 pub(crate) use common::*;
@@ -0,0 +1,153 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that withholds availability data.
+//!
+//! This malus variant backs candidates, stores its chunks and signs availability bitfields
+//! honestly, but pretends not to have any chunk or available data once someone asks for it.
+//! Peers fetching chunks or recovering the full data from it get nothing back, so the honest
+//! validators have to recover the candidates from the remaining chunks.
+//!
+//! Only requests answered by the availability store are withheld: chunks this node fetches for
+//! itself are still stored, so it keeps claiming availability in its bitfields.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::CandidateHash;
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+/// Wraps around the availability store and replaces it.
+#[derive(Clone)]
+struct AvailabilityWithholder {
+	/// Percentage of candidates whose data is withheld.
+	percentage: u8,
+}
+
+impl AvailabilityWithholder {
+	/// Withholding is decided per candidate, so the data of a candidate is either always
+	/// withheld or always served, no matter how often it is requested.
+	fn withholds(&self, candidate_hash: &CandidateHash) -> bool {
+		u32::from(candidate_hash.0[0]) * 100 < u32::from(self.percentage) * 256
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for AvailabilityWithholder
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Answer chunk and available data queries of withheld candidates with `None` and pass
+	/// the rest as normal.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} if self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					"😈 Withholding availability chunk.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAvailableData(candidate_hash, tx),
+			} if self.withholds(&candidate_hash) => {
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					"😈 Withholding available data.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdAvailabilityOptions {
+	/// Determines the percentage of candidates whose availability data is withheld. Value must
+	/// be in the range [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// WithholdAvailability implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct WithholdAvailability {
+	/// Percentage of candidates whose availability data is withheld.
+	pub percentage: u8,
+}
+
+impl OverseerGen for WithholdAvailability {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that withholds the availability data of {:?}% of candidates.",
+			&self.percentage,
+		);
+
+		let withholder = AvailabilityWithholder { percentage: self.percentage };
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |cb| InterceptedSubsystem::new(cb, withholder))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -0,0 +1,211 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	collator_protocol::CollatorProtocolOptions,
+	configuration::{TestAuthorities, TestConfiguration},
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{v2::CollationFetchingResponse, Requests};
+use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{
+		CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, MutateDescriptorV2,
+	},
+	BlockNumber, CandidateHash, CollatorPair, CoreIndex, Hash, HeadData, Header, Id,
+	PersistedValidationData, ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data, dummy_pvd,
+};
+use sc_network::ProtocolName;
+use sp_core::{Pair, H256};
+use std::{collections::HashMap, sync::Arc};
+
+const SESSION_INDEX: u32 = 0;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Network config, the emulated peers are the collators of our para
+	pub collators_config: TestConfiguration,
+	// Authority keys of the validators.
+	pub test_authorities: TestAuthorities,
+	// Network keys of the collators, the first one is our node.
+	pub collators: TestAuthorities,
+	// Collator keys, indexed as `collators`.
+	pub collator_pairs: Vec<CollatorPair>,
+	// The para our backing group is assigned to
+	pub para_id: Id,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Candidate receipts used to build the claim queue, one per core
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// Collations advertised by each collator, per relay parent
+	pub collations: HashMap<H256, Vec<CommittedCandidateReceipt>>,
+	// Minimum relay parents per active leaf
+	pub minimum_relay_parents: HashMap<H256, Vec<(Id, BlockNumber)>>,
+	// PersistedValidationData, we use one for all candidates
+	pub pvd: PersistedValidationData,
+	// PoV served with every collation
+	pub pov: Arc<PoV>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &CollatorProtocolOptions) -> Self {
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(config, &test_authorities);
+		// Group rotation never happens in the benchmark, so the core assigned to our backing
+		// group has the same index as the group.
+		let own_core = session_info
+			.validator_groups
+			.iter()
+			.position(|g| g.contains(&ValidatorIndex(NODE_UNDER_TEST)))
+			.unwrap();
+		let para_id = Id::new(own_core as u32 + 1);
+
+		let collators_config = TestConfiguration {
+			n_validators: options.n_collators_per_para + 1,
+			connectivity: 100,
+			..config.clone()
+		};
+		let collators = collators_config.generate_authorities();
+		let collator_pairs =
+			(0..collators_config.n_validators).map(|_| CollatorPair::generate().0).collect();
+
+		let pvd = dummy_pvd(dummy_head_data(), 0);
+		let pov_size = *config.pov_sizes().get(own_core).expect("This is a cycle; qed");
+		let pov = Arc::new(PoV { block_data: BlockData(vec![0; pov_size]) });
+		let block_infos: Vec<_> = (1..=config.num_blocks).map(generate_block_info).collect();
+
+		let mut state = Self {
+			config: config.clone(),
+			collators_config,
+			test_authorities,
+			collators,
+			collator_pairs,
+			para_id,
+			block_headers: block_infos.iter().map(generate_block_header).collect(),
+			block_infos,
+			candidate_receipts: Default::default(),
+			collations: Default::default(),
+			minimum_relay_parents: Default::default(),
+			pvd,
+			pov,
+		};
+
+		let mut template = dummy_committed_candidate_receipt_v2(dummy_hash());
+		template.descriptor.set_persisted_validation_data_hash(state.pvd.hash());
+		template.descriptor.set_pov_hash(state.pov.hash());
+		template.descriptor.set_session_index(SESSION_INDEX);
+
+		for block_info in state.block_infos.iter() {
+			for core_idx in 0..config.n_cores {
+				let mut receipt = template.clone();
+				receipt.descriptor.set_para_id(Id::new(core_idx as u32 + 1));
+				receipt.descriptor.set_relay_parent(block_info.hash);
+				receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+				state
+					.candidate_receipts
+					.entry(block_info.hash)
+					.or_default()
+					.push(receipt.to_plain());
+			}
+
+			// Every collator builds a different candidate, which differ in their output head.
+			let collations = (1..state.collators_config.n_validators)
+				.map(|collator_index| {
+					let mut receipt = template.clone();
+					receipt.descriptor.set_para_id(para_id);
+					receipt.descriptor.set_relay_parent(block_info.hash);
+					receipt.descriptor.set_core_index(CoreIndex(own_core as u32));
+					receipt.commitments.head_data =
+						HeadData((block_info.number, collator_index as u32).encode());
+					receipt.descriptor.set_para_head(receipt.commitments.head_data.hash());
+					receipt
+				})
+				.collect();
+			state.collations.insert(block_info.hash, collations);
+			state
+				.minimum_relay_parents
+				.insert(block_info.hash, vec![(para_id, block_info.number)]);
+		}
+
+		state
+	}
+
+	/// All collations that can be advertised, keyed by candidate hash.
+	pub fn collations_by_hash(&self) -> HashMap<CandidateHash, CommittedCandidateReceipt> {
+		self.collations
+			.values()
+			.flatten()
+			.map(|receipt| (receipt.hash(), receipt.clone()))
+			.collect()
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, Requests::CollationFetchingV2(req)) => {
+				let payload = req.payload;
+				let receipt = self
+					.collations
+					.get(&payload.relay_parent)
+					.and_then(|collations| {
+						collations.iter().find(|v| v.hash() == payload.candidate_hash)
+					})
+					.expect("Only advertised collations are requested")
+					.to_plain();
+				let response = CollationFetchingResponse::Collation(receipt, (*self.pov).clone());
+				let _ = req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
@@ -0,0 +1,245 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	disputes::DisputesOptions,
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use itertools::Itertools;
+use polkadot_node_network_protocol::request_response::{
+	v1::{DisputeRequest, DisputeResponse},
+	Requests,
+};
+use polkadot_node_primitives::{DisputeMessage, SignedDisputeStatement};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt, MutateDescriptorV2},
+	BlockNumber, CandidateHash, CoreIndex, GroupIndex, Hash, Header, Id, SessionInfo,
+	ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data, dummy_pvd,
+};
+use sc_network::ProtocolName;
+use sp_core::H256;
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Session info
+	pub session_info: SessionInfo,
+	// Candidate receipts per block, one per core
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// Candidates included per block, all of them are disputed
+	pub included_candidates: HashMap<H256, Vec<CandidateEvent>>,
+	// Pregenerated requests raising a dispute for each included candidate
+	pub dispute_requests: HashMap<CandidateHash, DisputeRequest>,
+	// Tracks to which validators the vote of the node under test was sent
+	pub votes_tracker: HashMap<CandidateHash, Vec<Arc<AtomicBool>>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &DisputesOptions) -> Self {
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(config, &test_authorities);
+		let block_infos: Vec<_> = (1..=config.num_blocks).map(generate_block_info).collect();
+		let mut state = Self {
+			config: config.clone(),
+			block_headers: block_infos.iter().map(generate_block_header).collect(),
+			block_infos,
+			test_authorities,
+			session_info,
+			candidate_receipts: Default::default(),
+			included_candidates: Default::default(),
+			dispute_requests: Default::default(),
+			votes_tracker: Default::default(),
+		};
+
+		let pvd = dummy_pvd(dummy_head_data(), 0);
+		let mut template = dummy_committed_candidate_receipt_v2(dummy_hash());
+		template.descriptor.set_persisted_validation_data_hash(pvd.hash());
+		template.descriptor.set_session_index(SESSION_INDEX);
+
+		let n_disputes = options.n_disputes_per_block.min(config.n_cores);
+		// Validators raising the disputes, the node under test never does.
+		let mut voters = (0..config.n_validators)
+			.filter(|index| *index != NODE_UNDER_TEST as usize)
+			.cycle();
+
+		for block_info in state.block_infos.iter() {
+			for core_idx in 0..config.n_cores {
+				let mut receipt = template.clone();
+				receipt.descriptor.set_para_id(Id::new(core_idx as u32 + 1));
+				receipt.descriptor.set_relay_parent(block_info.hash);
+				receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+				let receipt = receipt.to_plain();
+
+				if core_idx < n_disputes {
+					let candidate_hash = receipt.hash();
+					let (valid_index, invalid_index) =
+						(voters.next().unwrap(), voters.next().unwrap());
+					let request = DisputeRequest::from(
+						DisputeMessage::from_signed_statements(
+							sign_dispute_statement(
+								&state.test_authorities,
+								true,
+								candidate_hash,
+								valid_index,
+							),
+							ValidatorIndex(valid_index as u32),
+							sign_dispute_statement(
+								&state.test_authorities,
+								false,
+								candidate_hash,
+								invalid_index,
+							),
+							ValidatorIndex(invalid_index as u32),
+							receipt.clone(),
+							&state.session_info,
+						)
+						.expect("Dispute messages are generated correctly; qed"),
+					);
+
+					state.dispute_requests.insert(candidate_hash, request);
+					state.votes_tracker.insert(
+						candidate_hash,
+						(0..config.n_validators)
+							.map(|_| Arc::new(AtomicBool::new(false)))
+							.collect_vec(),
+					);
+					state.included_candidates.entry(block_info.hash).or_default().push(
+						CandidateEvent::CandidateIncluded(
+							receipt.clone(),
+							dummy_head_data(),
+							CoreIndex(core_idx as u32),
+							GroupIndex(core_idx as u32),
+						),
+					);
+				}
+
+				state.candidate_receipts.entry(block_info.hash).or_default().push(receipt);
+			}
+		}
+
+		state
+	}
+
+	/// Candidates disputed at `block_hash`.
+	pub fn disputed_candidates(&self, block_hash: &H256) -> Vec<CandidateHash> {
+		self.included_candidates
+			.get(block_hash)
+			.map(|events| {
+				events
+					.iter()
+					.filter_map(|event| match event {
+						CandidateEvent::CandidateIncluded(receipt, ..) => Some(receipt.hash()),
+						_ => None,
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	pub fn reset_trackers(&self) {
+		self.votes_tracker
+			.values()
+			.for_each(|v| v.iter().for_each(|v| v.as_ref().store(false, Ordering::SeqCst)));
+	}
+}
+
+fn sign_dispute_statement(
+	test_authorities: &TestAuthorities,
+	valid: bool,
+	candidate_hash: CandidateHash,
+	validator_index: usize,
+) -> SignedDisputeStatement {
+	SignedDisputeStatement::sign_explicit(
+		&test_authorities.keyring.keystore(),
+		valid,
+		candidate_hash,
+		SESSION_INDEX,
+		test_authorities.validator_public.get(validator_index).unwrap().clone(),
+	)
+	.unwrap()
+	.expect("Validator keys are in the keyring; qed")
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(authority_id, Requests::DisputeSendingV1(req)) => {
+				let index = self
+					.test_authorities
+					.validator_authority_id
+					.iter()
+					.position(|v| v == &authority_id)
+					.unwrap();
+				let candidate_hash = req.payload.0.candidate_receipt.hash();
+				if let Some(tracker) = self.votes_tracker.get(&candidate_hash) {
+					tracker.get(index).unwrap().as_ref().store(true, Ordering::SeqCst);
+				}
+
+				let response = DisputeResponse::Confirmed;
+				let _ = req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
@@ -72,6 +72,10 @@ use xcm_runtime_apis::{
 	authorized_aliases::{Error as AuthorizedAliasersApiError, OriginAliaser},
 	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
 	fees::Error as XcmPaymentApiError,
+	swaps::{
+		DestinationContext as SwapDestinationContext, Error as SwapAndTransferApiError,
+		SwapAndTransferXcm, SwapContext, SwapRoute,
+	},
 	trusted_query::Error as TrustedQueryApiError,
 };
 
@@ -371,6 +375,14 @@ pub mod pallet {
 			})?;
 			Ok(weight_used)
 		}
+
+		fn weigh(
+			message: Box<VersionedXcm<<T as Config>::RuntimeCall>>,
+		) -> Result<Weight, DispatchError> {
+			let mut message: Xcm<<T as Config>::RuntimeCall> =
+				(*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage.into())
+		}
 	}
 
 	impl<T: Config> SendControllerWeightInfo for Pallet<T> {
@@ -401,6 +413,33 @@ pub mod pallet {
 			Self::deposit_event(e);
 			Ok(message_id)
 		}
+
+		fn delivery_fees(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			message: Box<VersionedXcm<()>>,
+		) -> Result<VersionedAssets, DispatchError> {
+			let origin_location = T::SendXcmOrigin::ensure_origin(origin)?;
+			let interior: Junctions =
+				origin_location.try_into().map_err(|_| Error::<T>::InvalidOrigin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let mut message: Xcm<()> =
+				(*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let local_origin = interior.clone().into();
+			if <T::XcmExecutor as FeeManager>::is_waived(Some(&local_origin), FeeReason::ChargeFees)
+			{
+				return Ok(Assets::new().into())
+			}
+
+			// Mirrors `send_xcm`, the fees depend on the message as it is delivered.
+			if interior != Junctions::Here {
+				message.0.insert(0, DescendOrigin(interior));
+			}
+			let (_, fees) =
+				validate_send::<T::XcmRouter>(dest, message).map_err(Error::<T>::from)?;
+			Ok(fees.into())
+		}
 	}
 
 	impl<T: Config> QueryControllerWeightInfo for Pallet<T> {
@@ -1698,6 +1737,63 @@ pub mod pallet {
 				Err(Error::<T>::AliasNotFound.into())
 			}
 		}
+
+		/// Transfer `give` to an exchange chain, swap it there for at least `want` and deposit
+		/// the result to `beneficiary` on `dest`.
+		///
+		/// The programs are built from the `route`:
+		///  - `give` is moved to `route.exchange` using `route.to_exchange`, where
+		///    `route.exchange_fees` of it are used to buy execution and the rest is swapped using
+		///    `ExchangeAsset`;
+		///  - if `dest` is the exchange chain the swapped asset is deposited to `beneficiary`,
+		///    otherwise it is moved to `dest` using `route.from_exchange`, where
+		///    `route.destination_fees` of it are used to buy execution;
+		///  - any leftover of the exchange fees is deposited to `origin`'s location on the exchange
+		///    chain.
+		///
+		/// Remote reserves are not supported for either leg of the route. The runtime can't query
+		/// the remote chains, so the fees of each hop are taken from `route`. They should be
+		/// estimated with `xcm_runtime_apis::swaps::estimate_swap_route`, which also dry-runs the
+		/// programs on the remote chains. The fees for delivering the swapped asset from the
+		/// exchange chain to `dest` are deducted from the swapped asset by the exchange chain.
+		///
+		/// - `origin`: Must be capable of withdrawing `give` and executing XCM.
+		/// - `give`: The asset to be withdrawn and swapped, including the exchange fees.
+		/// - `want`: The minimum amount of the asset to receive in exchange, in the local context.
+		/// - `route`: The exchange chain, the transfer types and the fees of each hop.
+		/// - `dest`: Final destination of the swapped asset. Can be the exchange chain itself.
+		/// - `beneficiary`: A beneficiary location for the swapped asset in the context of `dest`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::transfer_assets())]
+		pub fn swap_and_transfer_assets(
+			origin: OriginFor<T>,
+			give: Box<VersionedAsset>,
+			want: Box<VersionedAsset>,
+			route: Box<SwapRoute>,
+			dest: Box<VersionedLocation>,
+			beneficiary: Box<VersionedLocation>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let give: Asset = (*give).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let want: Asset = (*want).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let dest: Location = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: Location =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			tracing::debug!(
+				target: "xcm::pallet_xcm::swap_and_transfer_assets",
+				?origin_location, ?give, ?want, ?route, ?dest, ?beneficiary,
+			);
+
+			let (exchange, local_xcm, exchange_xcm) = Self::build_swap_and_transfer_programs(
+				origin_location.clone(),
+				give,
+				want,
+				*route,
+				dest,
+				beneficiary,
+			)?;
+			Self::execute_xcm_transfer(origin_location, exchange, local_xcm, Some(exchange_xcm))
+		}
 	}
 }
 
@@ -1994,6 +2090,102 @@ impl<T: Config> Pallet<T> {
 		Self::execute_xcm_transfer(origin, dest, local_xcm, remote_xcm)
 	}
 
+	/// Builds the local and exchange chain programs of a swap and transfer, returning them
+	/// together with the location of the exchange chain.
+	fn build_swap_and_transfer_programs(
+		origin: Location,
+		give: Asset,
+		want: Asset,
+		route: SwapRoute,
+		dest: Location,
+		beneficiary: Location,
+	) -> Result<(Location, Xcm<<T as Config>::RuntimeCall>, Xcm<()>), Error<T>> {
+		let exchange: Location = route.exchange.try_into().map_err(|()| Error::<T>::BadVersion)?;
+		// the exchange chain has to receive the programs directly from us
+		ensure!(
+			!matches!(route.to_exchange, TransferType::RemoteReserve(_)),
+			Error::<T>::InvalidAssetUnsupportedReserve
+		);
+		let give_amount = match give.fun {
+			Fungible(amount) => amount,
+			NonFungible(_) => return Err(Error::<T>::Empty),
+		};
+		ensure!(give_amount > route.exchange_fees, Error::<T>::FeesNotMet);
+
+		let context = T::UniversalLocation::get();
+		let reanchor_to_exchange = |asset: Asset| {
+			asset.reanchored(&exchange, &context).map_err(|e| {
+				tracing::error!(target: "xcm::pallet_xcm::build_swap_and_transfer_programs", ?e, ?exchange, ?context, "Failed to re-anchor asset");
+				Error::<T>::CannotReanchor
+			})
+		};
+		// `give` without the exchange fees is swapped, fees are used for `BuyExecution`
+		let exchange_fees: Asset = (give.id.clone(), route.exchange_fees).into();
+		let swapped: Asset = (give.id.clone(), give_amount - route.exchange_fees).into();
+		let swapped = reanchor_to_exchange(swapped)?;
+		let want_on_exchange = reanchor_to_exchange(want.clone())?;
+		let origin_on_exchange =
+			origin.clone().reanchored(&exchange, &context).map_err(|e| {
+				tracing::error!(target: "xcm::pallet_xcm::build_swap_and_transfer_programs", ?e, ?origin, ?exchange, "Failed to re-anchor origin");
+				Error::<T>::CannotReanchor
+			})?;
+
+		let mut xcm_on_exchange = vec![ExchangeAsset {
+			give: Definite(swapped.into()),
+			want: want_on_exchange.clone().into(),
+			maximal: true,
+		}];
+		let received = Wild(AllOf { id: want_on_exchange.id, fun: WildFungible });
+		if dest == exchange {
+			xcm_on_exchange.push(DepositAsset { assets: received, beneficiary });
+		} else {
+			let dest_on_exchange = dest.clone().reanchored(&exchange, &context).map_err(|e| {
+				tracing::error!(target: "xcm::pallet_xcm::build_swap_and_transfer_programs", ?e, ?dest, ?exchange, "Failed to re-anchor destination");
+				Error::<T>::CannotReanchor
+			})?;
+			let destination_fees: Asset = (want.id, route.destination_fees).into();
+			let destination_fees = destination_fees.reanchored(&dest, &context).map_err(|e| {
+				tracing::error!(target: "xcm::pallet_xcm::build_swap_and_transfer_programs", ?e, ?dest, ?context, "Failed to re-anchor destination fees");
+				Error::<T>::CannotReanchor
+			})?;
+			let xcm_on_dest = Xcm(vec![
+				BuyExecution { fees: destination_fees, weight_limit: Unlimited },
+				DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+			]);
+			xcm_on_exchange.push(match route.from_exchange {
+				TransferType::LocalReserve => DepositReserveAsset {
+					assets: received,
+					dest: dest_on_exchange,
+					xcm: xcm_on_dest,
+				},
+				TransferType::DestinationReserve => InitiateReserveWithdraw {
+					assets: received,
+					reserve: dest_on_exchange,
+					xcm: xcm_on_dest,
+				},
+				TransferType::Teleport =>
+					InitiateTeleport { assets: received, dest: dest_on_exchange, xcm: xcm_on_dest },
+				TransferType::RemoteReserve(_) =>
+					return Err(Error::<T>::InvalidAssetUnsupportedReserve),
+			});
+		}
+		// return whatever is left of the exchange fees
+		xcm_on_exchange
+			.push(DepositAsset { assets: Wild(AllCounted(1)), beneficiary: origin_on_exchange });
+
+		let (local_xcm, exchange_xcm) = Self::build_xcm_transfer_type(
+			origin,
+			exchange.clone(),
+			Either::Right(Xcm(xcm_on_exchange)),
+			vec![give],
+			route.to_exchange,
+			FeesHandling::Batched { fees: exchange_fees },
+			Unlimited,
+		)?;
+		let exchange_xcm = exchange_xcm.ok_or(Error::<T>::InvalidAssetUnsupportedReserve)?;
+		Ok((exchange, local_xcm, exchange_xcm))
+	}
+
 	fn build_xcm_transfer_type(
 		origin: Location,
 		dest: Location,
@@ -2988,6 +3180,94 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Builds the programs of a swap and transfer, as executed by `swap_and_transfer_assets`,
+	/// together with the fees for delivering the program to the exchange chain.
+	///
+	/// Meant to be used in the `xcm_runtime_apis::swaps::SwapAndTransferApi` runtime API.
+	pub fn build_swap_and_transfer(
+		origin: VersionedLocation,
+		give: VersionedAsset,
+		want: VersionedAsset,
+		route: SwapRoute,
+		dest: VersionedLocation,
+		beneficiary: VersionedLocation,
+	) -> Result<SwapAndTransferXcm, SwapAndTransferApiError> {
+		let result_version = dest.identify_version().max(give.identify_version());
+		let convert_error = |()| SwapAndTransferApiError::VersionedConversionFailed;
+		let origin: Location = origin.try_into().map_err(convert_error)?;
+		let give: Asset = give.try_into().map_err(convert_error)?;
+		let want: Asset = want.try_into().map_err(convert_error)?;
+		let dest: Location = dest.try_into().map_err(convert_error)?;
+		let beneficiary: Location = beneficiary.try_into().map_err(convert_error)?;
+		let (give_id, want_id) = (give.id.clone(), want.id.clone());
+
+		let (exchange, local_xcm, exchange_xcm) = Self::build_swap_and_transfer_programs(
+			origin,
+			give,
+			want,
+			route,
+			dest.clone(),
+			beneficiary,
+		)
+		.map_err(|error| {
+			tracing::debug!(
+				target: "xcm::pallet_xcm::build_swap_and_transfer",
+				?error, "Failed to build swap and transfer programs",
+			);
+			match error {
+				Error::<T>::BadVersion => SwapAndTransferApiError::VersionedConversionFailed,
+				Error::<T>::InvalidAssetUnsupportedReserve | Error::<T>::Filtered =>
+					SwapAndTransferApiError::UnsupportedRoute,
+				Error::<T>::FeesNotMet => SwapAndTransferApiError::FeesNotMet,
+				_ => SwapAndTransferApiError::InvalidProgram,
+			}
+		})?;
+		let local_xcm = VersionedXcm::from(local_xcm.into::<()>())
+			.into_version(result_version)
+			.map_err(convert_error)?;
+		let exchange_xcm = VersionedXcm::from(exchange_xcm)
+			.into_version(result_version)
+			.map_err(convert_error)?;
+		let delivery_fees = Self::query_delivery_fees(
+			VersionedLocation::from(exchange.clone()),
+			exchange_xcm.clone(),
+		)
+		.map_err(|error| match error {
+			XcmPaymentApiError::Unroutable => SwapAndTransferApiError::Unroutable,
+			_ => SwapAndTransferApiError::VersionedConversionFailed,
+		})?;
+
+		// locations and assets as seen by the remote chains, for estimating their fees
+		let universal_location = T::UniversalLocation::get();
+		let reanchored = |location: &Location, target: &Location| {
+			location
+				.clone()
+				.reanchored(target, &universal_location)
+				.map_err(|_| SwapAndTransferApiError::InvalidProgram)
+		};
+		let reanchored_id = |id: AssetId, target: &Location| {
+			id.reanchored(target, &universal_location)
+				.map(VersionedAssetId::from)
+				.map_err(|()| SwapAndTransferApiError::InvalidProgram)
+		};
+		let destination = if dest == exchange {
+			None
+		} else {
+			Some(SwapDestinationContext {
+				dest_on_exchange: reanchored(&dest, &exchange)?.into(),
+				exchange_on_dest: reanchored(&exchange, &dest)?.into(),
+				destination_fees_asset: reanchored_id(want_id, &dest)?,
+			})
+		};
+		let context = SwapContext {
+			local_on_exchange: reanchored(&Location::here(), &exchange)?.into(),
+			exchange_fees_asset: reanchored_id(give_id, &exchange)?,
+			destination,
+		};
+
+		Ok(SwapAndTransferXcm { local_xcm, exchange_xcm, delivery_fees, context })
+	}
+
 	/// Given an Asset and a Location, returns if the provided location is a trusted reserve for the
 	/// given asset.
 	pub fn is_trusted_reserve(
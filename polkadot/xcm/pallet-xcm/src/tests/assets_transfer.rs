@@ -29,7 +29,8 @@ use frame_support::{
 use polkadot_parachain_primitives::primitives::Id as ParaId;
 use sp_runtime::traits::AccountIdConversion;
 use xcm::prelude::*;
-use xcm_executor::traits::ConvertLocation;
+use xcm_executor::traits::{ConvertLocation, TransferType};
+use xcm_runtime_apis::swaps::{DestinationContext, SwapContext, SwapRoute};
 
 /// Test `limited_teleport_assets`
 ///
@@ -2626,3 +2627,137 @@ fn limited_reserve_transfer_assets_with_remote_asset_reserve_and_remote_asset_fe
 		expected_result,
 	);
 }
+
+/// Test `swap_and_transfer_assets` swapping native asset on `OTHER_PARA_ID` for USDC and
+/// forwarding it to its reserve `USDC_RESERVE_PARA_ID`.
+///
+/// Asserts that the sender's balance is decreased and the built XCM program matches the one
+/// returned by the `SwapAndTransferApi` helper.
+#[test]
+fn swap_and_transfer_assets_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(OTHER_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	let origin_location: Location =
+		Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+	let beneficiary: Location = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+	let exchange: Location = Parachain(OTHER_PARA_ID).into();
+	let dest: Location = UsdcReserveLocation::get();
+	let want: Asset = (Usdc::get().id, SEND_AMOUNT).into();
+	let route = SwapRoute {
+		exchange: exchange.clone().into(),
+		to_exchange: TransferType::LocalReserve,
+		from_exchange: TransferType::DestinationReserve,
+		exchange_fees: FEE_AMOUNT,
+		destination_fees: FEE_AMOUNT,
+	};
+
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let built = XcmPallet::build_swap_and_transfer(
+			origin_location.clone().into(),
+			(Here, SEND_AMOUNT).into(),
+			want.clone().into(),
+			route.clone(),
+			dest.clone().into(),
+			beneficiary.clone().into(),
+		)
+		.unwrap();
+		assert_ok!(XcmPallet::swap_and_transfer_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new((Here, SEND_AMOUNT).into()),
+			Box::new(want.into()),
+			Box::new(route),
+			Box::new(dest.into()),
+			Box::new(beneficiary.clone().into()),
+		));
+
+		// Alice spent amount
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		// Exchange account (parachain account) has amount
+		let para_acc: AccountId = ParaId::from(OTHER_PARA_ID).into_account_truncating();
+		assert_eq!(Balances::free_balance(para_acc), INITIAL_BALANCE + SEND_AMOUNT);
+
+		let usdc_on_exchange =
+			Location::new(1, [Parachain(USDC_RESERVE_PARA_ID), USDC_INNER_JUNCTION]);
+		let expected_xcm = Xcm(vec![
+			ReserveAssetDeposited((Parent, SEND_AMOUNT).into()),
+			ClearOrigin,
+			buy_execution((Parent, FEE_AMOUNT)),
+			ExchangeAsset {
+				give: Definite((Parent, SEND_AMOUNT - FEE_AMOUNT).into()),
+				want: (usdc_on_exchange.clone(), SEND_AMOUNT).into(),
+				maximal: true,
+			},
+			InitiateReserveWithdraw {
+				assets: Wild(AllOf { id: usdc_on_exchange.into(), fun: WildFungible }),
+				reserve: Location::new(1, [Parachain(USDC_RESERVE_PARA_ID)]),
+				xcm: Xcm(vec![
+					buy_execution((USDC_INNER_JUNCTION, FEE_AMOUNT)),
+					DepositAsset { assets: AllCounted(1).into(), beneficiary },
+				]),
+			},
+			DepositAsset {
+				assets: AllCounted(1).into(),
+				beneficiary: Location::new(1, [AccountId32 { network: None, id: ALICE.into() }]),
+			},
+		]);
+		assert_eq!(sent_xcm(), vec![(exchange, expected_xcm.clone())]);
+		assert_eq!(built.exchange_xcm, VersionedXcm::from(expected_xcm));
+		// locations and assets for estimating the fees of the exchange and the destination
+		assert_eq!(
+			built.context,
+			SwapContext {
+				local_on_exchange: Location::parent().into(),
+				exchange_fees_asset: AssetId(Location::parent()).into(),
+				destination: Some(DestinationContext {
+					dest_on_exchange: Location::new(1, [Parachain(USDC_RESERVE_PARA_ID)]).into(),
+					exchange_on_dest: Location::new(1, [Parachain(OTHER_PARA_ID)]).into(),
+					destination_fees_asset: AssetId(USDC_INNER_JUNCTION.into()).into(),
+				}),
+			}
+		);
+		assert!(matches!(last_event(), RuntimeEvent::XcmPallet(crate::Event::Sent { .. })));
+	});
+}
+
+/// Test `swap_and_transfer_assets` rejects routes through a remote reserve or with exchange
+/// fees not covered by the given asset.
+#[test]
+fn swap_and_transfer_assets_with_invalid_route_disallowed() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	let beneficiary: Location = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+	let exchange: Location = Parachain(OTHER_PARA_ID).into();
+	let want: Asset = (Usdc::get().id, SEND_AMOUNT).into();
+	let route = SwapRoute {
+		exchange: exchange.clone().into(),
+		to_exchange: TransferType::LocalReserve,
+		from_exchange: TransferType::RemoteReserve(RelayLocation::get().into()),
+		exchange_fees: FEE_AMOUNT,
+		destination_fees: FEE_AMOUNT,
+	};
+
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let call = |route: SwapRoute| {
+			XcmPallet::swap_and_transfer_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new((Here, SEND_AMOUNT).into()),
+				Box::new(want.clone().into()),
+				Box::new(route),
+				Box::new(UsdcReserveLocation::get().into()),
+				Box::new(beneficiary.clone().into()),
+			)
+		};
+		assert_err!(call(route.clone()), crate::Error::<Test>::InvalidAssetUnsupportedReserve);
+		assert_err!(
+			call(SwapRoute {
+				from_exchange: TransferType::DestinationReserve,
+				exchange_fees: SEND_AMOUNT,
+				..route
+			}),
+			crate::Error::<Test>::FeesNotMet
+		);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert!(sent_xcm().is_empty());
+	});
+}
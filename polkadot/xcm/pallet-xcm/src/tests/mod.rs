@@ -373,6 +373,35 @@ fn send_fails_when_xcm_router_blocks() {
 	});
 }
 
+/// Test that the delivery fees of `send` are reported without sending anything
+///
+/// Asserts that waived origins and free routes report no fees.
+#[test]
+fn delivery_fees_works() {
+	use xcm_builder::SendController;
+
+	let not_waived = AccountId::new(XCM_FEES_NOT_WAIVED_USER_ACCOUNT);
+	let balances = vec![(ALICE, INITIAL_BALANCE), (not_waived.clone(), INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let message = Box::new(VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])));
+		let delivery_fees = |who: &AccountId, dest: Location| {
+			<XcmPallet as SendController<_>>::delivery_fees(
+				RuntimeOrigin::signed(who.clone()),
+				Box::new(dest.into()),
+				message.clone(),
+			)
+		};
+
+		assert_eq!(
+			delivery_fees(&not_waived, Para3000Location::get()),
+			Ok(Para3000PaymentAssets::get().into())
+		);
+		assert_eq!(delivery_fees(&not_waived, RelayLocation::get()), Ok(Assets::new().into()));
+		assert_eq!(delivery_fees(&ALICE, Para3000Location::get()), Ok(Assets::new().into()));
+		assert!(sent_xcm().is_empty());
+	});
+}
+
 /// Test local execution of XCM
 ///
 /// Asserts that the sender's balance is decreased and the beneficiary's balance
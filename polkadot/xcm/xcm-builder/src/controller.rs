@@ -43,6 +43,14 @@ impl<T, Origin, RuntimeCall, Timeout> Controller<Origin, RuntimeCall, Timeout> f
 pub trait ExecuteControllerWeightInfo {
 	/// Weight for [`ExecuteController::execute`]
 	fn execute() -> Weight;
+
+	/// Weight for [`ExecuteController::weigh`]
+	///
+	/// Defaults to the weight of [`ExecuteController::execute`], which weighs the message before
+	/// executing it and is therefore an upper bound.
+	fn weigh() -> Weight {
+		Self::execute()
+	}
 }
 
 /// Execute an XCM locally, for a given origin.
@@ -68,6 +76,16 @@ pub trait ExecuteController<Origin, RuntimeCall> {
 		message: Box<VersionedXcm<RuntimeCall>>,
 		max_weight: Weight,
 	) -> Result<Weight, DispatchErrorWithPostInfo>;
+
+	/// Weigh an XCM, returns Ok with the weight its local execution would consume, Err if it
+	/// cannot be weighed.
+	///
+	/// # Parameters
+	///
+	/// - `message`: the XCM program to be weighed.
+	fn weigh(_message: Box<VersionedXcm<RuntimeCall>>) -> Result<Weight, DispatchError> {
+		Err(DispatchError::Other("ExecuteController::weigh not implemented"))
+	}
 }
 
 /// Weight functions needed for [`SendController`].
@@ -99,6 +117,24 @@ pub trait SendController<Origin> {
 		dest: Box<VersionedLocation>,
 		message: Box<VersionedXcm<()>>,
 	) -> Result<XcmHash, DispatchError>;
+
+	/// Return the fees `origin` would be charged for delivering `message` to `dest` with
+	/// [`Self::send`].
+	///
+	/// Fails by default, as the fees are unknown.
+	///
+	/// # Parameters
+	///
+	/// - `origin`: the origin of the call.
+	/// - `dest`: the destination of the message.
+	/// - `msg`: the XCM to be sent.
+	fn delivery_fees(
+		_origin: Origin,
+		_dest: Box<VersionedLocation>,
+		_message: Box<VersionedXcm<()>>,
+	) -> Result<VersionedAssets, DispatchError> {
+		Err(DispatchError::Other("SendController::delivery_fees not implemented"))
+	}
 }
 
 /// Weight functions needed for [`QueryController`].
@@ -162,6 +198,14 @@ impl<Origin> SendController<Origin> for () {
 	) -> Result<XcmHash, DispatchError> {
 		Ok(Default::default())
 	}
+
+	fn delivery_fees(
+		_origin: Origin,
+		_dest: Box<VersionedLocation>,
+		_message: Box<VersionedXcm<()>>,
+	) -> Result<VersionedAssets, DispatchError> {
+		Ok(Assets::new().into())
+	}
 }
 
 impl SendControllerWeightInfo for () {
@@ -30,6 +30,9 @@ pub mod dry_run;
 /// Fee estimation API.
 /// Given an XCM program, it will return the fees needed to execute it properly or send it.
 pub mod fees;
+/// Swap and transfer API.
+/// Builds the XCM programs swapping an asset on an exchange chain and forwarding the result.
+pub mod swaps;
 /// Exposes runtime API for querying whether a Location is trusted as a reserve or teleporter for a
 /// given Asset.
 pub mod trusted_query;
@@ -0,0 +1,304 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for building multi-hop "swap and transfer" XCM programs.
+//!
+//! A swap and transfer moves an asset to an exchange chain, swaps it there for another asset
+//! using `ExchangeAsset` and forwards the result to its final destination. The fees required
+//! on each hop are part of the [`SwapRoute`].
+//!
+//! The local runtime can't query the remote chains, so the fees are estimated by clients with
+//! [`estimate_swap_route`]. It queries the [`XcmPaymentApi`](crate::fees::XcmPaymentApi) of the
+//! exchange and destination chains for the fees of each hop and validates the resulting programs
+//! with their [`DryRunApi::dry_run_xcm`](crate::dry_run::DryRunApi).
+
+use crate::{
+	dry_run::{Error as DryRunError, XcmDryRunEffects},
+	fees::Error as FeesError,
+};
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use sp_weights::Weight;
+use xcm::{
+	latest::{Location, Outcome},
+	VersionedAsset, VersionedAssetId, VersionedAssets, VersionedLocation, VersionedXcm,
+};
+use xcm_executor::traits::TransferType;
+
+/// Describes how assets travel through the exchange chain of a swap and transfer.
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, PartialEq, Debug, TypeInfo)]
+pub struct SwapRoute {
+	/// The chain executing the `ExchangeAsset` instruction, relative to the local chain.
+	pub exchange: VersionedLocation,
+	/// The XCM `TransferType` used to move the given asset to `exchange`.
+	pub to_exchange: TransferType,
+	/// The XCM `TransferType` used to move the swapped asset from `exchange` to the final
+	/// destination. Ignored when the destination is the exchange chain itself.
+	pub from_exchange: TransferType,
+	/// Amount of the given asset reserved for execution on `exchange`. It is not swapped and
+	/// whatever is left of it after execution is returned to the origin's account on `exchange`.
+	pub exchange_fees: u128,
+	/// Amount of the swapped asset used to buy execution on the final destination.
+	pub destination_fees: u128,
+}
+
+/// XCM programs built for a swap and transfer.
+#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo)]
+pub struct SwapAndTransferXcm {
+	/// The program executed locally on behalf of the origin.
+	pub local_xcm: VersionedXcm<()>,
+	/// The program sent to, and executed on, the exchange chain. It includes the instructions
+	/// forwarding the swapped asset to its final destination.
+	pub exchange_xcm: VersionedXcm<()>,
+	/// Fees charged to the origin for delivering `exchange_xcm`.
+	pub delivery_fees: VersionedAssets,
+	/// Locations and assets required to estimate the fees of the remote hops.
+	pub context: SwapContext,
+}
+
+/// Locations and assets of a swap and transfer, as seen by the remote chains executing it.
+#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo)]
+pub struct SwapContext {
+	/// The local chain, relative to the exchange chain. This is the origin of `exchange_xcm`.
+	pub local_on_exchange: VersionedLocation,
+	/// The asset paying for execution on the exchange chain, relative to the exchange chain.
+	pub exchange_fees_asset: VersionedAssetId,
+	/// The final destination, unless it is the exchange chain itself.
+	pub destination: Option<DestinationContext>,
+}
+
+/// Locations and assets of the final destination of a swap and transfer.
+#[derive(Clone, Encode, Decode, PartialEq, Debug, TypeInfo)]
+pub struct DestinationContext {
+	/// The final destination, relative to the exchange chain.
+	pub dest_on_exchange: VersionedLocation,
+	/// The exchange chain, relative to the final destination. This is the origin of the program
+	/// forwarding the swapped asset.
+	pub exchange_on_dest: VersionedLocation,
+	/// The asset paying for execution on the final destination, relative to it.
+	pub destination_fees_asset: VersionedAssetId,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for building the XCM programs of a swap and transfer, as executed by the
+	/// `swap_and_transfer_assets` extrinsic of `pallet-xcm`.
+	pub trait SwapAndTransferApi {
+		/// Returns the programs swapping `give` for at least `want` on the exchange chain of
+		/// `route` and depositing the result to `beneficiary` on `dest`.
+		///
+		/// # Arguments
+		/// * `origin`: The location paying for and initiating the transfer.
+		/// * `give`: The asset withdrawn from `origin`, including `route.exchange_fees`.
+		/// * `want`: The minimum amount of the asset to receive in exchange.
+		/// * `route`: `SwapRoute` describing the exchange chain and the fees of each hop.
+		/// * `dest`: The final destination, relative to the local chain.
+		/// * `beneficiary`: The beneficiary of the swapped asset, relative to `dest`.
+		fn build_swap_and_transfer(
+			origin: VersionedLocation,
+			give: VersionedAsset,
+			want: VersionedAsset,
+			route: SwapRoute,
+			dest: VersionedLocation,
+			beneficiary: VersionedLocation,
+		) -> Result<SwapAndTransferXcm, Error>;
+	}
+}
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 0)]
+	VersionedConversionFailed,
+	/// The route is not supported, e.g. the swapped asset would need a remote reserve.
+	#[codec(index = 1)]
+	UnsupportedRoute,
+	/// The given asset cannot cover the fees on the exchange chain.
+	#[codec(index = 2)]
+	FeesNotMet,
+	/// Building the programs failed, e.g. because an asset could not be reanchored.
+	#[codec(index = 3)]
+	InvalidProgram,
+	/// The exchange chain is known to be unroutable.
+	#[codec(index = 4)]
+	Unroutable,
+}
+
+/// Fee estimation and dry-run APIs of a remote chain involved in a swap and transfer.
+///
+/// Implemented by clients on top of the [`XcmPaymentApi`](crate::fees::XcmPaymentApi) and the
+/// [`DryRunApi`](crate::dry_run::DryRunApi) of the chain.
+pub trait SwapHopApi {
+	/// Events of the chain, as returned by the dry-run.
+	type Event;
+
+	/// See [`XcmPaymentApi::query_xcm_weight`](crate::fees::XcmPaymentApi::query_xcm_weight).
+	fn query_xcm_weight(&self, message: VersionedXcm<()>) -> Result<Weight, FeesError>;
+
+	/// See
+	/// [`XcmPaymentApi::query_weight_to_asset_fee`](crate::fees::XcmPaymentApi::query_weight_to_asset_fee).
+	fn query_weight_to_asset_fee(
+		&self,
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, FeesError>;
+
+	/// See [`XcmPaymentApi::query_delivery_fees`](crate::fees::XcmPaymentApi::query_delivery_fees).
+	fn query_delivery_fees(
+		&self,
+		destination: VersionedLocation,
+		message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, FeesError>;
+
+	/// See [`DryRunApi::dry_run_xcm`](crate::dry_run::DryRunApi::dry_run_xcm).
+	fn dry_run_xcm(
+		&self,
+		origin_location: VersionedLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<XcmDryRunEffects<Self::Event>, DryRunError>;
+}
+
+/// A swap and transfer with the estimated fees of each hop.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SwapEstimate {
+	/// The route with the estimated fees of the exchange chain and the final destination.
+	pub route: SwapRoute,
+	/// The programs built for `route`, as returned by [`SwapAndTransferApi`].
+	pub xcm: SwapAndTransferXcm,
+	/// Fees for delivering the swapped asset from the exchange chain to the final destination.
+	///
+	/// The exchange chain deducts them from the swapped asset, so the wanted amount should cover
+	/// them on top of `route.destination_fees`.
+	pub exchange_delivery_fees: Option<VersionedAssets>,
+}
+
+/// Errors of [`estimate_swap_route`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum EstimationError {
+	/// Building the programs with [`SwapAndTransferApi`] failed.
+	Build(Error),
+	/// A fee estimation API of a remote chain failed.
+	Fees(FeesError),
+	/// A dry-run API of a remote chain failed.
+	DryRun(DryRunError),
+	/// The final destination is not the exchange chain, but its APIs were not given.
+	MissingDestinationApi,
+	/// Executing the program on the exchange chain failed.
+	ExchangeExecutionFailed,
+	/// The exchange chain did not forward the swapped asset to the final destination.
+	NotForwarded,
+	/// Executing the forwarded program on the final destination failed.
+	DestinationExecutionFailed,
+}
+
+impl From<Error> for EstimationError {
+	fn from(error: Error) -> Self {
+		Self::Build(error)
+	}
+}
+
+impl From<FeesError> for EstimationError {
+	fn from(error: FeesError) -> Self {
+		Self::Fees(error)
+	}
+}
+
+impl From<DryRunError> for EstimationError {
+	fn from(error: DryRunError) -> Self {
+		Self::DryRun(error)
+	}
+}
+
+/// Estimates the fees of each hop of a swap and transfer and validates the resulting programs.
+///
+/// `build` builds the programs for a route, usually by calling
+/// [`SwapAndTransferApi::build_swap_and_transfer`] of the local chain with the fees of the
+/// route replaced. The fees of the given `route` are ignored:
+///  - `exchange_fees` are the cost of the weight of the program on the `exchange` chain;
+///  - `destination_fees` are the cost of the weight of the program that the exchange chain forwards
+///    to the final `destination`. The program is taken from a dry-run of the swap on the exchange
+///    chain, which also provides the fees for delivering it.
+///
+/// The programs built for the estimated route are dry-run on the exchange chain and on the
+/// final destination, and their execution must complete.
+pub fn estimate_swap_route<Exchange: SwapHopApi, Destination: SwapHopApi>(
+	build: impl Fn(SwapRoute) -> Result<SwapAndTransferXcm, Error>,
+	route: SwapRoute,
+	exchange: &Exchange,
+	destination: Option<&Destination>,
+) -> Result<SwapEstimate, EstimationError> {
+	let mut route = SwapRoute { exchange_fees: 0, destination_fees: 0, ..route };
+	let draft = build(route.clone())?;
+	let weight = exchange.query_xcm_weight(draft.exchange_xcm)?;
+	route.exchange_fees =
+		exchange.query_weight_to_asset_fee(weight, draft.context.exchange_fees_asset)?;
+
+	let Some(dest_context) = draft.context.destination else {
+		let xcm = build(route.clone())?;
+		dry_run_on_exchange(exchange, &xcm)?;
+		return Ok(SwapEstimate { route, xcm, exchange_delivery_fees: None })
+	};
+	let destination = destination.ok_or(EstimationError::MissingDestinationApi)?;
+
+	// the forwarded program is only known once the swap is executed
+	let xcm = build(route.clone())?;
+	let forwarded =
+		forwarded_to(dry_run_on_exchange(exchange, &xcm)?, &dest_context.dest_on_exchange)?;
+	let weight = destination.query_xcm_weight(forwarded)?;
+	route.destination_fees =
+		destination.query_weight_to_asset_fee(weight, dest_context.destination_fees_asset)?;
+
+	let xcm = build(route.clone())?;
+	let forwarded =
+		forwarded_to(dry_run_on_exchange(exchange, &xcm)?, &dest_context.dest_on_exchange)?;
+	let exchange_delivery_fees =
+		exchange.query_delivery_fees(dest_context.dest_on_exchange, forwarded.clone())?;
+	let effects = destination.dry_run_xcm(dest_context.exchange_on_dest, forwarded)?;
+	if !matches!(effects.execution_result, Outcome::Complete { .. }) {
+		return Err(EstimationError::DestinationExecutionFailed)
+	}
+
+	Ok(SwapEstimate { route, xcm, exchange_delivery_fees: Some(exchange_delivery_fees) })
+}
+
+/// Dry-runs the program of the exchange chain, returning the programs it forwards.
+fn dry_run_on_exchange<Exchange: SwapHopApi>(
+	exchange: &Exchange,
+	xcm: &SwapAndTransferXcm,
+) -> Result<Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>, EstimationError> {
+	let effects =
+		exchange.dry_run_xcm(xcm.context.local_on_exchange.clone(), xcm.exchange_xcm.clone())?;
+	match effects.execution_result {
+		Outcome::Complete { .. } => Ok(effects.forwarded_xcms),
+		_ => Err(EstimationError::ExchangeExecutionFailed),
+	}
+}
+
+/// Returns the single program forwarded to `dest`.
+fn forwarded_to(
+	forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+	dest: &VersionedLocation,
+) -> Result<VersionedXcm<()>, EstimationError> {
+	let dest = Location::try_from(dest.clone()).map_err(|()| Error::VersionedConversionFailed)?;
+	let mut programs = forwarded_xcms
+		.into_iter()
+		.filter(|(location, _)| Location::try_from(location.clone()).as_ref() == Ok(&dest))
+		.flat_map(|(_, programs)| programs);
+	match (programs.next(), programs.next()) {
+		(Some(program), None) => Ok(program),
+		_ => Err(EstimationError::NotForwarded),
+	}
+}
@@ -0,0 +1,254 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for estimating the fees of each hop of a swap and transfer.
+
+use core::cell::RefCell;
+use sp_weights::Weight;
+use xcm::prelude::*;
+use xcm_executor::traits::TransferType;
+use xcm_runtime_apis::{
+	dry_run::{Error as DryRunError, XcmDryRunEffects},
+	fees::Error as FeesError,
+	swaps::{
+		estimate_swap_route, DestinationContext, Error, EstimationError, SwapAndTransferXcm,
+		SwapContext, SwapHopApi, SwapRoute,
+	},
+};
+
+/// A remote chain, charging `fee_per_instruction` of `fee_asset` for each instruction of a
+/// program and forwarding `forwarded` when dry-running one.
+struct FakeChain {
+	fee_asset: AssetId,
+	fee_per_instruction: u128,
+	delivery_fee: u128,
+	forwarded: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+	complete: bool,
+	dry_runs: RefCell<Vec<(VersionedLocation, VersionedXcm<()>)>>,
+}
+
+impl FakeChain {
+	fn new(fee_asset: impl Into<AssetId>, fee_per_instruction: u128) -> Self {
+		Self {
+			fee_asset: fee_asset.into(),
+			fee_per_instruction,
+			delivery_fee: 0,
+			forwarded: vec![],
+			complete: true,
+			dry_runs: RefCell::new(vec![]),
+		}
+	}
+}
+
+impl SwapHopApi for FakeChain {
+	type Event = ();
+
+	fn query_xcm_weight(&self, message: VersionedXcm<()>) -> Result<Weight, FeesError> {
+		let message: Xcm<()> =
+			message.try_into().map_err(|()| FeesError::VersionedConversionFailed)?;
+		Ok(Weight::from_parts(message.len() as u64, 0))
+	}
+
+	fn query_weight_to_asset_fee(
+		&self,
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, FeesError> {
+		let asset: AssetId = asset.try_into().map_err(|()| FeesError::VersionedConversionFailed)?;
+		if asset != self.fee_asset {
+			return Err(FeesError::AssetNotFound)
+		}
+		Ok(weight.ref_time() as u128 * self.fee_per_instruction)
+	}
+
+	fn query_delivery_fees(
+		&self,
+		_destination: VersionedLocation,
+		_message: VersionedXcm<()>,
+	) -> Result<VersionedAssets, FeesError> {
+		Ok(Assets::from((Here, self.delivery_fee)).into())
+	}
+
+	fn dry_run_xcm(
+		&self,
+		origin_location: VersionedLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<XcmDryRunEffects<Self::Event>, DryRunError> {
+		self.dry_runs.borrow_mut().push((origin_location, xcm));
+		let execution_result = if self.complete {
+			Outcome::Complete { used: Weight::zero() }
+		} else {
+			Outcome::Incomplete { used: Weight::zero(), error: XcmError::TooExpensive }
+		};
+		Ok(XcmDryRunEffects {
+			execution_result,
+			emitted_events: vec![],
+			forwarded_xcms: self.forwarded.clone(),
+		})
+	}
+}
+
+fn dest_on_exchange() -> Location {
+	Location::new(1, [Parachain(2000)])
+}
+
+fn route() -> SwapRoute {
+	SwapRoute {
+		exchange: Location::new(0, [Parachain(1000)]).into(),
+		to_exchange: TransferType::Teleport,
+		from_exchange: TransferType::DestinationReserve,
+		exchange_fees: 1_000_000,
+		destination_fees: 1_000_000,
+	}
+}
+
+/// Builds a fake program of three instructions for the exchange chain, buying execution with
+/// the exchange fees of the route.
+fn build(
+	destination: Option<DestinationContext>,
+) -> impl Fn(SwapRoute) -> Result<SwapAndTransferXcm, Error> {
+	move |route| {
+		Ok(SwapAndTransferXcm {
+			local_xcm: VersionedXcm::from(Xcm::<()>(vec![ClearOrigin])),
+			exchange_xcm: VersionedXcm::from(Xcm::<()>(vec![
+				ReceiveTeleportedAsset((Parent, 100u128).into()),
+				ClearOrigin,
+				BuyExecution {
+					fees: (Parent, route.exchange_fees).into(),
+					weight_limit: Unlimited,
+				},
+			])),
+			delivery_fees: Assets::from((Here, 1u128)).into(),
+			context: SwapContext {
+				local_on_exchange: Location::parent().into(),
+				exchange_fees_asset: AssetId(Location::parent()).into(),
+				destination: destination.clone(),
+			},
+		})
+	}
+}
+
+fn destination_context() -> DestinationContext {
+	DestinationContext {
+		dest_on_exchange: dest_on_exchange().into(),
+		exchange_on_dest: Location::new(1, [Parachain(1000)]).into(),
+		destination_fees_asset: AssetId(Location::here()).into(),
+	}
+}
+
+/// A program of two instructions forwarded by the exchange chain to the destination.
+fn forwarded() -> VersionedXcm<()> {
+	VersionedXcm::from(Xcm::<()>(vec![WithdrawAsset((Here, 50u128).into()), ClearOrigin]))
+}
+
+#[test]
+fn estimates_fees_of_swap_on_exchange_chain() {
+	let exchange = FakeChain::new(Parent, 10);
+
+	let estimate = estimate_swap_route(build(None), route(), &exchange, None::<&FakeChain>)
+		.expect("estimation succeeds");
+
+	// three instructions of the exchange program
+	assert_eq!(estimate.route, SwapRoute { exchange_fees: 30, destination_fees: 0, ..route() });
+	assert_eq!(estimate.xcm, build(None)(estimate.route.clone()).unwrap());
+	assert_eq!(estimate.exchange_delivery_fees, None);
+	// the estimated program is validated on the exchange chain
+	assert_eq!(
+		exchange.dry_runs.into_inner(),
+		vec![(Location::parent().into(), estimate.xcm.exchange_xcm)]
+	);
+}
+
+#[test]
+fn estimates_fees_of_each_hop() {
+	let mut exchange = FakeChain::new(Parent, 10);
+	exchange.delivery_fee = 5;
+	exchange.forwarded = vec![
+		(Location::new(1, [Parachain(3000)]).into(), vec![VersionedXcm::from(Xcm::<()>(vec![]))]),
+		(dest_on_exchange().into(), vec![forwarded()]),
+	];
+	let destination = FakeChain::new(Here, 7);
+
+	let estimate = estimate_swap_route(
+		build(Some(destination_context())),
+		route(),
+		&exchange,
+		Some(&destination),
+	)
+	.expect("estimation succeeds");
+
+	// three instructions of the exchange program and two of the forwarded one
+	assert_eq!(estimate.route, SwapRoute { exchange_fees: 30, destination_fees: 14, ..route() });
+	assert_eq!(estimate.xcm, build(Some(destination_context()))(estimate.route.clone()).unwrap());
+	// fees for delivering the swapped asset to the destination
+	assert_eq!(estimate.exchange_delivery_fees, Some(Assets::from((Here, 5u128)).into()));
+	// the forwarded program is validated on the destination
+	assert_eq!(
+		destination.dry_runs.into_inner(),
+		vec![(Location::new(1, [Parachain(1000)]).into(), forwarded())]
+	);
+}
+
+#[test]
+fn estimation_fails_for_invalid_routes() {
+	let mut exchange = FakeChain::new(Parent, 10);
+	exchange.forwarded = vec![(dest_on_exchange().into(), vec![forwarded()])];
+	let mut destination = FakeChain::new(Here, 7);
+	let estimate = |exchange: &FakeChain, destination: Option<&FakeChain>| {
+		estimate_swap_route(build(Some(destination_context())), route(), exchange, destination)
+	};
+
+	// the destination APIs are required
+	assert_eq!(estimate(&exchange, None), Err(EstimationError::MissingDestinationApi));
+
+	// the fee asset is not accepted by the destination
+	destination.fee_asset = AssetId(Location::parent());
+	assert_eq!(
+		estimate(&exchange, Some(&destination)),
+		Err(EstimationError::Fees(FeesError::AssetNotFound))
+	);
+	destination.fee_asset = AssetId(Location::here());
+
+	// the program fails on the destination
+	destination.complete = false;
+	assert_eq!(
+		estimate(&exchange, Some(&destination)),
+		Err(EstimationError::DestinationExecutionFailed)
+	);
+	destination.complete = true;
+
+	// nothing is forwarded to the destination
+	exchange.forwarded = vec![];
+	assert_eq!(estimate(&exchange, Some(&destination)), Err(EstimationError::NotForwarded));
+
+	// the program fails on the exchange chain
+	exchange.complete = false;
+	assert_eq!(
+		estimate(&exchange, Some(&destination)),
+		Err(EstimationError::ExchangeExecutionFailed)
+	);
+
+	// the route can't be built
+	assert_eq!(
+		estimate_swap_route(
+			|_| Err(Error::UnsupportedRoute),
+			route(),
+			&exchange,
+			Some(&destination)
+		),
+		Err(EstimationError::Build(Error::UnsupportedRoute))
+	);
+}
@@ -0,0 +1,34 @@
+// Counts the dispute slashes reported or pending on the relay chain over the next `args[0]` blocks.
+async function run(nodeName, networkInfo, args) {
+  const { wsUri, userDefinedTypes } = networkInfo.nodesByName[nodeName];
+  const api = await zombie.connect(wsUri, userDefinedTypes);
+  const blocks = Number(args[0]);
+
+  let slashes = 0;
+  await new Promise(async (resolve, _) => {
+    let blockCount = 0;
+    const unsubscribe = await api.rpc.chain.subscribeNewHeads(async (header) => {
+      const block = await api.rpc.chain.getBlock(header.hash);
+      for (const extrinsic of block.block.extrinsics) {
+        const { section, method } = extrinsic.method;
+        if (section == "parasSlashing" && method == "reportDisputeLostUnsigned") {
+          console.log(`Slash reported in block ${header.number}: ${extrinsic.method.args[0]}`);
+          slashes++;
+        }
+      }
+
+      blockCount++;
+      if (blockCount == blocks) {
+        unsubscribe();
+        return resolve();
+      }
+    });
+  });
+
+  const unapplied = await api.query.parasSlashing.unappliedSlashes.entries();
+  unapplied.forEach(([key, _]) => console.log(`Unapplied slash: ${key.args}`));
+
+  return slashes + unapplied.length;
+}
+
+module.exports = { run };
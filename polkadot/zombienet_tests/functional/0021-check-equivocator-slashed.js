@@ -0,0 +1,41 @@
+// Waits for a validator to be reported for losing a dispute in which it voted on both sides.
+async function run(nodeName, networkInfo) {
+  const { wsUri, userDefinedTypes } = networkInfo.nodesByName[nodeName];
+  const api = await zombie.connect(wsUri, userDefinedTypes);
+
+  return await new Promise(async (resolve, _) => {
+    const unsubscribe = await api.rpc.chain.subscribeNewHeads(async (header) => {
+      const block = await api.rpc.chain.getBlock(header.hash);
+      const apiAt = await api.at(header.hash);
+
+      for (const extrinsic of block.block.extrinsics) {
+        const { section, method, args } = extrinsic.method;
+        if (section != "parasSlashing" || method != "reportDisputeLostUnsigned") {
+          continue;
+        }
+
+        const proof = args[0];
+        const validatorIndex = proof.validatorIndex.toNumber();
+        const { sessionIndex, candidateHash } = proof.timeSlot;
+        const dispute = await apiAt.query.parasDisputes.disputes(sessionIndex, candidateHash);
+        if (dispute.isNone) {
+          continue;
+        }
+
+        const { validatorsFor, validatorsAgainst } = dispute.unwrap();
+        if (
+          validatorsFor.toBoolArray()[validatorIndex] &&
+          validatorsAgainst.toBoolArray()[validatorIndex]
+        ) {
+          console.log(
+            `Validator ${validatorIndex} slashed for candidate ${candidateHash} in session ${sessionIndex}`,
+          );
+          unsubscribe();
+          return resolve(1);
+        }
+      }
+    });
+  });
+}
+
+module.exports = { run };
@@ -43,7 +43,7 @@ use frame_support::{
 	dispatch::{DispatchClass, DispatchInfo},
 	dynamic_params::{dynamic_pallet_params, dynamic_params},
 	genesis_builder_helper::{build_state, get_preset},
-	instances::{Instance1, Instance2},
+	instances::{Instance1, Instance2, Instance3},
 	ord_parameter_types,
 	pallet_prelude::Get,
 	parameter_types,
@@ -430,6 +430,7 @@ pub enum ProxyType {
 	NonTransfer,
 	Governance,
 	Staking,
+	CopyTrading,
 }
 impl Default for ProxyType {
 	fn default() -> Self {
@@ -461,6 +462,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 			ProxyType::Staking => {
 				matches!(c, RuntimeCall::Staking(..) | RuntimeCall::FastUnstake(..))
 			},
+			// Only authorizes `AssetConversionCopyTrading` to mirror trades; no call is proxied.
+			ProxyType::CopyTrading => false,
 		}
 	}
 	fn is_superset(&self, o: &Self) -> bool {
@@ -468,6 +471,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 			(x, y) if x == y => true,
 			(ProxyType::Any, _) => true,
 			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, ProxyType::CopyTrading) => false,
 			(ProxyType::NonTransfer, _) => true,
 			_ => false,
 		}
@@ -1483,6 +1487,8 @@ impl pallet_revive::Config for Runtime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type Xcm = ();
+	type Precompiles = ();
+	type XcmFeeSwap = ();
 	type ChainId = ConstU64<420_420_420>;
 	type NativeToEthRatio = ConstU32<1_000_000>; // 10^(18 - 12) Eth is 10^18, Native is 10^12.
 	type EthGasEncoder = ();
@@ -1874,6 +1880,7 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type MaxSwapPathLength = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<256>;
 	type MintMinLiquidity = MintMinLiquidity;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
@@ -1926,6 +1933,299 @@ impl pallet_asset_rewards::Config for Runtime {
 	type BenchmarkHelper = AssetRewardsBenchmarkHelper;
 }
 
+/// Benchmark Helper
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetConversionOrdersBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_asset_conversion_orders::BenchmarkHelper<AccountId, NativeOrWithId<u32>, Balance>
+	for AssetConversionOrdersBenchmarkHelper
+{
+	fn setup_swap(who: &AccountId) -> (Vec<NativeOrWithId<u32>>, Balance) {
+		use frame_support::{assert_ok, traits::fungibles::Mutate};
+		let _ = Balances::deposit_creating(who, 1_000 * DOLLARS);
+		for asset in [102, 103, 104] {
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset.into(),
+				who.clone().into(),
+				true,
+				1,
+			));
+			assert_ok!(Assets::mint_into(asset, who, 1_000 * DOLLARS));
+		}
+		// A path of `MaxPathLength` assets: asset `102` to asset `104` through three pools.
+		let path = vec![
+			NativeOrWithId::WithId(102),
+			NativeOrWithId::Native,
+			NativeOrWithId::WithId(103),
+			NativeOrWithId::WithId(104),
+		];
+		for pair in path.windows(2) {
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeOrigin::signed(who.clone()),
+				alloc::boxed::Box::new(pair[0].clone()),
+				alloc::boxed::Box::new(pair[1].clone()),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(who.clone()),
+				alloc::boxed::Box::new(pair[0].clone()),
+				alloc::boxed::Box::new(pair[1].clone()),
+				100 * DOLLARS,
+				100 * DOLLARS,
+				1,
+				1,
+				who.clone(),
+			));
+		}
+		(path, DOLLARS)
+	}
+}
+
+parameter_types! {
+	pub const OrderHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::AssetConversionOrders(pallet_asset_conversion_orders::HoldReason::OrderDeposit);
+	// 1 item, 107 bytes into the storage on order placement.
+	pub const OrderDeposit: Balance = deposit(1, 107);
+}
+
+impl pallet_asset_conversion_orders::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetKind = NativeOrWithId<u32>;
+	type Swap = AssetConversion;
+	type AssetsFreezer = NativeAndAssetsFreezer;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		OrderHoldReason,
+		ConstantStoragePrice<OrderDeposit, Balance>,
+	>;
+	type MaxPathLength = ConstU32<4>;
+	type WeightInfo = pallet_asset_conversion_orders::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetConversionOrdersBenchmarkHelper;
+}
+
+impl pallet_assets::Config<Instance3> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSignedBy<StrategyVaultsOrigin, AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = ConstU128<DOLLARS>;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Holder = VaultSharesHolder;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type CallbackHandle = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl pallet_assets_holder::Config<Instance3> for Runtime {
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+/// Benchmark Helper
+#[cfg(feature = "runtime-benchmarks")]
+pub struct StrategyVaultsBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_strategy_vaults::BenchmarkHelper<AccountId, NativeOrWithId<u32>, Balance>
+	for StrategyVaultsBenchmarkHelper
+{
+	fn setup_assets(who: &AccountId, n: u32) -> (Vec<NativeOrWithId<u32>>, Balance) {
+		use frame_support::{
+			assert_ok,
+			traits::fungibles::{Inspect, Mutate},
+		};
+		let _ = Balances::deposit_creating(who, 1_000 * DOLLARS);
+		// Asset `200` is the denomination, pooled with each other asset.
+		let assets = (200..200 + n).collect::<Vec<_>>();
+		for asset in assets.iter().copied() {
+			if !Assets::asset_exists(asset) {
+				assert_ok!(Assets::force_create(
+					RuntimeOrigin::root(),
+					asset.into(),
+					who.clone().into(),
+					true,
+					1,
+				));
+			}
+			assert_ok!(Assets::mint_into(asset, who, 1_000 * DOLLARS));
+		}
+		for asset in assets.iter().copied().skip(1) {
+			let pool = (NativeOrWithId::WithId(200), NativeOrWithId::WithId(asset));
+			if !pallet_asset_conversion::Pools::<Runtime>::contains_key(&pool) {
+				assert_ok!(AssetConversion::create_pool(
+					RuntimeOrigin::signed(who.clone()),
+					alloc::boxed::Box::new(pool.0.clone()),
+					alloc::boxed::Box::new(pool.1.clone()),
+				));
+				assert_ok!(AssetConversion::add_liquidity(
+					RuntimeOrigin::signed(who.clone()),
+					alloc::boxed::Box::new(pool.0),
+					alloc::boxed::Box::new(pool.1),
+					100 * DOLLARS,
+					100 * DOLLARS,
+					1,
+					1,
+					who.clone(),
+				));
+			}
+		}
+		(assets.into_iter().map(NativeOrWithId::WithId).collect(), 100 * DOLLARS)
+	}
+}
+
+parameter_types! {
+	pub const StrategyVaultsPalletId: PalletId = PalletId(*b"py/vault");
+	pub const VaultCreationHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::StrategyVaults(pallet_strategy_vaults::HoldReason::VaultCreation);
+	// 1 item, 141 bytes into the storage on vault creation.
+	pub const VaultCreationDeposit: Balance = deposit(1, 141);
+	pub const MaxVaultManagementFee: Permill = Permill::from_percent(5);
+	pub const MaxVaultPerformanceFee: Permill = Permill::from_percent(30);
+	pub const VaultBlocksPerYear: BlockNumber = 365 * DAYS;
+	pub const VaultWithdrawalDelay: BlockNumber = 1 * DAYS;
+	pub const VaultPriceWindow: BlockNumber = 1 * HOURS;
+	pub const VaultMintMinShares: Balance = 1_000;
+}
+
+ord_parameter_types! {
+	pub const StrategyVaultsOrigin: AccountId = AccountIdConversion::<AccountId>::into_account_truncating(&StrategyVaultsPalletId::get());
+}
+
+impl pallet_strategy_vaults::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = StrategyVaultsPalletId;
+	type Balance = Balance;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type ShareAssetId = <Self as pallet_assets::Config<Instance3>>::AssetId;
+	type ShareAssets = VaultShares;
+	type ShareAssetsHolder = VaultSharesHolder;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Swap = AssetConversion;
+	type PriceWindow = VaultPriceWindow;
+	type MintMinShares = VaultMintMinShares;
+	type CreateVaultOrigin = EnsureSigned<AccountId>;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		VaultCreationHoldReason,
+		ConstantStoragePrice<VaultCreationDeposit, Balance>,
+	>;
+	type MaxAssets = ConstU32<8>;
+	type MaxManagementFee = MaxVaultManagementFee;
+	type MaxPerformanceFee = MaxVaultPerformanceFee;
+	type BlocksPerYear = VaultBlocksPerYear;
+	type WithdrawalDelay = VaultWithdrawalDelay;
+	type WeightInfo = pallet_strategy_vaults::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = StrategyVaultsBenchmarkHelper;
+}
+
+/// Benchmark Helper
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetConversionCopyTradingBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_asset_conversion_copy_trading::BenchmarkHelper<AccountId, NativeOrWithId<u32>, Balance>
+	for AssetConversionCopyTradingBenchmarkHelper
+{
+	fn setup_swap(who: &AccountId, path_len: u32) -> (Vec<NativeOrWithId<u32>>, Balance) {
+		use frame_support::{
+			assert_ok,
+			traits::fungibles::{Inspect, Mutate},
+		};
+		let _ = Balances::deposit_creating(who, 4_000 * DOLLARS);
+		// The first account set up creates assets `104`, `105` and `106`, and the pools of the
+		// longest path: asset `104` to asset `106` through `Native` and asset `105`.
+		let path = [
+			NativeOrWithId::WithId(104),
+			NativeOrWithId::Native,
+			NativeOrWithId::WithId(105),
+			NativeOrWithId::WithId(106),
+		];
+		if !Assets::asset_exists(104) {
+			for asset in [104, 105, 106] {
+				assert_ok!(Assets::force_create(
+					RuntimeOrigin::root(),
+					asset.into(),
+					who.clone().into(),
+					true,
+					1,
+				));
+				assert_ok!(Assets::mint_into(asset, who, 3_000 * DOLLARS));
+			}
+			for pair in path.windows(2) {
+				assert_ok!(AssetConversion::create_pool(
+					RuntimeOrigin::signed(who.clone()),
+					alloc::boxed::Box::new(pair[0].clone()),
+					alloc::boxed::Box::new(pair[1].clone()),
+				));
+				assert_ok!(AssetConversion::add_liquidity(
+					RuntimeOrigin::signed(who.clone()),
+					alloc::boxed::Box::new(pair[0].clone()),
+					alloc::boxed::Box::new(pair[1].clone()),
+					1_000 * DOLLARS,
+					1_000 * DOLLARS,
+					1,
+					1,
+					who.clone(),
+				));
+			}
+		}
+		assert_ok!(Assets::mint_into(104, who, 100 * DOLLARS));
+		(path[..path_len as usize].to_vec(), DOLLARS)
+	}
+}
+
+parameter_types! {
+	pub const CopyTradingPalletId: PalletId = PalletId(*b"py/cptrd");
+	pub const CopyTradingProxyType: ProxyType = ProxyType::CopyTrading;
+	pub const SubscriptionHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::AssetConversionCopyTrading(pallet_asset_conversion_copy_trading::HoldReason::SubscriptionDeposit);
+	// 1 item, 72 bytes into the storage on follow.
+	pub const SubscriptionDeposit: Balance = deposit(1, 72);
+	pub const CopyTradingMaxSlippage: Permill = Permill::from_percent(5);
+	pub const CopyTradingPriceWindow: BlockNumber = 10 * MINUTES;
+}
+
+impl pallet_asset_conversion_copy_trading::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = CopyTradingPalletId;
+	type CopyTradingProxyType = CopyTradingProxyType;
+	type Balance = Balance;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type Swap = AssetConversion;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		SubscriptionHoldReason,
+		ConstantStoragePrice<SubscriptionDeposit, Balance>,
+	>;
+	type MaxFollowers = ConstU32<64>;
+	type MaxSlippage = CopyTradingMaxSlippage;
+	type PriceWindow = CopyTradingPriceWindow;
+	type WeightInfo = pallet_asset_conversion_copy_trading::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetConversionCopyTradingBenchmarkHelper;
+}
+
 impl pallet_asset_conversion_ops::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PriorAccountIdConverter = pallet_asset_conversion::AccountIdConverterNoSeed<(
@@ -2082,6 +2382,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 	type AssetId = <Self as pallet_assets::Config<Instance1>>::AssetId;
 	type Assets = Assets;
 	type Nfts = Nfts;
+	type RoyaltyBearingNfts = pallet_nfts::RoyaltyBearing<Runtime>;
 	type PalletId = NftFractionalizationPalletId;
 	type WeightInfo = pallet_nft_fractionalization::weights::SubstrateWeight<Runtime>;
 	type RuntimeHoldReason = RuntimeHoldReason;
@@ -2113,6 +2414,7 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = MaxTips;
 	type MaxDeadlineDuration = MaxDeadlineDuration;
 	type MaxAttributesPerCall = MaxAttributesPerCall;
+	type MaxRoyaltyRecipients = ConstU32<5>;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as traits::Verify>::Signer;
@@ -2290,6 +2592,8 @@ impl pallet_migrations::Config for Runtime {
 parameter_types! {
 	pub const BrokerPalletId: PalletId = PalletId(*b"py/broke");
 	pub const MinimumCreditPurchase: Balance =  100 * MILLICENTS;
+	pub const MarketOrderDeposit: Balance = 100 * MILLICENTS;
+	pub const MinimumBid: Balance = 100 * MILLICENTS;
 }
 
 pub struct IntoAuthor;
@@ -2330,6 +2634,7 @@ impl MaybeConvert<TaskId, AccountId> for SovereignAccountOf {
 impl pallet_broker::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type OnRevenue = IntoAuthor;
 	type TimeslicePeriod = ConstU32<2>;
 	type MaxLeasedCores = ConstU32<5>;
@@ -2343,6 +2648,10 @@ impl pallet_broker::Config for Runtime {
 	type MaxAutoRenewals = ConstU32<10>;
 	type PriceAdapter = pallet_broker::CenterTargetPrice<Balance>;
 	type MinimumCreditPurchase = MinimumCreditPurchase;
+	type MaxMarketOrders = ConstU32<20>;
+	type MarketOrderDeposit = MarketOrderDeposit;
+	type MinimumBid = MinimumBid;
+	type MaxBidsPerAccount = ConstU32<4>;
 }
 
 parameter_types! {
@@ -2770,6 +3079,21 @@ mod runtime {
 
 	#[runtime::pallet_index(89)]
 	pub type MetaTx = pallet_meta_tx::Pallet<Runtime>;
+
+	#[runtime::pallet_index(90)]
+	pub type AssetConversionOrders = pallet_asset_conversion_orders::Pallet<Runtime>;
+
+	#[runtime::pallet_index(91)]
+	pub type VaultShares = pallet_assets::Pallet<Runtime, Instance3>;
+
+	#[runtime::pallet_index(92)]
+	pub type StrategyVaults = pallet_strategy_vaults::Pallet<Runtime>;
+
+	#[runtime::pallet_index(93)]
+	pub type AssetConversionCopyTrading = pallet_asset_conversion_copy_trading::Pallet<Runtime>;
+
+	#[runtime::pallet_index(94)]
+	pub type VaultSharesHolder = pallet_assets_holder::Pallet<Runtime, Instance3>;
 }
 
 impl TryFrom<RuntimeCall> for pallet_revive::Call<Runtime> {
@@ -3032,6 +3356,9 @@ mod benches {
 		[pallet_safe_mode, SafeMode]
 		[pallet_example_mbm, PalletExampleMbms]
 		[pallet_asset_conversion_ops, AssetConversionMigration]
+		[pallet_asset_conversion_orders, AssetConversionOrders]
+		[pallet_asset_conversion_copy_trading, AssetConversionCopyTrading]
+		[pallet_strategy_vaults, StrategyVaults]
 		[pallet_verify_signature, VerifySignature]
 		[pallet_meta_tx, MetaTx]
 	);
@@ -3456,9 +3783,9 @@ impl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: pallet_revive::evm::TracerConfig
-		) -> Vec<(u32, pallet_revive::evm::CallTrace)> {
+		) -> Vec<(u32, pallet_revive::evm::Trace)> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let mut traces = vec![];
 			let (header, extrinsics) = block.deconstruct();
 
@@ -3468,7 +3795,7 @@ impl_runtime_apis! {
 					let _ = Executive::apply_extrinsic(ext);
 				});
 
-				if let Some(tx_trace) = tracer.collect_traces().pop() {
+				if let Some(tx_trace) = tracer.collect_trace() {
 					traces.push((index as u32, tx_trace));
 				}
 			}
@@ -3480,9 +3807,9 @@ impl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: pallet_revive::evm::TracerConfig
-		) -> Option<pallet_revive::evm::CallTrace> {
+		) -> Option<pallet_revive::evm::Trace> {
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let (header, extrinsics) = block.deconstruct();
 
 			Executive::initialize_block(&header);
@@ -3497,19 +3824,19 @@ impl_runtime_apis! {
 				}
 			}
 
-			tracer.collect_traces().pop()
+			tracer.collect_trace()
 		}
 
 		fn trace_call(
 			tx: pallet_revive::evm::GenericTransaction,
 			config: pallet_revive::evm::TracerConfig)
-			-> Result<pallet_revive::evm::CallTrace, pallet_revive::EthTransactError>
+			-> Result<pallet_revive::evm::Trace, pallet_revive::EthTransactError>
 		{
 			use pallet_revive::tracing::trace;
-			let mut tracer = config.build(Revive::evm_gas_from_weight);
+			let mut tracer = Revive::evm_tracer(config);
 			let result = trace(&mut tracer, || Self::eth_transact(tx));
 
-			if let Some(trace) = tracer.collect_traces().pop() {
+			if let Some(trace) = tracer.collect_trace() {
 				Ok(trace)
 			} else if let Err(err) = result {
 				Err(err)
@@ -3517,6 +3844,15 @@ impl_runtime_apis! {
 				Ok(Default::default())
 			}
 		}
+
+		fn quote_xcm_send_fee(
+			address: H160,
+			dest: staging_xcm::VersionedLocation,
+			message: staging_xcm::VersionedXcm<()>,
+			fee_asset: Vec<u8>,
+		) -> Result<u128, sp_runtime::DispatchError> {
+			Revive::quote_xcm_send_fee(address, dest, message, fee_asset)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
@@ -3554,6 +3890,15 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn quote_price_time_weighted(
+			asset1: NativeOrWithId<u32>,
+			asset2: NativeOrWithId<u32>,
+			amount: Balance,
+			window: u32,
+		) -> Option<Balance> {
+			AssetConversion::quote_price_time_weighted(asset1, asset2, amount, window)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
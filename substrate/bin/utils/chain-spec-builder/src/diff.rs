@@ -0,0 +1,387 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Comparison of chain specs by storage key.
+
+use crate::{ChainSpec, DiffCmd};
+use codec::{Compact, Decode};
+use frame_metadata::{
+	v14::{StorageEntryType, StorageHasher},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use sc_chain_spec::GenesisConfigBuilderRuntimeCaller;
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::Value;
+use sp_crypto_hashing::twox_128;
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt::Write,
+	path::Path,
+};
+
+/// The storage key of the runtime code.
+const CODE: &[u8] = b":code";
+
+/// The top storage of a raw chain spec, with keys and values decoded from hex.
+type TopStorage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// The keys of a single storage item which differ between two chain specs.
+#[derive(Default)]
+struct ItemDiff {
+	added: Vec<Vec<u8>>,
+	removed: Vec<Vec<u8>>,
+	changed: Vec<Vec<u8>>,
+}
+
+/// A storage item of a runtime.
+struct StorageItem {
+	/// The name of the item, prefixed by the name of its pallet.
+	name: String,
+	/// The hashers and the type of the keys, if the item is a map.
+	keys: Option<(Vec<StorageHasher>, u32)>,
+}
+
+/// The names of the storage items of a runtime, by the prefix of their storage keys.
+struct StorageNames {
+	pallets: HashMap<[u8; 16], String>,
+	items: HashMap<[u8; 32], StorageItem>,
+	types: PortableRegistry,
+}
+
+impl StorageNames {
+	/// Collects the names of all storage items in the SCALE encoded `RuntimeMetadataPrefixed`.
+	fn from_metadata(metadata: &[u8]) -> Result<Self, String> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| format!("Metadata could not be decoded: {e}"))?;
+		let (storages, types) = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => (
+				metadata
+					.pallets
+					.into_iter()
+					.filter_map(|pallet| pallet.storage)
+					.collect::<Vec<_>>(),
+				metadata.types,
+			),
+			RuntimeMetadata::V15(metadata) => (
+				metadata
+					.pallets
+					.into_iter()
+					.filter_map(|pallet| pallet.storage)
+					.collect::<Vec<_>>(),
+				metadata.types,
+			),
+			_ => return Err("Unsupported metadata version".into()),
+		};
+
+		let mut names = StorageNames { pallets: HashMap::new(), items: HashMap::new(), types };
+		for storage in storages {
+			let prefix = storage.prefix;
+			let pallet_hash = twox_128(prefix.as_bytes());
+			for entry in storage.entries {
+				let mut key = [0u8; 32];
+				key[..16].copy_from_slice(&pallet_hash);
+				key[16..].copy_from_slice(&twox_128(entry.name.as_bytes()));
+				let keys = match entry.ty {
+					StorageEntryType::Plain(_) => None,
+					StorageEntryType::Map { hashers, key, .. } => Some((hashers, key.id)),
+				};
+				names
+					.items
+					.insert(key, StorageItem { name: format!("{prefix}::{}", entry.name), keys });
+			}
+			names.pallets.insert(pallet_hash, prefix);
+		}
+		Ok(names)
+	}
+
+	/// The name of the storage item `key` belongs to.
+	fn name_of(&self, key: &[u8]) -> String {
+		// Well-known keys, such as `:code`, are readable as they are.
+		if key.starts_with(b":") {
+			return String::from_utf8_lossy(key).into_owned()
+		}
+		if let Some(item) = self.item_of(key) {
+			return item.name.clone()
+		}
+		let pallet = key.get(..16).and_then(|prefix| <[u8; 16]>::try_from(prefix).ok());
+		match pallet.and_then(|pallet| self.pallets.get(&pallet)) {
+			Some(pallet) => format!("{pallet}::<unknown>"),
+			None => "<unknown>".into(),
+		}
+	}
+
+	/// Describes `key` in a human readable way.
+	///
+	/// The keys of storage maps are decoded, except those behind opaque hashers, which are shown
+	/// as their hashes. Any other key is shown hex encoded.
+	fn describe(&self, key: &[u8]) -> String {
+		let decoded = self
+			.item_of(key)
+			.and_then(|item| item.keys.as_ref())
+			.and_then(|(hashers, ty)| decode_map_key(&self.types, hashers, *ty, &key[32..]).ok());
+		match decoded {
+			Some(decoded) => format!("[{decoded}]"),
+			None => array_bytes::bytes2hex("0x", key),
+		}
+	}
+
+	/// The storage item `key` belongs to.
+	fn item_of(&self, key: &[u8]) -> Option<&StorageItem> {
+		let item = key.get(..32).and_then(|prefix| <[u8; 32]>::try_from(prefix).ok())?;
+		self.items.get(&item)
+	}
+}
+
+/// Decodes the `key` of a storage map, without its prefix, into its parts separated by commas.
+fn decode_map_key(
+	types: &PortableRegistry,
+	hashers: &[StorageHasher],
+	ty: u32,
+	mut key: &[u8],
+) -> Result<String, String> {
+	// The type of the key of a map with several hashers is the tuple of the types of its parts.
+	let tys = if hashers.len() == 1 {
+		vec![ty]
+	} else {
+		match &types.resolve(ty).ok_or_else(|| format!("Unknown type {ty}"))?.type_def {
+			TypeDef::Tuple(tuple) if tuple.fields.len() == hashers.len() =>
+				tuple.fields.iter().map(|field| field.id).collect(),
+			_ => return Err(format!("Type {ty} doesn't match the hashers")),
+		}
+	};
+
+	let mut parts = Vec::with_capacity(hashers.len());
+	for (hasher, ty) in hashers.iter().zip(tys) {
+		let (hash_len, concat) = match hasher {
+			StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+			StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+			StorageHasher::Blake2_128Concat => (16, true),
+			StorageHasher::Twox64Concat => (8, true),
+			StorageHasher::Identity => (0, true),
+		};
+		if key.len() < hash_len {
+			return Err("Key is too short".into())
+		}
+		let (hash, rest) = key.split_at(hash_len);
+		key = rest;
+		parts.push(if concat {
+			decode_value(types, ty, &mut key)?
+		} else {
+			array_bytes::bytes2hex("0x", hash)
+		});
+	}
+	if !key.is_empty() {
+		return Err("Key is too long".into())
+	}
+	Ok(parts.join(", "))
+}
+
+/// Decodes a SCALE encoded value of type `ty` from `input` into a human readable string.
+///
+/// Byte arrays and sequences are shown hex encoded, compact values as the numbers they are.
+fn decode_value(types: &PortableRegistry, ty: u32, input: &mut &[u8]) -> Result<String, String> {
+	let type_def = &types.resolve(ty).ok_or_else(|| format!("Unknown type {ty}"))?.type_def;
+	Ok(match type_def {
+		TypeDef::Composite(composite) => match &composite.fields[..] {
+			// Wrapper types, such as account ids, are shown as the value they wrap.
+			[field] if field.name.is_none() => decode_value(types, field.ty.id, input)?,
+			fields => decode_fields(types, fields, input)?,
+		},
+		TypeDef::Variant(variant) => {
+			let index = u8::decode(input).map_err(|e| e.to_string())?;
+			let variant = variant
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or_else(|| format!("Unknown variant {index} of type {ty}"))?;
+			match &variant.fields[..] {
+				[] => variant.name.clone(),
+				fields if fields[0].name.is_some() =>
+					format!("{} {}", variant.name, decode_fields(types, fields, input)?),
+				fields => format!("{}{}", variant.name, decode_fields(types, fields, input)?),
+			}
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = Compact::<u32>::decode(input).map_err(|e| e.to_string())?.0;
+			decode_items(types, sequence.type_param.id, len as usize, input)?
+		},
+		TypeDef::Array(array) =>
+			decode_items(types, array.type_param.id, array.len as usize, input)?,
+		TypeDef::Tuple(tuple) => {
+			let items = tuple
+				.fields
+				.iter()
+				.map(|field| decode_value(types, field.id, input))
+				.collect::<Result<Vec<_>, _>>()?;
+			format!("({})", items.join(", "))
+		},
+		TypeDef::Primitive(primitive) => decode_primitive(primitive, input)?,
+		TypeDef::Compact(_) =>
+			Compact::<u128>::decode(input).map_err(|e| e.to_string())?.0.to_string(),
+		TypeDef::BitSequence(_) => return Err("Bit sequences are not supported".into()),
+	})
+}
+
+/// Decodes the values of `fields`, as a struct if they are named and as a tuple otherwise.
+fn decode_fields(
+	types: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+) -> Result<String, String> {
+	let mut named = true;
+	let mut values = Vec::with_capacity(fields.len());
+	for field in fields {
+		let value = decode_value(types, field.ty.id, input)?;
+		match field.name {
+			Some(ref name) => values.push(format!("{name}: {value}")),
+			None => {
+				named = false;
+				values.push(value);
+			},
+		}
+	}
+	Ok(if named && !values.is_empty() {
+		format!("{{ {} }}", values.join(", "))
+	} else {
+		format!("({})", values.join(", "))
+	})
+}
+
+/// Decodes `len` items of type `ty`.
+fn decode_items(
+	types: &PortableRegistry,
+	ty: u32,
+	len: usize,
+	input: &mut &[u8],
+) -> Result<String, String> {
+	let is_byte = matches!(
+		types.resolve(ty).map(|ty| &ty.type_def),
+		Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+	);
+	if is_byte {
+		if input.len() < len {
+			return Err("Not enough bytes".into())
+		}
+		let (bytes, rest) = input.split_at(len);
+		*input = rest;
+		return Ok(array_bytes::bytes2hex("0x", bytes))
+	}
+	let items = (0..len)
+		.map(|_| decode_value(types, ty, input))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(format!("[{}]", items.join(", ")))
+}
+
+/// Decodes a primitive value.
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<String, String> {
+	fn decode<T: Decode + ToString>(input: &mut &[u8]) -> Result<String, String> {
+		T::decode(input).map(|value| value.to_string()).map_err(|e| e.to_string())
+	}
+	match primitive {
+		TypeDefPrimitive::Bool => decode::<bool>(input),
+		TypeDefPrimitive::Char => u32::decode(input)
+			.ok()
+			.and_then(char::from_u32)
+			.map(|c| format!("{c:?}"))
+			.ok_or_else(|| "Invalid char".into()),
+		TypeDefPrimitive::Str =>
+			String::decode(input).map(|s| format!("{s:?}")).map_err(|e| e.to_string()),
+		TypeDefPrimitive::U8 => decode::<u8>(input),
+		TypeDefPrimitive::U16 => decode::<u16>(input),
+		TypeDefPrimitive::U32 => decode::<u32>(input),
+		TypeDefPrimitive::U64 => decode::<u64>(input),
+		TypeDefPrimitive::U128 => decode::<u128>(input),
+		TypeDefPrimitive::I8 => decode::<i8>(input),
+		TypeDefPrimitive::I16 => decode::<i16>(input),
+		TypeDefPrimitive::I32 => decode::<i32>(input),
+		TypeDefPrimitive::I64 => decode::<i64>(input),
+		TypeDefPrimitive::I128 => decode::<i128>(input),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => <[u8; 32]>::decode(input)
+			.map(|bytes| array_bytes::bytes2hex("0x", bytes))
+			.map_err(|e| e.to_string()),
+	}
+}
+
+/// Processes `DiffCmd` and returns the human readable differences between the chain specs.
+pub fn diff_chain_specs(cmd: &DiffCmd) -> Result<String, String> {
+	let left = top_storage(cmd.left_chain_spec.as_path())?;
+	let right = top_storage(cmd.right_chain_spec.as_path())?;
+
+	let code = right
+		.get(CODE)
+		.or_else(|| left.get(CODE))
+		.ok_or("Neither chain spec contains the runtime code")?;
+	let metadata = <GenesisConfigBuilderRuntimeCaller>::new(&code[..]).get_metadata()?;
+	let names = StorageNames::from_metadata(&metadata)?;
+
+	let mut items = BTreeMap::<String, ItemDiff>::new();
+	for (key, value) in &left {
+		match right.get(key) {
+			None => items.entry(names.name_of(key)).or_default().removed.push(key.clone()),
+			Some(right_value) if right_value != value =>
+				items.entry(names.name_of(key)).or_default().changed.push(key.clone()),
+			Some(_) => {},
+		}
+	}
+	for key in right.keys().filter(|key| !left.contains_key(*key)) {
+		items.entry(names.name_of(key)).or_default().added.push(key.clone());
+	}
+
+	let mut output = String::new();
+	for (name, diff) in &items {
+		writeln!(
+			output,
+			"{name}: {} added, {} removed, {} changed",
+			diff.added.len(),
+			diff.removed.len(),
+			diff.changed.len()
+		)
+		.expect("writing to a string can't fail; qed");
+		if cmd.keys {
+			for (sign, keys) in [("+", &diff.added), ("-", &diff.removed), ("~", &diff.changed)] {
+				for key in keys {
+					writeln!(output, "  {sign} {}", names.describe(key))
+						.expect("writing to a string can't fail; qed");
+				}
+			}
+		}
+	}
+	Ok(output)
+}
+
+/// Reads the top storage of the chain spec at `path`, converting it into the raw format if needed.
+fn top_storage(path: &Path) -> Result<TopStorage, String> {
+	let chain_spec = ChainSpec::from_json_file(path.to_path_buf())?;
+	let chain_spec = serde_json::from_str::<Value>(&chain_spec.as_json(true)?)
+		.map_err(|e| format!("Conversion to json failed: {e}"))?;
+
+	chain_spec
+		.pointer("/genesis/raw/top")
+		.and_then(Value::as_object)
+		.ok_or_else(|| format!("Chain spec {path:?} has no raw storage"))?
+		.iter()
+		.map(|(key, value)| {
+			let value = value.as_str().ok_or_else(|| format!("Invalid value of key {key}"))?;
+			Ok((
+				array_bytes::hex2bytes(key).map_err(|e| format!("Invalid key {key}: {e:?}"))?,
+				array_bytes::hex2bytes(value)
+					.map_err(|e| format!("Invalid value {value}: {e:?}"))?,
+			))
+		})
+		.collect()
+}
@@ -0,0 +1,210 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Building of development chain specs out of state exports.
+
+use crate::{extract_chain_spec_json, ForkCmd};
+use codec::Encode;
+use serde_json::{json, Map, Value};
+use sp_core::crypto::{AccountId32, KeyTypeId};
+use sp_crypto_hashing::{twox_128, twox_64};
+use std::str::FromStr;
+
+/// The key type of Aura session keys.
+const AURA: KeyTypeId = KeyTypeId(*b"aura");
+
+/// The names under which the para ID extension of a chain spec may be found.
+const PARA_ID: &[&str] = &["para_id", "paraId", "ParaId"];
+
+/// The names under which the relay chain extension of a chain spec may be found.
+const RELAY_CHAIN: &[&str] = &["relay_chain", "relayChain", "RelayChain"];
+
+/// The storage values tracking the progress of the relay chain and of the slots of a chain.
+///
+/// They are removed, so that the forked chain accepts the blocks and slots of a new local relay
+/// chain, which start over from genesis.
+const RELAY_CHAIN_PROGRESS: &[(&str, &str)] = &[
+	("ParachainSystem", "LastRelayChainBlockNumber"),
+	("AuraExt", "RelaySlotInfo"),
+	("Aura", "CurrentSlot"),
+];
+
+/// The top storage of a raw chain spec, mapping hex encoded keys to hex encoded values.
+type TopStorage = Map<String, Value>;
+
+/// Processes `ForkCmd` and returns string representation of JSON version of the forked
+/// `ChainSpec`.
+pub fn fork_chain_spec(cmd: &ForkCmd) -> Result<String, String> {
+	let mut chain_spec = extract_chain_spec_json(cmd.input_state.as_path())?;
+
+	let top = chain_spec
+		.pointer_mut("/genesis/raw/top")
+		.and_then(Value::as_object_mut)
+		.ok_or("State export should be a raw chain spec, as written by `export-state`")?;
+
+	if let Some(ref sudo) = cmd.sudo {
+		set_sudo(top, &parse_account(sudo)?)?;
+	}
+	if !cmd.collators.is_empty() {
+		let collators =
+			cmd.collators.iter().map(|c| parse_account(c)).collect::<Result<Vec<_>, _>>()?;
+		set_collators(top, &collators)?;
+	}
+	if let Some(para_id) = cmd.para_id {
+		set_para_id(top, para_id)?;
+	}
+	reset_relay_chain_progress(top);
+
+	let overrides = json!({
+		"name": cmd.chain_name,
+		"id": cmd.chain_id,
+		"chainType": "Development",
+		"bootNodes": [],
+		"telemetryEndpoints": null,
+		"codeSubstitutes": {},
+	});
+	let chain_spec = chain_spec.as_object_mut().ok_or("Chain spec should be a JSON object")?;
+	if let Value::Object(overrides) = overrides {
+		chain_spec.extend(overrides);
+	}
+	if let Some(para_id) = cmd.para_id {
+		set_extension(chain_spec, PARA_ID, json!(para_id));
+	}
+	if let Some(ref relay_chain) = cmd.relay_chain {
+		set_extension(chain_spec, RELAY_CHAIN, json!(relay_chain));
+	}
+
+	serde_json::to_string_pretty(&chain_spec).map_err(|e| format!("to pretty failed: {e}"))
+}
+
+/// Parses an account given either as SS58 address or as hex encoded account id.
+fn parse_account(account: &str) -> Result<AccountId32, String> {
+	AccountId32::from_str(account).map_err(|e| format!("Invalid account {account}: {e}"))
+}
+
+/// Sets `Sudo::Key` to `sudo`.
+fn set_sudo(top: &mut TopStorage, sudo: &AccountId32) -> Result<(), String> {
+	ensure_pallet(top, "Sudo")?;
+	insert(top, value_key("Sudo", "Key"), sudo);
+	Ok(())
+}
+
+/// Replaces the collator set, the session validators and their session keys, and the Aura
+/// authorities with `collators`.
+///
+/// Each collator's account key is used as its Aura key, which assumes the session keys of the
+/// runtime consist of an Aura key only, as is the case for system parachains.
+fn set_collators(top: &mut TopStorage, collators: &[AccountId32]) -> Result<(), String> {
+	let keys = collators.iter().cloned().map(<[u8; 32]>::from).collect::<Vec<_>>();
+	let mut rewritten = false;
+
+	if has_pallet(top, "Session") {
+		insert(top, value_key("Session", "Validators"), &keys);
+		insert(
+			top,
+			value_key("Session", "QueuedKeys"),
+			&keys.iter().map(|key| (key, key)).collect::<Vec<_>>(),
+		);
+		insert(top, value_key("Session", "DisabledValidators"), &Vec::<u32>::new());
+		clear_prefix(top, &value_key("Session", "NextKeys"));
+		clear_prefix(top, &value_key("Session", "KeyOwner"));
+		for key in &keys {
+			insert(top, map_key("Session", "NextKeys", key), key);
+			insert(top, map_key("Session", "KeyOwner", &(AURA, key.to_vec())), key);
+		}
+		rewritten = true;
+	}
+	for pallet in ["Aura", "AuraExt"] {
+		if has_pallet(top, pallet) {
+			insert(top, value_key(pallet, "Authorities"), &keys);
+			rewritten = true;
+		}
+	}
+	if has_pallet(top, "CollatorSelection") {
+		insert(top, value_key("CollatorSelection", "Invulnerables"), &keys);
+		insert(top, value_key("CollatorSelection", "CandidateList"), &Vec::<()>::new());
+		rewritten = true;
+	}
+
+	if !rewritten {
+		return Err("State export has none of `Session`, `Aura` or `CollatorSelection` pallets, \
+			can't replace the collators"
+			.into())
+	}
+	Ok(())
+}
+
+/// Sets `ParachainInfo::ParachainId` to `para_id`.
+fn set_para_id(top: &mut TopStorage, para_id: u32) -> Result<(), String> {
+	ensure_pallet(top, "ParachainInfo")?;
+	insert(top, value_key("ParachainInfo", "ParachainId"), &para_id);
+	Ok(())
+}
+
+/// Removes the `RELAY_CHAIN_PROGRESS` storage values.
+fn reset_relay_chain_progress(top: &mut TopStorage) {
+	for (pallet, item) in RELAY_CHAIN_PROGRESS {
+		clear_prefix(top, &value_key(pallet, item));
+	}
+}
+
+/// Sets the chain spec extension known by any of `names` to `value`.
+///
+/// The name used by the chain spec is kept, as the extensions can't be given under more than one
+/// of them. The first one is used if the chain spec has none.
+fn set_extension(chain_spec: &mut Map<String, Value>, names: &[&str], value: Value) {
+	let name = names.iter().find(|name| chain_spec.contains_key(**name)).unwrap_or(&names[0]);
+	chain_spec.insert(name.to_string(), value);
+}
+
+/// The storage key of the storage value `item` of `pallet`.
+fn value_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// The storage key of `key` in the `Twox64Concat` storage map `item` of `pallet`.
+fn map_key(pallet: &str, item: &str, key: &impl Encode) -> Vec<u8> {
+	let key = key.encode();
+	[value_key(pallet, item), twox_64(&key).to_vec(), key].concat()
+}
+
+/// Whether `top` holds any storage of `pallet`.
+fn has_pallet(top: &TopStorage, pallet: &str) -> bool {
+	let prefix = array_bytes::bytes2hex("0x", twox_128(pallet.as_bytes()));
+	top.keys().any(|key| key.to_lowercase().starts_with(&prefix))
+}
+
+fn ensure_pallet(top: &TopStorage, pallet: &str) -> Result<(), String> {
+	if has_pallet(top, pallet) {
+		Ok(())
+	} else {
+		Err(format!("State export has no `{pallet}` pallet"))
+	}
+}
+
+fn insert(top: &mut TopStorage, key: Vec<u8>, value: &impl Encode) {
+	top.insert(
+		array_bytes::bytes2hex("0x", key),
+		Value::String(array_bytes::bytes2hex("0x", value.encode())),
+	);
+}
+
+fn clear_prefix(top: &mut TopStorage, prefix: &[u8]) {
+	let prefix = array_bytes::bytes2hex("0x", prefix);
+	top.retain(|key, _| !key.to_lowercase().starts_with(&prefix));
+}
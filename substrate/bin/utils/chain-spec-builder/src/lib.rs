@@ -19,6 +19,12 @@
 #[cfg(feature = "generate-readme")]
 docify::compile_markdown!("README.docify.md", "README.md");
 
+mod diff;
+mod fork;
+
+pub use diff::diff_chain_specs;
+pub use fork::fork_chain_spec;
+
 use clap::{Parser, Subcommand};
 use sc_chain_spec::{
 	json_patch, set_code_substitute_in_json_chain_spec, update_code_in_json_chain_spec, ChainType,
@@ -53,6 +59,8 @@ pub enum ChainSpecBuilderCmd {
 	ListPresets(ListPresetsCmd),
 	DisplayPreset(DisplayPresetCmd),
 	AddCodeSubstitute(AddCodeSubstituteCmd),
+	Fork(ForkCmd),
+	Diff(DiffCmd),
 }
 
 /// Create a new chain spec by interacting with the provided runtime wasm blob.
@@ -175,6 +183,62 @@ pub struct AddCodeSubstituteCmd {
 	pub block_height: u64,
 }
 
+/// Builds a raw development chain spec out of a state export.
+///
+/// The input is the chain spec written by the `export-state` command of a node, which carries the
+/// state of a block of a live network as its raw genesis. The resulting chain spec starts a new
+/// local chain from that state, so that bugs seen on the live network can be reproduced. The boot
+/// nodes, telemetry endpoints and code substitutes of the live network are dropped.
+///
+/// The state is rewritten according to the given options. The relay chain block number and slot
+/// recorded by `ParachainSystem` and `AuraExt`, and the slot recorded by `Aura`, are reset in any
+/// case, so that the fork can follow a new local relay chain. The pallets involved are looked up
+/// by their conventional names (`Sudo`, `Session`, `Aura`, `AuraExt`, `CollatorSelection`,
+/// `ParachainSystem` and `ParachainInfo`).
+#[derive(Parser, Debug, Clone)]
+pub struct ForkCmd {
+	/// State export to fork from.
+	pub input_state: PathBuf,
+	/// The name of the forked chain.
+	#[arg(long, short = 'n', default_value = "Fork")]
+	pub chain_name: String,
+	/// The id of the forked chain.
+	#[arg(long, short = 'i', default_value = "fork")]
+	pub chain_id: String,
+	/// The new sudo key, as SS58 address or hex encoded account id.
+	#[arg(long)]
+	pub sudo: Option<String>,
+	/// The new collators, as SS58 addresses or hex encoded sr25519 public keys.
+	///
+	/// Replaces the collator set, the session validators and the Aura authorities. Each collator
+	/// gets its own account key as its Aura session key, as the development accounts do.
+	#[arg(long = "collator", value_delimiter = ',')]
+	pub collators: Vec<String>,
+	/// The new para ID.
+	#[arg(long, short = 'p')]
+	pub para_id: Option<u32>,
+	/// The relay chain to connect to. The one of the state export is kept if not given.
+	#[arg(long, short = 'c', requires = "para_id")]
+	pub relay_chain: Option<String>,
+}
+
+/// Compares two chain specs by storage key.
+///
+/// Both chain specs are converted into the raw format first. The differing keys are grouped by
+/// the pallet and storage item they belong to, as described by the metadata of the runtime of
+/// the second chain spec, which is also used to decode the keys of storage maps when listing
+/// them. Only the top storage is compared.
+#[derive(Parser, Debug, Clone)]
+pub struct DiffCmd {
+	/// The chain spec to compare from.
+	pub left_chain_spec: PathBuf,
+	/// The chain spec to compare to.
+	pub right_chain_spec: PathBuf,
+	/// List every differing key, not only the number of them per storage item.
+	#[arg(long, short = 'k')]
+	pub keys: bool,
+}
+
 /// Converts the given chain spec into the raw format.
 #[derive(Parser, Debug, Clone)]
 pub struct ConvertToRawCmd {
@@ -292,6 +356,13 @@ impl ChainSpecBuilder {
 					.map_err(|e| format!("Conversion to pretty failed: {e}"))?;
 				fs::write(chain_spec_path, chain_spec_json).map_err(|err| err.to_string())?;
 			},
+			ChainSpecBuilderCmd::Fork(cmd) => {
+				let chain_spec_json = fork_chain_spec(cmd)?;
+				fs::write(chain_spec_path, chain_spec_json).map_err(|err| err.to_string())?;
+			},
+			ChainSpecBuilderCmd::Diff(cmd) => {
+				print!("{}", diff_chain_specs(cmd)?);
+			},
 			ChainSpecBuilderCmd::Verify(VerifyCmd { ref input_chain_spec }) => {
 				let chain_spec = ChainSpec::from_json_file(input_chain_spec.clone())?;
 				serde_json::from_str::<serde_json::Value>(&chain_spec.as_json(true)?)
@@ -19,11 +19,13 @@
 use std::fs::File;
 
 use clap::Parser;
+use codec::Encode;
 
 use cmd_lib::spawn_with_output;
 use sc_chain_spec::update_code_in_json_chain_spec;
-use serde_json::{from_reader, from_str, Value};
-use staging_chain_spec_builder::ChainSpecBuilder;
+use serde_json::{from_reader, from_str, json, Value};
+use sp_crypto_hashing::{twox_128, twox_64};
+use staging_chain_spec_builder::{diff_chain_specs, ChainSpecBuilder, DiffCmd};
 
 // note: the runtime path will not be read, runtime code will be set directly, to avoid hassle with
 // creating the wasm file or providing a valid existing path during test execution.
@@ -257,6 +259,124 @@ fn test_create_with_properties() {
 	assert_output_eq_expected(true, SUFFIX, "tests/expected/create_with_properties.json");
 }
 
+/// The storage key of the storage value `item` of `pallet`.
+fn value_key(pallet: &str, item: &str) -> String {
+	array_bytes::bytes2hex("0x", [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat())
+}
+
+const ALICE: &str = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+const BOB: &str = "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+
+#[test]
+fn test_fork() {
+	const SUFFIX: &str = "12";
+	let input_path = OUTPUT_FILE.to_string() + SUFFIX + ".input";
+	let state = json!({
+		"name": "Live",
+		"id": "live",
+		"chainType": "Live",
+		"bootNodes": ["/dns/boot.live.network/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp"],
+		"telemetryEndpoints": null,
+		"protocolId": null,
+		"properties": null,
+		"codeSubstitutes": { "100": "0x010203" },
+		"relay_chain": "polkadot",
+		"paraId": 1000,
+		"genesis": { "raw": { "top": {
+			"0x3a636f6465": "0x010203",
+			value_key("Sudo", "Key"): ALICE,
+			value_key("Session", "Validators"): format!("0x04{}", &ALICE[2..]),
+			value_key("Session", "NextKeys") + "0011223344556677": ALICE,
+			value_key("Aura", "Authorities"): format!("0x04{}", &ALICE[2..]),
+			value_key("ParachainInfo", "ParachainId"): "0xe8030000",
+			value_key("ParachainSystem", "LastRelayChainBlockNumber"): "0x40420f00",
+			value_key("AuraExt", "RelaySlotInfo"): "0x40420f000000000001000000",
+			value_key("Aura", "CurrentSlot"): "0x40420f0000000000",
+		}, "childrenDefault": {} } }
+	});
+	std::fs::write(&input_path, state.to_string()).unwrap();
+
+	let builder = get_builder(
+		SUFFIX,
+		vec!["fork", &input_path, "--sudo", BOB, "--collator", BOB, "-p", "2000", "-c", "rococo"],
+	);
+	builder.run().unwrap();
+
+	let path = OUTPUT_FILE.to_string() + SUFFIX;
+	let output: Value = from_reader(File::open(&path).unwrap()).unwrap();
+	assert_eq!(output["name"], "Fork");
+	assert_eq!(output["chainType"], "Development");
+	assert_eq!(output["bootNodes"], json!([]));
+	assert_eq!(output["codeSubstitutes"], json!({}));
+	// the para ID is kept under the name used by the state export
+	assert_eq!(output["paraId"], 2000);
+	assert!(output.get("para_id").is_none());
+	assert_eq!(output["relay_chain"], "rococo");
+
+	let top = output["genesis"]["raw"]["top"].as_object().unwrap();
+	assert_eq!(top["0x3a636f6465"], "0x010203");
+	assert_eq!(top[&value_key("Sudo", "Key")], BOB);
+	assert_eq!(top[&value_key("Session", "Validators")], format!("0x04{}", &BOB[2..]));
+	assert_eq!(top[&value_key("Aura", "Authorities")], format!("0x04{}", &BOB[2..]));
+	assert_eq!(top[&value_key("ParachainInfo", "ParachainId")], "0xd0070000");
+	// the progress of the former relay chain is forgotten
+	assert!(!top.contains_key(&value_key("ParachainSystem", "LastRelayChainBlockNumber")));
+	assert!(!top.contains_key(&value_key("AuraExt", "RelaySlotInfo")));
+	assert!(!top.contains_key(&value_key("Aura", "CurrentSlot")));
+	// the session keys of the former validators are gone
+	let next_keys = value_key("Session", "NextKeys");
+	let next_keys = top.keys().filter(|key| key.starts_with(&next_keys)).collect::<Vec<_>>();
+	assert_eq!(next_keys.len(), 1);
+	assert!(next_keys[0].ends_with(&BOB[2..]));
+
+	std::fs::remove_file(input_path).expect("Failed to delete file");
+	std::fs::remove_file(path).expect("Failed to delete file");
+}
+
+#[test]
+fn test_diff() {
+	const SUFFIX: &str = "13";
+	let mut builder = get_builder(
+		SUFFIX,
+		vec!["create", "-r", DUMMY_PATH, "-s", "patch", "tests/input/patch.json"],
+	);
+	builder.set_create_cmd_runtime_code(substrate_test_runtime::WASM_BINARY.unwrap().into());
+	builder.run().unwrap();
+
+	let left_path = OUTPUT_FILE.to_string() + SUFFIX;
+	let right_path = left_path.clone() + ".right";
+	let mut right: Value = from_reader(File::open(&left_path).unwrap()).unwrap();
+	let top = right["genesis"]["raw"]["top"].as_object_mut().unwrap();
+	top.insert(value_key("Balances", "TotalIssuance"), json!("0x2a"));
+	top.insert(value_key("Balances", "Unknown"), json!("0x00"));
+	let block_number = 7u64.encode();
+	top.insert(
+		value_key("System", "BlockHash") +
+			&array_bytes::bytes2hex("", [twox_64(&block_number).to_vec(), block_number].concat()),
+		json!(format!("0x{}", "00".repeat(32))),
+	);
+	std::fs::write(&right_path, right.to_string()).unwrap();
+
+	let cmd = DiffCmd {
+		left_chain_spec: left_path.clone().into(),
+		right_chain_spec: right_path.clone().into(),
+		keys: true,
+	};
+	assert_eq!(
+		diff_chain_specs(&cmd).unwrap(),
+		format!(
+			"Balances::<unknown>: 1 added, 0 removed, 0 changed\n  + {}\n\
+			Balances::TotalIssuance: 0 added, 0 removed, 1 changed\n  ~ {}\n\
+			System::BlockHash: 1 added, 0 removed, 0 changed\n  + [7]\n",
+			value_key("Balances", "Unknown"),
+			value_key("Balances", "TotalIssuance"),
+		)
+	);
+
+	std::fs::remove_file(left_path).expect("Failed to delete file");
+	std::fs::remove_file(right_path).expect("Failed to delete file");
+}
+
 #[docify::export_content]
 fn cmd_create_default(runtime_path: &str) -> String {
 	bash!(
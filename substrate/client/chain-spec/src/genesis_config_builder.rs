@@ -171,6 +171,19 @@ where
 
 		Ok(preset_names)
 	}
+
+	/// Returns the SCALE encoded `RuntimeMetadataPrefixed` of the `runtime`.
+	///
+	/// Calls the `Metadata_metadata` runtime API provided by the `runtime`.
+	pub fn get_metadata(&self) -> core::result::Result<Vec<u8>, String> {
+		let mut t = BasicExternalities::new_empty();
+		let call_result = self
+			.call(&mut t, "Metadata_metadata", &vec![])
+			.map_err(|e| format!("wasm call error {e}"))?;
+
+		// The runtime returns the metadata as `OpaqueMetadata`, i.e. an encoded `Vec<u8>`.
+		Vec::<u8>::decode(&mut &call_result[..]).map_err(|e| format!("scale codec error: {e}"))
+	}
 }
 
 #[cfg(test)]
@@ -200,6 +213,16 @@ mod tests {
 		assert_eq!(from_str::<Value>(expected).unwrap(), config);
 	}
 
+	#[test]
+	fn get_metadata_works() {
+		let metadata =
+			<GenesisConfigBuilderRuntimeCaller>::new(substrate_test_runtime::wasm_binary_unwrap())
+				.get_metadata()
+				.unwrap();
+		// The metadata is prefixed with the magic number `meta`.
+		assert_eq!(&metadata[..4], b"meta");
+	}
+
 	#[test]
 	fn get_named_preset_works() {
 		sp_tracing::try_init_simple();
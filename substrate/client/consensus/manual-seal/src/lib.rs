@@ -23,7 +23,7 @@ use futures::prelude::*;
 use futures_timer::Delay;
 use prometheus_endpoint::Registry;
 use sc_client_api::{
-	backend::{Backend as ClientBackend, Finalizer},
+	backend::{Backend as ClientBackend, BlockImportOperation, Finalizer, LockImportRun},
 	client::BlockchainEvents,
 };
 use sc_consensus::{
@@ -34,7 +34,10 @@ use sp_blockchain::HeaderBackend;
 use sp_consensus::{Environment, Proposer, SelectChain};
 use sp_core::traits::SpawnNamed;
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::{traits::Block as BlockT, ConsensusEngineId};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	ConsensusEngineId,
+};
 use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 mod error;
@@ -48,10 +51,11 @@ pub use self::{
 	consensus::ConsensusDataProvider,
 	error::Error,
 	finalize_block::{finalize_block, FinalizeBlockParams},
-	rpc::{CreatedBlock, EngineCommand},
-	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
+	rpc::{CreatedBlock, EngineCommand, SnapshotId},
+	seal_block::{seal_block, SealBlockParams, TimestampOverride, MAX_PROPOSAL_DURATION},
 };
-use sc_transaction_pool_api::TransactionPool;
+use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool};
+use seal_block::TimestampAdjustment;
 use sp_api::ProvideRuntimeApi;
 
 const LOG_TARGET: &str = "manual-seal";
@@ -152,6 +156,33 @@ pub struct DelayedFinalizeParams<C, S> {
 
 /// Creates the background authorship task for the manually seal engine.
 pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
+	params: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+) where
+	B: BlockT + 'static,
+	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ LockImportRun<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ 'static,
+	CB: ClientBackend<B> + 'static,
+	E: Environment<B> + 'static,
+	E::Proposer: Proposer<B, Proof = P>,
+	CS: Stream<Item = EngineCommand<<B as BlockT>::Hash>> + Unpin + 'static,
+	SC: SelectChain<B> + 'static,
+	TP: MaintainedTransactionPool<Block = B>,
+	CIDP: CreateInherentDataProviders<B, ()>,
+	P: codec::Encode + Send + Sync + 'static,
+{
+	run_manual_seal_with_timestamp_override(params, TimestampOverride::default()).await
+}
+
+/// Creates the background authorship task for the manually seal engine, adjusting the timestamps
+/// of the sealed blocks with the given [`TimestampOverride`].
+///
+/// The caller may keep a clone of the override to learn the timestamp of the next block, e.g. to
+/// provide inherent data that has to agree with it.
+pub async fn run_manual_seal_with_timestamp_override<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	ManualSealParams {
 		mut block_import,
 		mut env,
@@ -162,19 +193,28 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 		consensus_data_provider,
 		create_inherent_data_providers,
 	}: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+	timestamp_override: TimestampOverride,
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ LockImportRun<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
 	CS: Stream<Item = EngineCommand<<B as BlockT>::Hash>> + Unpin + 'static,
 	SC: SelectChain<B> + 'static,
-	TP: TransactionPool<Block = B>,
+	TP: MaintainedTransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
+	// blocks (and timestamp adjustments) remembered by `EngineCommand::Snapshot`, indexed by
+	// the snapshot id
+	let mut snapshots: Vec<(<B as BlockT>::Hash, TimestampAdjustment)> = Vec::new();
+
 	while let Some(command) = commands_stream.next().await {
 		match command {
 			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, sender } => {
@@ -190,6 +230,7 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 					pool: pool.clone(),
 					client: client.clone(),
 					create_inherent_data_providers: &create_inherent_data_providers,
+					timestamp_override: &timestamp_override,
 				})
 				.await;
 			},
@@ -204,10 +245,55 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 				})
 				.await
 			},
+			EngineCommand::Snapshot { mut sender } => {
+				let result = select_chain.best_chain().await.map_err(Error::from).map(|best| {
+					snapshots.push((best.hash(), timestamp_override.save()));
+					log::info!(target: LOG_TARGET, "📸 Created snapshot of block {}", best.hash());
+					(snapshots.len() - 1) as SnapshotId
+				});
+				rpc::send_result(&mut sender, result)
+			},
+			EngineCommand::Revert { id, mut sender } => {
+				let result = match usize::try_from(id).ok().filter(|id| *id < snapshots.len()) {
+					Some(id) => {
+						let (hash, timestamp_adjustment) = snapshots[id];
+						revert_to(&*client, &*pool, hash).await.map(|_| {
+							snapshots.truncate(id);
+							timestamp_override.restore(timestamp_adjustment);
+							log::info!(target: LOG_TARGET, "⏪ Reverted to block {}", hash);
+							true
+						})
+					},
+					None => Ok(false),
+				};
+				rpc::send_result(&mut sender, result)
+			},
+			EngineCommand::SetNextBlockTimestamp { timestamp, mut sender } => {
+				timestamp_override.set_next(timestamp);
+				rpc::send_result(&mut sender, Ok(()))
+			},
 		}
 	}
 }
 
+/// Makes `hash` the best block and lets the pool know about it, so that the transactions of the
+/// blocks that are no longer part of the best chain are returned to the pool.
+async fn revert_to<B, CB, C, TP>(
+	client: &C,
+	pool: &TP,
+	hash: <B as BlockT>::Hash,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	C: LockImportRun<B, CB>,
+	CB: ClientBackend<B>,
+	TP: MaintainedTransactionPool<Block = B>,
+{
+	client.lock_import_and_run(|operation| operation.op.mark_head(hash))?;
+	pool.maintain(ChainEvent::NewBestBlock { hash, tree_route: None }).await;
+	Ok(())
+}
+
 /// runs the background authorship task for the instant seal engine.
 /// instant-seal creates a new block for every transaction imported into
 /// the transaction pool.
@@ -224,12 +310,16 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ LockImportRun<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
 	SC: SelectChain<B> + 'static,
-	TP: TransactionPool<Block = B>,
+	TP: MaintainedTransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
@@ -274,12 +364,16 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ LockImportRun<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
 	SC: SelectChain<B> + 'static,
-	TP: TransactionPool<Block = B>,
+	TP: MaintainedTransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
@@ -640,6 +734,315 @@ mod tests {
 		rx.await.unwrap().unwrap();
 	}
 
+	#[tokio::test]
+	async fn manual_seal_snapshot_and_revert() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), None, &spawner.clone()));
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+
+		let mut create_block = {
+			let mut sink = sink.clone();
+			move || {
+				let (tx, rx) = futures::channel::oneshot::channel();
+				sink.try_send(EngineCommand::SealNewBlock {
+					parent_hash: None,
+					sender: Some(tx),
+					create_empty: true,
+					finalize: false,
+				})
+				.unwrap();
+				rx
+			}
+		};
+
+		// create block#1 and take a snapshot
+		let block1 = create_block().await.unwrap().unwrap();
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Snapshot { sender: Some(tx) }).await.unwrap();
+		let snapshot_id = rx.await.unwrap().unwrap();
+		assert_eq!(snapshot_id, 0);
+
+		// create block#2 and block#3
+		create_block().await.unwrap().unwrap();
+		create_block().await.unwrap().unwrap();
+		assert_eq!(client.info().best_number, 3);
+
+		// unknown snapshot is not reverted
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Revert { id: 1, sender: Some(tx) }).await.unwrap();
+		assert!(!rx.await.unwrap().unwrap());
+		assert_eq!(client.info().best_number, 3);
+
+		// revert to block#1
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Revert { id: snapshot_id, sender: Some(tx) })
+			.await
+			.unwrap();
+		assert!(rx.await.unwrap().unwrap());
+		assert_eq!(client.info().best_hash, block1.hash);
+
+		// next block is built on top of block#1
+		let block2 = create_block().await.unwrap().unwrap();
+		let header = client.header(block2.hash).unwrap().unwrap();
+		assert_eq!(*header.parent_hash(), block1.hash);
+		assert_eq!(client.info().best_hash, block2.hash);
+
+		// the snapshot is discarded after revert
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Revert { id: snapshot_id, sender: Some(tx) })
+			.await
+			.unwrap();
+		assert!(!rx.await.unwrap().unwrap());
+	}
+
+	#[tokio::test]
+	async fn manual_seal_revert_returns_transactions_to_the_pool() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), None, &spawner.clone()));
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+
+		let mut create_block = {
+			let mut sink = sink.clone();
+			let pool = pool.clone();
+			move |create_empty| {
+				let (tx, rx) = futures::channel::oneshot::channel();
+				sink.try_send(EngineCommand::SealNewBlock {
+					parent_hash: None,
+					sender: Some(tx),
+					create_empty,
+					finalize: false,
+				})
+				.unwrap();
+				let pool = pool.clone();
+				async move {
+					let block = rx.await.unwrap().unwrap();
+					pool.maintain(sc_transaction_pool_api::ChainEvent::NewBestBlock {
+						hash: block.hash,
+						tree_route: None,
+					})
+					.await;
+					block
+				}
+			}
+		};
+
+		// create block#1 and take a snapshot
+		let block1 = create_block(true).await;
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Snapshot { sender: Some(tx) }).await.unwrap();
+		let snapshot_id = rx.await.unwrap().unwrap();
+
+		// include a transaction in block#2
+		assert!(pool.submit_one(block1.hash, SOURCE, uxt(Alice, 0)).await.is_ok());
+		create_block(false).await;
+		assert_eq!(client.info().best_number, 2);
+		assert_eq!(pool.status().ready, 0);
+
+		// the transaction of the reverted block is ready again
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::Revert { id: snapshot_id, sender: Some(tx) })
+			.await
+			.unwrap();
+		assert!(rx.await.unwrap().unwrap());
+		assert_eq!(client.info().best_hash, block1.hash);
+		assert_eq!(pool.status().ready, 1);
+
+		// and is included in the next block
+		let block2 = create_block(false).await;
+		let header = client.header(block2.hash).unwrap().unwrap();
+		assert_eq!(*header.parent_hash(), block1.hash);
+		assert_eq!(pool.status().ready, 0);
+	}
+
+	#[tokio::test]
+	async fn manual_seal_mine() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		}));
+
+		let rpc = rpc::ManualSeal::new(sink);
+		let created_blocks = rpc::ManualSealApiServer::mine(&rpc, 3, false).await.unwrap();
+		assert_eq!(created_blocks.len(), 3);
+		assert_eq!(client.info().best_number, 3);
+		assert_eq!(client.info().best_hash, created_blocks[2].hash);
+		for (parent, block) in created_blocks.iter().zip(&created_blocks[1..]) {
+			let header = client.header(block.hash).unwrap().unwrap();
+			assert_eq!(*header.parent_hash(), parent.hash);
+		}
+
+		// too many blocks are refused without creating any of them
+		assert!(rpc::ManualSealApiServer::mine(&rpc, rpc::MAX_MINE_COUNT + 1, false)
+			.await
+			.is_err());
+		assert_eq!(client.info().best_number, 3);
+	}
+
+	#[tokio::test]
+	async fn manual_seal_aura_slot_follows_timestamp_override() {
+		use sp_consensus_aura::{digests::CompatibleDigestItem, sr25519::AuthoritySignature, Slot};
+
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let timestamp_override = TimestampOverride::default();
+
+		// spawn the background authorship task, the test runtime has 1 second aura slots
+		tokio::spawn(run_manual_seal_with_timestamp_override(
+			ManualSealParams {
+				block_import: client.clone(),
+				env,
+				client: client.clone(),
+				pool: pool.clone(),
+				commands_stream,
+				select_chain,
+				consensus_data_provider: Some(Box::new(
+					consensus::aura::AuraConsensusDataProvider::new(client.clone()),
+				)),
+				create_inherent_data_providers: |_, _| async {
+					Ok(sp_timestamp::InherentDataProvider::new(sp_timestamp::Timestamp::new(0)))
+				},
+			},
+			timestamp_override.clone(),
+		));
+
+		let mut create_block = {
+			let mut sink = sink.clone();
+			let client = client.clone();
+			move || {
+				let (tx, rx) = futures::channel::oneshot::channel();
+				sink.try_send(EngineCommand::SealNewBlock {
+					parent_hash: None,
+					sender: Some(tx),
+					create_empty: true,
+					finalize: false,
+				})
+				.unwrap();
+				let client = client.clone();
+				async move {
+					let block = rx.await.unwrap().unwrap();
+					let header = client.header(block.hash).unwrap().unwrap();
+					header
+						.digest()
+						.logs()
+						.iter()
+						.find_map(CompatibleDigestItem::<AuthoritySignature>::as_aura_pre_digest)
+						.unwrap()
+				}
+			}
+		};
+
+		assert_eq!(create_block().await, Slot::from(0));
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SetNextBlockTimestamp { timestamp: 12_345_678, sender: Some(tx) })
+			.await
+			.unwrap();
+		rx.await.unwrap().unwrap();
+		assert_eq!(timestamp_override.adjust(0), 12_345_678);
+
+		// the slot follows the adjusted timestamp, and so does the slot of the following block
+		assert_eq!(create_block().await, Slot::from(12_345));
+		assert_eq!(create_block().await, Slot::from(12_345));
+	}
+
 	#[tokio::test]
 	async fn manual_seal_fork_blocks() {
 		let builder = TestClientBuilder::new();
@@ -59,8 +59,38 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to remember the current best block, so that the chain may later
+	/// be reverted to it using [`EngineCommand::Revert`].
+	Snapshot {
+		/// sender to report the id of the created snapshot to the rpc.
+		sender: Sender<SnapshotId>,
+	},
+	/// Tells the engine to make the block, remembered by the snapshot, the best block again.
+	///
+	/// The snapshot and all snapshots taken after it are discarded.
+	Revert {
+		/// id of the snapshot
+		id: SnapshotId,
+		/// sender to report whether the snapshot has been found to the rpc.
+		sender: Sender<bool>,
+	},
+	/// Tells the engine to use the supplied timestamp (in milliseconds) for the next block.
+	///
+	/// The following blocks keep the same offset from the inherent data provider clock.
+	SetNextBlockTimestamp {
+		/// timestamp of the next block
+		timestamp: u64,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
 }
 
+/// Maximal number of blocks that may be created by a single `engine_mine` call.
+pub const MAX_MINE_COUNT: u32 = 1_000;
+
+/// Identifier of the chain snapshot, returned by `engine_snapshot`.
+pub type SnapshotId = u64;
+
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
 #[rpc(client, server)]
 pub trait ManualSealApi<Hash> {
@@ -80,6 +110,25 @@ pub trait ManualSealApi<Hash> {
 		hash: Hash,
 		justification: Option<EncodedJustification>,
 	) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to create `count` new blocks, one on top
+	/// of another. At most [`MAX_MINE_COUNT`] blocks may be created at once
+	#[method(name = "engine_mine")]
+	async fn mine(&self, count: u32, finalize: bool) -> Result<Vec<CreatedBlock<Hash>>, Error>;
+
+	/// Instructs the manual-seal authorship task to remember the current best block
+	#[method(name = "engine_snapshot")]
+	async fn snapshot(&self) -> Result<SnapshotId, Error>;
+
+	/// Instructs the manual-seal authorship task to revert the chain to the snapshot.
+	/// Returns `false` if there's no snapshot with given id
+	#[method(name = "engine_revert")]
+	async fn revert(&self, id: SnapshotId) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to use given timestamp (in milliseconds)
+	/// for the next block
+	#[method(name = "engine_setNextBlockTimestamp")]
+	async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<bool, Error>;
 }
 
 /// A struct that implements the [`ManualSealApiServer`].
@@ -143,6 +192,43 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		sink.send(command).await?;
 		receiver.await.map(|_| true).map_err(Into::into)
 	}
+
+	async fn mine(&self, count: u32, finalize: bool) -> Result<Vec<CreatedBlock<Hash>>, Error> {
+		if count > MAX_MINE_COUNT {
+			return Err(Error::StringError(format!(
+				"Can't mine {} blocks at once, the limit is {}",
+				count, MAX_MINE_COUNT
+			)))
+		}
+
+		let mut created_blocks = Vec::with_capacity(count as usize);
+		for _ in 0..count {
+			created_blocks.push(self.create_block(true, finalize, None).await?);
+		}
+		Ok(created_blocks)
+	}
+
+	async fn snapshot(&self) -> Result<SnapshotId, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		sink.send(EngineCommand::Snapshot { sender: Some(sender) }).await?;
+		receiver.await?
+	}
+
+	async fn revert(&self, id: SnapshotId) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		sink.send(EngineCommand::Revert { id, sender: Some(sender) }).await?;
+		receiver.await?
+	}
+
+	async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::SetNextBlockTimestamp { timestamp, sender: Some(sender) };
+		sink.send(command).await?;
+		receiver.await?.map(|_| true)
+	}
 }
 
 /// report any errors or successes encountered by the authorship task back
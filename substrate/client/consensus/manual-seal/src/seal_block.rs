@@ -25,13 +25,91 @@ use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{self, BlockOrigin, Environment, Proposer, SelectChain};
-use sp_inherents::{CreateInherentDataProviders, InherentDataProvider};
+use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvider};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
-use std::{sync::Arc, time::Duration};
+use std::{
+	sync::{Arc, Mutex, MutexGuard, PoisonError},
+	time::Duration,
+};
 
 /// max duration for creating a proposal in secs
 pub const MAX_PROPOSAL_DURATION: u64 = 10;
 
+/// Adjusts the timestamp inherent of the sealed blocks, see
+/// [`rpc::EngineCommand::SetNextBlockTimestamp`].
+///
+/// All clones of the handle share the same adjustment, so inherent data providers and consensus
+/// data providers may learn the timestamp of the block that is about to be sealed.
+#[derive(Clone, Debug, Default)]
+pub struct TimestampOverride(Arc<Mutex<TimestampAdjustment>>);
+
+/// State of the [`TimestampOverride`], remembered by snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TimestampAdjustment {
+	/// timestamp to use for the next block
+	next: Option<u64>,
+	/// difference between the adjusted timestamp and the timestamp from inherent data providers
+	offset: i128,
+}
+
+impl TimestampAdjustment {
+	fn adjust(&self, timestamp: u64) -> u64 {
+		let offset = match self.next {
+			Some(next) => next as i128 - timestamp as i128,
+			None => self.offset,
+		};
+		(timestamp as i128 + offset).clamp(0, u64::MAX as i128) as u64
+	}
+}
+
+impl TimestampOverride {
+	/// Use given timestamp for the next block.
+	pub fn set_next(&self, timestamp: u64) {
+		self.lock().next = Some(timestamp);
+	}
+
+	/// Returns the timestamp the next block is going to use, given the `timestamp` provided
+	/// by the inherent data providers.
+	pub fn adjust(&self, timestamp: u64) -> u64 {
+		self.lock().adjust(timestamp)
+	}
+
+	pub(crate) fn save(&self) -> TimestampAdjustment {
+		*self.lock()
+	}
+
+	pub(crate) fn restore(&self, adjustment: TimestampAdjustment) {
+		*self.lock() = adjustment;
+	}
+
+	fn lock(&self) -> MutexGuard<'_, TimestampAdjustment> {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Replace the timestamp inherent, if there's any.
+	fn apply(&self, inherent_data: &mut InherentData) -> Result<(), Error> {
+		let Some(timestamp) = inherent_data
+			.get_data::<sp_timestamp::InherentType>(&sp_timestamp::INHERENT_IDENTIFIER)?
+		else {
+			return Ok(())
+		};
+
+		let timestamp = timestamp.as_millis();
+		let mut adjustment = self.lock();
+		let adjusted = adjustment.adjust(timestamp);
+		if let Some(next) = adjustment.next.take() {
+			adjustment.offset = next as i128 - timestamp as i128;
+		}
+		if adjusted != timestamp {
+			inherent_data.replace_data(
+				sp_timestamp::INHERENT_IDENTIFIER,
+				&sp_timestamp::InherentType::new(adjusted),
+			);
+		}
+		Ok(())
+	}
+}
+
 /// params for sealing a new block
 pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP, CIDP, P> {
 	/// if true, empty blocks(without extrinsics) will be created.
@@ -57,6 +135,8 @@ pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP
 	pub block_import: &'a mut BI,
 	/// Something that can create the inherent data providers.
 	pub create_inherent_data_providers: &'a CIDP,
+	/// Adjustments to the timestamp inherent.
+	pub timestamp_override: &'a TimestampOverride,
 }
 
 /// seals a new block with the given params
@@ -72,6 +152,7 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 		env,
 		create_inherent_data_providers,
 		consensus_data_provider: digest_provider,
+		timestamp_override,
 		mut sender,
 	}: SealBlockParams<'_, B, BI, SC, C, E, TP, CIDP, P>,
 ) where
@@ -104,7 +185,8 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 			.await
 			.map_err(|e| Error::Other(e))?;
 
-		let inherent_data = inherent_data_providers.create_inherent_data().await?;
+		let mut inherent_data = inherent_data_providers.create_inherent_data().await?;
+		timestamp_override.apply(&mut inherent_data)?;
 
 		let proposer = env.init(&parent).map_err(|err| Error::StringError(err.to_string())).await?;
 		let inherents_len = inherent_data.len();
@@ -161,3 +243,75 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 
 	rpc::send_result(&mut sender, future.await)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn timestamp_inherent(timestamp: u64) -> InherentData {
+		let mut inherent_data = InherentData::new();
+		inherent_data
+			.put_data(
+				sp_timestamp::INHERENT_IDENTIFIER,
+				&sp_timestamp::InherentType::new(timestamp),
+			)
+			.unwrap();
+		inherent_data
+	}
+
+	fn applied(timestamp_override: &TimestampOverride, timestamp: u64) -> u64 {
+		let mut inherent_data = timestamp_inherent(timestamp);
+		timestamp_override.apply(&mut inherent_data).unwrap();
+		inherent_data
+			.get_data::<sp_timestamp::InherentType>(&sp_timestamp::INHERENT_IDENTIFIER)
+			.unwrap()
+			.unwrap()
+			.as_millis()
+	}
+
+	#[test]
+	fn timestamp_is_untouched_by_default() {
+		let timestamp_override = TimestampOverride::default();
+		assert_eq!(timestamp_override.adjust(1_000), 1_000);
+		assert_eq!(applied(&timestamp_override, 1_000), 1_000);
+
+		// inherent data without timestamp is left as is
+		let mut inherent_data = InherentData::new();
+		timestamp_override.apply(&mut inherent_data).unwrap();
+		assert_eq!(inherent_data.len(), 0);
+	}
+
+	#[test]
+	fn next_timestamp_is_used_once_and_offset_is_kept() {
+		let timestamp_override = TimestampOverride::default();
+		timestamp_override.set_next(10_000);
+
+		// the next block uses the supplied timestamp
+		assert_eq!(timestamp_override.adjust(1_000), 10_000);
+		assert_eq!(applied(&timestamp_override, 1_000), 10_000);
+
+		// and the following blocks keep the offset from the provided clock
+		assert_eq!(timestamp_override.adjust(3_000), 12_000);
+		assert_eq!(applied(&timestamp_override, 3_000), 12_000);
+
+		// travelling back in time is clamped to zero
+		timestamp_override.set_next(500);
+		assert_eq!(applied(&timestamp_override, 4_000), 500);
+		assert_eq!(applied(&timestamp_override, 1_000), 0);
+	}
+
+	#[test]
+	fn clones_share_the_adjustment() {
+		let timestamp_override = TimestampOverride::default();
+		let clone = timestamp_override.clone();
+		clone.set_next(42);
+		assert_eq!(timestamp_override.adjust(0), 42);
+
+		let saved = timestamp_override.save();
+		assert_eq!(applied(&timestamp_override, 0), 42);
+		assert_eq!(clone.adjust(10), 52);
+
+		clone.restore(saved);
+		assert_eq!(timestamp_override.adjust(7), 42);
+	}
+}
@@ -1137,7 +1137,19 @@ impl<Block: BlockT> Backend<Block> {
 				Err(as_is) => return Err(as_is.into()),
 			};
 
-		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init)
+		Self::from_database(db as Arc<_>, canonicalization_delay, &db_config, needs_init, false)
+	}
+
+	/// Open an existing database backend without write access.
+	///
+	/// Nothing is written to the database, not even when it was created by an older version of
+	/// the node, so the backend must only be used to read blocks and state.
+	pub fn new_read_only(db_config: DatabaseSettings) -> ClientResult<Self> {
+		let db =
+			crate::utils::open_database_read_only::<Block>(&db_config.source, DatabaseType::Full)?;
+
+		// Blocks are never imported, so there is nothing to canonicalize.
+		Self::from_database(db, 0, &db_config, false, true)
 	}
 
 	/// Reset the shared trie cache.
@@ -1208,6 +1220,7 @@ impl<Block: BlockT> Backend<Block> {
 		canonicalization_delay: u64,
 		config: &DatabaseSettings,
 		should_init: bool,
+		read_only: bool,
 	) -> ClientResult<Self> {
 		let mut db_init_transaction = Transaction::new();
 
@@ -1268,7 +1281,9 @@ impl<Block: BlockT> Backend<Block> {
 			});
 		}
 
-		db.commit(db_init_transaction)?;
+		if !read_only {
+			db.commit(db_init_transaction)?;
+		}
 
 		Ok(backend)
 	}
@@ -51,6 +51,8 @@ pub enum UpgradeError {
 	UnsupportedVersion(u32),
 	/// Database version comes from future version of the client.
 	FutureDatabaseVersion(u32),
+	/// Database version needs an upgrade, which requires write access.
+	OutdatedDatabaseVersion(u32),
 	/// Invalid justification block.
 	DecodingJustificationBlock,
 	/// Common io error.
@@ -78,6 +80,9 @@ impl fmt::Display for UpgradeError {
 			UpgradeError::FutureDatabaseVersion(version) => {
 				write!(f, "Database version comes from future version of the client: {}", version)
 			},
+			UpgradeError::OutdatedDatabaseVersion(version) => {
+				write!(f, "Database version needs to be upgraded: {}", version)
+			},
 			UpgradeError::DecodingJustificationBlock => {
 				write!(f, "Decoding justification block failed")
 			},
@@ -110,6 +115,16 @@ pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> Upgra
 	Ok(())
 }
 
+/// Check that the database is at the current version, without upgrading it.
+pub fn check_version(db_path: &Path) -> UpgradeResult<()> {
+	match current_version(db_path)? {
+		CURRENT_VERSION => Ok(()),
+		db_version if db_version > CURRENT_VERSION =>
+			Err(UpgradeError::FutureDatabaseVersion(db_version)),
+		db_version => Err(UpgradeError::OutdatedDatabaseVersion(db_version)),
+	}
+}
+
 /// Migration from version1 to version2:
 /// 1) the number of columns has changed from 11 to 12;
 /// 2) transactions column is added;
@@ -225,6 +225,37 @@ fn open_database_at<Block: BlockT>(
 	Ok(db)
 }
 
+/// Opens the configured database without writing to it.
+///
+/// Unlike [`open_database`], this never creates, migrates or upgrades the database. A RocksDB
+/// database is opened as a secondary instance, so the node owning it may keep running.
+pub fn open_database_read_only<Block: BlockT>(
+	db_source: &DatabaseSource,
+	db_type: DatabaseType,
+) -> OpenDbResult {
+	let db: Arc<dyn Database<DbHash>> = match &db_source {
+		DatabaseSource::ParityDb { path } => open_parity_db_read_only::<Block>(path, db_type)?,
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::RocksDb { path, cache_size } =>
+			open_kvdb_rocksdb_read_only::<Block>(path, db_type, *cache_size)?,
+		DatabaseSource::Custom { db, .. } => db.clone(),
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size } =>
+			match open_kvdb_rocksdb_read_only::<Block>(rocksdb_path, db_type, *cache_size) {
+				Ok(db) => db,
+				Err(OpenDbError::NotEnabled(_)) | Err(OpenDbError::DoesNotExist) =>
+					open_parity_db_read_only::<Block>(paritydb_path, db_type)?,
+				Err(as_is) => return Err(as_is),
+			},
+	};
+
+	match db.get(COLUMN_META, meta_keys::TYPE) {
+		Some(stored_type) if db_type.as_str().as_bytes() != &*stored_type =>
+			Err(OpenDbError::UnexpectedDbType { expected: db_type, found: stored_type }),
+		Some(_) => Ok(db),
+		None => Err(OpenDbError::DoesNotExist),
+	}
+}
+
 #[derive(Debug)]
 pub enum OpenDbError {
 	// constructed only when rocksdb and paritydb are disabled
@@ -302,6 +333,13 @@ fn open_parity_db<Block: BlockT>(path: &Path, db_type: DatabaseType, create: boo
 	}
 }
 
+fn open_parity_db_read_only<Block: BlockT>(path: &Path, db_type: DatabaseType) -> OpenDbResult {
+	if !path.exists() {
+		return Err(OpenDbError::DoesNotExist)
+	}
+	Ok(crate::parity_db::open_read_only(path, db_type)?)
+}
+
 #[cfg(any(feature = "rocksdb", test))]
 fn open_kvdb_rocksdb<Block: BlockT>(
 	path: &Path,
@@ -362,6 +400,92 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	Err(OpenDbError::NotEnabled("with-kvdb-rocksdb"))
 }
 
+#[cfg(any(feature = "rocksdb", test))]
+fn open_kvdb_rocksdb_read_only<Block: BlockT>(
+	path: &Path,
+	db_type: DatabaseType,
+	cache_size: usize,
+) -> OpenDbResult {
+	if !path.exists() {
+		return Err(OpenDbError::DoesNotExist)
+	}
+	match crate::upgrade::check_version(path) {
+		Ok(()) => (),
+		Err(crate::upgrade::UpgradeError::MissingDatabaseVersionFile) =>
+			return Err(OpenDbError::DoesNotExist),
+		Err(err) => return Err(OpenDbError::Internal(err.to_string())),
+	}
+
+	// The secondary instance keeps its own log files, away from the primary database.
+	let secondary_dir = tempfile::Builder::new()
+		.prefix(&format!("substrate-{}-", db_type.as_str()))
+		.tempdir()?;
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	db_config.secondary = Some(secondary_dir.path().to_path_buf());
+	db_config.memory_budget =
+		(0..NUM_COLUMNS).map(|i| (i, cache_size / NUM_COLUMNS as usize)).collect();
+
+	let db = kvdb_rocksdb::Database::open(&db_config, path)?;
+	Ok(sp_database::as_database(SecondaryRocksDb { db, _secondary_dir: secondary_dir }))
+}
+
+/// A RocksDB secondary instance, removing its log directory once closed.
+#[cfg(any(feature = "rocksdb", test))]
+struct SecondaryRocksDb {
+	// Declared first to be closed before the directory is removed.
+	db: kvdb_rocksdb::Database,
+	_secondary_dir: tempfile::TempDir,
+}
+
+#[cfg(any(feature = "rocksdb", test))]
+impl kvdb::KeyValueDB for SecondaryRocksDb {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>> {
+		kvdb::KeyValueDB::get(&self.db, col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
+		kvdb::KeyValueDB::get_by_prefix(&self.db, col, prefix)
+	}
+
+	fn write(&self, transaction: kvdb::DBTransaction) -> io::Result<()> {
+		kvdb::KeyValueDB::write(&self.db, transaction)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = io::Result<kvdb::DBKeyValue>> + 'a> {
+		kvdb::KeyValueDB::iter(&self.db, col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = io::Result<kvdb::DBKeyValue>> + 'a> {
+		kvdb::KeyValueDB::iter_with_prefix(&self.db, col, prefix)
+	}
+
+	fn io_stats(&self, kind: kvdb::IoStatsKind) -> kvdb::IoStats {
+		kvdb::KeyValueDB::io_stats(&self.db, kind)
+	}
+
+	fn has_key(&self, col: u32, key: &[u8]) -> io::Result<bool> {
+		kvdb::KeyValueDB::has_key(&self.db, col, key)
+	}
+
+	fn has_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<bool> {
+		kvdb::KeyValueDB::has_prefix(&self.db, col, prefix)
+	}
+}
+
+#[cfg(not(any(feature = "rocksdb", test)))]
+fn open_kvdb_rocksdb_read_only<Block: BlockT>(
+	_path: &Path,
+	_db_type: DatabaseType,
+	_cache_size: usize,
+) -> OpenDbResult {
+	Err(OpenDbError::NotEnabled("with-kvdb-rocksdb"))
+}
+
 /// Check database type.
 pub fn check_database_type(
 	db: &dyn Database<DbHash>,
@@ -857,4 +981,56 @@ mod tests {
 			assert!(db_res.is_ok(), "Existing parity database should be reopened");
 		}
 	}
+
+	#[cfg(feature = "rocksdb")]
+	#[test]
+	fn test_open_database_read_only() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let db_path = db_dir.path().to_owned();
+		let paritydb_path = db_path.join("paritydb");
+		let rocksdb_path = db_path.join("rocksdb_path");
+
+		for source in [
+			DatabaseSource::ParityDb { path: paritydb_path.clone() },
+			DatabaseSource::RocksDb { path: rocksdb_path.clone(), cache_size: 128 },
+		] {
+			// it should not create a missing database
+			{
+				let db_res = open_database_read_only::<Block>(&source, DatabaseType::Full);
+				assert!(matches!(db_res, Err(OpenDbError::DoesNotExist)));
+				assert!(!source.path().unwrap().exists(), "Nothing should be created");
+			}
+
+			// it should open an existing database
+			{
+				let db_res = open_database::<Block>(&source, DatabaseType::Full, true);
+				assert!(db_res.is_ok(), "New database should be created.");
+			}
+			{
+				let db_res = open_database_read_only::<Block>(&source, DatabaseType::Full);
+				assert!(db_res.is_ok(), "Existing database should be opened");
+			}
+		}
+
+		// it should open an existing auto (rocksdb) database
+		{
+			let db_res = open_database_read_only::<Block>(
+				&DatabaseSource::Auto {
+					paritydb_path,
+					rocksdb_path: rocksdb_path.clone(),
+					cache_size: 128,
+				},
+				DatabaseType::Full,
+			);
+			assert!(db_res.is_ok(), "Existing rocksdb database should be opened");
+		}
+
+		// it should open the same rocksdb database read-only more than once at a time
+		{
+			let source = DatabaseSource::RocksDb { path: rocksdb_path, cache_size: 128 };
+			let first = open_database_read_only::<Block>(&source, DatabaseType::Full);
+			let second = open_database_read_only::<Block>(&source, DatabaseType::Full);
+			assert!(first.is_ok() && second.is_ok(), "Both instances should be opened");
+		}
+	}
 }
@@ -50,6 +50,9 @@ pub enum Error {
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
+	/// Resolving or calling a view function failed.
+	#[error("View function error: {}", .0)]
+	ViewFunction(String),
 }
 
 /// Base code for all state errors.
@@ -62,6 +65,7 @@ impl From<Error> for ErrorObjectOwned {
 				ErrorObject::owned(BASE_ERROR + 1, e.to_string(), None::<()>),
 			Error::InvalidCount { .. } =>
 				ErrorObject::owned(BASE_ERROR + 2, e.to_string(), None::<()>),
+			Error::ViewFunction(_) => ErrorObject::owned(BASE_ERROR + 4, e.to_string(), None::<()>),
 			e => ErrorObject::owned(BASE_ERROR + 3, e.to_string(), None::<()>),
 		}
 	}
@@ -30,3 +30,23 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// The arguments of a view function call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ViewFunctionArgs {
+	/// The SCALE encoded arguments, as hex string.
+	Scale(Bytes),
+	/// One JSON value per argument, encoded with the types from the runtime metadata.
+	Json(Vec<serde_json::Value>),
+}
+
+/// The output of a view function call, in the encoding of its [`ViewFunctionArgs`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ViewFunctionOutput {
+	/// The SCALE encoded output, as hex string.
+	Scale(Bytes),
+	/// The output decoded to JSON with the types from the runtime metadata.
+	Json(serde_json::Value),
+}
@@ -28,7 +28,7 @@ use sp_version::RuntimeVersion;
 pub mod error;
 pub mod helpers;
 
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, ViewFunctionArgs, ViewFunctionOutput};
 pub use error::Error;
 
 /// Substrate state API
@@ -38,6 +38,20 @@ pub trait StateApi<Hash> {
 	#[method(name = "state_call", aliases = ["state_callAt"], blocking)]
 	fn call(&self, name: String, bytes: Bytes, hash: Option<Hash>) -> Result<Bytes, Error>;
 
+	/// Call a view function of a pallet at a block's state.
+	///
+	/// The view function is looked up by `pallet` and `function` name in the runtime metadata.
+	/// `args` are either the SCALE encoded arguments or a JSON array with one value per argument,
+	/// and the output is returned in the same encoding.
+	#[method(name = "state_callViewFunction", blocking)]
+	fn call_view_function(
+		&self,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+		hash: Option<Hash>,
+	) -> Result<ViewFunctionOutput, Error>;
+
 	/// Returns the keys with prefix, leave empty to get all the keys.
 	#[method(name = "state_getKeys", blocking)]
 	#[deprecated(since = "2.0.0", note = "Please use `getKeysPaged` with proper paging support")]
@@ -28,6 +28,7 @@ use crate::{
 	},
 };
 use jsonrpsee::proc_macros::rpc;
+use sc_rpc::view_functions::ViewFunctionArgs;
 
 #[rpc(client, server)]
 pub trait ArchiveApi<Hash> {
@@ -97,6 +98,25 @@ pub trait ArchiveApi<Hash> {
 		call_parameters: String,
 	) -> Result<MethodResult, Error>;
 
+	/// Call a view function of a pallet at a specified block's state.
+	///
+	/// The view function is looked up by `pallet` and `function` name in the runtime metadata.
+	/// `args` are either the hexadecimal-encoded SCALE-encoded arguments or a JSON array with one
+	/// value per argument. On success, the result contains the hexadecimal-encoded
+	/// SCALE-encoded output of the view function.
+	///
+	/// # Unstable
+	///
+	/// This method is not part of the specification and can change in minor or patch releases.
+	#[method(name = "archive_unstable_callViewFunction")]
+	fn archive_unstable_call_view_function(
+		&self,
+		hash: Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> Result<MethodResult, Error>;
+
 	/// Returns storage entries at a specific block's state.
 	///
 	/// # Unstable
@@ -41,7 +41,10 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
 };
-use sc_rpc::utils::Subscription;
+use sc_rpc::{
+	utils::Subscription,
+	view_functions::{Error as ViewFunctionError, MetadataCache, ViewFunctionArgs},
+};
 use sp_api::{CallApiAt, CallContext};
 use sp_blockchain::{
 	Backend as BlockChainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata,
@@ -73,6 +76,8 @@ pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	executor: SubscriptionTaskExecutor,
 	/// The hexadecimal encoded hash of the genesis block.
 	genesis_hash: String,
+	/// The metadata describing view functions.
+	view_function_metadata: MetadataCache<Block>,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 }
@@ -86,7 +91,14 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 		executor: SubscriptionTaskExecutor,
 	) -> Self {
 		let genesis_hash = hex_string(&genesis_hash.as_ref());
-		Self { client, backend, executor, genesis_hash, _phantom: PhantomData }
+		Self {
+			client,
+			backend,
+			executor,
+			genesis_hash,
+			view_function_metadata: Default::default(),
+			_phantom: PhantomData,
+		}
 	}
 }
 
@@ -214,6 +226,28 @@ where
 		})
 	}
 
+	fn archive_unstable_call_view_function(
+		&self,
+		hash: Block::Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> Result<MethodResult, ArchiveError> {
+		let result = self
+			.view_function_metadata
+			.resolve(&*self.client, hash, &pallet, &function)
+			.and_then(|view_function| {
+				let input = view_function.encode_args(&args)?;
+				view_function.call(self.client.executor(), hash, input)
+			});
+
+		match result {
+			Ok(output) => Ok(MethodResult::ok(hex_string(&output))),
+			Err(ViewFunctionError::InvalidArgs(error)) => Err(ArchiveError::InvalidParam(error)),
+			Err(error) => Ok(MethodResult::err(error.to_string())),
+		}
+	}
+
 	fn archive_v1_storage(
 		&self,
 		pending: PendingSubscriptionSink,
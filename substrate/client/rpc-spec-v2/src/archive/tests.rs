@@ -343,6 +343,33 @@ async fn archive_call() {
 	assert_eq!(result, expected);
 }
 
+#[tokio::test]
+async fn archive_call_view_function() {
+	let (client, api) = setup_api();
+	let genesis_hash = format!("{:?}", client.chain_info().genesis_hash);
+
+	// Unknown view functions are reported as failed calls.
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_callViewFunction",
+			rpc_params![&genesis_hash, "System", "unknown", Vec::<serde_json::Value>::new()],
+		)
+		.await
+		.unwrap();
+	assert_matches!(result, MethodResult::Err(err) if err.error.contains("System::unknown not found"));
+
+	// Valid call, returning the SCALE encoded output.
+	let alice = Sr25519Keyring::Alice.public();
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_callViewFunction",
+			rpc_params![&genesis_hash, "SubstrateTest", "authority", [0]],
+		)
+		.await
+		.unwrap();
+	assert_eq!(result, MethodResult::ok(hex_string(&Some(alice).encode())));
+}
+
 #[tokio::test]
 async fn archive_storage_hashes_values() {
 	let (client, api) = setup_api();
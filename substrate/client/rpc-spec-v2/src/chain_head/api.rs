@@ -27,6 +27,7 @@ use crate::{
 	common::events::StorageQuery,
 };
 use jsonrpsee::{proc_macros::rpc, server::ResponsePayload};
+use sc_rpc::view_functions::ViewFunctionArgs;
 pub use sp_rpc::list::ListOrValue;
 
 #[rpc(client, server)]
@@ -108,6 +109,26 @@ pub trait ChainHeadApi<Hash> {
 		call_parameters: String,
 	) -> ResponsePayload<'static, MethodResponse>;
 
+	/// Call a view function of a pallet at a specified block's state.
+	///
+	/// The view function is looked up by `pallet` and `function` name in the runtime metadata.
+	/// `args` are either the hexadecimal-encoded SCALE-encoded arguments or a JSON array with one
+	/// value per argument. The hexadecimal-encoded SCALE-encoded output of the view function is
+	/// reported by an `operationCallDone` event.
+	///
+	/// # Unstable
+	///
+	/// This method is not part of the specification and can change in minor or patch releases.
+	#[method(name = "chainHead_unstable_callViewFunction", with_extensions)]
+	async fn chain_head_unstable_call_view_function(
+		&self,
+		follow_subscription: String,
+		hash: Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> ResponsePayload<'static, MethodResponse>;
+
 	/// Unpin a block or multiple blocks reported by the `follow` method.
 	///
 	/// Ongoing operations that require the provided block
@@ -45,7 +45,10 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
 };
-use sc_rpc::utils::Subscription;
+use sc_rpc::{
+	utils::Subscription,
+	view_functions::{MetadataCache, ViewFunctionArgs},
+};
 use sp_api::CallApiAt;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_core::{traits::CallContext, Bytes};
@@ -127,6 +130,8 @@ pub struct ChainHead<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Stop all subscriptions if the distance between the leaves and the current finalized
 	/// block is larger than this value.
 	max_lagging_distance: usize,
+	/// The metadata describing view functions, shared with the running operations.
+	view_function_metadata: Arc<MetadataCache<Block>>,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 	/// The maximum number of pending messages per subscription.
@@ -153,12 +158,90 @@ impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
 				backend,
 			),
 			max_lagging_distance: config.max_lagging_distance,
+			view_function_metadata: Default::default(),
 			subscription_buffer_cap: config.subscription_buffer_cap,
 			_phantom: PhantomData,
 		}
 	}
 }
 
+impl<BE, Block, Client> ChainHead<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+{
+	/// Run the runtime `call` at the pinned block `hash` of `follow_subscription` as operation.
+	///
+	/// The output of the call is reported by an `OperationCallDone` event.
+	fn call_operation(
+		&self,
+		ext: &Extensions,
+		follow_subscription: String,
+		hash: Block::Hash,
+		call: impl FnOnce() -> Result<Vec<u8>, String> + Send + 'static,
+	) -> ResponsePayload<'static, MethodResponse> {
+		let conn_id = ext
+			.get::<ConnectionId>()
+			.copied()
+			.expect("ConnectionId is always set by jsonrpsee; qed");
+
+		if !self.subscriptions.contains_subscription(conn_id, &follow_subscription) {
+			// The spec says to return `LimitReached` if the follow subscription is invalid or
+			// stale.
+			return ResponsePayload::success(MethodResponse::LimitReached);
+		}
+
+		let mut block_guard = match self.subscriptions.lock_block(&follow_subscription, hash, 1) {
+			Ok(block) => block,
+			Err(SubscriptionManagementError::SubscriptionAbsent) |
+			Err(SubscriptionManagementError::ExceededLimits) => {
+				// Invalid invalid subscription ID.
+				return ResponsePayload::success(MethodResponse::LimitReached)
+			},
+			Err(SubscriptionManagementError::BlockHashAbsent) => {
+				// Block is not part of the subscription.
+				return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
+			},
+			Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
+		};
+
+		// Reject subscription if with_runtime is false.
+		if !block_guard.has_runtime() {
+			return ResponsePayload::error(ChainHeadRpcError::InvalidRuntimeCall(
+				"The runtime updates flag must be set".to_string(),
+			));
+		}
+
+		let operation_id = block_guard.operation().operation_id();
+
+		let (rp, rp_fut) = method_started_response(operation_id.clone(), None);
+		let fut = async move {
+			// Wait for the server to send out the response and if it produces an error no event
+			// should be generated.
+			if rp_fut.await.is_err() {
+				return
+			}
+
+			let event = match call() {
+				Ok(result) => FollowEvent::<Block::Hash>::OperationCallDone(OperationCallDone {
+					operation_id: operation_id.clone(),
+					output: hex_string(&result),
+				}),
+				Err(error) => FollowEvent::<Block::Hash>::OperationError(OperationError {
+					operation_id: operation_id.clone(),
+					error,
+				}),
+			};
+
+			let _ = block_guard.response_sender().send(event).await;
+		};
+		self.executor
+			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+
+		rp
+	}
+}
+
 /// Helper to convert the `subscription ID` to a string.
 pub fn read_subscription_id_as_string(sink: &Subscription) -> String {
 	match sink.subscription_id() {
@@ -473,71 +556,35 @@ where
 			Err(err) => return ResponsePayload::error(err),
 		};
 
-		let conn_id = ext
-			.get::<ConnectionId>()
-			.copied()
-			.expect("ConnectionId is always set by jsonrpsee; qed");
-
-		if !self.subscriptions.contains_subscription(conn_id, &follow_subscription) {
-			// The spec says to return `LimitReached` if the follow subscription is invalid or
-			// stale.
-			return ResponsePayload::success(MethodResponse::LimitReached);
-		}
-
-		let mut block_guard = match self.subscriptions.lock_block(&follow_subscription, hash, 1) {
-			Ok(block) => block,
-			Err(SubscriptionManagementError::SubscriptionAbsent) |
-			Err(SubscriptionManagementError::ExceededLimits) => {
-				// Invalid invalid subscription ID.
-				return ResponsePayload::success(MethodResponse::LimitReached)
-			},
-			Err(SubscriptionManagementError::BlockHashAbsent) => {
-				// Block is not part of the subscription.
-				return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
-			},
-			Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
-		};
-
-		// Reject subscription if with_runtime is false.
-		if !block_guard.has_runtime() {
-			return ResponsePayload::error(ChainHeadRpcError::InvalidRuntimeCall(
-				"The runtime updates flag must be set".to_string(),
-			));
-		}
-
-		let operation_id = block_guard.operation().operation_id();
 		let client = self.client.clone();
-
-		let (rp, rp_fut) = method_started_response(operation_id.clone(), None);
-		let fut = async move {
-			// Wait for the server to send out the response and if it produces an error no event
-			// should be generated.
-			if rp_fut.await.is_err() {
-				return
-			}
-
-			let event = client
+		self.call_operation(ext, follow_subscription, hash, move || {
+			client
 				.executor()
 				.call(hash, &function, &call_parameters, CallContext::Offchain)
-				.map(|result| {
-					FollowEvent::<Block::Hash>::OperationCallDone(OperationCallDone {
-						operation_id: operation_id.clone(),
-						output: hex_string(&result),
-					})
-				})
-				.unwrap_or_else(|error| {
-					FollowEvent::<Block::Hash>::OperationError(OperationError {
-						operation_id: operation_id.clone(),
-						error: error.to_string(),
-					})
-				});
-
-			let _ = block_guard.response_sender().send(event).await;
-		};
-		self.executor
-			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+				.map_err(|error| error.to_string())
+		})
+	}
 
-		rp
+	async fn chain_head_unstable_call_view_function(
+		&self,
+		ext: &Extensions,
+		follow_subscription: String,
+		hash: Block::Hash,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> ResponsePayload<'static, MethodResponse> {
+		let client = self.client.clone();
+		let metadata = self.view_function_metadata.clone();
+		self.call_operation(ext, follow_subscription, hash, move || {
+			let view_function = metadata
+				.resolve(&*client, hash, &pallet, &function)
+				.map_err(|error| error.to_string())?;
+			let input = view_function.encode_args(&args).map_err(|error| error.to_string())?;
+			view_function
+				.call(client.executor(), hash, input)
+				.map_err(|error| error.to_string())
+		})
 	}
 
 	async fn chain_head_unstable_unpin(
@@ -619,6 +619,61 @@ async fn call_runtime() {
 	);
 }
 
+#[tokio::test]
+async fn call_view_function() {
+	let (_client, api, mut block_sub, sub_id, block) = setup_api().await;
+	let block_hash = format!("{:?}", block.header.hash());
+	let alice = Sr25519Keyring::Alice.public();
+
+	// Subscription ID is invalid.
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_callViewFunction",
+			rpc_params!["invalid_sub_id", &block_hash, "SubstrateTest", "authority", [0]],
+		)
+		.await
+		.unwrap();
+	assert_matches!(response, MethodResponse::LimitReached);
+
+	// Valid call with JSON arguments.
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_callViewFunction",
+			rpc_params![&sub_id, &block_hash, "SubstrateTest", "authority", [0]],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	// The SCALE encoded output is propagated to `chainHead_follow`.
+	let expected = hex_string(&Some(alice).encode());
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::OperationCallDone(done) if done.operation_id == operation_id && done.output == expected
+	);
+
+	// Unknown view functions are reported as operation errors.
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_callViewFunction",
+			rpc_params![&sub_id, &block_hash, "SubstrateTest", "unknown", Vec::<u32>::new()],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::OperationError(error) if error.operation_id == operation_id && error.error.contains("SubstrateTest::unknown not found")
+	);
+}
+
 #[tokio::test]
 async fn call_runtime_without_flag() {
 	let builder = TestClientBuilder::new();
@@ -59,6 +59,15 @@ where
 		call_data: Bytes,
 	) -> Result<Bytes, Error>;
 
+	/// Call a view function of a pallet at given block.
+	fn call_view_function(
+		&self,
+		block: Option<Block::Hash>,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> Result<ViewFunctionOutput, Error>;
+
 	/// Returns the keys with prefix, leave empty to get all the keys.
 	fn storage_keys(
 		&self,
@@ -209,6 +218,18 @@ where
 		self.backend.call(block, method, data).map_err(Into::into)
 	}
 
+	fn call_view_function(
+		&self,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+		block: Option<Block::Hash>,
+	) -> Result<ViewFunctionOutput, Error> {
+		self.backend
+			.call_view_function(block, pallet, function, args)
+			.map_err(Into::into)
+	}
+
 	fn storage_keys(
 		&self,
 		key_prefix: StorageKey,
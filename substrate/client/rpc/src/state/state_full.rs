@@ -27,6 +27,7 @@ use super::{
 };
 use crate::{
 	utils::{spawn_subscription_task, BoundedVecDeque, PendingSubscription},
+	view_functions::{self, MetadataCache},
 	DenyUnsafe, SubscriptionTaskExecutor,
 };
 
@@ -36,7 +37,7 @@ use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider, ProofProvider,
 	StorageProvider,
 };
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, ViewFunctionArgs, ViewFunctionOutput};
 use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_blockchain::{
 	CachedHeaderMetadata, Error as ClientError, HeaderBackend, HeaderMetadata,
@@ -65,6 +66,7 @@ struct QueryStorageRange<Block: BlockT> {
 pub struct FullState<BE, Block: BlockT, Client> {
 	client: Arc<Client>,
 	executor: SubscriptionTaskExecutor,
+	view_function_metadata: MetadataCache<Block>,
 	_phantom: PhantomData<(BE, Block)>,
 }
 
@@ -79,7 +81,7 @@ where
 {
 	/// Create new state API backend for full nodes.
 	pub fn new(client: Arc<Client>, executor: SubscriptionTaskExecutor) -> Self {
-		Self { client, executor, _phantom: PhantomData }
+		Self { client, executor, view_function_metadata: Default::default(), _phantom: PhantomData }
 	}
 
 	/// Returns given block hash or best block hash if None is passed.
@@ -204,6 +206,25 @@ where
 			.map_err(client_err)
 	}
 
+	fn call_view_function(
+		&self,
+		block: Option<Block::Hash>,
+		pallet: String,
+		function: String,
+		args: ViewFunctionArgs,
+	) -> std::result::Result<ViewFunctionOutput, Error> {
+		let block = self.block_or_best(block).map_err(client_err)?;
+		view_functions::call_view_function(
+			&self.view_function_metadata,
+			&*self.client,
+			block,
+			&pallet,
+			&function,
+			args,
+		)
+		.map_err(|e| Error::ViewFunction(e.to_string()))
+	}
+
 	// TODO: This is horribly broken; either remove it, or make it streaming.
 	fn storage_keys(
 		&self,
@@ -20,6 +20,7 @@ use self::error::Error;
 use super::*;
 use crate::testing::{allow_unsafe, test_executor, timeout_secs};
 use assert_matches::assert_matches;
+use codec::Encode;
 use futures::executor;
 use jsonrpsee::{core::EmptyServerParams as EmptyParams, MethodsError as RpcError};
 use sc_block_builder::BlockBuilderBuilder;
@@ -207,6 +208,50 @@ async fn should_call_contract() {
 	)
 }
 
+#[tokio::test]
+async fn should_call_view_function() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let (client, _child) = new_full(client, test_executor());
+	let alice = Sr25519Keyring::Alice.public();
+	let call = |args| {
+		client.call_view_function(
+			"SubstrateTest".into(),
+			"authority".into(),
+			args,
+			Some(genesis_hash),
+		)
+	};
+
+	// The output is returned in the encoding of the arguments.
+	assert_eq!(
+		call(ViewFunctionArgs::Json(vec![0.into()])).unwrap(),
+		ViewFunctionOutput::Json(sp_core::bytes::to_hex(alice.as_ref(), false).into()),
+	);
+	assert_eq!(
+		call(ViewFunctionArgs::Json(vec![3.into()])).unwrap(),
+		ViewFunctionOutput::Json(serde_json::Value::Null),
+	);
+	assert_eq!(
+		call(ViewFunctionArgs::Scale(0u32.encode().into())).unwrap(),
+		ViewFunctionOutput::Scale(Some(alice).encode().into()),
+	);
+
+	assert_matches!(
+		call(ViewFunctionArgs::Json(vec!["first".into()])),
+		Err(Error::ViewFunction(error)) if error.contains("Invalid arguments")
+	);
+	assert_matches!(
+		client.call_view_function(
+			"SubstrateTest".into(),
+			"unknown".into(),
+			ViewFunctionArgs::Json(vec![]),
+			None,
+		),
+		Err(Error::ViewFunction(error)) if error.contains("SubstrateTest::unknown not found")
+	);
+}
+
 #[tokio::test]
 async fn should_notify_about_storage_changes() {
 	let mut sub = {
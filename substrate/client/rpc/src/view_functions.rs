@@ -0,0 +1,748 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Calling pallet view functions by name.
+//!
+//! The runtime dispatches view functions through the `RuntimeViewFunction` runtime API by their
+//! 32 byte id. [`ViewFunction`] looks the id and the types of a view function up in the runtime
+//! metadata, so it can be called by pallet and function name with SCALE or JSON encoded
+//! arguments.
+//!
+//! JSON values map to SCALE types as follows:
+//!
+//! - Structs with named fields are objects, tuples and tuple structs are arrays. Structs with a
+//!   single unnamed field are represented by the value of the field.
+//! - Enum variants without fields are strings with the variant name, other variants are objects
+//!   with the variant name as only key. `Option`s are `null` or the value itself.
+//! - Byte sequences and arrays are hex strings, other sequences and arrays are arrays.
+//! - Integers are numbers, or decimal strings if they don't fit into 64 bits.
+//!
+//! Fetching and decoding the metadata is expensive, so [`MetadataCache`] keeps it for the most
+//! recently used runtimes, by the hash of their code.
+
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{v16::RuntimeMetadataV16, RuntimeMetadata, RuntimeMetadataPrefixed};
+use parking_lot::Mutex;
+use sc_client_api::{Backend, CallExecutor, ExecutorProvider, StorageProvider};
+use scale_info::{form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+use schnellru::{ByLength, LruMap};
+use serde_json::{Map, Value};
+use sp_core::{
+	bytes::{from_hex, to_hex},
+	storage::{well_known_keys, StorageKey},
+	traits::CallContext,
+};
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt, sync::Arc};
+
+pub use sc_rpc_api::state::{ViewFunctionArgs, ViewFunctionOutput};
+
+/// The runtime API method executing view functions.
+const EXECUTE_VIEW_FUNCTION: &str = "RuntimeViewFunction_execute_view_function";
+
+/// The runtime API method returning the metadata at a given version.
+const METADATA_AT_VERSION: &str = "Metadata_metadata_at_version";
+
+/// The metadata versions describing view functions, V16 is still exposed as unstable version.
+const VIEW_FUNCTIONS_METADATA_VERSIONS: [u32; 2] = [16, u32::MAX];
+
+/// The number of runtimes whose metadata is cached.
+const METADATA_CACHE_SIZE: u32 = 4;
+
+/// Errors of resolving and calling view functions.
+#[derive(Debug)]
+pub enum Error {
+	/// Calling into the runtime failed.
+	Runtime(String),
+	/// The runtime doesn't provide metadata describing view functions.
+	MetadataUnavailable,
+	/// The runtime metadata couldn't be decoded.
+	InvalidMetadata(codec::Error),
+	/// The runtime metadata doesn't describe the view function.
+	NotFound {
+		/// The name of the pallet.
+		pallet: String,
+		/// The name of the view function.
+		function: String,
+	},
+	/// The arguments don't match the inputs of the view function.
+	InvalidArgs(String),
+	/// The runtime failed to dispatch the view function.
+	Dispatch(String),
+	/// The output doesn't match the output type of the view function.
+	InvalidOutput(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Runtime(error) => write!(f, "Runtime call failed: {error}"),
+			Error::MetadataUnavailable =>
+				write!(f, "The runtime metadata doesn't describe view functions"),
+			Error::InvalidMetadata(error) => write!(f, "Invalid runtime metadata: {error}"),
+			Error::NotFound { pallet, function } =>
+				write!(f, "View function {pallet}::{function} not found"),
+			Error::InvalidArgs(error) => write!(f, "Invalid arguments: {error}"),
+			Error::Dispatch(error) => write!(f, "View function dispatch failed: {error}"),
+			Error::InvalidOutput(error) => write!(f, "Invalid output: {error}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// Mirrors `frame_support::view_functions::ViewFunctionDispatchError`.
+#[derive(Decode)]
+enum DispatchError {
+	NotImplemented,
+	NotFound([u8; 32]),
+	Codec,
+}
+
+impl fmt::Display for DispatchError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DispatchError::NotImplemented =>
+				write!(f, "the runtime doesn't implement view functions"),
+			DispatchError::NotFound(id) => write!(f, "unknown view function {}", to_hex(id, false)),
+			DispatchError::Codec => write!(f, "failed to decode the input"),
+		}
+	}
+}
+
+/// The metadata describing view functions of the most recently used runtimes.
+pub struct MetadataCache<Block: BlockT> {
+	/// The metadata by the hash of the runtime code.
+	metadata: Mutex<LruMap<Block::Hash, Arc<RuntimeMetadataV16>>>,
+}
+
+impl<Block: BlockT> Default for MetadataCache<Block> {
+	fn default() -> Self {
+		Self { metadata: Mutex::new(LruMap::new(ByLength::new(METADATA_CACHE_SIZE))) }
+	}
+}
+
+impl<Block: BlockT> MetadataCache<Block> {
+	/// Look up `function` of `pallet` in the metadata of the runtime at `hash`.
+	pub fn resolve<BE, Client>(
+		&self,
+		client: &Client,
+		hash: Block::Hash,
+		pallet: &str,
+		function: &str,
+	) -> Result<ViewFunction, Error>
+	where
+		BE: Backend<Block>,
+		Client: StorageProvider<Block, BE> + ExecutorProvider<Block>,
+	{
+		ViewFunction::from_metadata(self.metadata(client, hash)?, pallet, function)
+	}
+
+	/// The metadata of the runtime at `hash`, fetched from the runtime if it isn't cached.
+	fn metadata<BE, Client>(
+		&self,
+		client: &Client,
+		hash: Block::Hash,
+	) -> Result<Arc<RuntimeMetadataV16>, Error>
+	where
+		BE: Backend<Block>,
+		Client: StorageProvider<Block, BE> + ExecutorProvider<Block>,
+	{
+		let code_hash = client
+			.storage_hash(hash, &StorageKey(well_known_keys::CODE.to_vec()))
+			.map_err(|error| Error::Runtime(error.to_string()))?
+			.ok_or_else(|| Error::Runtime("no runtime code".into()))?;
+		if let Some(metadata) = self.metadata.lock().get(&code_hash) {
+			return Ok(metadata.clone())
+		}
+
+		// Don't hold the lock while calling into the runtime.
+		let metadata = Arc::new(metadata(client.executor(), hash)?);
+		self.metadata.lock().insert(code_hash, metadata.clone());
+		Ok(metadata)
+	}
+}
+
+/// A view function resolved from the runtime metadata.
+pub struct ViewFunction {
+	/// The id dispatching the view function.
+	id: [u8; 32],
+	/// The types of the inputs.
+	inputs: Vec<u32>,
+	/// The type of the output.
+	output: u32,
+	/// The metadata of the runtime, describing the types.
+	metadata: Arc<RuntimeMetadataV16>,
+}
+
+impl ViewFunction {
+	fn from_metadata(
+		metadata: Arc<RuntimeMetadataV16>,
+		pallet: &str,
+		function: &str,
+	) -> Result<Self, Error> {
+		let not_found =
+			|| Error::NotFound { pallet: pallet.to_string(), function: function.to_string() };
+		let view_function = metadata
+			.pallets
+			.iter()
+			.find(|metadata| metadata.name == pallet)
+			.ok_or_else(not_found)?
+			.view_functions
+			.iter()
+			.find(|metadata| metadata.name == function)
+			.ok_or_else(not_found)?;
+
+		Ok(Self {
+			id: view_function.id,
+			inputs: view_function.inputs.iter().map(|input| input.ty.id).collect(),
+			output: view_function.output.id,
+			metadata: metadata.clone(),
+		})
+	}
+
+	/// Encode `args` as input of the view function.
+	pub fn encode_args(&self, args: &ViewFunctionArgs) -> Result<Vec<u8>, Error> {
+		match args {
+			ViewFunctionArgs::Scale(input) => Ok(input.to_vec()),
+			ViewFunctionArgs::Json(values) => {
+				if values.len() != self.inputs.len() {
+					return Err(Error::InvalidArgs(format!(
+						"expected {} arguments, got {}",
+						self.inputs.len(),
+						values.len()
+					)))
+				}
+				let mut input = Vec::new();
+				for (value, ty) in values.iter().zip(&self.inputs) {
+					encode_value(&self.metadata.types, *ty, value, &mut input)
+						.map_err(Error::InvalidArgs)?;
+				}
+				Ok(input)
+			},
+		}
+	}
+
+	/// Execute the view function with the SCALE encoded `input` at `hash`.
+	///
+	/// Returns the SCALE encoded output.
+	pub fn call<Block: BlockT, E: CallExecutor<Block>>(
+		&self,
+		executor: &E,
+		hash: Block::Hash,
+		input: Vec<u8>,
+	) -> Result<Vec<u8>, Error> {
+		let call_data = (self.id, input).encode();
+		let result = executor
+			.call(hash, EXECUTE_VIEW_FUNCTION, &call_data, CallContext::Offchain)
+			.map_err(|error| Error::Runtime(error.to_string()))?;
+		<Result<Vec<u8>, DispatchError>>::decode(&mut &result[..])
+			.map_err(|error| Error::Runtime(error.to_string()))?
+			.map_err(|error| Error::Dispatch(error.to_string()))
+	}
+
+	/// Decode the SCALE encoded `output` of the view function to JSON.
+	pub fn output_to_json(&self, mut output: &[u8]) -> Result<Value, Error> {
+		let value = decode_value(&self.metadata.types, self.output, &mut output)
+			.map_err(Error::InvalidOutput)?;
+		if !output.is_empty() {
+			return Err(Error::InvalidOutput(format!("{} trailing bytes", output.len())))
+		}
+		Ok(value)
+	}
+}
+
+/// Call `function` of `pallet` at `hash`, returning the output in the encoding of `args`.
+pub fn call_view_function<Block, BE, Client>(
+	cache: &MetadataCache<Block>,
+	client: &Client,
+	hash: Block::Hash,
+	pallet: &str,
+	function: &str,
+	args: ViewFunctionArgs,
+) -> Result<ViewFunctionOutput, Error>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	Client: StorageProvider<Block, BE> + ExecutorProvider<Block>,
+{
+	let view_function = cache.resolve(client, hash, pallet, function)?;
+	let output = view_function.call(client.executor(), hash, view_function.encode_args(&args)?)?;
+	Ok(match args {
+		ViewFunctionArgs::Scale(_) => ViewFunctionOutput::Scale(output.into()),
+		ViewFunctionArgs::Json(_) =>
+			ViewFunctionOutput::Json(view_function.output_to_json(&output)?),
+	})
+}
+
+/// The metadata of the runtime at `hash`, in the first version describing view functions.
+fn metadata<Block: BlockT, E: CallExecutor<Block>>(
+	executor: &E,
+	hash: Block::Hash,
+) -> Result<RuntimeMetadataV16, Error> {
+	for version in VIEW_FUNCTIONS_METADATA_VERSIONS {
+		let result = executor
+			.call(hash, METADATA_AT_VERSION, &version.encode(), CallContext::Offchain)
+			.map_err(|error| Error::Runtime(error.to_string()))?;
+		let Some(metadata) =
+			<Option<Vec<u8>>>::decode(&mut &result[..]).map_err(Error::InvalidMetadata)?
+		else {
+			continue
+		};
+		let RuntimeMetadataPrefixed(_, metadata) =
+			Decode::decode(&mut &metadata[..]).map_err(Error::InvalidMetadata)?;
+		if let RuntimeMetadata::V16(metadata) = metadata {
+			return Ok(metadata)
+		}
+	}
+	Err(Error::MetadataUnavailable)
+}
+
+fn resolve(types: &PortableRegistry, ty: u32) -> Result<&Type<PortableForm>, String> {
+	types.resolve(ty).ok_or_else(|| format!("unknown type {ty}"))
+}
+
+fn is_option(ty: &Type<PortableForm>) -> bool {
+	ty.path.segments == ["Option"]
+}
+
+fn is_byte(types: &PortableRegistry, ty: u32) -> Result<bool, String> {
+	Ok(matches!(resolve(types, ty)?.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)))
+}
+
+/// Encode the JSON `value` as SCALE encoded type `ty` into `out`.
+fn encode_value(
+	types: &PortableRegistry,
+	ty: u32,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	let type_info = resolve(types, ty)?;
+	match &type_info.type_def {
+		TypeDef::Composite(composite) => encode_fields(types, &composite.fields, value, out),
+		TypeDef::Variant(variant) => {
+			let (name, fields) = match value {
+				_ if is_option(type_info) && value.is_null() => ("None", &Value::Null),
+				_ if is_option(type_info) => ("Some", value),
+				Value::String(name) => (name.as_str(), &Value::Null),
+				Value::Object(map) if map.len() == 1 => {
+					let (name, fields) = map.iter().next().expect("map has one entry; qed");
+					(name.as_str(), fields)
+				},
+				value => return Err(format!("expected an enum variant, got {value}")),
+			};
+			let variant = variant
+				.variants
+				.iter()
+				.find(|variant| variant.name == name)
+				.ok_or_else(|| format!("unknown variant {name}"))?;
+			out.push(variant.index);
+			encode_fields(types, &variant.fields, fields, out)
+		},
+		TypeDef::Sequence(sequence) => {
+			let item = sequence.type_param.id;
+			if let (Value::String(hex), true) = (value, is_byte(types, item)?) {
+				let bytes = from_hex(hex).map_err(|error| error.to_string())?;
+				bytes.encode_to(out);
+				return Ok(())
+			}
+			let items =
+				value.as_array().ok_or_else(|| format!("expected an array, got {value}"))?;
+			Compact(items.len() as u32).encode_to(out);
+			items.iter().try_for_each(|value| encode_value(types, item, value, out))
+		},
+		TypeDef::Array(array) => {
+			let item = array.type_param.id;
+			if let (Value::String(hex), true) = (value, is_byte(types, item)?) {
+				let bytes = from_hex(hex).map_err(|error| error.to_string())?;
+				if bytes.len() != array.len as usize {
+					return Err(format!("expected {} bytes, got {}", array.len, bytes.len()))
+				}
+				out.extend(bytes);
+				return Ok(())
+			}
+			let items =
+				value.as_array().ok_or_else(|| format!("expected an array, got {value}"))?;
+			if items.len() != array.len as usize {
+				return Err(format!("expected {} items, got {}", array.len, items.len()))
+			}
+			items.iter().try_for_each(|value| encode_value(types, item, value, out))
+		},
+		TypeDef::Tuple(tuple) => {
+			let fields: Vec<u32> = tuple.fields.iter().map(|field| field.id).collect();
+			encode_unnamed(types, &fields, value, out)
+		},
+		TypeDef::Primitive(primitive) => encode_primitive(primitive, value, out),
+		TypeDef::Compact(compact) => {
+			// Compact encoding only depends on the value, not on the width of the type.
+			let primitive = compact_primitive(types, compact.type_param.id)?;
+			let bits = uint_bits(&primitive)
+				.ok_or_else(|| format!("unsupported compact type {primitive:?}"))?;
+			Compact(parse_uint(value, bits)?).encode_to(out);
+			Ok(())
+		},
+		TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+	}
+}
+
+fn encode_fields(
+	types: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	match fields {
+		[] => Ok(()),
+		[field] if field.name.is_none() => encode_value(types, field.ty.id, value, out),
+		fields if fields.iter().all(|field| field.name.is_some()) => {
+			let map =
+				value.as_object().ok_or_else(|| format!("expected an object, got {value}"))?;
+			fields.iter().try_for_each(|field| {
+				let name = field.name.as_deref().expect("all fields are named; qed");
+				let value = map.get(name).ok_or_else(|| format!("missing field {name}"))?;
+				encode_value(types, field.ty.id, value, out)
+			})
+		},
+		fields => {
+			let fields: Vec<u32> = fields.iter().map(|field| field.ty.id).collect();
+			encode_unnamed(types, &fields, value, out)
+		},
+	}
+}
+
+fn encode_unnamed(
+	types: &PortableRegistry,
+	fields: &[u32],
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	if fields.is_empty() {
+		return Ok(())
+	}
+	let items = value.as_array().ok_or_else(|| format!("expected an array, got {value}"))?;
+	if items.len() != fields.len() {
+		return Err(format!("expected {} items, got {}", fields.len(), items.len()))
+	}
+	items
+		.iter()
+		.zip(fields)
+		.try_for_each(|(value, ty)| encode_value(types, *ty, value, out))
+}
+
+fn encode_primitive(
+	primitive: &TypeDefPrimitive,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	let expected = |kind: &str| format!("expected {kind}, got {value}");
+	match primitive {
+		TypeDefPrimitive::Bool => value.as_bool().ok_or_else(|| expected("a bool"))?.encode_to(out),
+		TypeDefPrimitive::Char => {
+			let mut chars = value.as_str().ok_or_else(|| expected("a char"))?.chars();
+			match (chars.next(), chars.next()) {
+				(Some(char), None) => (char as u32).encode_to(out),
+				_ => return Err(expected("a char")),
+			}
+		},
+		TypeDefPrimitive::Str => value.as_str().ok_or_else(|| expected("a string"))?.encode_to(out),
+		TypeDefPrimitive::U8 => (parse_uint(value, 8)? as u8).encode_to(out),
+		TypeDefPrimitive::U16 => (parse_uint(value, 16)? as u16).encode_to(out),
+		TypeDefPrimitive::U32 => (parse_uint(value, 32)? as u32).encode_to(out),
+		TypeDefPrimitive::U64 => (parse_uint(value, 64)? as u64).encode_to(out),
+		TypeDefPrimitive::U128 => parse_uint(value, 128)?.encode_to(out),
+		TypeDefPrimitive::I8 => (parse_int(value, 8)? as i8).encode_to(out),
+		TypeDefPrimitive::I16 => (parse_int(value, 16)? as i16).encode_to(out),
+		TypeDefPrimitive::I32 => (parse_int(value, 32)? as i32).encode_to(out),
+		TypeDefPrimitive::I64 => (parse_int(value, 64)? as i64).encode_to(out),
+		TypeDefPrimitive::I128 => parse_int(value, 128)?.encode_to(out),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			return Err("256 bit integers are not supported".into()),
+	}
+	Ok(())
+}
+
+/// The primitive type wrapped by `Compact`, looking through single field structs.
+fn compact_primitive(types: &PortableRegistry, ty: u32) -> Result<TypeDefPrimitive, String> {
+	match &resolve(types, ty)?.type_def {
+		TypeDef::Primitive(primitive) => Ok(primitive.clone()),
+		TypeDef::Composite(composite) if composite.fields.len() == 1 =>
+			compact_primitive(types, composite.fields[0].ty.id),
+		_ => Err(format!("unsupported compact type {ty}")),
+	}
+}
+
+fn uint_bits(primitive: &TypeDefPrimitive) -> Option<u32> {
+	match primitive {
+		TypeDefPrimitive::U8 => Some(8),
+		TypeDefPrimitive::U16 => Some(16),
+		TypeDefPrimitive::U32 => Some(32),
+		TypeDefPrimitive::U64 => Some(64),
+		TypeDefPrimitive::U128 => Some(128),
+		_ => None,
+	}
+}
+
+/// Parse an unsigned integer of `bits` from a number or a decimal string.
+fn parse_uint(value: &Value, bits: u32) -> Result<u128, String> {
+	let int = match value {
+		Value::Number(number) => number.as_u64().map(Into::into),
+		Value::String(string) => string.parse().ok(),
+		_ => None,
+	};
+	int.filter(|int| bits == 128 || *int < 1 << bits)
+		.ok_or_else(|| format!("expected a {bits} bit unsigned integer, got {value}"))
+}
+
+/// Parse a signed integer of `bits` from a number or a decimal string.
+fn parse_int(value: &Value, bits: u32) -> Result<i128, String> {
+	let int = match value {
+		Value::Number(number) => number.as_i64().map(Into::into),
+		Value::String(string) => string.parse().ok(),
+		_ => None,
+	};
+	int.filter(|int| bits == 128 || (-(1 << (bits - 1))..1 << (bits - 1)).contains(int))
+		.ok_or_else(|| format!("expected a {bits} bit signed integer, got {value}"))
+}
+
+/// Decode the SCALE encoded type `ty` from `input` to JSON.
+fn decode_value(types: &PortableRegistry, ty: u32, input: &mut &[u8]) -> Result<Value, String> {
+	let type_info = resolve(types, ty)?;
+	match &type_info.type_def {
+		TypeDef::Composite(composite) => decode_fields(types, &composite.fields, input),
+		TypeDef::Variant(variant) => {
+			let index = u8::decode(input).map_err(|error| error.to_string())?;
+			let variant = variant
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or_else(|| format!("unknown variant index {index}"))?;
+			let fields = decode_fields(types, &variant.fields, input)?;
+			Ok(if is_option(type_info) {
+				fields
+			} else if variant.fields.is_empty() {
+				Value::String(variant.name.clone())
+			} else {
+				Value::Object(Map::from_iter([(variant.name.clone(), fields)]))
+			})
+		},
+		TypeDef::Sequence(sequence) => {
+			let len = <Compact<u32>>::decode(input).map_err(|error| error.to_string())?.0;
+			decode_items(types, sequence.type_param.id, len as usize, input)
+		},
+		TypeDef::Array(array) =>
+			decode_items(types, array.type_param.id, array.len as usize, input),
+		TypeDef::Tuple(tuple) => {
+			let fields: Vec<u32> = tuple.fields.iter().map(|field| field.id).collect();
+			decode_unnamed(types, &fields, input)
+		},
+		TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+		TypeDef::Compact(_) => {
+			let int = <Compact<u128>>::decode(input).map_err(|error| error.to_string())?.0;
+			Ok(u64::try_from(int).map_or_else(|_| int.to_string().into(), Into::into))
+		},
+		TypeDef::BitSequence(_) => Err("bit sequences are not supported".into()),
+	}
+}
+
+fn decode_fields(
+	types: &PortableRegistry,
+	fields: &[Field<PortableForm>],
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	match fields {
+		[] => Ok(Value::Null),
+		[field] if field.name.is_none() => decode_value(types, field.ty.id, input),
+		fields if fields.iter().all(|field| field.name.is_some()) => fields
+			.iter()
+			.map(|field| {
+				let name = field.name.clone().expect("all fields are named; qed");
+				Ok((name, decode_value(types, field.ty.id, input)?))
+			})
+			.collect::<Result<Map<_, _>, String>>()
+			.map(Value::Object),
+		fields => {
+			let fields: Vec<u32> = fields.iter().map(|field| field.ty.id).collect();
+			decode_unnamed(types, &fields, input)
+		},
+	}
+}
+
+fn decode_unnamed(
+	types: &PortableRegistry,
+	fields: &[u32],
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	if fields.is_empty() {
+		return Ok(Value::Null)
+	}
+	fields
+		.iter()
+		.map(|ty| decode_value(types, *ty, input))
+		.collect::<Result<_, _>>()
+		.map(Value::Array)
+}
+
+fn decode_items(
+	types: &PortableRegistry,
+	item: u32,
+	len: usize,
+	input: &mut &[u8],
+) -> Result<Value, String> {
+	if is_byte(types, item)? {
+		if input.len() < len {
+			return Err(format!("expected {len} bytes, got {}", input.len()))
+		}
+		let (bytes, rest) = input.split_at(len);
+		*input = rest;
+		return Ok(to_hex(bytes, false).into())
+	}
+	(0..len)
+		.map(|_| decode_value(types, item, input))
+		.collect::<Result<_, _>>()
+		.map(Value::Array)
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, String> {
+	fn decode<T: Decode>(input: &mut &[u8]) -> Result<T, String> {
+		T::decode(input).map_err(|error| error.to_string())
+	}
+
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => decode::<bool>(input)?.into(),
+		TypeDefPrimitive::Char => char::from_u32(decode(input)?)
+			.ok_or_else(|| "invalid char".to_string())?
+			.to_string()
+			.into(),
+		TypeDefPrimitive::Str => decode::<String>(input)?.into(),
+		TypeDefPrimitive::U8 => decode::<u8>(input)?.into(),
+		TypeDefPrimitive::U16 => decode::<u16>(input)?.into(),
+		TypeDefPrimitive::U32 => decode::<u32>(input)?.into(),
+		TypeDefPrimitive::U64 => decode::<u64>(input)?.into(),
+		TypeDefPrimitive::U128 => {
+			let int = decode::<u128>(input)?;
+			u64::try_from(int).map_or_else(|_| int.to_string().into(), Into::into)
+		},
+		TypeDefPrimitive::I8 => decode::<i8>(input)?.into(),
+		TypeDefPrimitive::I16 => decode::<i16>(input)?.into(),
+		TypeDefPrimitive::I32 => decode::<i32>(input)?.into(),
+		TypeDefPrimitive::I64 => decode::<i64>(input)?.into(),
+		TypeDefPrimitive::I128 => {
+			let int = decode::<i128>(input)?;
+			i64::try_from(int).map_or_else(|_| int.to_string().into(), Into::into)
+		},
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			return Err("256 bit integers are not supported".into()),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_info::TypeInfo;
+	use serde_json::json;
+
+	#[derive(Encode, TypeInfo)]
+	enum Status {
+		Idle,
+		Busy(u8),
+		Locked { until: u32 },
+	}
+
+	#[derive(Encode, TypeInfo)]
+	struct Vault {
+		owner: [u8; 4],
+		balance: u128,
+		#[codec(compact)]
+		nonce: u32,
+		strategies: Vec<(bool, i16)>,
+		status: Status,
+		limit: Option<u64>,
+		name: Vec<u8>,
+	}
+
+	fn registry<T: TypeInfo + 'static>() -> (PortableRegistry, u32) {
+		let mut registry = scale_info::Registry::new();
+		let ty = registry.register_type(&scale_info::meta_type::<T>()).id;
+		(registry.into(), ty)
+	}
+
+	fn encode_json<T: TypeInfo + 'static>(value: Value) -> Result<Vec<u8>, String> {
+		let (types, ty) = registry::<T>();
+		let mut out = Vec::new();
+		encode_value(&types, ty, &value, &mut out)?;
+		Ok(out)
+	}
+
+	fn decode_json<T: TypeInfo + 'static>(mut input: &[u8]) -> Result<Value, String> {
+		let (types, ty) = registry::<T>();
+		let value = decode_value(&types, ty, &mut input)?;
+		assert!(input.is_empty());
+		Ok(value)
+	}
+
+	#[test]
+	fn json_round_trip_works() {
+		let vault = Vault {
+			owner: [1, 2, 3, 4],
+			balance: u128::MAX,
+			nonce: 7,
+			strategies: vec![(true, -3), (false, 300)],
+			status: Status::Locked { until: 42 },
+			limit: None,
+			name: b"vault".to_vec(),
+		};
+		let json = json!({
+			"owner": "0x01020304",
+			"balance": u128::MAX.to_string(),
+			"nonce": 7,
+			"strategies": [[true, -3], [false, 300]],
+			"status": { "Locked": { "until": 42 } },
+			"limit": null,
+			"name": "0x7661756c74",
+		});
+
+		assert_eq!(encode_json::<Vault>(json.clone()), Ok(vault.encode()));
+		assert_eq!(decode_json::<Vault>(&vault.encode()), Ok(json));
+	}
+
+	#[test]
+	fn enums_and_options_work() {
+		for (status, json) in [
+			(Status::Idle, json!("Idle")),
+			(Status::Busy(3), json!({ "Busy": 3 })),
+			(Status::Locked { until: 1 }, json!({ "Locked": { "until": 1 } })),
+		] {
+			assert_eq!(encode_json::<Status>(json.clone()), Ok(status.encode()));
+			assert_eq!(decode_json::<Status>(&status.encode()), Ok(json));
+		}
+
+		assert_eq!(encode_json::<Option<u64>>(json!(5)), Ok(Some(5u64).encode()));
+		assert_eq!(encode_json::<Option<u64>>(json!(null)), Ok(None::<u64>.encode()));
+		assert_eq!(decode_json::<Option<u64>>(&Some(5u64).encode()), Ok(json!(5)));
+	}
+
+	#[test]
+	fn invalid_values_are_rejected() {
+		assert!(encode_json::<u8>(json!(256)).is_err());
+		assert!(encode_json::<i8>(json!(-129)).is_err());
+		assert!(encode_json::<u32>(json!("not a number")).is_err());
+		assert!(encode_json::<[u8; 4]>(json!("0x010203")).is_err());
+		assert!(encode_json::<Status>(json!("Unknown")).is_err());
+		assert!(encode_json::<Vault>(json!({ "owner": "0x01020304" })).is_err());
+	}
+}
@@ -0,0 +1,193 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion Copy Trading pallet benchmarking.
+
+use super::*;
+use crate::Pallet as AssetConversionCopyTrading;
+use frame_benchmarking::{v2::*, whitelisted_caller};
+use frame_support::traits::{Consideration, Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::One, Saturating};
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AccountId, AssetKind, Balance> {
+	/// Prepares a swap of `path[0]` for `path[last]` by `who` along a path of `path_len` assets,
+	/// returning the `path` and an amount of `path[0]` that `who` can swap while staying alive.
+	///
+	/// The pools of the `path` must exist and have enough liquidity for the swaps of a leader,
+	/// [`Config::MaxFollowers`] followers and a trader moving their prices. The function is
+	/// called once for each of these accounts, with the same `path` expected for the same
+	/// `path_len`. `path_len` is at
+	/// most the longest path allowed by [`Config::Swap`].
+	fn setup_swap(who: &AccountId, path_len: u32) -> (Vec<AssetKind>, Balance);
+}
+
+/// The loosest limits allowed.
+fn limits<T: Config>() -> RiskLimits {
+	RiskLimits { max_portion: Permill::one(), max_slippage: T::MaxSlippage::get() }
+}
+
+/// Add the proxy of `who` to the pallet's account and fund the deposits of a subscription.
+fn authorize<T: Config>(who: &T::AccountId) {
+	let proxy_deposit = T::ProxyDepositBase::get().saturating_add(T::ProxyDepositFactor::get());
+	let _ = <T as pallet_proxy::Config>::Currency::deposit_creating(
+		who,
+		<T as pallet_proxy::Config>::Currency::minimum_balance().saturating_add(proxy_deposit),
+	);
+	pallet_proxy::Pallet::<T>::add_proxy_delegate(
+		who,
+		AssetConversionCopyTrading::<T>::account_id(),
+		T::CopyTradingProxyType::get(),
+		Zero::zero(),
+	)
+	.unwrap();
+	T::Consideration::ensure_successful(
+		who,
+		AssetConversionCopyTrading::<T>::subscription_footprint(),
+	);
+}
+
+/// Make `follower` follow `leader`.
+fn add_follower<T: Config>(leader: &T::AccountId, follower: &T::AccountId) {
+	authorize::<T>(follower);
+	AssetConversionCopyTrading::<T>::follow(
+		RawOrigin::Signed(follower.clone()).into(),
+		leader.clone(),
+		limits::<T>(),
+	)
+	.unwrap();
+}
+
+/// Add `n` followers to `leader`, returning the last one.
+fn add_followers<T: Config>(leader: &T::AccountId, n: u32) -> Option<T::AccountId> {
+	(0..n)
+		.map(|i| {
+			let follower: T::AccountId = account("follower", i, 0);
+			add_follower::<T>(leader, &follower);
+			follower
+		})
+		.last()
+}
+
+/// Advance past the [`Config::PriceWindow`] of the pools of the path of `path_len` assets, and
+/// move their prices within it, for their time-weighted prices to be searched in their
+/// observations.
+fn move_prices<T: Config>(path_len: u32) {
+	let trader: T::AccountId = account("trader", 0, 0);
+	let (path, amount) = T::BenchmarkHelper::setup_swap(&trader, path_len);
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::PriceWindow::get()));
+	T::Swap::swap_exact_tokens_for_tokens(trader.clone(), path, amount, None, trader, true)
+		.unwrap();
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(One::one()));
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn follow() {
+		let leader: T::AccountId = account("leader", 0, 0);
+		add_followers::<T>(&leader, T::MaxFollowers::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		authorize::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), leader.clone(), limits::<T>());
+
+		assert_last_event::<T>(
+			Event::Followed { follower: caller, leader, limits: limits::<T>() }.into(),
+		);
+	}
+
+	#[benchmark]
+	fn set_limits() {
+		let leader: T::AccountId = account("leader", 0, 0);
+		let follower = add_followers::<T>(&leader, T::MaxFollowers::get()).unwrap();
+		let limits =
+			RiskLimits { max_portion: Permill::from_percent(50), max_slippage: Zero::zero() };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(follower.clone()), leader.clone(), limits);
+
+		assert_last_event::<T>(Event::LimitsSet { follower, leader, limits }.into());
+	}
+
+	#[benchmark]
+	fn unfollow() {
+		let leader: T::AccountId = account("leader", 0, 0);
+		let follower = add_followers::<T>(&leader, T::MaxFollowers::get()).unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(follower.clone()), leader.clone());
+
+		assert_last_event::<T>(Event::Unfollowed { follower, leader }.into());
+	}
+
+	/// The swap of a leader without followers, whose trades are weighed by `mirror_trade`.
+	#[benchmark]
+	fn swap(p: Linear<2, { T::Swap::max_path_len() }>) {
+		let leader: T::AccountId = whitelisted_caller();
+		let (path, amount) = T::BenchmarkHelper::setup_swap(&leader, p);
+		move_prices::<T>(p);
+		let path = path.into_iter().map(Box::new).collect::<Vec<_>>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(leader.clone()), path, amount, 1u32.into(), true);
+	}
+
+	#[benchmark]
+	fn mirror_trade(p: Linear<2, { T::Swap::max_path_len() }>) {
+		let leader: T::AccountId = whitelisted_caller();
+		let (path, amount) = T::BenchmarkHelper::setup_swap(&leader, p);
+		let follower: T::AccountId = account("follower", 0, 0);
+		T::BenchmarkHelper::setup_swap(&follower, p);
+		add_follower::<T>(&leader, &follower);
+		move_prices::<T>(p);
+		let follower = Follower { who: follower, limits: limits::<T>() };
+		let leader_balance = T::Assets::balance(path[0].clone(), &leader);
+		let reference_quote = AssetConversionCopyTrading::<T>::quote_time_weighted(&path, amount);
+		assert!(reference_quote.is_some());
+		let result;
+
+		#[block]
+		{
+			result = AssetConversionCopyTrading::<T>::mirror_trade(
+				&follower,
+				&path,
+				amount,
+				reference_quote,
+				leader_balance,
+			);
+		}
+
+		assert!(result.is_ok());
+	}
+
+	impl_benchmark_test_suite!(
+		AssetConversionCopyTrading,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,601 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Conversion Copy Trading Pallet
+//!
+//! Mirrors the swaps of leaders into the accounts of their followers, within per-follower risk
+//! limits.
+//!
+//! ## Overview
+//!
+//! This pallet allows accounts to:
+//!
+//! - [follow a leader](`Pallet::follow`), with [`RiskLimits`] bounding the trades mirrored into the
+//!   follower's account;
+//! - [update the limits](`Pallet::set_limits`) of a subscription;
+//! - [unfollow a leader](`Pallet::unfollow`);
+//! - [swap as a leader](`Pallet::swap`), mirroring the swap into the accounts of all followers.
+//!
+//! ### Authorization
+//!
+//! A follower authorizes the mirroring of trades into their account by adding a proxy of type
+//! [`Config::CopyTradingProxyType`] and without delay to the [`Pallet::account_id`] of this
+//! pallet, with [`pallet_proxy`]. Removing the proxy stops the mirroring: trades are then skipped
+//! until the proxy is added again or the follower unfollows the leader. Since no one can sign for
+//! the pallet account, the proxy type does not need to allow any call.
+//!
+//! The storage of a subscription is paid for by the follower with [`Config::Consideration`].
+//!
+//! ### Mirroring
+//!
+//! When a leader swaps with [`Pallet::swap`], every follower swaps, in the same call, the same
+//! portion of their balance of `path[0]` as the leader, along the same path. A follower's trade
+//! is skipped, with a [`Event::TradeSkipped`] event, when:
+//!
+//! - the portion exceeds the follower's [`RiskLimits::max_portion`];
+//! - the amount quoted by the pools of the path, or received by the follower, falls short of the
+//!   reference price by more than the follower's [`RiskLimits::max_slippage`];
+//! - the reference price is not known, e.g. for a pool created within the last
+//!   [`Config::PriceWindow`] blocks;
+//! - the proxy of the follower is missing, or the swap fails.
+//!
+//! The reference price is the time-weighted average price of the path quoted by [`Config::Swap`]
+//! over the last [`Config::PriceWindow`] blocks, excluding fees. It can't be moved within a block,
+//! so a leader can't make their followers trade at a price they manipulated before the call, and
+//! the slippage a follower accepts, fees included, is bounded by [`Config::MaxSlippage`].
+//! Followers trade first, in order, and the leader trades last, against pools moved by their
+//! followers. Only swaps made through [`Pallet::swap`] are mirrored.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{
+		fungibles::Inspect,
+		tokens::{Balance, Fortitude, Preservation},
+		Consideration, Footprint,
+	},
+	PalletId,
+};
+use pallet_asset_conversion::{QuotePrice, QuoteTimeWeightedPrice, Swap};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Get, Saturating, Zero},
+	DispatchError, Permill, Rounding,
+};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
+
+/// The limits of the trades mirrored into a follower's account.
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct RiskLimits {
+	/// The largest portion of the follower's balance of the sold asset swapped by a trade.
+	pub max_portion: Permill,
+	/// The largest shortfall of the amount received, fees included, relative to the time-weighted
+	/// average price of the path.
+	pub max_slippage: Permill,
+}
+
+/// A follower of a leader.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct Follower<AccountId> {
+	/// The follower's account.
+	pub who: AccountId,
+	/// The limits of the trades mirrored into the follower's account.
+	pub limits: RiskLimits,
+}
+
+/// The reason a leader's trade was not mirrored into a follower's account.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, TypeInfo)]
+pub enum SkipReason {
+	/// The follower has no proxy authorizing the trade.
+	MissingProxy,
+	/// The follower's share of the trade is zero.
+	InsufficientBalance,
+	/// The trade exceeds the follower's maximum portion.
+	PortionExceeded,
+	/// The price quoted for the trade exceeds the follower's maximum slippage.
+	SlippageExceeded,
+	/// The swap failed.
+	SwapFailed {
+		/// The error of the swap.
+		error: DispatchError,
+	},
+	/// The time-weighted average price of the path is not known over the price window.
+	PriceUnavailable,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_arithmetic::MultiplyRational;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Cost associated with storing a subscription on-chain.
+		#[codec(index = 0)]
+		SubscriptionDeposit,
+	}
+
+	#[pallet::config]
+	pub trait Config: pallet_proxy::Config + frame_system::Config {
+		/// Overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The pallet's unique identifier, used to derive the account followers add as their
+		/// proxy.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The proxy type followers grant to the pallet's account to authorize the mirroring of
+		/// trades into their accounts.
+		#[pallet::constant]
+		type CopyTradingProxyType: Get<<Self as pallet_proxy::Config>::ProxyType>;
+
+		/// The type in which the assets for swapping are measured.
+		type Balance: Balance;
+
+		/// Type of asset class, sourced from [`Config::Swap`], swapped by the leaders.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// Registry of the assets swapped, used to size the followers' trades.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>;
+
+		/// The swap and price quoting implementation, typically [`pallet_asset_conversion`].
+		type Swap: Swap<Self::AccountId, Balance = Self::Balance, AssetKind = Self::AssetKind>
+			+ QuoteTimeWeightedPrice<
+				Balance = Self::Balance,
+				AssetKind = Self::AssetKind,
+				BlockNumber = BlockNumberFor<Self>,
+			>;
+
+		/// The number of blocks over which the reference prices of the followers' trades are
+		/// averaged. Must not be zero.
+		#[pallet::constant]
+		type PriceWindow: Get<BlockNumberFor<Self>>;
+
+		/// Means for associating a cost with the on-chain storage of a subscription, which is
+		/// incurred by the follower.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+
+		/// The maximum number of followers of a leader.
+		#[pallet::constant]
+		type MaxFollowers: Get<u32>;
+
+		/// The highest [`RiskLimits::max_slippage`] of a follower.
+		#[pallet::constant]
+		type MaxSlippage: Get<Permill>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::AssetKind, Self::Balance>;
+	}
+
+	/// The followers of each leader, in the order their trades are mirrored.
+	#[pallet::storage]
+	pub type Followers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<Follower<T::AccountId>, T::MaxFollowers>,
+		ValueQuery,
+	>;
+
+	/// The cost associated with storing a subscription, incurred by the follower, keyed by leader
+	/// and follower.
+	///
+	/// This storage is separate from [`Followers`] to maintain their `MaxEncodedLen` and
+	/// `TypeInfo` independent of the [`Config::Consideration`] implementation.
+	#[pallet::storage]
+	pub type SubscriptionCost<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Consideration,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account started following a leader.
+		Followed {
+			/// The follower.
+			follower: T::AccountId,
+			/// The leader.
+			leader: T::AccountId,
+			/// The limits of the trades mirrored into the follower's account.
+			limits: RiskLimits,
+		},
+		/// The limits of a subscription were updated.
+		LimitsSet {
+			/// The follower.
+			follower: T::AccountId,
+			/// The leader.
+			leader: T::AccountId,
+			/// The new limits.
+			limits: RiskLimits,
+		},
+		/// An account stopped following a leader.
+		Unfollowed {
+			/// The follower.
+			follower: T::AccountId,
+			/// The leader.
+			leader: T::AccountId,
+		},
+		/// A leader swapped, and the swap was mirrored to their followers.
+		LeaderSwapped {
+			/// The leader.
+			leader: T::AccountId,
+			/// The swap path, from the asset sold to the asset bought.
+			path: Vec<T::AssetKind>,
+			/// The amount of `path[0]` swapped by the leader.
+			amount_in: T::Balance,
+			/// The amount of `path[last]` received by the leader.
+			amount_out: T::Balance,
+		},
+		/// A leader's trade was mirrored into a follower's account.
+		TradeMirrored {
+			/// The leader.
+			leader: T::AccountId,
+			/// The follower.
+			follower: T::AccountId,
+			/// The amount of `path[0]` swapped by the follower.
+			amount_in: T::Balance,
+			/// The amount of `path[last]` received by the follower.
+			amount_out: T::Balance,
+		},
+		/// A leader's trade was not mirrored into a follower's account.
+		TradeSkipped {
+			/// The leader.
+			leader: T::AccountId,
+			/// The follower.
+			follower: T::AccountId,
+			/// The reason the trade was skipped.
+			reason: SkipReason,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An account cannot follow itself.
+		CannotFollowSelf,
+		/// The maximum portion of the limits is zero, or their maximum slippage exceeds
+		/// [`Config::MaxSlippage`].
+		InvalidLimits,
+		/// The account already follows the leader.
+		AlreadyFollowing,
+		/// The account does not follow the leader.
+		NotFollowing,
+		/// The leader has the maximum number of followers.
+		TooManyFollowers,
+		/// The account has no proxy of the copy trading type, without delay, to the pallet's
+		/// account.
+		MissingProxy,
+		/// The swap path is shorter than two assets or longer than allowed, or its pools can't
+		/// quote the swap.
+		InvalidPath,
+		/// The amount to swap is zero.
+		ZeroAmount,
+	}
+
+	#[pallet::call(weight(<T as Config>::WeightInfo))]
+	impl<T: Config> Pallet<T> {
+		/// Follow `leader`, mirroring their swaps into the caller's account within `limits`.
+		///
+		/// The caller must have added a proxy of type [`Config::CopyTradingProxyType`], without
+		/// delay, to the [`Pallet::account_id`].
+		#[pallet::call_index(0)]
+		pub fn follow(
+			origin: OriginFor<T>,
+			leader: T::AccountId,
+			limits: RiskLimits,
+		) -> DispatchResult {
+			let follower = ensure_signed(origin)?;
+			ensure!(follower != leader, Error::<T>::CannotFollowSelf);
+			Self::ensure_limits(&limits)?;
+			ensure!(
+				!SubscriptionCost::<T>::contains_key(&leader, &follower),
+				Error::<T>::AlreadyFollowing
+			);
+			ensure!(Self::has_proxy(&follower), Error::<T>::MissingProxy);
+
+			Followers::<T>::try_append(&leader, Follower { who: follower.clone(), limits })
+				.map_err(|_| Error::<T>::TooManyFollowers)?;
+			let cost = T::Consideration::new(&follower, Self::subscription_footprint())?;
+			SubscriptionCost::<T>::insert(&leader, &follower, cost);
+
+			Self::deposit_event(Event::Followed { follower, leader, limits });
+			Ok(())
+		}
+
+		/// Set the `limits` of the trades of `leader` mirrored into the caller's account.
+		#[pallet::call_index(1)]
+		pub fn set_limits(
+			origin: OriginFor<T>,
+			leader: T::AccountId,
+			limits: RiskLimits,
+		) -> DispatchResult {
+			let follower = ensure_signed(origin)?;
+			Self::ensure_limits(&limits)?;
+
+			Followers::<T>::try_mutate(&leader, |followers| {
+				let entry = followers
+					.iter_mut()
+					.find(|entry| entry.who == follower)
+					.ok_or(Error::<T>::NotFollowing)?;
+				entry.limits = limits;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::LimitsSet { follower, leader, limits });
+			Ok(())
+		}
+
+		/// Stop following `leader`, releasing the deposit of the subscription.
+		#[pallet::call_index(2)]
+		pub fn unfollow(origin: OriginFor<T>, leader: T::AccountId) -> DispatchResult {
+			let follower = ensure_signed(origin)?;
+			let cost =
+				SubscriptionCost::<T>::take(&leader, &follower).ok_or(Error::<T>::NotFollowing)?;
+
+			Followers::<T>::mutate_exists(&leader, |followers| {
+				if let Some(entries) = followers {
+					entries.retain(|entry| entry.who != follower);
+					if entries.is_empty() {
+						*followers = None;
+					}
+				}
+			});
+			cost.drop(&follower)?;
+
+			Self::deposit_event(Event::Unfollowed { follower, leader });
+			Ok(())
+		}
+
+		/// Swap exactly `amount_in` of `path[0]` for at least `amount_out_min` of `path[last]`,
+		/// and mirror the swap into the accounts of the caller's followers.
+		///
+		/// Each follower swaps the same portion of their balance of `path[0]` as the caller,
+		/// unless it exceeds their [`RiskLimits`], in which case their trade is skipped. The
+		/// followers swap before the caller, priced against the time-weighted average price of
+		/// the path over the last [`Config::PriceWindow`] blocks. `amount_out_min` of zero sets no
+		/// limit to the caller's swap.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::swap_weight(T::MaxFollowers::get(), path.len() as u32))]
+		pub fn swap(
+			origin: OriginFor<T>,
+			path: Vec<Box<T::AssetKind>>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let leader = ensure_signed(origin)?;
+			ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				path.len() >= 2 && path.len() as u32 <= T::Swap::max_path_len(),
+				Error::<T>::InvalidPath
+			);
+			let path = path.into_iter().map(|asset| *asset).collect::<Vec<_>>();
+
+			let leader_balance = T::Assets::balance(path[0].clone(), &leader);
+			ensure!(Self::quote(&path, amount_in).is_some(), Error::<T>::InvalidPath);
+			let reference_quote = Self::quote_time_weighted(&path, amount_in);
+
+			let followers = Followers::<T>::get(&leader);
+			for follower in followers.iter() {
+				let event = match Self::mirror_trade(
+					follower,
+					&path,
+					amount_in,
+					reference_quote,
+					leader_balance,
+				) {
+					Ok((amount_in, amount_out)) => Event::TradeMirrored {
+						leader: leader.clone(),
+						follower: follower.who.clone(),
+						amount_in,
+						amount_out,
+					},
+					Err(reason) => Event::TradeSkipped {
+						leader: leader.clone(),
+						follower: follower.who.clone(),
+						reason,
+					},
+				};
+				Self::deposit_event(event);
+			}
+
+			let amount_out = T::Swap::swap_exact_tokens_for_tokens(
+				leader.clone(),
+				path.clone(),
+				amount_in,
+				(!amount_out_min.is_zero()).then_some(amount_out_min),
+				leader.clone(),
+				keep_alive,
+			)?;
+			Self::deposit_event(Event::LeaderSwapped {
+				leader,
+				path: path.clone(),
+				amount_in,
+				amount_out,
+			});
+
+			Ok(Some(Self::swap_weight(followers.len() as u32, path.len() as u32)).into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account followers add as their proxy to authorize the mirroring of trades.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The storage footprint of a subscription.
+		pub fn subscription_footprint() -> Footprint {
+			Footprint::from_mel::<(T::AccountId, Follower<T::AccountId>)>()
+		}
+
+		/// The weight of [`Pallet::swap`] along a path of `path_len` assets, mirrored to
+		/// `followers` followers.
+		pub fn swap_weight(followers: u32, path_len: u32) -> Weight {
+			<T as Config>::WeightInfo::swap(path_len).saturating_add(
+				<T as Config>::WeightInfo::mirror_trade(path_len).saturating_mul(followers.into()),
+			)
+		}
+
+		/// Whether `who` authorized the mirroring of trades into their account.
+		pub fn has_proxy(who: &T::AccountId) -> bool {
+			pallet_proxy::Pallet::<T>::find_proxy(
+				who,
+				&Self::account_id(),
+				Some(T::CopyTradingProxyType::get()),
+			)
+			.is_ok_and(|proxy| proxy.delay.is_zero())
+		}
+
+		/// The amount of `path[last]` quoted for a swap of `amount_in` of `path[0]`, including
+		/// the fees of the pools.
+		pub(crate) fn quote(path: &[T::AssetKind], amount_in: T::Balance) -> Option<T::Balance> {
+			path.windows(2).try_fold(amount_in, |amount, pair| {
+				T::Swap::quote_price_exact_tokens_for_tokens(
+					pair[0].clone(),
+					pair[1].clone(),
+					amount,
+					true,
+				)
+			})
+		}
+
+		/// The amount of `path[last]` worth `amount_in` of `path[0]` at the time-weighted average
+		/// prices of the pools over the last [`Config::PriceWindow`] blocks, excluding fees.
+		pub(crate) fn quote_time_weighted(
+			path: &[T::AssetKind],
+			amount_in: T::Balance,
+		) -> Option<T::Balance> {
+			path.windows(2).try_fold(amount_in, |amount, pair| {
+				T::Swap::quote_price_time_weighted(
+					pair[0].clone(),
+					pair[1].clone(),
+					amount,
+					T::PriceWindow::get(),
+				)
+			})
+		}
+
+		fn ensure_limits(limits: &RiskLimits) -> DispatchResult {
+			ensure!(
+				!limits.max_portion.is_zero() && limits.max_slippage <= T::MaxSlippage::get(),
+				Error::<T>::InvalidLimits
+			);
+			Ok(())
+		}
+
+		/// Mirror into the account of `follower` a trade of `amount_in` of `path[0]`, worth
+		/// `reference_quote` of `path[last]` at the time-weighted average price, by a leader with
+		/// `leader_balance` of `path[0]`.
+		///
+		/// Returns the amounts swapped and received by the follower.
+		///
+		/// Weighed by [`WeightInfo::mirror_trade`], see [`Pallet::swap_weight`].
+		pub(crate) fn mirror_trade(
+			follower: &Follower<T::AccountId>,
+			path: &[T::AssetKind],
+			amount_in: T::Balance,
+			reference_quote: Option<T::Balance>,
+			leader_balance: T::Balance,
+		) -> Result<(T::Balance, T::Balance), SkipReason> {
+			ensure!(Self::has_proxy(&follower.who), SkipReason::MissingProxy);
+
+			let balance = T::Assets::reducible_balance(
+				path[0].clone(),
+				&follower.who,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			);
+			let follower_in = balance
+				.multiply_rational(amount_in, leader_balance, Rounding::Down)
+				.ok_or(SkipReason::PortionExceeded)?;
+			ensure!(!follower_in.is_zero(), SkipReason::InsufficientBalance);
+			ensure!(
+				follower_in <= follower.limits.max_portion.mul_floor(balance),
+				SkipReason::PortionExceeded
+			);
+
+			// The amount received at the time-weighted average price, less the allowed slippage.
+			// The spot quote and the swap are both bounded by it.
+			let reference_quote = reference_quote.ok_or(SkipReason::PriceUnavailable)?;
+			let reference_out = follower_in
+				.multiply_rational(reference_quote, amount_in, Rounding::Down)
+				.ok_or(SkipReason::SlippageExceeded)?;
+			let min_out =
+				reference_out.saturating_sub(follower.limits.max_slippage.mul_floor(reference_out));
+			let quote = Self::quote(path, follower_in).ok_or(SkipReason::SlippageExceeded)?;
+			ensure!(quote >= min_out, SkipReason::SlippageExceeded);
+
+			let follower_out = T::Swap::swap_exact_tokens_for_tokens(
+				follower.who.clone(),
+				path.to_vec(),
+				follower_in,
+				(!min_out.is_zero()).then_some(min_out),
+				follower.who.clone(),
+				true,
+			)
+			.map_err(|error| SkipReason::SwapFailed { error })?;
+			Ok((follower_in, follower_out))
+		}
+	}
+}
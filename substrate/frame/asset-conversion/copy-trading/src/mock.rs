@@ -0,0 +1,365 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Asset Conversion Copy Trading pallet.
+
+use super::*;
+use crate as pallet_asset_conversion_copy_trading;
+use core::default::Default;
+use frame_support::{
+	assert_ok, construct_runtime, derive_impl,
+	instances::{Instance1, Instance2},
+	ord_parameter_types, parameter_types,
+	traits::{
+		tokens::{
+			fungible::{HoldConsideration, NativeFromLeft, NativeOrWithId, UnionOf},
+			imbalance::ResolveAssetTo,
+		},
+		AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, InstanceFilter, LinearStoragePrice,
+	},
+	PalletId,
+};
+use frame_system::{EnsureSigned, EnsureSignedBy};
+use pallet_asset_conversion::{AccountIdConverter, Ascending, Chain, WithFirstAsset};
+use sp_runtime::{
+	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+	BuildStorage, Permill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets::<Instance1>,
+		PoolAssets: pallet_assets::<Instance2>,
+		Proxy: pallet_proxy,
+		AssetConversion: pallet_asset_conversion,
+		AssetConversionCopyTrading: pallet_asset_conversion_copy_trading,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountId = u128;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = u128;
+	type ExistentialDeposit = ConstU128<100>;
+	type AccountStore = System;
+}
+
+impl pallet_assets::Config<Instance1> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<10>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Holder = ();
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type CallbackHandle = ();
+	pallet_assets::runtime_benchmarks_enabled! {
+		type BenchmarkHelper = ();
+	}
+}
+
+impl pallet_assets::Config<Instance2> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin =
+		AsEnsureOriginWithArg<EnsureSignedBy<AssetConversionOrigin, Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Holder = ();
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type CallbackHandle = ();
+	pallet_assets::runtime_benchmarks_enabled! {
+		type BenchmarkHelper = ();
+	}
+}
+
+/// The kinds of proxies of the test runtime.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Debug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum ProxyType {
+	Any,
+	CopyTrading,
+}
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, _: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::CopyTrading => false,
+		}
+	}
+	fn is_superset(&self, o: &Self) -> bool {
+		self == &ProxyType::Any || self == o
+	}
+}
+
+impl pallet_proxy::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ConstU128<1>;
+	type ProxyDepositFactor = ConstU128<1>;
+	type MaxProxies = ConstU32<4>;
+	type WeightInfo = ();
+	type CallHasher = BlakeTwo256;
+	type MaxPending = ConstU32<2>;
+	type AnnouncementDepositBase = ConstU128<1>;
+	type AnnouncementDepositFactor = ConstU128<1>;
+	type BlockNumberProvider = System;
+}
+
+parameter_types! {
+	pub const AssetConversionPalletId: PalletId = PalletId(*b"py/ascon");
+	pub const Native: NativeOrWithId<u32> = NativeOrWithId::Native;
+	pub storage LiquidityWithdrawalFee: Permill = Permill::from_percent(0);
+	pub const CopyTradingPalletId: PalletId = PalletId(*b"py/cptrd");
+	pub const CopyTradingProxyType: ProxyType = ProxyType::CopyTrading;
+	pub const MaxSlippage: Permill = Permill::from_percent(10);
+	pub const SubscriptionHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::AssetConversionCopyTrading(pallet_asset_conversion_copy_trading::HoldReason::SubscriptionDeposit);
+}
+
+ord_parameter_types! {
+	pub const AssetConversionOrigin: u128 = AccountIdConversion::<u128>::into_account_truncating(&AssetConversionPalletId::get());
+}
+
+pub type NativeAndAssets = UnionOf<Balances, Assets, NativeFromLeft, NativeOrWithId<u32>, u128>;
+pub type PoolIdToAccountId =
+	AccountIdConverter<AssetConversionPalletId, (NativeOrWithId<u32>, NativeOrWithId<u32>)>;
+pub type AscendingLocator = Ascending<u128, NativeOrWithId<u32>, PoolIdToAccountId>;
+pub type WithFirstAssetLocator =
+	WithFirstAsset<Native, u128, NativeOrWithId<u32>, PoolIdToAccountId>;
+
+impl pallet_asset_conversion::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = <Self as pallet_balances::Config>::Balance;
+	type HigherPrecisionBalance = sp_core::U256;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type PoolId = (Self::AssetKind, Self::AssetKind);
+	type PoolLocator = Chain<WithFirstAssetLocator, AscendingLocator>;
+	type PoolAssetId = u32;
+	type PoolAssets = PoolAssets;
+	type PoolSetupFee = ConstU128<100>;
+	type PoolSetupFeeAsset = Native;
+	type PoolSetupFeeTarget = ResolveAssetTo<AssetConversionOrigin, Self::Assets>;
+	type PalletId = AssetConversionPalletId;
+	type WeightInfo = ();
+	type LPFee = ConstU32<3>;
+	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
+	type MaxSwapPathLength = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<8>;
+	type MintMinLiquidity = ConstU128<100>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct CopyTradingBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<u128, NativeOrWithId<u32>, u128> for CopyTradingBenchmarkHelper {
+	fn setup_swap(who: &u128, path_len: u32) -> (Vec<NativeOrWithId<u32>>, u128) {
+		use frame_support::traits::fungibles::{Inspect, Mutate};
+		// The genesis pool, followed by pools of `Native` and asset `2`, and of assets `2` and
+		// `3`, created when missing, as the genesis pool may have been wiped by the benchmark
+		// tests along with the storage of a previous run.
+		let path = [
+			NativeOrWithId::WithId(1),
+			NativeOrWithId::Native,
+			NativeOrWithId::WithId(2),
+			NativeOrWithId::WithId(3),
+		];
+		for asset in [2, 3] {
+			if !Assets::asset_exists(asset) {
+				assert_ok!(Assets::force_create(
+					RuntimeOrigin::root(),
+					asset,
+					LIQUIDITY_PROVIDER,
+					true,
+					1,
+				));
+			}
+		}
+		for pair in path.windows(2) {
+			if AssetConversion::get_reserves(pair[0].clone(), pair[1].clone()).is_ok() {
+				continue
+			}
+			for asset in pair {
+				if let NativeOrWithId::WithId(id) = asset {
+					assert_ok!(Assets::mint_into(*id, &LIQUIDITY_PROVIDER, 2_000_000));
+				}
+			}
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				Box::new(pair[0].clone()),
+				Box::new(pair[1].clone()),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				Box::new(pair[0].clone()),
+				Box::new(pair[1].clone()),
+				1_000_000,
+				1_000_000,
+				1,
+				1,
+				LIQUIDITY_PROVIDER,
+			));
+		}
+		assert_ok!(Assets::mint_into(1, who, 110));
+		// For `who` to receive the swaps ending in `Native`.
+		assert_ok!(<Balances as frame_support::traits::fungible::Mutate<_>>::mint_into(who, 100));
+		(path[..path_len as usize].to_vec(), 100)
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = CopyTradingPalletId;
+	type CopyTradingProxyType = CopyTradingProxyType;
+	type Balance = <Self as pallet_balances::Config>::Balance;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type Swap = AssetConversion;
+	type Consideration = HoldConsideration<
+		u128,
+		Balances,
+		SubscriptionHoldReason,
+		LinearStoragePrice<ConstU128<100>, ConstU128<0>, u128>,
+	>;
+	type MaxFollowers = ConstU32<8>;
+	type MaxSlippage = MaxSlippage;
+	type PriceWindow = ConstU64<1>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = CopyTradingBenchmarkHelper;
+}
+
+/// The account providing the liquidity of the genesis pool.
+pub(crate) const LIQUIDITY_PROVIDER: u128 = 100;
+/// The leader followed in tests.
+pub(crate) const LEADER: u128 = 1;
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	pallet_assets::GenesisConfig::<Test, Instance1> {
+		assets: vec![(1, LIQUIDITY_PROVIDER, true, 1)],
+		metadata: vec![],
+		accounts: vec![
+			(1, LEADER, 100_000),
+			(1, 2, 10_001),
+			(1, 3, 20_001),
+			(1, 4, 10_001),
+			(1, LIQUIDITY_PROVIDER, 2_000_000),
+		],
+		next_asset_id: None,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(LEADER, 10_000),
+			(2, 10_000),
+			(3, 10_000),
+			(4, 10_000),
+			(LIQUIDITY_PROVIDER, 10_000_000),
+		],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		// A pool of `Native` and asset `1` with a price of one.
+		let (native, asset) =
+			(Box::new(NativeOrWithId::Native), Box::new(NativeOrWithId::WithId(1)));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			native.clone(),
+			asset.clone(),
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			native,
+			asset,
+			1_000_000,
+			1_000_000,
+			1,
+			1,
+			LIQUIDITY_PROVIDER,
+		));
+		// The price of the pool is known over the price window.
+		System::set_block_number(2);
+		System::reset_events();
+	});
+	ext
+}
@@ -0,0 +1,512 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold, NativeOrWithId},
+		Get,
+	},
+};
+
+const ASSET: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+
+#[allow(clippy::vec_box)]
+fn asset_to_native() -> Vec<Box<NativeOrWithId<u32>>> {
+	vec![Box::new(ASSET), Box::new(NativeOrWithId::Native)]
+}
+
+fn limits(max_portion: u32, max_slippage: u32) -> RiskLimits {
+	RiskLimits {
+		max_portion: Permill::from_percent(max_portion),
+		max_slippage: Permill::from_percent(max_slippage),
+	}
+}
+
+fn deposit(who: u128) -> u128 {
+	Balances::balance_on_hold(&SubscriptionHoldReason::get(), &who)
+}
+
+fn authorize(who: u128) {
+	assert_ok!(Proxy::add_proxy(
+		RuntimeOrigin::signed(who),
+		AssetConversionCopyTrading::account_id(),
+		ProxyType::CopyTrading,
+		0,
+	));
+}
+
+fn follow(who: u128, limits: RiskLimits) {
+	authorize(who);
+	assert_ok!(AssetConversionCopyTrading::follow(RuntimeOrigin::signed(who), LEADER, limits));
+}
+
+fn leader_swap(amount_in: u128) {
+	assert_ok!(AssetConversionCopyTrading::swap(
+		RuntimeOrigin::signed(LEADER),
+		asset_to_native(),
+		amount_in,
+		1,
+		true,
+	));
+}
+
+/// The amounts received by consecutive swaps of `amounts_in` of asset `1` for `Native`.
+fn amounts_out(amounts_in: &[u128]) -> Vec<u128> {
+	let (mut reserve_in, mut reserve_out) =
+		AssetConversion::get_reserves(ASSET, NativeOrWithId::Native).unwrap();
+	amounts_in
+		.iter()
+		.map(|amount_in| {
+			let amount_out =
+				AssetConversion::get_amount_out(amount_in, &reserve_in, &reserve_out).unwrap();
+			reserve_in += amount_in;
+			reserve_out -= amount_out;
+			amount_out
+		})
+		.collect()
+}
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			RuntimeEvent::AssetConversionCopyTrading(inner) => Some(inner),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	result
+}
+
+#[test]
+fn follow_works() {
+	new_test_ext().execute_with(|| {
+		follow(2, limits(20, 5));
+
+		assert_eq!(
+			events(),
+			vec![Event::Followed { follower: 2, leader: LEADER, limits: limits(20, 5) }]
+		);
+		assert_eq!(
+			Followers::<Test>::get(LEADER).into_inner(),
+			vec![Follower { who: 2, limits: limits(20, 5) }]
+		);
+		assert_eq!(deposit(2), 100);
+
+		follow(3, limits(10, 1));
+		assert_eq!(Followers::<Test>::get(LEADER).len(), 2);
+	});
+}
+
+#[test]
+fn follow_fails_for_invalid_input() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(2), LEADER, limits(20, 5)),
+			Error::<Test>::MissingProxy
+		);
+
+		// A proxy with a delay does not authorize the mirroring.
+		assert_ok!(Proxy::add_proxy(
+			RuntimeOrigin::signed(2),
+			AssetConversionCopyTrading::account_id(),
+			ProxyType::CopyTrading,
+			1,
+		));
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(2), LEADER, limits(20, 5)),
+			Error::<Test>::MissingProxy
+		);
+
+		authorize(3);
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(3), 3, limits(20, 5)),
+			Error::<Test>::CannotFollowSelf
+		);
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(3), LEADER, limits(0, 5)),
+			Error::<Test>::InvalidLimits
+		);
+		// The slippage is above `MaxSlippage`.
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(3), LEADER, limits(20, 11)),
+			Error::<Test>::InvalidLimits
+		);
+		assert_ok!(AssetConversionCopyTrading::follow(
+			RuntimeOrigin::signed(3),
+			LEADER,
+			limits(20, 5)
+		));
+		assert_noop!(
+			AssetConversionCopyTrading::follow(RuntimeOrigin::signed(3), LEADER, limits(20, 5)),
+			Error::<Test>::AlreadyFollowing
+		);
+	});
+}
+
+#[test]
+fn follow_fails_for_too_many_followers() {
+	new_test_ext().execute_with(|| {
+		let max_followers: u32 = <Test as Config>::MaxFollowers::get();
+		for who in (10..).take(max_followers as usize + 1) {
+			assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), who, 1_000));
+			authorize(who);
+		}
+		for who in (10..).take(max_followers as usize) {
+			assert_ok!(AssetConversionCopyTrading::follow(
+				RuntimeOrigin::signed(who),
+				LEADER,
+				limits(20, 5)
+			));
+		}
+
+		assert_noop!(
+			AssetConversionCopyTrading::follow(
+				RuntimeOrigin::signed(10 + max_followers as u128),
+				LEADER,
+				limits(20, 5)
+			),
+			Error::<Test>::TooManyFollowers
+		);
+	});
+}
+
+#[test]
+fn set_limits_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetConversionCopyTrading::set_limits(RuntimeOrigin::signed(2), LEADER, limits(10, 1)),
+			Error::<Test>::NotFollowing
+		);
+
+		follow(2, limits(20, 5));
+		follow(3, limits(20, 5));
+		System::reset_events();
+
+		assert_noop!(
+			AssetConversionCopyTrading::set_limits(RuntimeOrigin::signed(3), LEADER, limits(0, 1)),
+			Error::<Test>::InvalidLimits
+		);
+		assert_noop!(
+			AssetConversionCopyTrading::set_limits(
+				RuntimeOrigin::signed(3),
+				LEADER,
+				limits(10, 11)
+			),
+			Error::<Test>::InvalidLimits
+		);
+		assert_ok!(AssetConversionCopyTrading::set_limits(
+			RuntimeOrigin::signed(3),
+			LEADER,
+			limits(10, 1)
+		));
+
+		assert_eq!(
+			events(),
+			vec![Event::LimitsSet { follower: 3, leader: LEADER, limits: limits(10, 1) }]
+		);
+		assert_eq!(
+			Followers::<Test>::get(LEADER).into_inner(),
+			vec![
+				Follower { who: 2, limits: limits(20, 5) },
+				Follower { who: 3, limits: limits(10, 1) }
+			]
+		);
+	});
+}
+
+#[test]
+fn unfollow_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetConversionCopyTrading::unfollow(RuntimeOrigin::signed(2), LEADER),
+			Error::<Test>::NotFollowing
+		);
+
+		follow(2, limits(20, 5));
+		follow(3, limits(20, 5));
+		System::reset_events();
+
+		assert_ok!(AssetConversionCopyTrading::unfollow(RuntimeOrigin::signed(2), LEADER));
+		assert_eq!(events(), vec![Event::Unfollowed { follower: 2, leader: LEADER }]);
+		assert_eq!(
+			Followers::<Test>::get(LEADER).into_inner(),
+			vec![Follower { who: 3, limits: limits(20, 5) }]
+		);
+		assert_eq!(deposit(2), 0);
+
+		assert_ok!(AssetConversionCopyTrading::unfollow(RuntimeOrigin::signed(3), LEADER));
+		assert!(!Followers::<Test>::contains_key(LEADER));
+		assert_eq!(SubscriptionCost::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn swap_mirrors_trades_proportionally() {
+	new_test_ext().execute_with(|| {
+		follow(2, limits(20, 5));
+		follow(3, limits(20, 5));
+		System::reset_events();
+		let native_before = (Balances::free_balance(2), Balances::free_balance(3));
+		let amounts_out = amounts_out(&[1_000, 2_000, 10_000]);
+
+		// The leader swaps a tenth of its balance.
+		leader_swap(10_000);
+
+		// The followers swap a tenth of their reducible balances, before the leader.
+		assert_eq!(
+			events(),
+			vec![
+				Event::TradeMirrored {
+					leader: LEADER,
+					follower: 2,
+					amount_in: 1_000,
+					amount_out: amounts_out[0],
+				},
+				Event::TradeMirrored {
+					leader: LEADER,
+					follower: 3,
+					amount_in: 2_000,
+					amount_out: amounts_out[1],
+				},
+				Event::LeaderSwapped {
+					leader: LEADER,
+					path: vec![ASSET, NativeOrWithId::Native],
+					amount_in: 10_000,
+					amount_out: amounts_out[2],
+				},
+			]
+		);
+		assert_eq!(Assets::balance(1, LEADER), 90_000);
+		assert_eq!(Assets::balance(1, 2), 9_001);
+		assert_eq!(Assets::balance(1, 3), 18_001);
+		assert_eq!(Balances::free_balance(2), native_before.0 + amounts_out[0]);
+		assert_eq!(Balances::free_balance(3), native_before.1 + amounts_out[1]);
+	});
+}
+
+#[test]
+fn swap_skips_trades_exceeding_limits() {
+	new_test_ext().execute_with(|| {
+		// At most 5% of the balance per trade.
+		follow(2, limits(5, 5));
+		// A tenth of its balance moves the price by about 9%, more than 5% below the time-weighted
+		// price.
+		follow(LIQUIDITY_PROVIDER, limits(20, 5));
+		// The proxy is removed after following.
+		follow(4, limits(20, 5));
+		assert_ok!(Proxy::remove_proxies(RuntimeOrigin::signed(4)));
+		System::reset_events();
+		let amounts_out = amounts_out(&[10_000]);
+
+		leader_swap(10_000);
+
+		assert_eq!(
+			events(),
+			vec![
+				Event::TradeSkipped {
+					leader: LEADER,
+					follower: 2,
+					reason: SkipReason::PortionExceeded,
+				},
+				Event::TradeSkipped {
+					leader: LEADER,
+					follower: LIQUIDITY_PROVIDER,
+					reason: SkipReason::SlippageExceeded,
+				},
+				Event::TradeSkipped {
+					leader: LEADER,
+					follower: 4,
+					reason: SkipReason::MissingProxy,
+				},
+				Event::LeaderSwapped {
+					leader: LEADER,
+					path: vec![ASSET, NativeOrWithId::Native],
+					amount_in: 10_000,
+					amount_out: amounts_out[0],
+				},
+			]
+		);
+		assert_eq!(Assets::balance(1, 2), 10_001);
+		assert_eq!(Assets::balance(1, LIQUIDITY_PROVIDER), 1_000_000);
+		assert_eq!(Assets::balance(1, 4), 10_001);
+	});
+}
+
+#[test]
+fn swap_prices_trades_at_the_time_weighted_price() {
+	new_test_ext().execute_with(|| {
+		follow(2, limits(20, 5));
+		// The price of asset `1` is pushed down by about 17% within the block.
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			asset_to_native(),
+			100_000,
+			1,
+			LIQUIDITY_PROVIDER,
+			true,
+		));
+		System::reset_events();
+
+		leader_swap(10_000);
+
+		// The follower's quote falls more than 5% short of the price averaged over the window.
+		assert_eq!(
+			events().first(),
+			Some(&Event::TradeSkipped {
+				leader: LEADER,
+				follower: 2,
+				reason: SkipReason::SlippageExceeded,
+			})
+		);
+		assert_eq!(Assets::balance(1, 2), 10_001);
+
+		// Once the window only covers the new price, the trade is mirrored.
+		System::set_block_number(4);
+		let amounts_out = amounts_out(&[1_000]);
+
+		leader_swap(9_000);
+
+		assert_eq!(
+			events().first(),
+			Some(&Event::TradeMirrored {
+				leader: LEADER,
+				follower: 2,
+				amount_in: 1_000,
+				amount_out: amounts_out[0],
+			})
+		);
+	});
+}
+
+#[test]
+fn swap_skips_trades_without_a_known_price() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::fungibles::Mutate;
+		follow(2, limits(20, 5));
+		// A pool of `Native` and asset `2` created in this block.
+		let asset = NativeOrWithId::WithId(2);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 2, LIQUIDITY_PROVIDER, true, 1));
+		assert_ok!(Assets::mint_into(2, &LIQUIDITY_PROVIDER, 2_000_000));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			Box::new(NativeOrWithId::Native),
+			Box::new(asset.clone()),
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			Box::new(NativeOrWithId::Native),
+			Box::new(asset.clone()),
+			1_000_000,
+			1_000_000,
+			1,
+			1,
+			LIQUIDITY_PROVIDER,
+		));
+		System::reset_events();
+
+		assert_ok!(AssetConversionCopyTrading::swap(
+			RuntimeOrigin::signed(LEADER),
+			vec![Box::new(ASSET), Box::new(NativeOrWithId::Native), Box::new(asset)],
+			10_000,
+			1,
+			true,
+		));
+
+		assert_eq!(
+			events().first(),
+			Some(&Event::TradeSkipped {
+				leader: LEADER,
+				follower: 2,
+				reason: SkipReason::PriceUnavailable,
+			})
+		);
+		assert_eq!(Assets::balance(1, 2), 10_001);
+		assert_eq!(Assets::balance(1, LEADER), 90_000);
+	});
+}
+
+#[test]
+fn swap_skips_followers_without_balance() {
+	new_test_ext().execute_with(|| {
+		follow(2, limits(20, 5));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), 1, 3, 10_000));
+		System::reset_events();
+
+		leader_swap(10_000);
+
+		assert_eq!(
+			events().first(),
+			Some(&Event::TradeSkipped {
+				leader: LEADER,
+				follower: 2,
+				reason: SkipReason::InsufficientBalance,
+			})
+		);
+	});
+}
+
+#[test]
+fn swap_fails_for_invalid_input() {
+	new_test_ext().execute_with(|| {
+		follow(2, limits(20, 5));
+
+		assert_noop!(
+			AssetConversionCopyTrading::swap(
+				RuntimeOrigin::signed(LEADER),
+				asset_to_native(),
+				0,
+				1,
+				true
+			),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			AssetConversionCopyTrading::swap(
+				RuntimeOrigin::signed(LEADER),
+				vec![Box::new(ASSET)],
+				10_000,
+				1,
+				true
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			AssetConversionCopyTrading::swap(
+				RuntimeOrigin::signed(LEADER),
+				vec![Box::new(ASSET), Box::new(NativeOrWithId::WithId(2))],
+				10_000,
+				1,
+				true
+			),
+			Error::<Test>::InvalidPath
+		);
+		// The leader's swap failing fails the call, reverting the followers' trades.
+		assert_noop!(
+			AssetConversionCopyTrading::swap(
+				RuntimeOrigin::signed(LEADER),
+				asset_to_native(),
+				10_000,
+				10_000,
+				true
+			),
+			pallet_asset_conversion::Error::<Test>::ProvidedMinimumNotSufficientForSwap
+		);
+	});
+}
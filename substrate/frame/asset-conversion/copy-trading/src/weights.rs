@@ -0,0 +1,176 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_asset_conversion_copy_trading`
+//!
+//! These weights are estimated from the storage accesses of each operation and the weights of
+//! `pallet_asset_conversion`. They should be regenerated with the pallet's benchmarks:
+//!
+//! frame-omni-bencher v1 benchmark pallet
+//! --runtime=target/production/wbuild/kitchensink-runtime/kitchensink_runtime.wasm
+//! --pallet=pallet_asset_conversion_copy_trading --extrinsic=*
+//! --template=substrate/.maintain/frame-weight-template.hbs
+//! --output=substrate/frame/asset-conversion/copy-trading/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+#![allow(dead_code)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_asset_conversion_copy_trading`.
+pub trait WeightInfo {
+	fn follow() -> Weight;
+	fn set_limits() -> Weight;
+	fn unfollow() -> Weight;
+	fn swap(p: u32, ) -> Weight;
+	fn mirror_trade(p: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_asset_conversion_copy_trading` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AssetConversionCopyTrading::SubscriptionCost` (r:1 w:1)
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn follow() -> Weight {
+		Weight::from_parts(55_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	fn set_limits() -> Weight {
+		Weight::from_parts(20_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::SubscriptionCost` (r:1 w:1)
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn unfollow() -> Weight {
+		Weight::from_parts(50_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:12 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `p` is `[2, 4]`.
+	fn swap(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			// Standard Error: 30_000
+			.saturating_add(Weight::from_parts(75_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((10_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 5_200).saturating_mul(p.into()))
+	}
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `p` is `[2, 4]`.
+	fn mirror_trade(p: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			// Standard Error: 30_000
+			.saturating_add(Weight::from_parts(65_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 5_200).saturating_mul(p.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `AssetConversionCopyTrading::SubscriptionCost` (r:1 w:1)
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn follow() -> Weight {
+		Weight::from_parts(55_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	fn set_limits() -> Weight {
+		Weight::from_parts(20_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::SubscriptionCost` (r:1 w:1)
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn unfollow() -> Weight {
+		Weight::from_parts(50_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AssetConversionCopyTrading::Followers` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:12 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `p` is `[2, 4]`.
+	fn swap(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			// Standard Error: 30_000
+			.saturating_add(Weight::from_parts(75_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((10_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 5_200).saturating_mul(p.into()))
+	}
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `p` is `[2, 4]`.
+	fn mirror_trade(p: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			// Standard Error: 30_000
+			.saturating_add(Weight::from_parts(65_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 5_200).saturating_mul(p.into()))
+	}
+}
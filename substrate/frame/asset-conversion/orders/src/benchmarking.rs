@@ -0,0 +1,136 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion Orders pallet benchmarking.
+
+use super::*;
+use crate::Pallet as AssetConversionOrders;
+use frame_benchmarking::{v2::*, whitelisted_caller};
+use frame_support::traits::{Consideration, Get, Hooks};
+use frame_system::RawOrigin;
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AccountId, AssetKind, Balance> {
+	/// Prepares a swap of `path[0]` for `path[last]` by `who`, returning the `path` and an
+	/// amount of `path[0]` that `who` can swap while staying alive.
+	///
+	/// The pools of the `path` must exist and have enough liquidity for the swap. The `path`
+	/// must be of [`Config::MaxPathLength`] assets, for the swap to be the worst case.
+	fn setup_swap(who: &AccountId) -> (Vec<AssetKind>, Balance);
+}
+
+fn setup_order_owner<T: Config>() -> (T::AccountId, Vec<Box<T::AssetKind>>, T::Balance) {
+	frame_system::Pallet::<T>::set_block_number(1u32.into());
+	let caller: T::AccountId = whitelisted_caller();
+	let (path, amount) = T::BenchmarkHelper::setup_swap(&caller);
+	assert_eq!(path.len() as u32, T::MaxPathLength::get(), "the path must be the longest allowed");
+	T::Consideration::ensure_successful(&caller, AssetConversionOrders::<T>::order_footprint());
+	(caller, path.into_iter().map(Box::new).collect(), amount)
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn place_limit_order() {
+		let (caller, path, amount) = setup_order_owner::<T>();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			path,
+			amount,
+			1u32.into(),
+			Some(DispatchTime::After(100u32.into())),
+		);
+
+		assert!(Orders::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn place_recurring_order() {
+		let (caller, path, amount) = setup_order_owner::<T>();
+		let executions = 10u32;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			path,
+			amount / executions.into(),
+			1u32.into(),
+			10u32.into(),
+			executions,
+			Some(DispatchTime::After(100u32.into())),
+		);
+
+		assert!(Orders::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn cancel_order() {
+		let (caller, path, amount) = setup_order_owner::<T>();
+		AssetConversionOrders::<T>::place_limit_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			path,
+			amount,
+			1u32.into(),
+			None,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0);
+
+		assert_last_event::<T>(Event::OrderCancelled { order_id: 0 }.into());
+	}
+
+	#[benchmark]
+	fn execute_order() {
+		let (caller, path, amount) = setup_order_owner::<T>();
+		AssetConversionOrders::<T>::place_limit_order(
+			RawOrigin::Signed(caller.clone()).into(),
+			path,
+			amount,
+			1u32.into(),
+			None,
+		)
+		.unwrap();
+		let now = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			AssetConversionOrders::<T>::on_idle(
+				now,
+				T::WeightInfo::execute_order()
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+			);
+		}
+
+		assert_last_event::<T>(Event::OrderCompleted { order_id: 0 }.into());
+	}
+
+	impl_benchmark_test_suite!(
+		AssetConversionOrders,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,583 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Conversion Orders Pallet
+//!
+//! Limit and recurring swap orders for [`pallet_asset_conversion`] pools.
+//!
+//! ## Overview
+//!
+//! This pallet allows accounts to:
+//!
+//! - [place a limit order](`Pallet::place_limit_order`), swapping an amount of the first asset of a
+//!   path once it yields at least a given amount of the last asset of the path;
+//! - [place a recurring order](`Pallet::place_recurring_order`), swapping an amount of the first
+//!   asset of a path every given number of blocks, a given number of times, optionally bounded by a
+//!   minimum amount received per execution;
+//! - [cancel an order](`Pallet::cancel_order`).
+//!
+//! The amount sold by an order over all its executions is frozen in the owner's account with
+//! [`FreezeReason::Order`] until it is swapped, or until the order is cancelled or expires. The
+//! storage of an order is paid for by its owner with [`Config::Consideration`].
+//!
+//! Orders are executed in [`on_idle`](`frame_support::traits::Hooks::on_idle`), as far as the
+//! remaining weight of the block allows. Orders are visited in a round-robin manner, resuming
+//! from where the previous block stopped. A visited order that is due is only swapped if the
+//! price quoted by the pools of its path meets its limit; otherwise, or if the swap fails, it is
+//! retried on a later visit. Failed swaps are reported with [`Event::OrderExecutionFailed`].
+//!
+//! Note that orders are only executed in blocks with spare weight, so their execution is best
+//! effort: an order is not guaranteed to be executed in the block its price is reached.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungibles::{Inspect, MutateFreeze},
+		schedule::DispatchTime,
+		tokens::{Balance, Fortitude, Preservation},
+		Consideration, Defensive, Footprint,
+	},
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_asset_conversion::{QuotePrice, Swap};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{EnsureAdd, EnsureMul, Get, Saturating, Zero},
+	DispatchError, SaturatedConversion, TokenError,
+};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
+
+/// Unique identifier of an order.
+pub type OrderId = u32;
+
+/// The kind of an order.
+#[derive(
+	Debug, Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, MaxEncodedLen, TypeInfo,
+)]
+pub enum OrderKind<BlockNumber> {
+	/// The order is executed once, as soon as its price is reached.
+	Limit,
+	/// The order is executed every `period` blocks, until no execution remains.
+	Recurring {
+		/// The number of blocks between two executions.
+		period: BlockNumber,
+		/// The number of executions left, including the next one.
+		remaining: u32,
+		/// The block from which the next execution is due.
+		next_execution: BlockNumber,
+	},
+}
+
+/// A swap order placed by an account.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Order<T: Config> {
+	/// The account that placed the order, whose funds are swapped.
+	pub owner: T::AccountId,
+	/// The swap path, from the asset sold to the asset bought.
+	pub path: BoundedVec<T::AssetKind, T::MaxPathLength>,
+	/// The amount of `path[0]` swapped by each execution.
+	pub amount_in: T::Balance,
+	/// The minimum amount of `path[last]` received by each execution. Zero sets no limit.
+	pub amount_out_min: T::Balance,
+	/// The kind of the order.
+	pub kind: OrderKind<BlockNumberFor<T>>,
+	/// The block from which the order expires, if any.
+	pub expiry: Option<BlockNumberFor<T>>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::BadOrigin;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet freezing funds.
+	#[pallet::composite_enum]
+	pub enum FreezeReason {
+		/// Funds are reserved for the execution of an order.
+		#[codec(index = 0)]
+		Order,
+	}
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Cost associated with storing an order on-chain.
+		#[codec(index = 0)]
+		OrderDeposit,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The type in which the assets for swapping are measured.
+		type Balance: Balance;
+
+		/// Type of asset class, sourced from [`Config::Swap`], swapped by the orders.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The swap and price quoting implementation, typically [`pallet_asset_conversion`].
+		type Swap: Swap<Self::AccountId, Balance = Self::Balance, AssetKind = Self::AssetKind>
+			+ QuotePrice<Balance = Self::Balance, AssetKind = Self::AssetKind>;
+
+		/// Freezer for the assets sold by the orders.
+		type AssetsFreezer: MutateFreeze<
+			Self::AccountId,
+			Id = Self::RuntimeFreezeReason,
+			AssetId = Self::AssetKind,
+			Balance = Self::Balance,
+		>;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason>;
+
+		/// Means for associating a cost with the on-chain storage of an order, which is incurred
+		/// by the owner of the order.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+
+		/// The maximum number of assets in the swap path of an order.
+		///
+		/// Paths are also bounded by the [`Swap::max_path_len`] of [`Config::Swap`].
+		#[pallet::constant]
+		type MaxPathLength: Get<u32>;
+
+		/// Weight information for extrinsics and order execution in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::AssetKind, Self::Balance>;
+	}
+
+	/// The orders waiting for execution.
+	#[pallet::storage]
+	pub type Orders<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, Order<T>>;
+
+	/// The cost associated with storing an order, incurred by its owner.
+	///
+	/// This storage is separate from [`Orders`] to maintain the order's `MaxEncodedLen` and
+	/// `TypeInfo` independent of the [`Config::Consideration`] implementation.
+	#[pallet::storage]
+	pub type OrderCost<T: Config> = StorageMap<_, Blake2_128Concat, OrderId, T::Consideration>;
+
+	/// The id of the next order placed.
+	#[pallet::storage]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The last order visited in `on_idle`, after which the next visits resume.
+	///
+	/// `None` when the previous visits reached the end of [`Orders`].
+	#[pallet::storage]
+	pub type ExecutionCursor<T: Config> = StorageValue<_, OrderId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An order was placed.
+		OrderPlaced {
+			/// The order's id.
+			order_id: OrderId,
+			/// The account that placed the order.
+			owner: T::AccountId,
+			/// The swap path of the order.
+			path: BoundedVec<T::AssetKind, T::MaxPathLength>,
+			/// The amount of `path[0]` swapped by each execution.
+			amount_in: T::Balance,
+			/// The minimum amount of `path[last]` received by each execution.
+			amount_out_min: T::Balance,
+			/// The kind of the order.
+			kind: OrderKind<BlockNumberFor<T>>,
+			/// The block from which the order expires, if any.
+			expiry: Option<BlockNumberFor<T>>,
+		},
+		/// An order was executed.
+		OrderExecuted {
+			/// The order's id.
+			order_id: OrderId,
+			/// The owner of the order.
+			owner: T::AccountId,
+			/// The amount of `path[0]` swapped.
+			amount_in: T::Balance,
+			/// The amount of `path[last]` received.
+			amount_out: T::Balance,
+		},
+		/// The execution of an order failed, for a reason other than its price not being reached.
+		/// The order is retried on a later visit.
+		OrderExecutionFailed {
+			/// The order's id.
+			order_id: OrderId,
+			/// The error of the execution.
+			error: DispatchError,
+		},
+		/// An order was removed after its last execution.
+		OrderCompleted {
+			/// The order's id.
+			order_id: OrderId,
+		},
+		/// An order was cancelled by its owner.
+		OrderCancelled {
+			/// The order's id.
+			order_id: OrderId,
+		},
+		/// An order was removed after reaching its expiry.
+		OrderExpired {
+			/// The order's id.
+			order_id: OrderId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The swap path is shorter than two assets or longer than allowed.
+		InvalidPath,
+		/// The amount to swap, or the minimum amount to receive by a limit order, is zero.
+		ZeroAmount,
+		/// The period or the number of executions of a recurring order is zero.
+		InvalidSchedule,
+		/// The expiry of the order is not in the future.
+		ExpiryMustBeInTheFuture,
+		/// The order does not exist.
+		UnknownOrder,
+		/// The price quoted for the order does not meet its limit.
+		PriceNotReached,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let base_weight = T::DbWeight::get().reads_writes(1, 1);
+			let order_weight = T::WeightInfo::execute_order();
+			let max_orders = remaining_weight
+				.checked_sub(&base_weight)
+				.and_then(|weight| weight.checked_div_per_component(&order_weight))
+				.unwrap_or_default();
+			if max_orders.is_zero() {
+				return Weight::zero();
+			}
+
+			let orders = match ExecutionCursor::<T>::get() {
+				Some(last) => Orders::<T>::iter_from(Orders::<T>::hashed_key_for(last)),
+				None => Orders::<T>::iter(),
+			}
+			.take(max_orders.saturated_into())
+			.collect::<Vec<_>>();
+
+			match orders.last() {
+				Some((last, _)) if orders.len() as u64 == max_orders =>
+					ExecutionCursor::<T>::put(last),
+				_ => ExecutionCursor::<T>::kill(),
+			}
+
+			let weight =
+				base_weight.saturating_add(order_weight.saturating_mul(orders.len() as u64));
+			for (order_id, order) in orders {
+				Self::process_order(order_id, order, now);
+			}
+			weight
+		}
+	}
+
+	#[pallet::call(weight(<T as Config>::WeightInfo))]
+	impl<T: Config> Pallet<T> {
+		/// Place an order swapping `amount_in` of `path[0]` for at least `amount_out_min` of
+		/// `path[last]`, executed once the price quoted by the pools of the path allows it.
+		///
+		/// `amount_in` is frozen in the caller's account until the order is executed, cancelled
+		/// or expires. The order expires at `expiry`, if any.
+		#[pallet::call_index(0)]
+		pub fn place_limit_order(
+			origin: OriginFor<T>,
+			path: Vec<Box<T::AssetKind>>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+			expiry: Option<DispatchTime<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!amount_out_min.is_zero(), Error::<T>::ZeroAmount);
+
+			Self::do_place_order(owner, path, amount_in, amount_out_min, OrderKind::Limit, expiry)
+		}
+
+		/// Place an order swapping `amount_in` of `path[0]` for `path[last]` every `period`
+		/// blocks, `executions` times, starting with the current block.
+		///
+		/// Each execution waits for at least `amount_out_min` of `path[last]` to be received, if
+		/// non-zero. The amount of all executions is frozen in the caller's account and released
+		/// as the executions happen, or when the order is cancelled or expires. The order expires
+		/// at `expiry`, if any.
+		#[pallet::call_index(1)]
+		pub fn place_recurring_order(
+			origin: OriginFor<T>,
+			path: Vec<Box<T::AssetKind>>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+			period: BlockNumberFor<T>,
+			executions: u32,
+			expiry: Option<DispatchTime<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!period.is_zero() && !executions.is_zero(), Error::<T>::InvalidSchedule);
+
+			let kind = OrderKind::Recurring {
+				period,
+				remaining: executions,
+				next_execution: frame_system::Pallet::<T>::block_number(),
+			};
+			Self::do_place_order(owner, path, amount_in, amount_out_min, kind, expiry)
+		}
+
+		/// Cancel an order of the caller, releasing its remaining frozen funds and its deposit.
+		#[pallet::call_index(2)]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+			ensure!(order.owner == who, BadOrigin);
+
+			Self::remove_order(order_id, &order, Self::frozen_amount(&order)?)?;
+
+			Self::deposit_event(Event::OrderCancelled { order_id });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The storage footprint of an order.
+		pub fn order_footprint() -> Footprint {
+			Footprint::from_mel::<(OrderId, Order<T>)>()
+		}
+
+		/// The amount of `path[0]` frozen for the remaining executions of `order`.
+		pub fn frozen_amount(order: &Order<T>) -> Result<T::Balance, DispatchError> {
+			match order.kind {
+				OrderKind::Limit => Ok(order.amount_in),
+				OrderKind::Recurring { remaining, .. } =>
+					Ok(order.amount_in.ensure_mul(remaining.into())?),
+			}
+		}
+
+		fn do_place_order(
+			owner: T::AccountId,
+			path: Vec<Box<T::AssetKind>>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+			kind: OrderKind<BlockNumberFor<T>>,
+			expiry: Option<DispatchTime<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				path.len() >= 2 && path.len() as u32 <= T::Swap::max_path_len(),
+				Error::<T>::InvalidPath
+			);
+			let path: BoundedVec<_, T::MaxPathLength> = path
+				.into_iter()
+				.map(|asset| *asset)
+				.collect::<Vec<_>>()
+				.try_into()
+				.map_err(|_| Error::<T>::InvalidPath)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let expiry = expiry.map(|expiry| expiry.evaluate(now));
+			ensure!(
+				expiry.map_or(true, |expiry| expiry > now),
+				Error::<T>::ExpiryMustBeInTheFuture
+			);
+
+			let order = Order::<T> { owner, path, amount_in, amount_out_min, kind, expiry };
+
+			// Freezes don't require the frozen funds to be available, so the order must be backed
+			// by the funds not already frozen by other orders.
+			let frozen = Self::frozen_amount(&order)?;
+			let reducible = T::AssetsFreezer::reducible_balance(
+				order.path[0].clone(),
+				&order.owner,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			);
+			ensure!(reducible >= frozen, TokenError::FundsUnavailable);
+			T::AssetsFreezer::increase_frozen(
+				order.path[0].clone(),
+				&FreezeReason::Order.into(),
+				&order.owner,
+				frozen,
+			)?;
+
+			let order_id = NextOrderId::<T>::get();
+			let cost = T::Consideration::new(&order.owner, Self::order_footprint())?;
+			OrderCost::<T>::insert(order_id, cost);
+			NextOrderId::<T>::put(order_id.ensure_add(1)?);
+
+			Self::deposit_event(Event::OrderPlaced {
+				order_id,
+				owner: order.owner.clone(),
+				path: order.path.clone(),
+				amount_in,
+				amount_out_min,
+				kind: order.kind.clone(),
+				expiry,
+			});
+			Orders::<T>::insert(order_id, order);
+
+			Ok(())
+		}
+
+		/// Expire, execute or skip `order`, depending on its state at block `now`.
+		pub(crate) fn process_order(
+			order_id: OrderId,
+			mut order: Order<T>,
+			now: BlockNumberFor<T>,
+		) {
+			if order.expiry.is_some_and(|expiry| expiry <= now) {
+				let _ = Self::frozen_amount(&order)
+					.and_then(|frozen| Self::remove_order(order_id, &order, frozen))
+					.defensive();
+				Self::deposit_event(Event::OrderExpired { order_id });
+				return;
+			}
+
+			if let OrderKind::Recurring { next_execution, .. } = order.kind {
+				if next_execution > now {
+					return;
+				}
+			}
+
+			// The order is retried on a later visit if the price is not reached yet or the swap
+			// failed.
+			let amount_out = match Self::execute_order(&order) {
+				Ok(amount_out) => amount_out,
+				Err(error) => {
+					if error != Error::<T>::PriceNotReached.into() {
+						Self::deposit_event(Event::OrderExecutionFailed { order_id, error });
+					}
+					return;
+				},
+			};
+
+			Self::deposit_event(Event::OrderExecuted {
+				order_id,
+				owner: order.owner.clone(),
+				amount_in: order.amount_in,
+				amount_out,
+			});
+
+			match &mut order.kind {
+				OrderKind::Recurring { period, remaining, next_execution } if *remaining > 1 => {
+					remaining.saturating_dec();
+					*next_execution = now.saturating_add(*period);
+					Orders::<T>::insert(order_id, order);
+				},
+				_ => {
+					let _ = Self::remove_order(order_id, &order, Zero::zero()).defensive();
+					Self::deposit_event(Event::OrderCompleted { order_id });
+				},
+			}
+		}
+
+		/// Swap the `amount_in` of `order`, if the price quoted for it meets its limit.
+		///
+		/// Returns the amount of `path[last]` received.
+		fn execute_order(order: &Order<T>) -> Result<T::Balance, DispatchError> {
+			let path = order.path.to_vec();
+			let quote = path
+				.windows(2)
+				.try_fold(order.amount_in, |amount, pair| {
+					T::Swap::quote_price_exact_tokens_for_tokens(
+						pair[0].clone(),
+						pair[1].clone(),
+						amount,
+						true,
+					)
+				})
+				.ok_or(Error::<T>::PriceNotReached)?;
+			ensure!(quote >= order.amount_out_min, Error::<T>::PriceNotReached);
+
+			let amount_out_min = (!order.amount_out_min.is_zero()).then_some(order.amount_out_min);
+			with_storage_layer(|| {
+				T::AssetsFreezer::decrease_frozen(
+					path[0].clone(),
+					&FreezeReason::Order.into(),
+					&order.owner,
+					order.amount_in,
+				)?;
+				T::Swap::swap_exact_tokens_for_tokens(
+					order.owner.clone(),
+					path,
+					order.amount_in,
+					amount_out_min,
+					order.owner.clone(),
+					true,
+				)
+			})
+		}
+
+		/// Remove `order`, thawing `frozen` of its `path[0]` and dropping its storage cost.
+		fn remove_order(order_id: OrderId, order: &Order<T>, frozen: T::Balance) -> DispatchResult {
+			if !frozen.is_zero() {
+				T::AssetsFreezer::decrease_frozen(
+					order.path[0].clone(),
+					&FreezeReason::Order.into(),
+					&order.owner,
+					frozen,
+				)?;
+			}
+			if let Some(cost) = OrderCost::<T>::take(order_id) {
+				cost.drop(&order.owner)?;
+			}
+			Orders::<T>::remove(order_id);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,504 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold, NativeOrWithId},
+		fungibles::{Inspect, InspectFreeze},
+		tokens::{Fortitude, Preservation},
+		Get, Hooks,
+	},
+	weights::{RuntimeDbWeight, Weight},
+};
+use sp_runtime::{traits::BadOrigin, TokenError};
+
+const ASSET: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+
+#[allow(clippy::vec_box)]
+fn asset_to_native() -> Vec<Box<NativeOrWithId<u32>>> {
+	vec![Box::new(ASSET), Box::new(NativeOrWithId::Native)]
+}
+
+fn frozen(who: u128) -> u128 {
+	NativeAndAssetsFreezer::balance_frozen(ASSET, &FreezeReason::Order.into(), &who)
+}
+
+fn deposit(who: u128) -> u128 {
+	Balances::balance_on_hold(&OrderHoldReason::get(), &who)
+}
+
+fn quote(amount: u128) -> u128 {
+	AssetConversion::quote_price_exact_tokens_for_tokens(
+		ASSET,
+		NativeOrWithId::Native,
+		amount,
+		true,
+	)
+	.unwrap()
+}
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			RuntimeEvent::AssetConversionOrders(inner) => Some(inner),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	result
+}
+
+fn run_to_block(n: u64) {
+	System::set_block_number(n);
+	idle(Weight::MAX);
+}
+
+fn idle(weight: Weight) -> Weight {
+	AssetConversionOrders::on_idle(System::block_number(), weight)
+}
+
+/// Buys asset `1` with `Native` from the pool, raising the price of asset `1`.
+fn raise_asset_price() {
+	assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+		RuntimeOrigin::signed(2),
+		vec![Box::new(NativeOrWithId::Native), Box::new(ASSET)],
+		2_000,
+		1,
+		2,
+		false,
+	));
+}
+
+#[test]
+fn place_limit_order_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			120,
+			Some(DispatchTime::After(10)),
+		));
+
+		let path: BoundedVec<_, _> = vec![ASSET, NativeOrWithId::Native].try_into().unwrap();
+		assert_eq!(
+			Orders::<Test>::get(0),
+			Some(Order {
+				owner: 1,
+				path: path.clone(),
+				amount_in: 100,
+				amount_out_min: 120,
+				kind: OrderKind::Limit,
+				expiry: Some(11),
+			})
+		);
+		assert_eq!(NextOrderId::<Test>::get(), 1);
+		assert_eq!(
+			events(),
+			[Event::<Test>::OrderPlaced {
+				order_id: 0,
+				owner: 1,
+				path,
+				amount_in: 100,
+				amount_out_min: 120,
+				kind: OrderKind::Limit,
+				expiry: Some(11),
+			}]
+		);
+
+		// The amount to swap is frozen and the deposit is held.
+		assert_eq!(frozen(1), 100);
+		assert_eq!(deposit(1), 100);
+		assert_eq!(
+			<Assets as Inspect<u128>>::reducible_balance(
+				1,
+				&1,
+				Preservation::Expendable,
+				Fortitude::Polite
+			),
+			900
+		);
+	});
+}
+
+#[test]
+fn limit_order_executes_once_price_is_reached() {
+	new_test_ext().execute_with(|| {
+		assert!(quote(100) < 120);
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			120,
+			None,
+		));
+		events();
+
+		// The price is not reached, the order waits.
+		run_to_block(2);
+		assert!(Orders::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 100);
+		assert!(events().is_empty());
+
+		raise_asset_price();
+		events();
+		let amount_out = quote(100);
+		assert!(amount_out >= 120);
+		let native_balance = Balances::free_balance(1);
+
+		run_to_block(3);
+		assert_eq!(
+			events(),
+			[
+				Event::<Test>::OrderExecuted { order_id: 0, owner: 1, amount_in: 100, amount_out },
+				Event::<Test>::OrderCompleted { order_id: 0 },
+			]
+		);
+		assert!(!Orders::<Test>::contains_key(0));
+		assert!(!OrderCost::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(Assets::balance(1, &1), 900);
+		// The swapped amount is received and the deposit is released.
+		assert_eq!(Balances::free_balance(1), native_balance + amount_out + 100);
+	});
+}
+
+#[test]
+fn recurring_order_executes_every_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_recurring_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			0,
+			5,
+			3,
+			None,
+		));
+		assert_eq!(frozen(1), 300);
+		events();
+
+		// The first execution is due at once.
+		idle(Weight::MAX);
+		assert!(matches!(events()[..], [Event::<Test>::OrderExecuted { order_id: 0, .. }]));
+		assert_eq!(frozen(1), 200);
+		assert_eq!(
+			Orders::<Test>::get(0).unwrap().kind,
+			OrderKind::Recurring { period: 5, remaining: 2, next_execution: 6 }
+		);
+
+		run_to_block(5);
+		assert!(events().is_empty());
+		assert_eq!(frozen(1), 200);
+
+		run_to_block(6);
+		assert!(matches!(events()[..], [Event::<Test>::OrderExecuted { order_id: 0, .. }]));
+		assert_eq!(frozen(1), 100);
+		assert_eq!(
+			Orders::<Test>::get(0).unwrap().kind,
+			OrderKind::Recurring { period: 5, remaining: 1, next_execution: 11 }
+		);
+
+		run_to_block(11);
+		assert!(matches!(
+			events()[..],
+			[
+				Event::<Test>::OrderExecuted { order_id: 0, .. },
+				Event::<Test>::OrderCompleted { order_id: 0 }
+			]
+		));
+		assert!(!Orders::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(Assets::balance(1, &1), 700);
+	});
+}
+
+#[test]
+fn failed_execution_is_reported_and_retried() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			1,
+			None,
+		));
+		events();
+
+		// The price is reached but the owner's account is frozen, so the swap fails.
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 1, 1));
+		run_to_block(2);
+		assert!(matches!(events()[..], [Event::<Test>::OrderExecutionFailed { order_id: 0, .. }]));
+		assert!(Orders::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 100);
+
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 1, 1));
+		run_to_block(3);
+		assert!(matches!(
+			events()[..],
+			[
+				Event::<Test>::OrderExecuted { order_id: 0, .. },
+				Event::<Test>::OrderCompleted { order_id: 0 }
+			]
+		));
+	});
+}
+
+#[test]
+fn order_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			1_000,
+			Some(DispatchTime::At(10)),
+		));
+		events();
+
+		run_to_block(9);
+		assert!(Orders::<Test>::contains_key(0));
+		assert!(events().is_empty());
+
+		run_to_block(10);
+		assert_eq!(events(), [Event::<Test>::OrderExpired { order_id: 0 }]);
+		assert!(!Orders::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(Assets::balance(1, &1), 1_000);
+	});
+}
+
+#[test]
+fn cancel_order_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_recurring_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			100,
+			0,
+			5,
+			3,
+			None,
+		));
+		idle(Weight::MAX);
+		assert_eq!(frozen(1), 200);
+		events();
+
+		assert_noop!(AssetConversionOrders::cancel_order(RuntimeOrigin::signed(2), 0), BadOrigin);
+		assert_noop!(
+			AssetConversionOrders::cancel_order(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::UnknownOrder
+		);
+
+		assert_ok!(AssetConversionOrders::cancel_order(RuntimeOrigin::signed(1), 0));
+		assert_eq!(events(), [Event::<Test>::OrderCancelled { order_id: 0 }]);
+		assert!(!Orders::<Test>::contains_key(0));
+		assert!(!OrderCost::<Test>::contains_key(0));
+		assert_eq!(frozen(1), 0);
+		assert_eq!(deposit(1), 0);
+		assert_eq!(Assets::balance(1, &1), 900);
+
+		assert_noop!(
+			AssetConversionOrders::cancel_order(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::UnknownOrder
+		);
+	});
+}
+
+#[test]
+fn place_order_fails_for_invalid_input() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(1);
+
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				origin.clone(),
+				vec![Box::new(ASSET)],
+				100,
+				1,
+				None
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				origin.clone(),
+				vec![Box::new(ASSET); 5],
+				100,
+				1,
+				None
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(origin.clone(), asset_to_native(), 0, 1, None),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				origin.clone(),
+				asset_to_native(),
+				100,
+				0,
+				None
+			),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			AssetConversionOrders::place_recurring_order(
+				origin.clone(),
+				asset_to_native(),
+				100,
+				0,
+				0,
+				3,
+				None
+			),
+			Error::<Test>::InvalidSchedule
+		);
+		assert_noop!(
+			AssetConversionOrders::place_recurring_order(
+				origin.clone(),
+				asset_to_native(),
+				100,
+				0,
+				5,
+				0,
+				None
+			),
+			Error::<Test>::InvalidSchedule
+		);
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				origin.clone(),
+				asset_to_native(),
+				100,
+				1,
+				Some(DispatchTime::At(1))
+			),
+			Error::<Test>::ExpiryMustBeInTheFuture
+		);
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				origin.clone(),
+				asset_to_native(),
+				1_001,
+				1,
+				None
+			),
+			TokenError::FundsUnavailable
+		);
+		assert_noop!(
+			AssetConversionOrders::place_recurring_order(
+				origin,
+				asset_to_native(),
+				400,
+				0,
+				5,
+				3,
+				None
+			),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn place_order_fails_if_not_backed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			600,
+			1,
+			None
+		));
+
+		// The funds frozen by the first order cannot back another one.
+		assert_noop!(
+			AssetConversionOrders::place_limit_order(
+				RuntimeOrigin::signed(1),
+				asset_to_native(),
+				600,
+				1,
+				None
+			),
+			TokenError::FundsUnavailable
+		);
+		assert_noop!(
+			AssetConversionOrders::place_recurring_order(
+				RuntimeOrigin::signed(1),
+				asset_to_native(),
+				200,
+				0,
+				5,
+				3,
+				None
+			),
+			TokenError::FundsUnavailable
+		);
+		assert_ok!(AssetConversionOrders::place_limit_order(
+			RuntimeOrigin::signed(1),
+			asset_to_native(),
+			399,
+			1,
+			None
+		));
+		assert_eq!(frozen(1), 999);
+	});
+}
+
+#[test]
+fn on_idle_executes_orders_round_robin_within_weight() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(AssetConversionOrders::place_limit_order(
+				RuntimeOrigin::signed(1),
+				asset_to_native(),
+				100,
+				1,
+				None,
+			));
+		}
+		assert_eq!(frozen(1), 300);
+
+		// Not enough weight for any order.
+		let base_weight = <<Test as frame_system::Config>::DbWeight as Get<RuntimeDbWeight>>::get()
+			.reads_writes(1, 1);
+		assert_eq!(idle(base_weight), Weight::zero());
+		assert_eq!(Orders::<Test>::iter().count(), 3);
+
+		// Each call visits a single order, resuming after the previous one.
+		let weight = base_weight.saturating_add(<() as WeightInfo>::execute_order());
+		for remaining in (0..3).rev() {
+			assert_eq!(idle(weight), weight);
+			assert_eq!(Orders::<Test>::iter().count(), remaining);
+			assert!(ExecutionCursor::<Test>::get().is_some());
+		}
+		assert_eq!(frozen(1), 0);
+
+		// The end of the orders is reached.
+		idle(weight);
+		assert_eq!(ExecutionCursor::<Test>::get(), None);
+	});
+}
@@ -0,0 +1,163 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_asset_conversion_orders`
+//!
+//! These weights are estimated from the storage accesses of each operation and the weights of
+//! `pallet_asset_conversion`, `execute_order` for a swap through the three pools of a path of
+//! `MaxPathLength` (four) assets, the worst case set up by its benchmark. They have not been
+//! measured yet and should be regenerated with the pallet's benchmarks:
+//!
+//! frame-omni-bencher v1 benchmark pallet
+//! --runtime=target/production/wbuild/kitchensink-runtime/kitchensink_runtime.wasm
+//! --pallet=pallet_asset_conversion_orders --extrinsic=*
+//! --template=substrate/.maintain/frame-weight-template.hbs
+//! --output=substrate/frame/asset-conversion/orders/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+#![allow(dead_code)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_asset_conversion_orders`.
+pub trait WeightInfo {
+	fn place_limit_order() -> Weight;
+	fn place_recurring_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn execute_order() -> Weight;
+}
+
+/// Weights for `pallet_asset_conversion_orders` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:0 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	fn place_limit_order() -> Weight {
+		Weight::from_parts(70_000_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:0 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	fn place_recurring_order() -> Weight {
+		Weight::from_parts(70_000_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(65_000_000, 6360)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn execute_order() -> Weight {
+		Weight::from_parts(260_000_000, 21_000)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(25_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:0 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	fn place_limit_order() -> Weight {
+		Weight::from_parts(70_000_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:0 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	fn place_recurring_order() -> Weight {
+		Weight::from_parts(70_000_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:0)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn cancel_order() -> Weight {
+		Weight::from_parts(65_000_000, 6360)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetsFreezer::Freezes` (r:1 w:1)
+	/// Storage: `AssetsFreezer::FrozenBalances` (r:1 w:1)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `AssetConversionOrders::OrderCost` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn execute_order() -> Weight {
+		Weight::from_parts(260_000_000, 21_000)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(25_u64))
+	}
+}
@@ -410,5 +410,36 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn update_price_oracle() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+
+		create_fee_asset::<T>(&caller);
+		let (_, liquidity1, liquidity2) = create_asset_and_pool::<T>(&caller, &asset1, &asset2);
+		assert_ok!(AssetConversion::<T>::add_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			liquidity1,
+			liquidity2,
+			T::Balance::one(),
+			T::Balance::zero(),
+			caller.clone(),
+		));
+
+		// The worst case is the first change of the pool in a block, which stores an observation.
+		let now = frame_system::Pallet::<T>::block_number() + One::one();
+		frame_system::Pallet::<T>::set_block_number(now);
+
+		#[block]
+		{
+			AssetConversion::<T>::update_price_oracle(&asset1, &asset2);
+		}
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_eq!(PriceOracles::<T>::get(&pool_id).map(|oracle| oracle.latest.block), Some(now));
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
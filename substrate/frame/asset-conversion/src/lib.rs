@@ -34,6 +34,8 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [query a time-weighted average price](`AssetConversionApi::quote_price_time_weighted`) via a
+//!    runtime api endpoint, which unlike the spot price can't be moved within a single block.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -88,13 +90,16 @@ use frame_support::{
 	},
 	PalletId,
 };
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding, FixedPointNumber, FixedU128, Rounding,
+};
 use sp_core::Get;
 use sp_runtime::{
 	traits::{
 		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, IntegerSquareRoot, MaybeDisplay,
 		One, TrailingZeroInput, Zero,
 	},
-	DispatchError, Saturating, TokenError, TransactionOutcome,
+	DispatchError, SaturatedConversion, Saturating, TokenError, TransactionOutcome,
 };
 
 #[frame_support::pallet]
@@ -186,6 +191,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The max number of price observations kept for each pool, which bounds the window
+		/// time-weighted average prices can be queried for. Zero disables the price oracle.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -209,6 +219,30 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+	/// Map from `PoolId` to the state of the pool's price oracle, updated whenever the pool's
+	/// reserves change.
+	#[pallet::storage]
+	pub type PriceOracles<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		PriceOracle<T::AssetKind, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Ring buffer of the price observations of each pool, of [`Config::MaxPriceObservations`]
+	/// slots. At most one observation is stored per block.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Twox64Concat,
+		u32,
+		PriceObservation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	// Pallet's events.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -438,7 +472,9 @@ pub mod pallet {
 		/// Once liquidity is added, someone may successfully call
 		/// [`Pallet::swap_exact_tokens_for_tokens`].
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::add_liquidity().saturating_add(Pallet::<T>::price_oracle_weight(1))
+		)]
 		pub fn add_liquidity(
 			origin: OriginFor<T>,
 			asset1: Box<T::AssetKind>,
@@ -505,8 +541,8 @@ pub mod pallet {
 				Error::<T>::AmountTwoLessThanMinimal
 			);
 
-			T::Assets::transfer(*asset1, &sender, &pool_account, amount1, Preserve)?;
-			T::Assets::transfer(*asset2, &sender, &pool_account, amount2, Preserve)?;
+			T::Assets::transfer(*asset1.clone(), &sender, &pool_account, amount1, Preserve)?;
+			T::Assets::transfer(*asset2.clone(), &sender, &pool_account, amount2, Preserve)?;
 
 			let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
 
@@ -530,6 +566,7 @@ pub mod pallet {
 			);
 
 			T::PoolAssets::mint_into(pool.lp_token.clone(), &mint_to, lp_token_amount)?;
+			Self::update_price_oracle(&asset1, &asset2);
 
 			Self::deposit_event(Event::LiquidityAdded {
 				who: sender,
@@ -548,7 +585,9 @@ pub mod pallet {
 		/// burned in the process. With the usage of `amount1_min_receive`/`amount2_min_receive`
 		/// it's possible to control the min amount of returned tokens you're happy with.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		#[pallet::weight(
+			T::WeightInfo::remove_liquidity().saturating_add(Pallet::<T>::price_oracle_weight(1))
+		)]
 		pub fn remove_liquidity(
 			origin: OriginFor<T>,
 			asset1: Box<T::AssetKind>,
@@ -608,8 +647,9 @@ pub mod pallet {
 				Polite,
 			)?;
 
-			T::Assets::transfer(*asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
-			T::Assets::transfer(*asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
+			T::Assets::transfer(*asset1.clone(), &pool_account, &withdraw_to, amount1, Expendable)?;
+			T::Assets::transfer(*asset2.clone(), &pool_account, &withdraw_to, amount2, Expendable)?;
+			Self::update_price_oracle(&asset1, &asset2);
 
 			Self::deposit_event(Event::LiquidityRemoved {
 				who: sender,
@@ -632,7 +672,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32).saturating_add(
+				Pallet::<T>::price_oracle_weight(path.len().saturating_sub(1) as u32)
+			)
+		)]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -660,7 +704,11 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32).saturating_add(
+				Pallet::<T>::price_oracle_weight(path.len().saturating_sub(1) as u32)
+			)
+		)]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -949,6 +997,10 @@ pub mod pallet {
 		/// Note: It's assumed that the provided `path` is valid and `credit_in` corresponds to the
 		/// first asset in the `path`.
 		///
+		/// Updates the price oracle of every pool of the `path`. Since the [`Swap`] and
+		/// [`SwapCredit`] implementations end up here, their callers must account for
+		/// [`Self::price_oracle_weight`] of `path.len() - 1` pools on top of the swap weights.
+		///
 		/// WARNING: This may return an error after a partial storage mutation. It should be used
 		/// only inside a transactional storage context and an Err result must imply a storage
 		/// rollback.
@@ -1000,6 +1052,12 @@ pub mod pallet {
 			T::Assets::resolve(&pool_to, credit_in)
 				.map_err(|c| (c, Error::<T>::BelowMinimum.into()))?;
 
+			for pair in path.windows(2) {
+				if let [(asset1, _), (asset2, _)] = pair {
+					Self::update_price_oracle(asset1, asset2);
+				}
+			}
+
 			Ok(credit_out)
 		}
 
@@ -1142,6 +1200,188 @@ pub mod pallet {
 			}
 		}
 
+		/// The weight of updating the price oracles of `pools` pools after a change of their
+		/// reserves, on top of the weight of the change itself.
+		///
+		/// A swap along a path of `n` assets updates `n - 1` oracles.
+		pub fn price_oracle_weight(pools: u32) -> Weight {
+			T::WeightInfo::update_price_oracle().saturating_mul(pools.into())
+		}
+
+		/// Returns the time-weighted average price of `asset1` in terms of `asset2` over the last
+		/// `window` blocks, i.e. the average of the pool's spot prices at the end of each of
+		/// these blocks.
+		///
+		/// Returns `None` if the pool doesn't exist or if its price observations don't cover the
+		/// window.
+		pub fn time_weighted_average_price(
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			window: BlockNumberFor<T>,
+		) -> Option<FixedU128> {
+			if window.is_zero() {
+				return None
+			}
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).ok()?;
+			let oracle = PriceOracles::<T>::get(&pool_id)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let start = now.checked_sub(&window)?;
+
+			let (end_base, end_quote) = Self::cumulative_prices_at(&pool_id, &oracle, now)?;
+			let (start_base, start_quote) = Self::cumulative_prices_at(&pool_id, &oracle, start)?;
+			let (end_cumulative, start_cumulative) = if oracle.base_asset == asset1 {
+				(end_base, start_base)
+			} else {
+				(end_quote, start_quote)
+			};
+			// Cumulative prices wrap around, their difference is exact as long as it doesn't
+			// overflow itself.
+			let delta = end_cumulative.wrapping_sub(start_cumulative);
+
+			Some(FixedU128::from_inner(delta / window.saturated_into::<u128>()))
+		}
+
+		/// Provides a quote for swapping `amount` of `asset1` into `asset2` at the time-weighted
+		/// average price over the last `window` blocks, excluding fees.
+		pub fn quote_price_time_weighted(
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			amount: T::Balance,
+			window: BlockNumberFor<T>,
+		) -> Option<T::Balance> {
+			let price = Self::time_weighted_average_price(asset1, asset2, window)?;
+			let amount: u128 = amount.try_into().ok()?;
+			price.checked_mul_int(amount)?.try_into().ok()
+		}
+
+		/// Returns the cumulative prices of a pool at block `at`, interpolated from its price
+		/// observations.
+		///
+		/// The interpolation is exact, as the spot price of a pool only changes at the blocks of
+		/// its observations. Cumulative prices wrap around at `u128::MAX`.
+		fn cumulative_prices_at(
+			pool_id: &T::PoolId,
+			oracle: &PriceOracle<T::AssetKind, BlockNumberFor<T>>,
+			at: BlockNumberFor<T>,
+		) -> Option<(u128, u128)> {
+			let latest = &oracle.latest;
+			if at >= latest.block {
+				let elapsed = at.saturating_sub(latest.block).saturated_into::<u128>();
+				return Some((
+					accumulate_price(latest.base_price_cumulative, oracle.base_price, elapsed),
+					accumulate_price(latest.quote_price_cumulative, oracle.quote_price, elapsed),
+				))
+			}
+
+			let capacity = T::MaxPriceObservations::get() as u64;
+			let len = (oracle.observations as u64).min(capacity);
+			if len.is_zero() {
+				return None
+			}
+			let oldest_slot = (oracle.next_slot as u64 + capacity - len) % capacity;
+			let observation = |index: u64| {
+				PriceObservations::<T>::get(pool_id, ((oldest_slot + index) % capacity) as u32)
+			};
+
+			// The newest observation is the latest one, which is after `at`.
+			let (mut low, mut high) = (0, len - 1);
+			if observation(low)?.block > at {
+				return None
+			}
+			while high - low > 1 {
+				let mid = low + (high - low) / 2;
+				if observation(mid)?.block <= at {
+					low = mid;
+				} else {
+					high = mid;
+				}
+			}
+
+			let (before, after) = (observation(low)?, observation(high)?);
+			let elapsed = at.saturating_sub(before.block).saturated_into::<u128>();
+			let span = after.block.saturating_sub(before.block).saturated_into::<u128>();
+			let interpolate = |before: u128, after: u128| {
+				multiply_by_rational_with_rounding(
+					after.wrapping_sub(before),
+					elapsed,
+					span,
+					Rounding::Down,
+				)
+				.map(|delta| before.wrapping_add(delta))
+			};
+
+			Some((
+				interpolate(before.base_price_cumulative, after.base_price_cumulative)?,
+				interpolate(before.quote_price_cumulative, after.quote_price_cumulative)?,
+			))
+		}
+
+		/// Updates the price oracle of the pool of `asset1` and `asset2` after a change of its
+		/// reserves.
+		///
+		/// The spot price of the pool since its previous change is accumulated for the blocks
+		/// elapsed since, and an observation is stored if it's the first change in this block.
+		///
+		/// Weighed by [`WeightInfo::update_price_oracle`], see [`Self::price_oracle_weight`].
+		pub(crate) fn update_price_oracle(asset1: &T::AssetKind, asset2: &T::AssetKind) {
+			let capacity = T::MaxPriceObservations::get();
+			if capacity.is_zero() {
+				return
+			}
+			let Ok(pool_id) = T::PoolLocator::pool_id(asset1, asset2) else { return };
+			let Ok(pool_account) = T::PoolLocator::address(&pool_id) else { return };
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let mut oracle = PriceOracles::<T>::get(&pool_id).unwrap_or_else(|| PriceOracle {
+				base_asset: asset1.clone(),
+				base_price: Zero::zero(),
+				quote_price: Zero::zero(),
+				latest: PriceObservation {
+					block: now,
+					base_price_cumulative: 0,
+					quote_price_cumulative: 0,
+				},
+				next_slot: 0,
+				observations: 0,
+			});
+
+			if oracle.observations.is_zero() || oracle.latest.block < now {
+				let elapsed = now.saturating_sub(oracle.latest.block).saturated_into::<u128>();
+				oracle.latest = PriceObservation {
+					block: now,
+					base_price_cumulative: accumulate_price(
+						oracle.latest.base_price_cumulative,
+						oracle.base_price,
+						elapsed,
+					),
+					quote_price_cumulative: accumulate_price(
+						oracle.latest.quote_price_cumulative,
+						oracle.quote_price,
+						elapsed,
+					),
+				};
+				let slot = oracle.next_slot % capacity;
+				PriceObservations::<T>::insert(&pool_id, slot, oracle.latest);
+				oracle.next_slot = (slot + 1) % capacity;
+				oracle.observations = oracle.observations.saturating_add(1).min(capacity);
+			}
+
+			let (base_asset, quote_asset) = if oracle.base_asset == *asset1 {
+				(asset1.clone(), asset2.clone())
+			} else {
+				(asset2.clone(), asset1.clone())
+			};
+			let reserve_base: u128 = Self::get_balance(&pool_account, base_asset).saturated_into();
+			let reserve_quote: u128 =
+				Self::get_balance(&pool_account, quote_asset).saturated_into();
+			oracle.base_price =
+				FixedU128::checked_from_rational(reserve_quote, reserve_base).unwrap_or_default();
+			oracle.quote_price =
+				FixedU128::checked_from_rational(reserve_base, reserve_quote).unwrap_or_default();
+
+			PriceOracles::<T>::insert(&pool_id, oracle);
+		}
+
 		/// Calculates the optimal amount from the reserves.
 		pub fn quote(
 			amount: &T::Balance,
@@ -1291,9 +1531,16 @@ pub mod pallet {
 	}
 }
 
+/// Accumulates `price` for `elapsed` blocks on top of the cumulative price `cumulative`,
+/// wrapping around at `u128::MAX`.
+fn accumulate_price(cumulative: u128, price: FixedU128, elapsed: u128) -> u128 {
+	cumulative.wrapping_add(price.into_inner().wrapping_mul(elapsed))
+}
+
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
+	#[api_version(2)]
 	pub trait AssetConversionApi<Balance, AssetId>
 	where
 		Balance: frame_support::traits::tokens::Balance + MaybeDisplay,
@@ -1323,6 +1570,18 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Provides a quote for swapping `amount` of `asset1` into `asset2` at the time-weighted
+		/// average price over the last `window` blocks, excluding fees.
+		///
+		/// Unlike the spot price quotes, this price can't be moved within a single block.
+		#[api_version(2)]
+		fn quote_price_time_weighted(
+			asset1: AssetId,
+			asset2: AssetId,
+			amount: Balance,
+			window: u32,
+		) -> Option<Balance>;
 	}
 }
 
@@ -18,6 +18,7 @@
 //! Traits and implementations for swap between the various asset classes.
 
 use super::*;
+use frame_system::pallet_prelude::BlockNumberFor;
 
 /// Trait for providing methods to swap between the various asset classes.
 pub trait Swap<AccountId> {
@@ -143,6 +144,23 @@ pub trait QuotePrice {
 	) -> Option<Self::Balance>;
 }
 
+/// Trait providing a quote of swap prices between asset classes which can't be moved within a
+/// block.
+pub trait QuoteTimeWeightedPrice: QuotePrice {
+	/// Type of the block number measuring the quote's averaging window.
+	type BlockNumber;
+	/// Quotes the amount of `asset2` worth the exact `amount` of `asset1`, at the time-weighted
+	/// average price of their pool over the last `window` blocks, excluding fees.
+	///
+	/// If the pool does not exist or its price is not known over the window, `None` is returned.
+	fn quote_price_time_weighted(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		window: Self::BlockNumber,
+	) -> Option<Self::Balance>;
+}
+
 impl<T: Config> Swap<T::AccountId> for Pallet<T> {
 	type Balance = T::Balance;
 	type AssetKind = T::AssetKind;
@@ -262,3 +280,15 @@ impl<T: Config> QuotePrice for Pallet<T> {
 		Self::quote_price_tokens_for_exact_tokens(asset1, asset2, amount, include_fee)
 	}
 }
+
+impl<T: Config> QuoteTimeWeightedPrice for Pallet<T> {
+	type BlockNumber = BlockNumberFor<T>;
+	fn quote_price_time_weighted(
+		asset1: Self::AssetKind,
+		asset2: Self::AssetKind,
+		amount: Self::Balance,
+		window: Self::BlockNumber,
+	) -> Option<Self::Balance> {
+		Self::quote_price_time_weighted(asset1, asset2, amount, window)
+	}
+}
@@ -27,7 +27,7 @@ use frame_support::{
 		Get,
 	},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{FixedPointNumber, FixedU128, Permill};
 use sp_runtime::{DispatchError, TokenError};
 
 fn events() -> Vec<Event<Test>> {
@@ -2493,3 +2493,212 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+#[test]
+fn time_weighted_average_price_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let spot_price = |asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>| {
+			let (reserve1, reserve2) = AssetConversion::get_reserves(asset1, asset2).unwrap();
+			FixedU128::checked_from_rational(reserve2, reserve1).unwrap()
+		};
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			200,
+			1,
+			1,
+			user,
+		));
+		let price_1 = spot_price(token_1.clone(), token_2.clone());
+
+		System::set_block_number(11);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			10,
+			1,
+			user,
+			false,
+		));
+		let price_2 = spot_price(token_1.clone(), token_2.clone());
+		assert_ne!(price_1, price_2);
+
+		System::set_block_number(21);
+		let twap = |window| {
+			AssetConversion::time_weighted_average_price(token_1.clone(), token_2.clone(), window)
+		};
+		assert_eq!(twap(10), Some(price_2));
+		assert_eq!(
+			twap(15),
+			Some(FixedU128::from_inner(
+				(price_1.into_inner() * 5 + price_2.into_inner() * 10) / 15
+			))
+		);
+		assert_eq!(
+			twap(20),
+			Some(FixedU128::from_inner((price_1.into_inner() + price_2.into_inner()) / 2))
+		);
+		assert_eq!(twap(0), None);
+		// The window starts before the first observation.
+		assert_eq!(twap(21), None);
+
+		let inverse_price = spot_price(token_2.clone(), token_1.clone());
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_2.clone(), token_1.clone(), 10),
+			Some(inverse_price)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_time_weighted(token_2.clone(), token_1.clone(), 100, 10),
+			Some(inverse_price.saturating_mul_int(100))
+		);
+
+		// Moving the spot price within a block doesn't move the time-weighted average price.
+		let native_before = balance(user, token_1.clone());
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_2.clone(), token_1.clone()],
+			500,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(twap(10), Some(price_2));
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			balance(user, token_1.clone()) - native_before,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(twap(10), Some(price_2));
+
+		System::set_block_number(22);
+		assert_eq!(twap(1), Some(spot_price(token_1.clone(), token_2.clone())));
+	});
+}
+
+#[test]
+fn time_weighted_average_price_is_bounded_by_observations() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let max_observations: u32 = <Test as Config>::MaxPriceObservations::get();
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			200,
+			1,
+			1,
+			user,
+		));
+
+		// One more observation than there are slots, evicting the one of block 1.
+		for block in 2..=max_observations as u64 + 1 {
+			System::set_block_number(block);
+			assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user),
+				bvec![token_2.clone(), token_1.clone()],
+				10,
+				1,
+				user,
+				false,
+			));
+		}
+		assert_eq!(
+			PriceObservations::<Test>::iter_prefix(&pool_id).count(),
+			max_observations as usize
+		);
+
+		System::set_block_number(max_observations as u64 + 2);
+		let twap = |window| {
+			AssetConversion::time_weighted_average_price(token_1.clone(), token_2.clone(), window)
+		};
+		assert!(twap(max_observations as u64).is_some());
+		assert_eq!(twap(max_observations as u64 + 1), None);
+	});
+}
+
+#[test]
+fn time_weighted_average_price_wraps_around() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 1000));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			10000,
+			200,
+			1,
+			1,
+			user,
+		));
+
+		let price = FixedU128::from_u32(2);
+		assert_eq!(accumulate_price(u128::MAX - 1, price, 1), price.into_inner() - 2);
+		PriceOracles::<Test>::mutate(&pool_id, |oracle| {
+			let oracle = oracle.as_mut().unwrap();
+			oracle.latest.base_price_cumulative = u128::MAX - 1;
+			oracle.latest.quote_price_cumulative = u128::MAX - 1;
+		});
+
+		// The cumulative prices wrap around within the window.
+		System::set_block_number(3);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_1.clone(), token_2.clone(), 2),
+			Some(FixedU128::from_rational(200, 10000))
+		);
+		assert_eq!(
+			AssetConversion::time_weighted_average_price(token_2, token_1, 1),
+			Some(FixedU128::from_u32(50))
+		);
+	});
+}
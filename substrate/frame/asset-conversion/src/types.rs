@@ -41,6 +41,39 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// Cumulative prices of a pool at a given block, from which time-weighted average prices are
+/// derived.
+///
+/// The cumulative prices are the sums, over every block, of the pool's spot price at the end of
+/// the previous block, as the inner values of [`FixedU128`]. They wrap around on overflow, hence
+/// only the difference between two observations is meaningful.
+#[derive(Decode, Encode, Default, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block of the observation.
+	pub block: BlockNumber,
+	/// The cumulative price of the base asset, in terms of the other asset of the pool.
+	pub base_price_cumulative: u128,
+	/// The cumulative price of the other asset of the pool, in terms of the base asset.
+	pub quote_price_cumulative: u128,
+}
+
+/// The state of the price oracle of a pool.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct PriceOracle<AssetKind, BlockNumber> {
+	/// The asset of the pool the base prices refer to.
+	pub base_asset: AssetKind,
+	/// The spot price of the base asset after the latest change of the pool's reserves.
+	pub base_price: FixedU128,
+	/// The spot price of the other asset after the latest change of the pool's reserves.
+	pub quote_price: FixedU128,
+	/// The latest observation, also stored in [`PriceObservations`].
+	pub latest: PriceObservation<BlockNumber>,
+	/// The slot of [`PriceObservations`] the next observation is stored at.
+	pub next_slot: u32,
+	/// The number of observations stored in [`PriceObservations`].
+	pub observations: u32,
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
@@ -962,6 +962,31 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(())
 	}
 
+	/// Removes the approval from `owner` to spend asset `id` tokens by `delegate`, unreserving
+	/// the deposit taken for it.
+	pub fn do_cancel_approval(
+		id: T::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		let mut d = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(d.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+
+		let approval =
+			Approvals::<T, I>::take((id.clone(), owner, delegate)).ok_or(Error::<T, I>::Unknown)?;
+		T::Currency::unreserve(owner, approval.deposit);
+
+		d.approvals.saturating_dec();
+		Asset::<T, I>::insert(id.clone(), d);
+
+		Self::deposit_event(Event::ApprovalCancelled {
+			asset_id: id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+		});
+		Ok(())
+	}
+
 	/// Reduces the asset `id` balance of `owner` by some `amount` and increases the balance of
 	/// `dest` by (similar) amount, checking that 'delegate' has an existing approval from `owner`
 	/// to spend`amount`.
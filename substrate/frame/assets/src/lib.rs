@@ -1513,18 +1513,7 @@ pub mod pallet {
 			let owner = ensure_signed(origin)?;
 			let delegate = T::Lookup::lookup(delegate)?;
 			let id: T::AssetId = id.into();
-			let mut d = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
-			ensure!(d.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
-
-			let approval = Approvals::<T, I>::take((id.clone(), &owner, &delegate))
-				.ok_or(Error::<T, I>::Unknown)?;
-			T::Currency::unreserve(&owner, approval.deposit);
-
-			d.approvals.saturating_dec();
-			Asset::<T, I>::insert(id.clone(), d);
-
-			Self::deposit_event(Event::ApprovalCancelled { asset_id: id, owner, delegate });
-			Ok(())
+			Self::do_cancel_approval(id, &owner, &delegate)
 		}
 
 		/// Cancel all of some asset approved for delegated transfer by a third-party account.
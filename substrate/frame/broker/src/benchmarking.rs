@@ -33,7 +33,7 @@ use frame_system::{Pallet as System, RawOrigin};
 use sp_arithmetic::Perbill;
 use sp_core::Get;
 use sp_runtime::{
-	traits::{BlockNumberProvider, MaybeConvert},
+	traits::{BlockNumberProvider, Bounded, MaybeConvert, Zero},
 	Saturating,
 };
 
@@ -115,6 +115,90 @@ fn setup_and_start_sale<T: Config>() -> Result<u16, BenchmarkError> {
 		.unwrap())
 }
 
+fn setup_market_region<T: Config>() -> Result<(T::AccountId, RegionId), BenchmarkError> {
+	setup_and_start_sale::<T>()?;
+
+	advance_to::<T>(2);
+
+	// Enough for the Region, the deposit of its listing and of two relisted remainders.
+	let seller: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(
+		&seller.clone(),
+		T::Currency::minimum_balance()
+			.saturating_add(10_000_000u32.into())
+			.saturating_add(T::MarketOrderDeposit::get().saturating_mul(3u32.into())),
+	);
+
+	let region = Broker::<T>::do_purchase(seller.clone(), 10_000_000u32.into())
+		.expect("Offer not high enough for configuration.");
+
+	Ok((seller, region))
+}
+
+// The amount offered by the bids of the benchmarks, and the price of the parts of Regions they
+// seek.
+fn market_bid<T: Config>() -> BalanceOf<T> {
+	T::MinimumBid::get().max(1_000_000u32.into())
+}
+
+// A buyer with enough funds for a single bid.
+fn market_buyer<T: Config>(index: u32) -> T::AccountId {
+	let buyer: T::AccountId = account("buyer", index, SEED);
+	T::Currency::set_balance(
+		&buyer.clone(),
+		T::Currency::minimum_balance()
+			.saturating_add(market_bid::<T>())
+			.saturating_add(T::MarketOrderDeposit::get()),
+	);
+	buyer
+}
+
+// Fill the orders on the core of `region` with `listings` listings and `bids` bids which cover the
+// parts of `region`, so all of them have to be considered when matching orders for it, yet none
+// of them is ever satisfied.
+fn fill_market<T: Config>(
+	region: RegionId,
+	listings: u32,
+	bids: u32,
+) -> Result<(), BenchmarkError> {
+	let filler: T::AccountId = account("filler", 0, SEED);
+	let region_id = RegionId { begin: region.begin + 1, ..region };
+	let listing = ListingRecord {
+		region_id,
+		end: region.begin + 3,
+		seller: filler.clone(),
+		price: MarketPrice::Fixed(BalanceOf::<T>::max_value()),
+		listed_at: RCBlockNumberProviderOf::<T::Coretime>::current_block_number(),
+		allow_partial: true,
+		deposit: Zero::zero(),
+	};
+	let bid = BidRecord {
+		bidder: filler,
+		region_id,
+		end: region.begin + 3,
+		amount: Zero::zero(),
+		deposit: Zero::zero(),
+	};
+	Listings::<T>::insert(
+		region.core,
+		ListingsRecordOf::<T>::try_from(vec![listing; listings as usize])
+			.map_err(|_| BenchmarkError::Weightless)?,
+	);
+	Bids::<T>::insert(
+		region.core,
+		BidsRecordOf::<T>::try_from(vec![bid; bids as usize])
+			.map_err(|_| BenchmarkError::Weightless)?,
+	);
+	Ok(())
+}
+
+// The middle timeslice of half of `region`; carving it out leaves three remainders to be relisted.
+fn market_part(region: RegionId) -> (RegionId, Timeslice) {
+	let part =
+		RegionId { begin: region.begin + 1, core: region.core, mask: CoreMask::from_chunk(0, 40) };
+	(part, region.begin + 2)
+}
+
 #[benchmarks]
 mod benches {
 	use super::*;
@@ -1252,6 +1336,156 @@ mod benches {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn list_region() -> Result<(), BenchmarkError> {
+		let (seller, region) = setup_market_region::<T>()?;
+		let max_orders = T::MaxMarketOrders::get();
+		// The listing and its three remainders fill up the listings, while the only bid it
+		// satisfies is the last one considered.
+		fill_market::<T>(region, max_orders.saturating_sub(3), max_orders.saturating_sub(1))?;
+		let buyer = market_buyer::<T>(0);
+		let (part, end) = market_part(region);
+		Broker::<T>::do_place_bid(buyer.clone(), part, end, market_bid::<T>())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(seller),
+			region,
+			MarketPrice::Fixed(market_bid::<T>().saturating_mul(6u32.into())),
+			true,
+		);
+
+		assert_eq!(Regions::<T>::get(part).and_then(|r| r.owner), Some(buyer));
+		assert_eq!(Listings::<T>::get(region.core).len() as u32, max_orders);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delist_region() -> Result<(), BenchmarkError> {
+		let (seller, region) = setup_market_region::<T>()?;
+		fill_market::<T>(region, T::MaxMarketOrders::get().saturating_sub(1), 0)?;
+		Broker::<T>::do_list_region(region, seller.clone(), MarketPrice::Fixed(1u32.into()), true)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(seller.clone()), region);
+
+		assert_last_event::<T>(Event::Delisted { region_id: region, seller }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn purchase_listed() -> Result<(), BenchmarkError> {
+		let (seller, region) = setup_market_region::<T>()?;
+		let max_orders = T::MaxMarketOrders::get();
+		fill_market::<T>(region, max_orders.saturating_sub(3), 0)?;
+		Broker::<T>::do_list_region(
+			region,
+			seller.clone(),
+			MarketPrice::Fixed(market_bid::<T>().saturating_mul(6u32.into())),
+			true,
+		)
+		.map_err(|_| BenchmarkError::Weightless)?;
+		let buyer = market_buyer::<T>(0);
+		let (part, end) = market_part(region);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), part, end, market_bid::<T>());
+
+		assert_eq!(Regions::<T>::get(part).and_then(|r| r.owner), Some(buyer));
+		assert_eq!(Listings::<T>::get(region.core).len() as u32, max_orders);
+
+		Ok(())
+	}
+
+	// A bid which a listing satisfies buys the Region right away, as measured by
+	// `purchase_listed`. This measures a bid which has to be put on hold.
+	#[benchmark]
+	fn place_bid() -> Result<(), BenchmarkError> {
+		let (_, region) = setup_market_region::<T>()?;
+		let max_orders = T::MaxMarketOrders::get();
+		fill_market::<T>(region, max_orders, max_orders.saturating_sub(1))?;
+		let buyer = market_buyer::<T>(0);
+		let (part, end) = market_part(region);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), part, end, market_bid::<T>());
+
+		assert_eq!(Bids::<T>::get(region.core).len() as u32, max_orders);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_bid() -> Result<(), BenchmarkError> {
+		let (_, region) = setup_market_region::<T>()?;
+		fill_market::<T>(region, 0, T::MaxMarketOrders::get().saturating_sub(1))?;
+		let buyer = market_buyer::<T>(0);
+		let end = region.begin + 3;
+		Broker::<T>::do_place_bid(buyer.clone(), region, end, market_bid::<T>())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), region, end);
+
+		assert_last_event::<T>(
+			Event::BidCancelled { who: buyer, region_id: region, end, amount: market_bid::<T>() }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn process_market(n: Linear<0, { T::MaxMarketOrders::get() }>) -> Result<(), BenchmarkError> {
+		let (seller, region) = setup_market_region::<T>()?;
+		let max_orders = T::MaxMarketOrders::get();
+		// The orders which are never satisfied are considered before the `n` bids to settle.
+		fill_market::<T>(region, max_orders.saturating_sub(1), max_orders.saturating_sub(n))?;
+		// The auction ends at a price which satisfies every bid one block after listing.
+		Broker::<T>::do_list_region(
+			region,
+			seller.clone(),
+			MarketPrice::Dutch {
+				start_price: market_bid::<T>().saturating_mul(100u32.into()),
+				end_price: 1_000u32.into(),
+				duration: 1u32.into(),
+			},
+			true,
+		)
+		.map_err(|_| BenchmarkError::Weightless)?;
+
+		// Each bid of a distinct buyer seeks a distinct slice of the core for the whole Region.
+		let end = region.begin + 3;
+		let width = CORE_MASK_BITS as u32 / n.max(1);
+		for i in 0..n {
+			let mask = CoreMask::from_chunk(i * width, (i + 1) * width);
+			Broker::<T>::do_place_bid(
+				market_buyer::<T>(i),
+				RegionId { mask, ..region },
+				end,
+				market_bid::<T>(),
+			)
+			.map_err(|_| BenchmarkError::Weightless)?;
+		}
+
+		let now = RCBlockNumberProviderOf::<T::Coretime>::current_block_number();
+		RCBlockNumberProviderOf::<T::Coretime>::set_block_number(now.saturating_add(1u32.into()));
+		let when = Status::<T>::get().ok_or(BenchmarkError::Weightless)?.last_committed_timeslice;
+
+		#[block]
+		{
+			Broker::<T>::process_market(when, region.core);
+		}
+
+		assert_eq!(Bids::<T>::get(region.core).len() as u32, max_orders.saturating_sub(n));
+
+		Ok(())
+	}
+
 	// Implements a test for each benchmark. Execute with:
 	// `cargo test -p pallet-broker --features runtime-benchmarks`.
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -18,7 +18,11 @@
 use super::*;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{fungible::Mutate, tokens::Preservation::Expendable, DefensiveResult},
+	traits::{
+		fungible::{Mutate, MutateHold},
+		tokens::{Precision::Exact, Preservation::Expendable},
+		DefensiveResult,
+	},
 };
 use sp_arithmetic::traits::{CheckedDiv, Saturating, Zero};
 use sp_runtime::traits::{BlockNumberProvider, Convert};
@@ -591,6 +595,146 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	pub(crate) fn do_list_region(
+		region_id: RegionId,
+		seller: T::AccountId,
+		price: MarketPriceOf<T>,
+		allow_partial: bool,
+	) -> DispatchResult {
+		let status = Status::<T>::get().ok_or(Error::<T>::Uninitialized)?;
+		if let MarketPrice::Dutch { start_price, end_price, duration } = price {
+			ensure!(
+				start_price >= end_price && !duration.is_zero(),
+				Error::<T>::InvalidMarketPrice
+			);
+		}
+		let region = Regions::<T>::get(&region_id).ok_or(Error::<T>::UnknownRegion)?;
+		ensure!(region.end > status.last_committed_timeslice + 1, Error::<T>::AlreadyExpired);
+
+		let mut listings = Listings::<T>::get(region_id.core);
+		let now = RCBlockNumberProviderOf::<T::Coretime>::current_block_number();
+		listings
+			.try_push(ListingRecord {
+				region_id,
+				end: region.end,
+				seller: seller.clone(),
+				price: price.clone(),
+				listed_at: now,
+				allow_partial,
+				deposit: T::MarketOrderDeposit::get(),
+			})
+			.map_err(|_| Error::<T>::TooManyOrders)?;
+		Self::do_transfer(region_id, Some(seller.clone()), Self::account_id())?;
+		Self::hold_market_deposit(&seller)?;
+		Self::deposit_event(Event::Listed { region_id, seller, price, allow_partial });
+
+		let mut bids = Bids::<T>::get(region_id.core);
+		if Self::match_bids(&mut listings, &mut bids, now, 1) > 0 {
+			Bids::<T>::insert(region_id.core, &bids);
+		}
+		Listings::<T>::insert(region_id.core, &listings);
+		Ok(())
+	}
+
+	pub(crate) fn do_delist_region(region_id: RegionId, seller: T::AccountId) -> DispatchResult {
+		let mut listings = Listings::<T>::get(region_id.core);
+		let index = listings
+			.iter()
+			.position(|listing| listing.region_id == region_id)
+			.ok_or(Error::<T>::NotListed)?;
+		ensure!(listings[index].seller == seller, Error::<T>::NotOwner);
+
+		let listing = listings.remove(index);
+		Listings::<T>::insert(region_id.core, &listings);
+		Self::do_transfer(region_id, None, seller.clone())?;
+		Self::release_market_deposit(&seller, listing.deposit);
+		Self::deposit_event(Event::Delisted { region_id, seller });
+		Ok(())
+	}
+
+	pub(crate) fn do_purchase_listed(
+		who: T::AccountId,
+		region_id: RegionId,
+		end: Timeslice,
+		price_limit: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(end > region_id.begin && !region_id.mask.is_void(), Error::<T>::InvalidRegionPart);
+
+		let mut listings = Listings::<T>::get(region_id.core);
+		let now = RCBlockNumberProviderOf::<T::Coretime>::current_block_number();
+		let (index, price) = Self::cheapest_listing(&listings, &region_id, end, now)
+			.ok_or(Error::<T>::NoMatchingListing)?;
+		ensure!(price_limit >= price, Error::<T>::Overpriced);
+
+		Self::sell_listed(&mut listings, index, who, region_id, end, price, false)?;
+		Listings::<T>::insert(region_id.core, &listings);
+		Ok(())
+	}
+
+	pub(crate) fn do_place_bid(
+		who: T::AccountId,
+		region_id: RegionId,
+		end: Timeslice,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let status = Status::<T>::get().ok_or(Error::<T>::Uninitialized)?;
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		ensure!(amount >= T::MinimumBid::get(), Error::<T>::BidTooSmall);
+		// Only Regions which may exist can be sought, so bids can't be parked indefinitely.
+		ensure!(
+			end > region_id.begin &&
+				end <= sale.region_end &&
+				region_id.core < status.core_count &&
+				!region_id.mask.is_void(),
+			Error::<T>::InvalidRegionPart
+		);
+		ensure!(region_id.begin > status.last_committed_timeslice, Error::<T>::AlreadyExpired);
+
+		let mut listings = Listings::<T>::get(region_id.core);
+		let now = RCBlockNumberProviderOf::<T::Coretime>::current_block_number();
+		if let Some((index, price)) = Self::cheapest_listing(&listings, &region_id, end, now)
+			.filter(|(_, price)| *price <= amount)
+		{
+			Self::sell_listed(&mut listings, index, who, region_id, end, price, false)?;
+			Listings::<T>::insert(region_id.core, &listings);
+			return Ok(())
+		}
+
+		let mut bids = Bids::<T>::get(region_id.core);
+		ensure!(
+			bids.iter().filter(|bid| bid.bidder == who).count() <
+				T::MaxBidsPerAccount::get() as usize,
+			Error::<T>::TooManyBids
+		);
+		let deposit = T::MarketOrderDeposit::get();
+		bids.try_push(BidRecord { bidder: who.clone(), region_id, end, amount, deposit })
+			.map_err(|_| Error::<T>::TooManyOrders)?;
+		T::Currency::hold(&HoldReason::MarketBid.into(), &who, amount)?;
+		Self::hold_market_deposit(&who)?;
+		Bids::<T>::insert(region_id.core, &bids);
+		Self::deposit_event(Event::BidPlaced { who, region_id, end, amount });
+		Ok(())
+	}
+
+	pub(crate) fn do_cancel_bid(
+		who: T::AccountId,
+		region_id: RegionId,
+		end: Timeslice,
+	) -> DispatchResult {
+		let mut bids = Bids::<T>::get(region_id.core);
+		let index = bids
+			.iter()
+			.position(|bid| bid.bidder == who && bid.region_id == region_id && bid.end == end)
+			.ok_or(Error::<T>::UnknownBid)?;
+
+		let bid = bids.remove(index);
+		Bids::<T>::insert(region_id.core, &bids);
+		T::Currency::release(&HoldReason::MarketBid.into(), &who, bid.amount, Exact)?;
+		Self::release_market_deposit(&who, bid.deposit);
+		Self::deposit_event(Event::BidCancelled { who, region_id, end, amount: bid.amount });
+		Ok(())
+	}
+
 	pub(crate) fn ensure_cores_for_sale(
 		status: &StatusRecord,
 		sale: &SaleInfoRecordOf<T>,
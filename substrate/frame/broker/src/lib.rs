@@ -59,7 +59,7 @@ pub mod pallet {
 	use frame_support::{
 		pallet_prelude::{DispatchResult, DispatchResultWithPostInfo, *},
 		traits::{
-			fungible::{Balanced, Credit, Mutate},
+			fungible::{Balanced, Credit, Mutate, MutateHold},
 			BuildGenesisConfig, EnsureOrigin, OnUnbalanced,
 		},
 		PalletId,
@@ -73,6 +73,17 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of an open listing or bid on the secondary market.
+		#[codec(index = 0)]
+		MarketOrderDeposit,
+		/// The amount offered by an open bid on the secondary market.
+		#[codec(index = 1)]
+		MarketBid,
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		#[allow(deprecated)]
@@ -82,7 +93,12 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		/// Currency used to pay for Coretime.
-		type Currency: Mutate<Self::AccountId> + Balanced<Self::AccountId>;
+		type Currency: Mutate<Self::AccountId>
+			+ Balanced<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
 
 		/// The origin test needed for administrating this pallet.
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -131,6 +147,34 @@ pub mod pallet {
 		/// Needed to prevent spam attacks.
 		#[pallet::constant]
 		type MinimumCreditPurchase: Get<BalanceOf<Self>>;
+
+		/// Maximum number of open listings, and separately of open bids, on the Regions of a
+		/// single core in the secondary market.
+		///
+		/// All orders of a core are matched against each other once per timeslice, so this has to
+		/// be limited.
+		#[pallet::constant]
+		type MaxMarketOrders: Get<u32>;
+
+		/// The deposit held for each open listing and bid on the secondary market.
+		///
+		/// Needed to prevent spamming the limited orders of a core, e.g. by listing many
+		/// interlaced parts of a single Region.
+		#[pallet::constant]
+		type MarketOrderDeposit: Get<BalanceOf<Self>>;
+
+		/// The smallest amount a bid on the secondary market can offer.
+		///
+		/// Needed to prevent spam attacks.
+		#[pallet::constant]
+		type MinimumBid: Get<BalanceOf<Self>>;
+
+		/// Maximum number of open bids of a single account on the Regions of a single core in the
+		/// secondary market.
+		///
+		/// Needed to prevent a single account from taking up all the bids of a core.
+		#[pallet::constant]
+		type MaxBidsPerAccount: Get<u32>;
 	}
 
 	/// The current configuration of this pallet.
@@ -202,6 +246,15 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type RevenueInbox<T> = StorageValue<_, OnDemandRevenueRecordOf<T>, OptionQuery>;
 
+	/// Regions listed for sale on the secondary market, keyed by the core of the Region.
+	#[pallet::storage]
+	pub type Listings<T: Config> =
+		StorageMap<_, Twox64Concat, CoreIndex, ListingsRecordOf<T>, ValueQuery>;
+
+	/// Open bids for Regions on the secondary market, keyed by the core of the Region sought.
+	#[pallet::storage]
+	pub type Bids<T: Config> = StorageMap<_, Twox64Concat, CoreIndex, BidsRecordOf<T>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -486,6 +539,77 @@ pub mod pallet {
 		/// This should never happen, given that enable_auto_renew checks for this before enabling
 		/// auto-renewal.
 		AutoRenewalLimitReached,
+		/// A Region has been listed for sale on the secondary market.
+		Listed {
+			/// The Region which was listed.
+			region_id: RegionId,
+			/// The account which listed the Region.
+			seller: T::AccountId,
+			/// The pricing of the whole Region.
+			price: MarketPriceOf<T>,
+			/// Whether parts of the Region may be sold separately.
+			allow_partial: bool,
+		},
+		/// A listing has been withdrawn from the secondary market by its seller.
+		Delisted {
+			/// The Region which was listed.
+			region_id: RegionId,
+			/// The account which listed the Region and to which it has been returned.
+			seller: T::AccountId,
+		},
+		/// A listing has been removed from the secondary market because its Region has ended.
+		ListingExpired {
+			/// The Region which was listed.
+			region_id: RegionId,
+			/// The account which listed the Region and to which it has been returned.
+			seller: T::AccountId,
+		},
+		/// A listed Region, or a part of one, has been sold on the secondary market.
+		Sold {
+			/// The Region which was sold.
+			region_id: RegionId,
+			/// The duration of the Region.
+			duration: Timeslice,
+			/// The account which sold the Region.
+			seller: T::AccountId,
+			/// The account which bought the Region.
+			buyer: T::AccountId,
+			/// The price paid for the Region.
+			price: BalanceOf<T>,
+		},
+		/// A bid has been placed on the secondary market.
+		BidPlaced {
+			/// The account which placed the bid.
+			who: T::AccountId,
+			/// The Region sought.
+			region_id: RegionId,
+			/// The end of the Region sought.
+			end: Timeslice,
+			/// The amount offered, which is now on hold.
+			amount: BalanceOf<T>,
+		},
+		/// A bid has been withdrawn from the secondary market by its bidder.
+		BidCancelled {
+			/// The account which placed the bid.
+			who: T::AccountId,
+			/// The Region sought.
+			region_id: RegionId,
+			/// The end of the Region sought.
+			end: Timeslice,
+			/// The amount which has been refunded.
+			amount: BalanceOf<T>,
+		},
+		/// A bid has been removed from the secondary market because the Region sought has begun.
+		BidExpired {
+			/// The account which placed the bid.
+			who: T::AccountId,
+			/// The Region sought.
+			region_id: RegionId,
+			/// The end of the Region sought.
+			end: Timeslice,
+			/// The amount which has been refunded.
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -548,7 +672,8 @@ pub mod pallet {
 		UnknownReservation,
 		/// The renewal record cannot be found.
 		UnknownRenewal,
-		/// The lease expiry time has already passed.
+		/// The lease expiry time (or for the secondary market, the Region's start or end) has
+		/// already passed.
 		AlreadyExpired,
 		/// The configuration could not be applied because it is invalid.
 		InvalidConfig,
@@ -569,6 +694,23 @@ pub mod pallet {
 		/// Needed to prevent spam attacks.The amount of credits the user attempted to purchase is
 		/// below `T::MinimumCreditPurchase`.
 		CreditPurchaseTooSmall,
+		/// The Dutch auction's start price is below its end price or its duration is zero.
+		InvalidMarketPrice,
+		/// The Region sought does not end after it begins, has a void mask, or lies on a core or
+		/// in timeslices which are not being sold.
+		InvalidRegionPart,
+		/// The given Region is not listed on the secondary market.
+		NotListed,
+		/// No listing on the secondary market offers the Region sought.
+		NoMatchingListing,
+		/// The limit of open orders on the Regions of this core has been reached.
+		TooManyOrders,
+		/// The given bid does not exist.
+		UnknownBid,
+		/// The amount offered by the bid is below `T::MinimumBid`.
+		BidTooSmall,
+		/// The account has the maximum number of open bids on the Regions of this core.
+		TooManyBids,
 	}
 
 	#[derive(frame_support::DefaultNoBound)]
@@ -1014,6 +1156,101 @@ pub mod pallet {
 			Self::do_remove_assignment(region_id)
 		}
 
+		/// List a Bulk Coretime Region for sale on the secondary market.
+		///
+		/// The Region is held in escrow by the pallet until it is sold, delisted or ends. Any open
+		/// bid which the listing satisfies is settled immediately; further matches are settled
+		/// automatically once per timeslice.
+		///
+		/// - `origin`: Must be a Signed origin of the account which owns the Region `region_id`.
+		/// - `region_id`: The Region which should be listed.
+		/// - `price`: The price of the whole Region, either fixed or falling as a Dutch auction.
+		/// - `allow_partial`: Whether parts of the Region may be sold separately at a pro rata
+		///   price. Unsold parts remain listed on the same terms.
+		#[pallet::call_index(27)]
+		pub fn list_region(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			price: MarketPriceOf<T>,
+			allow_partial: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_list_region(region_id, who, price, allow_partial)?;
+			Ok(())
+		}
+
+		/// Withdraw a Region from sale on the secondary market, returning it to its seller.
+		///
+		/// - `origin`: Must be a Signed origin of the account which listed the Region.
+		/// - `region_id`: The listed Region.
+		#[pallet::call_index(28)]
+		pub fn delist_region(origin: OriginFor<T>, region_id: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_delist_region(region_id, who)?;
+			Ok(())
+		}
+
+		/// Buy a Region, or a part of one, listed on the secondary market at its current price.
+		///
+		/// - `origin`: Must be a Signed origin with at least enough funds to pay the price.
+		/// - `region_id`: The Region sought. It must lie within a single listed Region, and be the
+		///   whole of it unless the listing allows partial sales.
+		/// - `end`: The end of the Region sought.
+		/// - `price_limit`: An amount no more than which should be paid.
+		#[pallet::call_index(29)]
+		pub fn purchase_listed(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			end: Timeslice,
+			price_limit: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_purchase_listed(who, region_id, end, price_limit)?;
+			Ok(())
+		}
+
+		/// Bid for a Region, or a part of one, on the secondary market.
+		///
+		/// If a listing already offers the Region for no more than `amount`, it is bought
+		/// immediately. Otherwise `amount` is put on hold and the bid is settled automatically
+		/// as soon as a listing offers the Region for no more than `amount`, or released once the
+		/// Region sought begins.
+		///
+		/// - `origin`: Must be a Signed origin with at least `amount` of funds, which must be no
+		///   less than `T::MinimumBid`. An account can have at most `T::MaxBidsPerAccount` open
+		///   bids on the Regions of a core.
+		/// - `region_id`: The Region sought. It must not have begun yet.
+		/// - `end`: The end of the Region sought.
+		/// - `amount`: The most which should be paid.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::place_bid().max(T::WeightInfo::purchase_listed()))]
+		pub fn place_bid(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			end: Timeslice,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_place_bid(who, region_id, end, amount)?;
+			Ok(())
+		}
+
+		/// Withdraw a bid from the secondary market, releasing the amount on hold.
+		///
+		/// - `origin`: Must be a Signed origin of the account which placed the bid.
+		/// - `region_id`: The Region sought by the bid.
+		/// - `end`: The end of the Region sought by the bid.
+		#[pallet::call_index(31)]
+		pub fn cancel_bid(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			end: Timeslice,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_bid(who, region_id, end)?;
+			Ok(())
+		}
+
 		#[pallet::call_index(99)]
 		#[pallet::weight(T::WeightInfo::swap_leases())]
 		pub fn swap_leases(origin: OriginFor<T>, id: TaskId, other: TaskId) -> DispatchResult {
@@ -22,7 +22,7 @@ use alloc::collections::btree_map::BTreeMap;
 use frame_support::{
 	assert_ok, derive_impl, ensure, ord_parameter_types, parameter_types,
 	traits::{
-		fungible::{Balanced, Credit, Inspect, ItemOf, Mutate},
+		fungible::{Balanced, Credit, Inspect, InspectHold, ItemOf, Mutate},
 		nonfungible::Inspect as NftInspect,
 		EitherOfDiverse, Hooks, OnUnbalanced,
 	},
@@ -70,6 +70,7 @@ parameter_types! {
 	pub static CoretimeWorkplan: BTreeMap<(u32, CoreIndex), Vec<(CoreAssignment, PartsOf57600)>> = Default::default();
 	pub static CoretimeUsage: BTreeMap<CoreIndex, Vec<(CoreAssignment, PartsOf57600)>> = Default::default();
 	pub static CoretimeInPool: CoreMaskBitCount = 0;
+	pub static MarketOrderDeposit: u64 = 0;
 }
 
 pub struct TestCoretimeProvider;
@@ -191,7 +192,8 @@ impl MaybeConvert<TaskId, u64> for SovereignAccountOf {
 
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type Currency = ItemOf<TestFungibles<(), u64, (), ConstU64<0>, ()>, (), u64>;
+	type Currency = ItemOf<TestFungibles<(), u64, (), ConstU64<0>, RuntimeHoldReason>, (), u64>;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type OnRevenue = IntoZero;
 	type TimeslicePeriod = ConstU64<2>;
 	type MaxLeasedCores = ConstU32<5>;
@@ -205,6 +207,10 @@ impl crate::Config for Test {
 	type MaxAutoRenewals = ConstU32<3>;
 	type PriceAdapter = CenterTargetPrice<BalanceOf<Self>>;
 	type MinimumCreditPurchase = MinimumCreditPurchase;
+	type MaxMarketOrders = ConstU32<4>;
+	type MarketOrderDeposit = MarketOrderDeposit;
+	type MinimumBid = ConstU64<10>;
+	type MaxBidsPerAccount = ConstU32<2>;
 }
 
 pub fn advance_to(b: u64) {
@@ -241,6 +247,10 @@ pub fn balance(who: u64) -> u64 {
 	<<Test as Config>::Currency as Inspect<_>>::total_balance(&who)
 }
 
+pub fn held(who: u64, reason: HoldReason) -> u64 {
+	<<Test as Config>::Currency as InspectHold<_>>::balance_on_hold(&reason.into(), &who)
+}
+
 pub fn attribute<T: codec::Decode>(nft: RegionId, attribute: impl codec::Encode) -> T {
 	<Broker as NftInspect<_>>::typed_attribute::<_, T>(&nft.into(), &attribute).unwrap()
 }
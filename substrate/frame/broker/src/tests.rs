@@ -2192,3 +2192,228 @@ fn force_reserve_works() {
 		assert_eq!(Workplan::<Test>::get((10, 0)), Some(system_workload.clone()));
 	});
 }
+
+#[test]
+fn market_fixed_price_sale_works() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let end = region.begin + 3;
+
+		assert_noop!(
+			Broker::do_list_region(region, 2, MarketPrice::Fixed(50), false),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Broker::do_list_region(region, 1, MarketPrice::Fixed(50), false));
+		// The Region is held in escrow while listed.
+		assert_eq!(<Broker as NftInspect<_>>::owner(&region.into()), Some(Broker::account_id()));
+		assert_noop!(Broker::do_assign(region, Some(1), 1001, Final), Error::<Test>::NotOwner);
+
+		let seller_balance = balance(1);
+		assert_noop!(Broker::do_purchase_listed(2, region, end, 49), Error::<Test>::Overpriced);
+		// Partial sales are not allowed by this listing.
+		assert_noop!(
+			Broker::do_purchase_listed(2, RegionId { begin: region.begin + 1, ..region }, end, 50),
+			Error::<Test>::NoMatchingListing
+		);
+		assert_ok!(Broker::do_purchase_listed(2, region, end, 50));
+		assert_eq!(balance(1), seller_balance + 50);
+		assert_eq!(balance(2), 950);
+		assert_eq!(<Broker as NftInspect<_>>::owner(&region.into()), Some(2));
+		assert!(Listings::<Test>::get(region.core).is_empty());
+		System::assert_last_event(
+			Event::Sold { region_id: region, duration: 3, seller: 1, buyer: 2, price: 50 }.into(),
+		);
+	});
+}
+
+#[test]
+fn market_partial_sale_relists_remainder() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let end = region.begin + 3;
+		assert_ok!(Broker::do_list_region(region, 1, MarketPrice::Fixed(300), true));
+
+		// Buy half of the core for the middle timeslice: one sixth of the Region.
+		let part = RegionId {
+			begin: region.begin + 1,
+			core: region.core,
+			mask: CoreMask::from_chunk(0, 40),
+		};
+		assert_ok!(Broker::do_purchase_listed(2, part, region.begin + 2, 50));
+		assert_eq!(balance(2), 950);
+		assert_eq!(<Broker as NftInspect<_>>::owner(&part.into()), Some(2));
+
+		let remainders: Vec<_> = Listings::<Test>::get(region.core)
+			.into_iter()
+			.map(|listing| (listing.region_id, listing.end, listing.price))
+			.collect();
+		assert_eq!(
+			remainders,
+			vec![
+				(region, region.begin + 1, MarketPrice::Fixed(100)),
+				(RegionId { begin: region.begin + 2, ..region }, end, MarketPrice::Fixed(100)),
+				(
+					RegionId { mask: CoreMask::from_chunk(40, 80), ..part },
+					region.begin + 2,
+					MarketPrice::Fixed(50)
+				),
+			]
+		);
+	});
+}
+
+#[test]
+fn market_bid_settles_when_dutch_auction_reaches_it() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let end = region.begin + 3;
+
+		// Nothing is listed yet, so the amount of the bid is put on hold.
+		let pot_balance = pot();
+		assert_ok!(Broker::do_place_bid(2, region, end, 60));
+		assert_eq!(balance(2), 940);
+		assert_eq!(held(2, HoldReason::MarketBid), 60);
+		assert_eq!(pot(), pot_balance);
+
+		assert_noop!(
+			Broker::do_list_region(
+				region,
+				1,
+				MarketPrice::Dutch { start_price: 20, end_price: 100, duration: 4 },
+				false
+			),
+			Error::<Test>::InvalidMarketPrice
+		);
+		// The auction starts above the bid...
+		let seller_balance = balance(1);
+		assert_ok!(Broker::do_list_region(
+			region,
+			1,
+			MarketPrice::Dutch { start_price: 100, end_price: 20, duration: 4 },
+			false
+		));
+		assert_eq!(Bids::<Test>::get(region.core).len(), 1);
+
+		// ...and reaches it halfway through, when the bid is settled at the next timeslice.
+		advance_to(4);
+		assert_eq!(<Broker as NftInspect<_>>::owner(&region.into()), Some(2));
+		assert_eq!(balance(1), seller_balance + 60);
+		assert_eq!(balance(2), 940);
+		assert_eq!(held(2, HoldReason::MarketBid), 0);
+		assert_eq!(pot(), pot_balance);
+		assert!(Bids::<Test>::get(region.core).is_empty());
+		assert!(Listings::<Test>::get(region.core).is_empty());
+		System::assert_has_event(
+			Event::Sold { region_id: region, duration: 3, seller: 1, buyer: 2, price: 60 }.into(),
+		);
+	});
+}
+
+#[test]
+fn market_orders_can_be_cancelled_and_expire() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).endow(3, 1000).execute_with(|| {
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let end = region.begin + 3;
+
+		assert_noop!(Broker::do_place_bid(2, region, end, 9), Error::<Test>::BidTooSmall);
+		for who in [2, 3] {
+			for _ in 0..2 {
+				assert_ok!(Broker::do_place_bid(who, region, end, 10));
+			}
+		}
+		assert_noop!(Broker::do_place_bid(2, region, end, 10), Error::<Test>::TooManyBids);
+		assert_noop!(Broker::do_place_bid(4, region, end, 10), Error::<Test>::TooManyOrders);
+		assert_noop!(Broker::do_cancel_bid(4, region, end), Error::<Test>::UnknownBid);
+		assert_ok!(Broker::do_cancel_bid(2, region, end));
+		for _ in 0..2 {
+			assert_ok!(Broker::do_cancel_bid(3, region, end));
+		}
+		assert_eq!(balance(2), 990);
+		assert_eq!(held(2, HoldReason::MarketBid), 10);
+		assert_eq!(balance(3), 1000);
+
+		assert_ok!(Broker::do_list_region(region, 1, MarketPrice::Fixed(50), false));
+		assert_noop!(Broker::do_delist_region(region, 2), Error::<Test>::NotOwner);
+		assert_ok!(Broker::do_delist_region(region, 1));
+		assert_eq!(<Broker as NftInspect<_>>::owner(&region.into()), Some(1));
+		assert_ok!(Broker::do_list_region(region, 1, MarketPrice::Fixed(50), false));
+
+		// The bid is released once the Region sought begins.
+		advance_to(6);
+		assert_eq!(balance(2), 1000);
+		assert_eq!(held(2, HoldReason::MarketBid), 0);
+		assert!(Bids::<Test>::get(region.core).is_empty());
+		System::assert_has_event(
+			Event::BidExpired { who: 2, region_id: region, end, amount: 10 }.into(),
+		);
+
+		// The Region is returned to the seller once it has ended.
+		advance_to(10);
+		assert_eq!(<Broker as NftInspect<_>>::owner(&region.into()), Some(1));
+		assert!(Listings::<Test>::get(region.core).is_empty());
+		System::assert_has_event(Event::ListingExpired { region_id: region, seller: 1 }.into());
+	});
+}
+
+#[test]
+fn market_orders_hold_deposits() {
+	TestExt::new().endow(1, 1000).endow(2, 1000).execute_with(|| {
+		MarketOrderDeposit::set(10);
+		assert_ok!(Broker::do_start_sales(100, 1));
+		advance_to(2);
+		let region = Broker::do_purchase(1, u64::max_value()).unwrap();
+		let end = region.begin + 3;
+		let seller_balance = balance(1);
+
+		// Only Regions which may exist can be sought.
+		assert_noop!(
+			Broker::do_place_bid(2, RegionId { core: 1, ..region }, end, 10),
+			Error::<Test>::InvalidRegionPart
+		);
+		assert_noop!(
+			Broker::do_place_bid(2, region, end + 1, 10),
+			Error::<Test>::InvalidRegionPart
+		);
+
+		// Bids hold a deposit on top of the amount offered.
+		assert_ok!(Broker::do_place_bid(2, region, end, 10));
+		assert_eq!(balance(2), 980);
+		assert_eq!(held(2, HoldReason::MarketOrderDeposit), 10);
+		assert_ok!(Broker::do_cancel_bid(2, region, end));
+		assert_eq!(balance(2), 1000);
+
+		// Every interlaced part of a Region needs its own deposit to be listed.
+		let (left, right) =
+			Broker::do_interlace(region, Some(1), CoreMask::from_chunk(0, 40)).unwrap();
+		assert_ok!(Broker::do_list_region(left, 1, MarketPrice::Fixed(120), true));
+		assert_ok!(Broker::do_list_region(right, 1, MarketPrice::Fixed(120), true));
+		assert_eq!(balance(1), seller_balance - 20);
+		assert_eq!(held(1, HoldReason::MarketOrderDeposit), 20);
+		assert_ok!(Broker::do_delist_region(right, 1));
+		assert_eq!(balance(1), seller_balance - 10);
+
+		// Only the first of the three remainders of a partial sale keeps the deposit of the
+		// listing, the others need deposits of their own.
+		let part =
+			RegionId { begin: left.begin + 1, core: left.core, mask: CoreMask::from_chunk(0, 20) };
+		assert_ok!(Broker::do_purchase_listed(2, part, left.begin + 2, 20));
+		assert_eq!(balance(2), 980);
+		assert_eq!(balance(1), seller_balance + 20 - 30);
+		let listings = Listings::<Test>::get(region.core);
+		assert_eq!(listings.len(), 3);
+		assert!(listings.iter().all(|listing| listing.deposit == 10));
+
+		// All deposits are returned once the listings expire.
+		advance_to(10);
+		assert!(Listings::<Test>::get(region.core).is_empty());
+		assert_eq!(balance(1), seller_balance + 20);
+	});
+}
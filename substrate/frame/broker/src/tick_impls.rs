@@ -17,7 +17,11 @@
 
 use super::*;
 use alloc::{vec, vec::Vec};
-use frame_support::{pallet_prelude::*, traits::defensive_prelude::*, weights::WeightMeter};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{defensive_prelude::*, fungible::MutateHold, tokens::Precision::Exact},
+	weights::WeightMeter,
+};
 use sp_arithmetic::traits::{One, SaturatedConversion, Saturating, Zero};
 use sp_runtime::traits::{BlockNumberProvider, ConvertBack, MaybeConvert};
 use CompletionStatus::Complete;
@@ -32,6 +36,7 @@ impl<T: Config> Pallet<T> {
 	/// - Rotate the sale period
 	/// - Request revenue information for a previous timeslice
 	/// - Initialize an instantaneous core pool historical revenue record
+	/// - Expire and settle orders on the secondary market
 	pub(crate) fn do_tick() -> Weight {
 		let mut meter = WeightMeter::new();
 		meter.consume(T::WeightInfo::do_tick_base());
@@ -67,6 +72,8 @@ impl<T: Config> Pallet<T> {
 			for core in 0..status.core_count {
 				Self::process_core_schedule(commit_timeslice, rc_begin, core);
 				meter.consume(T::WeightInfo::process_core_schedule());
+				let processed = Self::process_market(commit_timeslice, core);
+				meter.consume(T::WeightInfo::process_market(processed));
 			}
 		}
 
@@ -383,4 +390,56 @@ impl<T: Config> Pallet<T> {
 
 		AutoRenewals::<T>::set(auto_renewals);
 	}
+
+	/// Expire the orders on the Regions of `core` which can no longer be fulfilled now that
+	/// `when` is committed, then settle any open bids which are satisfied by a listing.
+	///
+	/// Returns the number of orders expired or settled.
+	pub(crate) fn process_market(when: Timeslice, core: CoreIndex) -> u32 {
+		let mut listings = Listings::<T>::get(core);
+		let mut bids = Bids::<T>::get(core);
+		if listings.is_empty() && bids.is_empty() {
+			return 0
+		}
+		let orders = (listings.len() + bids.len()) as u32;
+
+		// Listed Regions whose last timeslice is committed have nothing left to sell.
+		listings.retain(|listing| {
+			if listing.end > when.saturating_add(1) {
+				return true
+			}
+			Self::do_transfer(listing.region_id, None, listing.seller.clone()).defensive_ok();
+			Self::release_market_deposit(&listing.seller, listing.deposit);
+			Self::deposit_event(Event::<T>::ListingExpired {
+				region_id: listing.region_id,
+				seller: listing.seller.clone(),
+			});
+			false
+		});
+		// Bids for Regions which have begun could only ever be partially fulfilled.
+		bids.retain(|bid| {
+			if bid.region_id.begin > when {
+				return true
+			}
+			T::Currency::release(&HoldReason::MarketBid.into(), &bid.bidder, bid.amount, Exact)
+				.defensive_ok();
+			Self::release_market_deposit(&bid.bidder, bid.deposit);
+			Self::deposit_event(Event::<T>::BidExpired {
+				who: bid.bidder.clone(),
+				region_id: bid.region_id,
+				end: bid.end,
+				amount: bid.amount,
+			});
+			false
+		});
+
+		let expired = orders.saturating_sub((listings.len() + bids.len()) as u32);
+
+		let now = RCBlockNumberProviderOf::<T::Coretime>::current_block_number();
+		let settled = Self::match_bids(&mut listings, &mut bids, now, T::MaxMarketOrders::get());
+
+		Listings::<T>::insert(core, &listings);
+		Bids::<T>::insert(core, &bids);
+		expired.saturating_add(settled)
+	}
 }
@@ -376,3 +376,70 @@ pub struct AutoRenewalRecord {
 	/// tasks to ensure that the renewal process does not begin until the lease expires.
 	pub next_renewal: Timeslice,
 }
+
+/// The pricing of a Region listed on the secondary market.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum MarketPrice<Balance, RelayBlockNumber> {
+	/// The whole Region is offered at a constant price.
+	Fixed(Balance),
+	/// The price of the whole Region falls linearly from `start_price` to `end_price` over
+	/// `duration` Relay-chain blocks from the time of listing, and stays at `end_price`
+	/// thereafter.
+	Dutch { start_price: Balance, end_price: Balance, duration: RelayBlockNumber },
+}
+pub type MarketPriceOf<T> = MarketPrice<BalanceOf<T>, RelayBlockNumberOf<T>>;
+
+/// A Region offered for sale on the secondary market. The Region is held in escrow by the pallet
+/// for as long as it is listed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ListingRecord<AccountId, Balance, RelayBlockNumber> {
+	/// The Region on offer.
+	pub region_id: RegionId,
+	/// The end of the Region on offer.
+	pub end: Timeslice,
+	/// The account which listed the Region and which receives the proceeds of its sale.
+	pub seller: AccountId,
+	/// The pricing of the whole Region. Parts of the Region are sold pro rata.
+	pub price: MarketPrice<Balance, RelayBlockNumber>,
+	/// The Relay-chain block at which the Region was listed; this is when a Dutch auction begins.
+	pub listed_at: RelayBlockNumber,
+	/// Whether parts of the Region may be sold separately.
+	pub allow_partial: bool,
+	/// The deposit held from the seller for as long as the listing is open.
+	pub deposit: Balance,
+}
+pub type ListingRecordOf<T> =
+	ListingRecord<<T as SConfig>::AccountId, BalanceOf<T>, RelayBlockNumberOf<T>>;
+
+/// Record of all Regions of a single core which are listed on the secondary market.
+pub type ListingsRecordOf<T> = BoundedVec<ListingRecordOf<T>, <T as Config>::MaxMarketOrders>;
+
+/// An offer to buy a Region, or a part of one, on the secondary market. The amount offered is on
+/// hold in the bidder's account until the bid is settled, cancelled or expires.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BidRecord<AccountId, Balance> {
+	/// The account which placed the bid and which receives the Region.
+	pub bidder: AccountId,
+	/// The identity of the Region sought.
+	pub region_id: RegionId,
+	/// The end of the Region sought.
+	pub end: Timeslice,
+	/// The most the bidder is willing to pay. Any amount not needed to settle is refunded.
+	pub amount: Balance,
+	/// The deposit held from the bidder for as long as the bid is open.
+	pub deposit: Balance,
+}
+pub type BidRecordOf<T> = BidRecord<<T as SConfig>::AccountId, BalanceOf<T>>;
+
+/// Record of all open bids on the Regions of a single core, in the order they were placed.
+pub type BidsRecordOf<T> = BoundedVec<BidRecordOf<T>, <T as Config>::MaxMarketOrders>;
@@ -16,17 +16,22 @@
 // limitations under the License.
 
 use super::*;
+use alloc::vec::Vec;
 use frame_support::{
 	pallet_prelude::*,
+	storage::with_storage_layer,
 	traits::{
-		fungible::Balanced,
-		tokens::{Fortitude::Polite, Precision::Exact, Preservation::Expendable},
+		defensive_prelude::*,
+		fungible::{Balanced, Mutate, MutateHold},
+		tokens::{
+			Fortitude::Polite, Precision::Exact, Preservation::Expendable, Restriction::Free,
+		},
 		OnUnbalanced,
 	},
 };
 use sp_arithmetic::{
-	traits::{SaturatedConversion, Saturating},
-	FixedPointNumber, FixedU64,
+	traits::{SaturatedConversion, Saturating, Zero},
+	FixedPointNumber, FixedU64, Perbill,
 };
 use sp_runtime::traits::{AccountIdConversion, BlockNumberProvider};
 
@@ -137,4 +142,250 @@ impl<T: Config> Pallet<T> {
 
 		Ok(Some((region_id, region)))
 	}
+
+	/// Put the deposit of a new order on the secondary market on hold in the account of `who`.
+	///
+	/// Returns the amount held.
+	pub(crate) fn hold_market_deposit(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
+		let deposit = T::MarketOrderDeposit::get();
+		if !deposit.is_zero() {
+			T::Currency::hold(&HoldReason::MarketOrderDeposit.into(), who, deposit)?;
+		}
+		Ok(deposit)
+	}
+
+	/// Release the `deposit` of an order of `who` which has left the secondary market.
+	pub(crate) fn release_market_deposit(who: &T::AccountId, deposit: BalanceOf<T>) {
+		if !deposit.is_zero() {
+			T::Currency::release(&HoldReason::MarketOrderDeposit.into(), who, deposit, Exact)
+				.defensive_ok();
+		}
+	}
+
+	/// The amount of Coretime in the Region `region_id` ending at `end`, measured in Core Mask
+	/// Bits over all of its timeslices.
+	pub(crate) fn coretime_amount(region_id: &RegionId, end: Timeslice) -> u64 {
+		u64::from(region_id.mask.count_ones())
+			.saturating_mul(end.saturating_sub(region_id.begin).into())
+	}
+
+	/// Whether `listing` offers the Region `region_id` ending at `end` for sale.
+	pub(crate) fn listing_covers(
+		listing: &ListingRecordOf<T>,
+		region_id: &RegionId,
+		end: Timeslice,
+	) -> bool {
+		if listing.region_id == *region_id && listing.end == end {
+			return true
+		}
+		listing.allow_partial &&
+			listing.region_id.core == region_id.core &&
+			listing.region_id.begin <= region_id.begin &&
+			end <= listing.end &&
+			(region_id.mask & !listing.region_id.mask).is_void()
+	}
+
+	/// The price at `now` of the Region `region_id` ending at `end` out of `listing`.
+	pub fn listing_price(
+		listing: &ListingRecordOf<T>,
+		region_id: &RegionId,
+		end: Timeslice,
+		now: RelayBlockNumberOf<T>,
+	) -> BalanceOf<T> {
+		let whole = match listing.price {
+			MarketPrice::Fixed(price) => price,
+			MarketPrice::Dutch { start_price, end_price, duration } => {
+				let elapsed = now.saturating_sub(listing.listed_at).min(duration);
+				let through = Perbill::from_rational(
+					elapsed.saturated_into::<u64>(),
+					duration.saturated_into::<u64>(),
+				);
+				start_price.saturating_sub(through * start_price.saturating_sub(end_price))
+			},
+		};
+		if listing.region_id == *region_id && listing.end == end {
+			return whole
+		}
+		Perbill::from_rational(
+			Self::coretime_amount(region_id, end),
+			Self::coretime_amount(&listing.region_id, listing.end),
+		) * whole
+	}
+
+	/// The index of the cheapest listing in `listings` which offers the Region `region_id` ending
+	/// at `end`, together with its price at `now`.
+	pub(crate) fn cheapest_listing(
+		listings: &ListingsRecordOf<T>,
+		region_id: &RegionId,
+		end: Timeslice,
+		now: RelayBlockNumberOf<T>,
+	) -> Option<(usize, BalanceOf<T>)> {
+		listings
+			.iter()
+			.enumerate()
+			.filter(|(_, listing)| Self::listing_covers(listing, region_id, end))
+			.map(|(index, listing)| (index, Self::listing_price(listing, region_id, end, now)))
+			.min_by_key(|(_, price)| *price)
+	}
+
+	/// Sell the Region `region_id` ending at `end` out of the listing at `index` of `listings` to
+	/// `buyer` for `price`, which is paid out of the funds `buyer` has on hold for a bid if
+	/// `from_bid`, or out of their free funds otherwise.
+	///
+	/// The parts of the listed Region which are not sold remain listed on the same terms, at a
+	/// pro rata price. `listings` is only changed once the sale can no longer fail.
+	pub(crate) fn sell_listed(
+		listings: &mut ListingsRecordOf<T>,
+		index: usize,
+		buyer: T::AccountId,
+		region_id: RegionId,
+		end: Timeslice,
+		price: BalanceOf<T>,
+		from_bid: bool,
+	) -> DispatchResult {
+		let listing = listings.get(index).cloned().ok_or(Error::<T>::NotListed)?;
+		if from_bid {
+			T::Currency::transfer_on_hold(
+				&HoldReason::MarketBid.into(),
+				&buyer,
+				&listing.seller,
+				price,
+				Exact,
+				Free,
+				Polite,
+			)?;
+		} else {
+			T::Currency::transfer(&buyer, &listing.seller, price, Expendable)?;
+		}
+
+		// Carve the Region sought out of the listed Region.
+		let mut remainders = Vec::new();
+		let mut current = listing.region_id;
+		let mut current_end = listing.end;
+		if region_id.begin > current.begin {
+			let (before, rest) =
+				Self::do_partition(current, None, region_id.begin.saturating_sub(current.begin))?;
+			remainders.push((before, region_id.begin));
+			current = rest;
+		}
+		if end < current_end {
+			let (rest, after) =
+				Self::do_partition(current, None, end.saturating_sub(current.begin))?;
+			remainders.push((after, current_end));
+			current = rest;
+			current_end = end;
+		}
+		if region_id.mask != current.mask {
+			let (rest, other) = Self::do_interlace(current, None, region_id.mask)?;
+			remainders.push((other, current_end));
+			current = rest;
+		}
+		Self::do_transfer(current, None, buyer.clone())?;
+
+		listings.remove(index);
+		let whole = Self::coretime_amount(&listing.region_id, listing.end);
+		// The first remainder keeps the deposit of the listing, any other needs a deposit of its
+		// own so that partial sales can't be used to fill up the listings.
+		let mut inherited = Some(listing.deposit);
+		for (remainder, remainder_end) in remainders {
+			let proportion =
+				Perbill::from_rational(Self::coretime_amount(&remainder, remainder_end), whole);
+			let price = match listing.price {
+				MarketPrice::Fixed(price) => MarketPrice::Fixed(proportion * price),
+				MarketPrice::Dutch { start_price, end_price, duration } => MarketPrice::Dutch {
+					start_price: proportion * start_price,
+					end_price: proportion * end_price,
+					duration,
+				},
+			};
+			let relisted = match inherited.take() {
+				Some(deposit) => Ok(deposit),
+				None => Self::hold_market_deposit(&listing.seller).map_err(|_| ()),
+			}
+			.and_then(|deposit| {
+				let record = ListingRecord {
+					region_id: remainder,
+					end: remainder_end,
+					price,
+					deposit,
+					..listing.clone()
+				};
+				listings
+					.try_push(record)
+					.map_err(|_| Self::release_market_deposit(&listing.seller, deposit))
+			});
+			if relisted.is_err() {
+				// No room or deposit to keep the remainder listed; give it back to the seller
+				// instead.
+				Self::do_transfer(remainder, None, listing.seller.clone()).defensive_ok();
+				Self::deposit_event(Event::Delisted {
+					region_id: remainder,
+					seller: listing.seller.clone(),
+				});
+			}
+		}
+
+		if let Some(deposit) = inherited {
+			Self::release_market_deposit(&listing.seller, deposit);
+		}
+
+		Self::deposit_event(Event::Sold {
+			region_id: current,
+			duration: end.saturating_sub(current.begin),
+			seller: listing.seller,
+			buyer,
+			price,
+		});
+		Ok(())
+	}
+
+	/// Settle up to `limit` of the open `bids` against the `listings` which satisfy them at `now`,
+	/// in the order the bids were placed. Each bid is settled at the price of the cheapest
+	/// satisfying listing and the rest of the amount on hold is released.
+	///
+	/// Returns the number of bids settled. It is the responsibility of the caller to write back
+	/// the changed `listings` and `bids` to storage.
+	pub(crate) fn match_bids(
+		listings: &mut ListingsRecordOf<T>,
+		bids: &mut BidsRecordOf<T>,
+		now: RelayBlockNumberOf<T>,
+		limit: u32,
+	) -> u32 {
+		let mut settled = 0;
+		let mut i = 0;
+		while i < bids.len() && settled < limit {
+			let bid = bids[i].clone();
+			let Some((index, price)) =
+				Self::cheapest_listing(listings, &bid.region_id, bid.end, now)
+					.filter(|(_, price)| *price <= bid.amount)
+			else {
+				i += 1;
+				continue
+			};
+			let result = with_storage_layer(|| -> DispatchResult {
+				let surplus = bid.amount.saturating_sub(price);
+				if !surplus.is_zero() {
+					T::Currency::release(
+						&HoldReason::MarketBid.into(),
+						&bid.bidder,
+						surplus,
+						Exact,
+					)?;
+				}
+				Self::release_market_deposit(&bid.bidder, bid.deposit);
+				Self::sell_listed(listings, index, bid.bidder, bid.region_id, bid.end, price, true)
+			});
+			match result {
+				Ok(()) => {
+					bids.remove(i);
+					settled.saturating_inc();
+				},
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Failed to settle bid: {:?}", e);
+					i += 1;
+				},
+			}
+		}
+		settled
+	}
 }
@@ -45,6 +45,7 @@ where
 		settings: CollectionSettings::all_enabled(),
 		max_supply: None,
 		mint_settings: MintSettings::default(),
+		max_royalty: 0,
 	}
 }
 
@@ -122,6 +122,10 @@ pub mod pallet {
 				CollectionId = Self::NftCollectionId,
 			> + Transfer<Self::AccountId>;
 
+		/// The NFTs whose sales carry royalties. They can't be fractionalized, as trading their
+		/// fractions would bypass the royalties.
+		type RoyaltyBearingNfts: Contains<(Self::NftCollectionId, Self::NftId)>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -186,6 +190,8 @@ pub mod pallet {
 		NftNotFound,
 		/// NFT has not yet been fractionalised.
 		NftNotFractionalized,
+		/// NFT is subject to royalties, which trading its fractions would bypass.
+		NftBearsRoyalties,
 	}
 
 	/// A reason for the pallet placing a hold on funds.
@@ -201,7 +207,8 @@ pub mod pallet {
 		/// Lock the NFT and mint a new fungible asset.
 		///
 		/// The dispatch origin for this call must be Signed.
-		/// The origin must be the owner of the NFT they are trying to lock.
+		/// The origin must be the owner of the NFT they are trying to lock. NFTs whose sales carry
+		/// royalties can't be locked.
 		///
 		/// `Deposit` funds of sender are reserved.
 		///
@@ -231,6 +238,10 @@ pub mod pallet {
 			let nft_owner =
 				T::Nfts::owner(&nft_collection_id, &nft_id).ok_or(Error::<T>::NftNotFound)?;
 			ensure!(nft_owner == who, Error::<T>::NoPermission);
+			ensure!(
+				!T::RoyaltyBearingNfts::contains(&(nft_collection_id, nft_id)),
+				Error::<T>::NftBearsRoyalties
+			);
 
 			let pallet_account = Self::get_pallet_account();
 			let deposit = T::Deposit::get();
@@ -103,6 +103,7 @@ impl pallet_nfts::Config for Test {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = ConstU64<10000>;
 	type MaxAttributesPerCall = ConstU32<2>;
+	type MaxRoyaltyRecipients = ConstU32<3>;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
@@ -132,6 +133,7 @@ impl Config for Test {
 	type AssetId = <Self as pallet_assets::Config>::AssetId;
 	type Assets = Assets;
 	type Nfts = Nfts;
+	type RoyaltyBearingNfts = pallet_nfts::RoyaltyBearing<Test>;
 	type PalletId = NftFractionalizationPalletId;
 	type WeightInfo = ();
 	type StringLimit = StringLimit;
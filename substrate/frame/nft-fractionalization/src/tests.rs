@@ -24,7 +24,7 @@ use fungible::{hold::Inspect as InspectHold, Mutate as MutateFungible};
 use fungibles::{metadata::Inspect, InspectEnumerable};
 use TokenError::FundsUnavailable;
 
-use pallet_nfts::CollectionConfig;
+use pallet_nfts::{CollectionConfig, RoyaltyRecipient};
 
 fn assets() -> Vec<u32> {
 	let mut s: Vec<_> = <<Test as Config>::Assets>::asset_ids().collect();
@@ -307,3 +307,58 @@ fn unify_should_work() {
 		assert_eq!(Nfts::owner(nft_collection_id, nft_id), Some(account(2)));
 	});
 }
+
+#[test]
+fn fractionalize_should_not_bypass_royalties() {
+	new_test_ext().execute_with(|| {
+		let nft_collection_id = 0;
+		let nft_id = 0;
+		let asset_id = 0;
+		let fractions = 1000;
+
+		Balances::set_balance(&account(1), 100);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			account(1),
+			CollectionConfig { max_royalty: 1_000, ..Default::default() },
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(account(1)),
+			nft_collection_id,
+			nft_id,
+			account(1),
+			None,
+		));
+
+		// royalties could be set on the collection's items at any time
+		assert_noop!(
+			NftFractionalization::fractionalize(
+				RuntimeOrigin::signed(account(1)),
+				nft_collection_id,
+				nft_id,
+				asset_id,
+				account(2),
+				fractions,
+			),
+			Error::<Test>::NftBearsRoyalties
+		);
+
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(account(1)),
+			nft_collection_id,
+			vec![RoyaltyRecipient { account: account(5), rate: 500 }].try_into().unwrap(),
+		));
+		assert_noop!(
+			NftFractionalization::fractionalize(
+				RuntimeOrigin::signed(account(1)),
+				nft_collection_id,
+				nft_id,
+				asset_id,
+				account(2),
+				fractions,
+			),
+			Error::<Test>::NftBearsRoyalties
+		);
+	});
+}
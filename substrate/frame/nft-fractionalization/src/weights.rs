@@ -99,13 +99,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(140), added: 2615, mode: `MaxEncodedLen`)
 	/// Storage: `NftFractionalization::NftToAsset` (r:0 w:1)
 	/// Proof: `NftFractionalization::NftToAsset` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
 	fn fractionalize() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `364`
-		//  Estimated: `4326`
-		// Minimum execution time: 173_042_000 picoseconds.
-		Weight::from_parts(176_398_000, 4326)
-			.saturating_add(T::DbWeight::get().reads(9_u64))
+		// Not benchmarked: estimated as the last run plus the royalty checks.
+		Weight::from_parts(184_398_000, 12228)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
 	/// Storage: `NftFractionalization::NftToAsset` (r:1 w:1)
@@ -132,14 +135,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn unify() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1174`
-		//  Estimated: `4326`
-		// Minimum execution time: 124_038_000 picoseconds.
-		Weight::from_parts(127_219_000, 4326)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(130_219_000, 4326)
 			.saturating_add(T::DbWeight::get().reads(9_u64))
-			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
 	}
 }
 
@@ -165,13 +169,16 @@ impl WeightInfo for () {
 	/// Proof: `Assets::Metadata` (`max_values`: None, `max_size`: Some(140), added: 2615, mode: `MaxEncodedLen`)
 	/// Storage: `NftFractionalization::NftToAsset` (r:0 w:1)
 	/// Proof: `NftFractionalization::NftToAsset` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRoyaltiesOf` (`max_values`: None, `max_size`: Some(211), added: 2686, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionRoyaltiesOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoyaltiesOf` (`max_values`: None, `max_size`: Some(191), added: 2666, mode: `MaxEncodedLen`)
 	fn fractionalize() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `364`
-		//  Estimated: `4326`
-		// Minimum execution time: 173_042_000 picoseconds.
-		Weight::from_parts(176_398_000, 4326)
-			.saturating_add(RocksDbWeight::get().reads(9_u64))
+		// Not benchmarked: estimated as the last run plus the royalty checks.
+		Weight::from_parts(184_398_000, 12228)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
 			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
 	/// Storage: `NftFractionalization::NftToAsset` (r:1 w:1)
@@ -198,13 +205,14 @@ impl WeightInfo for () {
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemPriceRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemPriceRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingSwapRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingSwapRoyaltyOf` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
 	fn unify() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1174`
-		//  Estimated: `4326`
-		// Minimum execution time: 124_038_000 picoseconds.
-		Weight::from_parts(127_219_000, 4326)
+		// Not benchmarked: estimated as the last run plus the royalty snapshot accesses.
+		Weight::from_parts(130_219_000, 4326)
 			.saturating_add(RocksDbWeight::get().reads(9_u64))
-			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
 	}
 }
@@ -189,6 +189,18 @@ fn add_collection_attribute<T: Config<I>, I: 'static>(
 	(key, caller, caller_lookup)
 }
 
+fn royalty_recipients<T: Config<I>, I: 'static>(n: u32) -> RoyaltyRecipientsOf<T, I> {
+	(0..n)
+		.map(|i| {
+			let account: T::AccountId = account("recipient", i, SEED);
+			T::Currency::make_free_balance_be(&account, T::Currency::minimum_balance());
+			RoyaltyRecipient { account, rate: 1 }
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -204,6 +216,7 @@ fn make_collection_config<T: Config<I>, I: 'static>(
 		settings: CollectionSettings::from_disabled(disable_settings),
 		max_supply: None,
 		mint_settings: MintSettings::default(),
+		max_royalty: MAX_ROYALTY_RATE,
 	}
 }
 
@@ -666,7 +679,10 @@ benchmarks_instance_pallet! {
 		let (item, ..) = mint_item::<T, I>(0);
 		let buyer: T::AccountId = account("buyer", 0, SEED);
 		let buyer_lookup = T::Lookup::unlookup(buyer.clone());
-		let price = ItemPrice::<T, I>::from(0u32);
+		let price = ItemPrice::<T, I>::from(MAX_ROYALTY_RATE as u32);
+		let recipients = royalty_recipients::<T, I>(T::MaxRoyaltyRecipients::get());
+		CollectionRoyaltiesOf::<T, I>::insert(collection, recipients);
+		T::Currency::make_free_balance_be(&seller, T::Currency::minimum_balance());
 		let origin = SystemOrigin::Signed(seller.clone()).into();
 		Nfts::<T, I>::set_price(origin, collection, item, Some(price), Some(buyer_lookup))?;
 		T::Currency::make_free_balance_be(&buyer, DepositBalanceOf::<T, I>::max_value());
@@ -753,13 +769,16 @@ benchmarks_instance_pallet! {
 		let (collection, caller, _) = create_collection::<T, I>();
 		let (item1, ..) = mint_item::<T, I>(0);
 		let (item2, ..) = mint_item::<T, I>(1);
-		let price = ItemPrice::<T, I>::from(0u32);
+		let price = ItemPrice::<T, I>::from(MAX_ROYALTY_RATE as u32);
 		let price_direction = PriceDirection::Receive;
 		let price_with_direction = PriceWithDirection { amount: price, direction: price_direction };
 		let duration = T::MaxDeadlineDuration::get();
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		let recipients = royalty_recipients::<T, I>(T::MaxRoyaltyRecipients::get());
+		ItemRoyaltiesOf::<T, I>::insert(collection, item1, recipients);
+		T::Currency::make_free_balance_be(&target, DepositBalanceOf::<T, I>::max_value());
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
 		let origin = SystemOrigin::Signed(caller.clone());
 		T::BlockNumberProvider::set_block_number(One::one());
 		Nfts::<T, I>::transfer(origin.clone().into(), collection, item2, target_lookup)?;
@@ -787,6 +806,25 @@ benchmarks_instance_pallet! {
 		}.into());
 	}
 
+	set_collection_royalties {
+		let n in 0 .. T::MaxRoyaltyRecipients::get();
+		let (collection, caller, _) = create_collection::<T, I>();
+		let recipients = royalty_recipients::<T, I>(n);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, recipients.clone())
+	verify {
+		assert_last_event::<T, I>(Event::CollectionRoyaltiesSet { collection, recipients }.into());
+	}
+
+	set_item_royalties {
+		let n in 0 .. T::MaxRoyaltyRecipients::get();
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let recipients = royalty_recipients::<T, I>(n);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, recipients.clone())
+	verify {
+		assert_last_event::<T, I>(Event::ItemRoyaltiesSet { collection, item, recipients }.into());
+	}
+
 	mint_pre_signed {
 		let n in 0 .. T::MaxAttributesPerCall::get() as u32;
 		let (caller_public, caller) = T::Helper::signer();
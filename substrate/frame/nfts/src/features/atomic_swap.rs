@@ -36,8 +36,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// `maybe_desired_item` is `Some`, the specified item is expected in return for the swap. If
 	/// `maybe_desired_item` is `None`, it indicates that any item from the `desired_collection` can
 	/// be offered in return. The `maybe_price` specifies an optional price for the swap. If
-	/// specified, the other party must offer the specified `price` or higher for the swap. After
-	/// creating the swap, the function emits the `SwapCreated` event.
+	/// specified, the other party must offer the specified `price` or higher for the swap. The
+	/// royalties due on the offered item when its owner gets paid are capped at the rate which
+	/// applies to it now. After creating the swap, the function emits the `SwapCreated` event.
 	///
 	/// - `caller`: The account creating the swap offer, which must be the owner of the item.
 	/// - `offered_collection_id`: The collection ID containing the offered item.
@@ -89,6 +90,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				deadline,
 			},
 		);
+		PendingSwapRoyaltyOf::<T, I>::insert(
+			&offered_collection_id,
+			&offered_item_id,
+			Self::royalty_rate(&offered_collection_id, &offered_item_id),
+		);
 
 		Self::deposit_event(Event::SwapCreated {
 			offered_collection: offered_collection_id,
@@ -127,6 +133,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 
 		PendingSwapOf::<T, I>::remove(&offered_collection_id, &offered_item_id);
+		PendingSwapRoyaltyOf::<T, I>::remove(&offered_collection_id, &offered_item_id);
 
 		Self::deposit_event(Event::SwapCancelled {
 			offered_collection: offered_collection_id,
@@ -146,8 +153,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// `send_item_id`, `receive_collection_id`, and `receive_item_id`. The `caller` account must be
 	/// the owner of the item specified by `send_collection_id` and `send_item_id`. If the claimed
 	/// swap has an associated `price`, it will be transferred between the owners of the two items
-	/// based on the `price.direction`. After the swap is completed, the function emits the
-	/// `SwapClaimed` event.
+	/// based on the `price.direction`, less the royalties of the item whose owner gets paid, up to
+	/// the rate which applied to the offered item when the swap was created. After
+	/// the swap is completed, the function emits the `SwapClaimed` event.
 	///
 	/// - `caller`: The account claiming the swap offer, which must be the owner of the sent item.
 	/// - `send_collection_id`: The identifier of the collection containing the item being sent.
@@ -191,20 +199,28 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		ensure!(now <= swap.deadline, Error::<T, I>::DeadlineExpired);
 
 		if let Some(ref price) = swap.price {
-			match price.direction {
-				PriceDirection::Send => T::Currency::transfer(
+			// The royalties are due on the item whose owner gets paid. The caller accepts those of
+			// the item they send, while the owner of the offered item accepted those which applied
+			// when the swap was created.
+			let (payer, payee, collection, item, max_rate) = match price.direction {
+				PriceDirection::Send => (
 					&receive_item.owner,
 					&send_item.owner,
-					price.amount,
-					KeepAlive,
-				)?,
-				PriceDirection::Receive => T::Currency::transfer(
+					send_collection_id,
+					send_item_id,
+					MAX_ROYALTY_RATE,
+				),
+				PriceDirection::Receive => (
 					&send_item.owner,
 					&receive_item.owner,
-					price.amount,
-					KeepAlive,
-				)?,
+					receive_collection_id,
+					receive_item_id,
+					PendingSwapRoyaltyOf::<T, I>::get(&receive_collection_id, &receive_item_id),
+				),
 			};
+			let royalties =
+				Self::do_pay_royalties(collection, item, payer, price.amount, max_rate)?;
+			T::Currency::transfer(payer, payee, price.amount.saturating_sub(royalties), KeepAlive)?;
 		}
 
 		// This also removes the swap.
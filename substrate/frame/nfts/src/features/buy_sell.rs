@@ -60,7 +60,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// This function is used to set the price and whitelist a buyer for an item in the
 	/// specified `collection`. The `sender` account must be the owner of the item. The item's price
 	/// and the whitelisted buyer can be set to allow trading the item. If `price` is `None`, the
-	/// item will be marked as not for sale.
+	/// item will be marked as not for sale. The royalties paid when the item is bought are capped
+	/// at the rate which applies to it now.
 	///
 	/// - `collection`: The identifier of the collection containing the item.
 	/// - `item`: The identifier of the item for which the price and whitelist information will be
@@ -98,6 +99,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		if let Some(ref price) = price {
 			ItemPriceOf::<T, I>::insert(&collection, &item, (price, whitelisted_buyer.clone()));
+			ItemPriceRoyaltyOf::<T, I>::insert(
+				&collection,
+				&item,
+				Self::royalty_rate(&collection, &item),
+			);
 			Self::deposit_event(Event::ItemPriceSet {
 				collection,
 				item,
@@ -106,6 +112,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			});
 		} else {
 			ItemPriceOf::<T, I>::remove(&collection, &item);
+			ItemPriceRoyaltyOf::<T, I>::remove(&collection, &item);
 			Self::deposit_event(Event::ItemPriceRemoved { collection, item });
 		}
 
@@ -115,11 +122,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Buys the specified item from the collection.
 	///
 	/// This function is used to buy an item from the specified `collection`. The `buyer` account
-	/// will attempt to buy the item with the provided `bid_price`. The item's current owner will
-	/// receive the bid price if it is equal to or higher than the item's set price. If
-	/// `whitelisted_buyer` is specified in the item's price information, only that account is
-	/// allowed to buy the item. If the item is not for sale, or the bid price is too low, the
-	/// function will return an error.
+	/// will attempt to buy the item with the provided `bid_price`. If the bid price is equal to or
+	/// higher than the item's set price, the royalties of the item, up to the rate which applied
+	/// when the price was set, are paid out of the set price and the item's current owner receives
+	/// the rest. If `whitelisted_buyer` is specified in the item's price information, only that
+	/// account is allowed to buy the item. If the item is not for sale, or the bid price is too
+	/// low, the function will return an error.
 	///
 	/// - `collection`: The identifier of the collection containing the item to be bought.
 	/// - `item`: The identifier of the item to be bought.
@@ -148,10 +156,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(only_buyer == buyer, Error::<T, I>::NoPermission);
 		}
 
+		let max_rate = ItemPriceRoyaltyOf::<T, I>::get(&collection, &item);
+		let royalties = Self::do_pay_royalties(collection, item, &buyer, price_info.0, max_rate)?;
 		T::Currency::transfer(
 			&buyer,
 			&details.owner,
-			price_info.0,
+			price_info.0.saturating_sub(royalties),
 			ExistenceRequirement::KeepAlive,
 		)?;
 
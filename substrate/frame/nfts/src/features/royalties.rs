@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module contains helper functions to configure and pay the royalties of collections and
+//! items of the NFTs pallet.
+//! The bitflag [`PalletFeature::Trading`] needs to be set in the [`Config::Features`] for NFTs
+//! to have the functionality defined in this module.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+use sp_runtime::Permill;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Set the royalties paid on sales of the items of a collection.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check permissions.
+	/// - `collection`: The ID of the collection for which to set the royalties.
+	/// - `recipients`: The accounts entitled to a share of each sale, with their rates. An empty
+	///   list clears the royalties.
+	///
+	/// This function checks that the caller, if given, is the owner of the collection and that
+	/// the rates of the `recipients` add up to no more than the `max_royalty` of the collection
+	/// configuration. It emits a `CollectionRoyaltiesSet` event.
+	pub(crate) fn do_set_collection_royalties(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		recipients: RoyaltyRecipientsOf<T, I>,
+	) -> DispatchResult {
+		Self::ensure_royalties_settable(maybe_check_owner.as_ref(), &collection, &recipients)?;
+
+		if recipients.is_empty() {
+			CollectionRoyaltiesOf::<T, I>::remove(&collection);
+		} else {
+			CollectionRoyaltiesOf::<T, I>::insert(&collection, &recipients);
+		}
+		Self::deposit_event(Event::CollectionRoyaltiesSet { collection, recipients });
+		Ok(())
+	}
+
+	/// Set the royalties paid on sales of an item, overriding those of its collection.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check permissions.
+	/// - `collection`: The ID of the collection of the item.
+	/// - `item`: The ID of the item for which to set the royalties.
+	/// - `recipients`: The accounts entitled to a share of each sale, with their rates. An empty
+	///   list clears the royalties, so that those of the collection apply again.
+	///
+	/// This function checks that the item exists, that the caller, if given, is the owner of the
+	/// collection and that the rates of the `recipients` add up to no more than the `max_royalty`
+	/// of the collection configuration. It emits an `ItemRoyaltiesSet` event.
+	pub(crate) fn do_set_item_royalties(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		recipients: RoyaltyRecipientsOf<T, I>,
+	) -> DispatchResult {
+		ensure!(Item::<T, I>::contains_key(&collection, &item), Error::<T, I>::UnknownItem);
+		Self::ensure_royalties_settable(maybe_check_owner.as_ref(), &collection, &recipients)?;
+
+		if recipients.is_empty() {
+			ItemRoyaltiesOf::<T, I>::remove(&collection, &item);
+		} else {
+			ItemRoyaltiesOf::<T, I>::insert(&collection, &item, &recipients);
+		}
+		Self::deposit_event(Event::ItemRoyaltiesSet { collection, item, recipients });
+		Ok(())
+	}
+
+	/// Pay the royalties due on the sale of an item for `price`.
+	///
+	/// - `collection`: The ID of the collection of the item sold.
+	/// - `item`: The ID of the item sold.
+	/// - `payer`: The account paying for the item.
+	/// - `price`: The price the item was sold for.
+	/// - `max_rate`: The highest total royalty rate the seller agreed to.
+	///
+	/// The royalties of the item apply if set, those of its collection otherwise. Each recipient
+	/// is paid its share of `price` by the `payer`, until `max_rate` of it is paid, and a
+	/// `RoyaltyPaid` event is emitted. A share too small to create the account of its recipient is
+	/// left to the seller. Returns the total amount paid, which the caller should deduct from what
+	/// the seller receives.
+	pub(crate) fn do_pay_royalties(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		payer: &T::AccountId,
+		price: ItemPrice<T, I>,
+		max_rate: RoyaltyRate,
+	) -> Result<ItemPrice<T, I>, DispatchError> {
+		let Some(recipients) = Self::royalties(&collection, &item) else { return Ok(Zero::zero()) };
+
+		let cap = Self::royalty_share(max_rate.min(MAX_ROYALTY_RATE), price);
+		let mut paid: ItemPrice<T, I> = Zero::zero();
+		for RoyaltyRecipient { account, rate } in recipients {
+			let amount = Self::royalty_share(rate, price).min(cap.saturating_sub(paid));
+			if amount.is_zero() ||
+				(amount < T::Currency::minimum_balance() &&
+					T::Currency::total_balance(&account).is_zero())
+			{
+				continue
+			}
+			T::Currency::transfer(payer, &account, amount, KeepAlive)?;
+			paid.saturating_accrue(amount);
+			Self::deposit_event(Event::RoyaltyPaid {
+				collection,
+				item,
+				recipient: account,
+				amount,
+			});
+		}
+		Ok(paid)
+	}
+
+	/// The royalties which apply to an item: its own if set, those of its collection otherwise.
+	pub(crate) fn royalties(
+		collection: &T::CollectionId,
+		item: &T::ItemId,
+	) -> Option<RoyaltyRecipientsOf<T, I>> {
+		ItemRoyaltiesOf::<T, I>::get(collection, item)
+			.or_else(|| CollectionRoyaltiesOf::<T, I>::get(collection))
+	}
+
+	/// The total rate of the royalties which apply to an item.
+	pub(crate) fn royalty_rate(collection: &T::CollectionId, item: &T::ItemId) -> RoyaltyRate {
+		Self::royalties(collection, item).map_or(0, |recipients| {
+			recipients
+				.iter()
+				.fold(0 as RoyaltyRate, |total, recipient| total.saturating_add(recipient.rate))
+		})
+	}
+
+	/// The share of `price` due at the royalty `rate`.
+	fn royalty_share(rate: RoyaltyRate, price: ItemPrice<T, I>) -> ItemPrice<T, I> {
+		Permill::from_parts(u32::from(rate).saturating_mul(100)) * price
+	}
+
+	/// Ensure that royalties paid to `recipients` may be set on the `collection` or its items.
+	fn ensure_royalties_settable(
+		maybe_check_owner: Option<&T::AccountId>,
+		collection: &T::CollectionId,
+		recipients: &RoyaltyRecipientsOf<T, I>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Trading),
+			Error::<T, I>::MethodDisabled
+		);
+
+		let details =
+			Collection::<T, I>::get(collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		let collection_config = Self::get_collection_config(collection)?;
+		let total = recipients
+			.iter()
+			.try_fold(0 as RoyaltyRate, |total, recipient| total.checked_add(recipient.rate))
+			.ok_or(Error::<T, I>::RoyaltyTooHigh)?;
+		ensure!(
+			total <= collection_config.max_royalty.min(MAX_ROYALTY_RATE),
+			Error::<T, I>::RoyaltyTooHigh
+		);
+		Ok(())
+	}
+}
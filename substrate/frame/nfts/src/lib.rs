@@ -79,7 +79,7 @@ pub mod pallet {
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -217,6 +217,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttributesPerCall: Get<u32>;
 
+		/// The max number of accounts which could share the royalties of a collection or an item.
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
+
 		/// Disables some of pallet's features.
 		#[pallet::constant]
 		type Features: Get<PalletFeatures>;
@@ -362,6 +366,36 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The total royalty rate which applied to an item when its price was set. The royalties paid
+	/// when the item is bought are capped at this rate.
+	#[pallet::storage]
+	pub type ItemPriceRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRate,
+		ValueQuery,
+	>;
+
+	/// The royalties paid on sales of the items of a collection.
+	#[pallet::storage]
+	pub type CollectionRoyaltiesOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, RoyaltyRecipientsOf<T, I>, OptionQuery>;
+
+	/// The royalties paid on sales of an item, overriding those of its collection.
+	#[pallet::storage]
+	pub type ItemRoyaltiesOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRecipientsOf<T, I>,
+		OptionQuery,
+	>;
+
 	/// Item attribute approvals.
 	#[pallet::storage]
 	pub type ItemAttributesApprovalsOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
@@ -397,6 +431,20 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The total royalty rate which applied to an offered item when its swap was created. The
+	/// royalties paid when the swap is claimed and the offered item's owner gets paid are capped at
+	/// this rate.
+	#[pallet::storage]
+	pub type PendingSwapRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRate,
+		ValueQuery,
+	>;
+
 	/// Config of a collection.
 	#[pallet::storage]
 	pub type CollectionConfigOf<T: Config<I>, I: 'static = ()> =
@@ -594,6 +642,24 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// The royalties of a `collection` were set. Empty `recipients` means they were cleared.
+		CollectionRoyaltiesSet {
+			collection: T::CollectionId,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		},
+		/// The royalties of an `item` were set. Empty `recipients` means they were cleared.
+		ItemRoyaltiesSet {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		},
+		/// A royalty was paid on the sale of an `item`.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipient: T::AccountId,
+			amount: ItemPrice<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -688,6 +754,8 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The total royalty rate exceeds the maximum allowed by the collection's config.
+		RoyaltyTooHigh,
 	}
 
 	#[pallet::call]
@@ -1931,6 +1999,58 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set (or clear) the royalties paid on sales of the items of a collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// - `collection`: The identifier of the collection to change.
+		/// - `recipients`: The accounts which receive a share of each sale, with their rates in
+		///   basis points. The rates must not add up to more than the `max_royalty` of the
+		///   collection's config. An empty list clears the royalties.
+		///
+		/// Emits `CollectionRoyaltiesSet` event when successful.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_collection_royalties(recipients.len() as u32))]
+		pub fn set_collection_royalties(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_collection_royalties(maybe_check_owner, collection, recipients)
+		}
+
+		/// Set (or clear) the royalties paid on sales of an item, overriding those of its
+		/// collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item to change.
+		/// - `recipients`: The accounts which receive a share of each sale, with their rates in
+		///   basis points. The rates must not add up to more than the `max_royalty` of the
+		///   collection's config. An empty list clears the royalties, so that those of the
+		///   collection apply again.
+		///
+		/// Emits `ItemRoyaltiesSet` event when successful.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::set_item_royalties(recipients.len() as u32))]
+		pub fn set_item_royalties(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_item_royalties(maybe_check_owner, collection, item, recipients)
+		}
 	}
 }
 
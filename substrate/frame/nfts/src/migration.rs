@@ -118,3 +118,80 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use frame_support::{
+		migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+		weights::Weight,
+	};
+
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct OldCollectionConfig<Price, BlockNumber, CollectionId> {
+		pub settings: CollectionSettings,
+		pub max_supply: Option<u32>,
+		pub mint_settings: MintSettings<Price, BlockNumber, CollectionId>,
+	}
+
+	impl<Price, BlockNumber, CollectionId> OldCollectionConfig<Price, BlockNumber, CollectionId> {
+		/// Migrates the old collection config to the new v2 format, with royalties disabled.
+		fn migrate_to_v2(self) -> CollectionConfig<Price, BlockNumber, CollectionId> {
+			CollectionConfig {
+				settings: self.settings,
+				max_supply: self.max_supply,
+				mint_settings: self.mint_settings,
+				max_royalty: 0,
+			}
+		}
+	}
+
+	/// Migrates the collection configs to the v2 format, without checking the storage version.
+	///
+	/// Use [`MigrateToV2`] instead, which only runs it on storage version 1.
+	pub struct UncheckedMigrateToV2<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			CollectionConfigOf::<T>::translate::<
+				OldCollectionConfig<BalanceOf<T>, BlockNumberFor<T>, T::CollectionId>,
+				_,
+			>(|_, old_value| {
+				translated.saturating_inc();
+				Some(old_value.migrate_to_v2())
+			});
+
+			log::info!(target: LOG_TARGET, "Upgraded {} collection configs to v2", translated);
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let prev_count = CollectionConfigOf::<T>::iter_keys().count();
+			Ok((prev_count as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(prev_count: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			let post_count = CollectionConfigOf::<T>::iter().count() as u32;
+			ensure!(
+				prev_count == post_count,
+				"the records count before and after the migration should be the same"
+			);
+
+			Ok(())
+		}
+	}
+
+	/// A migration utility to update the storage version from v1 to v2 for the pallet.
+	pub type MigrateToV2<T> = VersionedMigration<
+		1,
+		2,
+		UncheckedMigrateToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -123,6 +123,7 @@ fn collection_config_from_disabled_settings(
 		settings: CollectionSettings::from_disabled(settings),
 		max_supply: None,
 		mint_settings: MintSettings::default(),
+		max_royalty: 0,
 	}
 }
 
@@ -131,6 +132,7 @@ fn collection_config_with_all_settings_enabled() -> CollectionConfigFor<Test> {
 		settings: CollectionSettings::all_enabled(),
 		max_supply: None,
 		mint_settings: MintSettings::default(),
+		max_royalty: 0,
 	}
 }
 
@@ -3039,6 +3041,431 @@ fn claim_swap_should_work() {
 	});
 }
 
+#[test]
+fn royalties_are_paid_on_buy_item() {
+	new_test_ext().execute_with(|| {
+		let creator = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let artist = account(4);
+		let curator = account(5);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 1000;
+		let initial_balance = 10000;
+
+		Balances::make_free_balance_be(&creator, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			creator.clone(),
+			CollectionConfig { max_royalty: 1_000, ..default_collection_config() }
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None
+		));
+
+		// only the owner may set royalties, and no higher than `max_royalty`
+		let recipients: RoyaltyRecipientsOf<Test> =
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 500 }];
+		assert_noop!(
+			Nfts::set_collection_royalties(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				recipients.clone()
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::set_collection_royalties(
+				RuntimeOrigin::signed(creator.clone()),
+				collection_id,
+				bvec![
+					RoyaltyRecipient { account: artist.clone(), rate: 600 },
+					RoyaltyRecipient { account: curator.clone(), rate: 401 },
+				]
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			recipients.clone()
+		));
+		assert!(events().contains(&Event::<Test>::CollectionRoyaltiesSet {
+			collection: collection_id,
+			recipients,
+		}));
+
+		// the collection royalties are paid out of the price
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&artist), 50);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 50);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - price);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_id,
+			recipient: artist.clone(),
+			amount: 50,
+		}));
+
+		// the item royalties override those of the collection
+		assert_ok!(Nfts::set_item_royalties(
+			RuntimeOrigin::root(),
+			collection_id,
+			item_id,
+			bvec![
+				RoyaltyRecipient { account: artist.clone(), rate: 200 },
+				RoyaltyRecipient { account: curator.clone(), rate: 300 },
+			]
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&artist), 50 + 20);
+		assert_eq!(Balances::total_balance(&curator), 30);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - 50);
+
+		// clearing the item royalties brings back those of the collection
+		assert_ok!(Nfts::set_item_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_id,
+			bvec![]
+		));
+		assert!(!ItemRoyaltiesOf::<Test>::contains_key(collection_id, item_id));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&artist), 50 + 20 + 50);
+		assert_eq!(Balances::total_balance(&curator), 30);
+
+		// royalties go away with the item and the collection
+		assert_ok!(Nfts::set_item_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_id,
+			bvec![RoyaltyRecipient { account: curator.clone(), rate: 100 }]
+		));
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(user_3.clone()), collection_id, item_id));
+		assert!(!ItemRoyaltiesOf::<Test>::contains_key(collection_id, item_id));
+		let witness = Nfts::get_destroy_witness(&collection_id).unwrap();
+		assert_ok!(Nfts::destroy(RuntimeOrigin::signed(creator), collection_id, witness));
+		assert!(!CollectionRoyaltiesOf::<Test>::contains_key(collection_id));
+	});
+}
+
+#[test]
+fn royalties_are_paid_on_claim_swap() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let artist = account(4);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 1000;
+		let initial_balance = 10000;
+
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			creator.clone(),
+			CollectionConfig { max_royalty: 1_000, ..default_collection_config() }
+		));
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_1,
+			user_2.clone(),
+			default_item_config(),
+		));
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_2,
+			user_3.clone(),
+			default_item_config(),
+		));
+		assert_ok!(Nfts::set_item_royalties(
+			RuntimeOrigin::signed(creator),
+			collection_id,
+			item_1,
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 1_000 }]
+		));
+
+		// the royalties of the item whose owner gets paid are due
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&artist), 100);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 100);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - price);
+
+		// the other way round, the royalties of the item sent by the claimer are due
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			Some(item_1),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			item_2,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&artist), 200);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 100);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - 100);
+	});
+}
+
+#[test]
+fn royalties_are_capped_at_the_rate_agreed_by_the_seller() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let creator = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let artist = account(4);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 1000;
+		let initial_balance = 10000;
+
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			creator.clone(),
+			CollectionConfig { max_royalty: 1_000, ..default_collection_config() }
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_1,
+			user_2.clone(),
+			None
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_2,
+			user_3.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 100 }]
+		));
+
+		// raising the royalties after the price is set doesn't affect the sale
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None,
+		));
+		assert_eq!(ItemPriceRoyaltyOf::<Test>::get(collection_id, item_1), 100);
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 1_000 }]
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&artist), 10);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 10);
+		assert!(!ItemPriceRoyaltyOf::<Test>::contains_key(collection_id, item_1));
+
+		// lowering them does
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 200 }]
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&artist), 10 + 20);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - 20);
+
+		// the same goes for the royalties of an offered item
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_eq!(PendingSwapRoyaltyOf::<Test>::get(collection_id, item_1), 200);
+		assert_ok!(Nfts::set_item_royalties(
+			RuntimeOrigin::signed(creator),
+			collection_id,
+			item_1,
+			bvec![RoyaltyRecipient { account: artist.clone(), rate: 1_000 }]
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&artist), 10 + 20 + 20);
+		assert!(!PendingSwapRoyaltyOf::<Test>::contains_key(collection_id, item_1));
+	});
+}
+
+#[test]
+fn royalties_below_existential_deposit_are_left_to_the_seller() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(10);
+		let creator = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let artist = account(4);
+		let curator = account(5);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 1000;
+		let initial_balance = 10000;
+
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+		Balances::make_free_balance_be(&curator, 10);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			creator.clone(),
+			CollectionConfig { max_royalty: 1_000, ..default_collection_config() }
+		));
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(creator.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			default_item_config(),
+		));
+		assert_ok!(Nfts::set_collection_royalties(
+			RuntimeOrigin::signed(creator),
+			collection_id,
+			bvec![
+				RoyaltyRecipient { account: artist.clone(), rate: 50 },
+				RoyaltyRecipient { account: curator.clone(), rate: 50 },
+			]
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+
+		// the artist's share is too small to create their account, so the seller keeps it
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Nfts::owner(collection_id, item_id), Some(user_3));
+		assert_eq!(Balances::total_balance(&artist), 0);
+		assert_eq!(Balances::total_balance(&curator), 10 + 5);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 5);
+	});
+}
+
 #[test]
 fn various_collection_settings() {
 	new_test_ext().execute_with(|| {
@@ -24,7 +24,7 @@ use codec::{DecodeWithMemTracking, EncodeLike};
 use enumflags2::{bitflags, BitFlags};
 use frame_support::{
 	pallet_prelude::{BoundedVec, MaxEncodedLen},
-	traits::Get,
+	traits::{Contains, Get},
 	BoundedBTreeMap, BoundedBTreeSet,
 };
 use scale_info::{build::Fields, meta_type, Path, Type, TypeInfo, TypeParameter};
@@ -73,6 +73,11 @@ pub type ItemTipOf<T, I = ()> = ItemTip<
 /// A type alias for the settings configuration of a collection.
 pub type CollectionConfigFor<T, I = ()> =
 	CollectionConfig<BalanceOf<T, I>, BlockNumberFor<T, I>, <T as Config<I>>::CollectionId>;
+/// A type alias for the royalty recipients of a collection or an item.
+pub type RoyaltyRecipientsOf<T, I = ()> = BoundedVec<
+	RoyaltyRecipient<<T as SystemConfig>::AccountId>,
+	<T as Config<I>>::MaxRoyaltyRecipients,
+>;
 /// A type alias for the pre-signed minting configuration for a specified collection.
 pub type PreSignedMintOf<T, I = ()> = PreSignedMint<
 	<T as Config<I>>::CollectionId,
@@ -226,6 +231,44 @@ pub struct ItemTip<CollectionId, ItemId, AccountId, Amount> {
 	pub amount: Amount,
 }
 
+/// A royalty rate, in basis points (hundredths of a percent) of the sale price.
+pub type RoyaltyRate = u16;
+
+/// The royalty rate which amounts to the whole sale price.
+pub const MAX_ROYALTY_RATE: RoyaltyRate = 10_000;
+
+/// An account entitled to a share of each sale of an item.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct RoyaltyRecipient<AccountId> {
+	/// The account receiving the royalty.
+	pub account: AccountId,
+	/// The share of the sale price paid to the account, in basis points.
+	pub rate: RoyaltyRate,
+}
+
+/// Matches the items whose sales may carry royalties: those with royalties set and all the items
+/// of collections which allow royalties.
+///
+/// Pallets letting items change hands other than by their sales in this pallet, e.g. by trading
+/// fungible fractions of them, can use it to keep such items out and their royalties enforced.
+pub struct RoyaltyBearing<T, I = ()>(core::marker::PhantomData<(T, I)>);
+impl<T: Config<I>, I: 'static> Contains<(T::CollectionId, T::ItemId)> for RoyaltyBearing<T, I> {
+	fn contains((collection, item): &(T::CollectionId, T::ItemId)) -> bool {
+		CollectionConfigOf::<T, I>::get(collection).is_some_and(|config| config.max_royalty > 0) ||
+			Pallet::<T, I>::royalties(collection, item).is_some()
+	}
+}
+
 /// Information about the pending swap.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo, MaxEncodedLen)]
 pub struct PendingSwap<CollectionId, ItemId, ItemPriceWithDirection, Deadline> {
@@ -467,6 +510,9 @@ pub struct CollectionConfig<Price, BlockNumber, CollectionId> {
 	pub max_supply: Option<u32>,
 	/// Default settings each item will get during the mint.
 	pub mint_settings: MintSettings<Price, BlockNumber, CollectionId>,
+	/// The highest total royalty rate, in basis points, which may be set on the collection or any
+	/// of its items. Zero disables royalties.
+	pub max_royalty: RoyaltyRate,
 }
 
 impl<Price, BlockNumber, CollectionId> CollectionConfig<Price, BlockNumber, CollectionId> {
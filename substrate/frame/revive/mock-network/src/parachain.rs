@@ -19,7 +19,7 @@
 mod contracts_config;
 use crate::{
 	mocks::msg_queue::pallet as mock_msg_queue,
-	primitives::{AccountId, AssetIdForAssets, Balance},
+	primitives::{AccountId, AssetIdForAssets, Balance, CENTS},
 };
 use core::marker::PhantomData;
 use frame_support::{
@@ -232,6 +232,30 @@ impl Contains<Location> for ThisParachain {
 
 pub type XcmRouter = crate::ParachainXcmRouter<MsgQueue>;
 
+parameter_types! {
+	/// The native tokens charged for delivering a message sent with `pallet_xcm`.
+	pub const DeliveryFee: Balance = 5 * CENTS;
+}
+
+/// Charges [`DeliveryFee`] for every message sent through `R`.
+pub struct WithDeliveryFee<R>(PhantomData<R>);
+impl<R: SendXcm> SendXcm for WithDeliveryFee<R> {
+	type Ticket = R::Ticket;
+
+	fn validate(
+		dest: &mut Option<Location>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let (ticket, mut fees) = R::validate(dest, msg)?;
+		fees.push((Here, DeliveryFee::get()).into());
+		Ok((ticket, fees))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		R::deliver(ticket)
+	}
+}
+
 pub type Barrier = (
 	xcm_builder::AllowUnpaidExecutionFrom<ThisParachain>,
 	WithComputedOrigin<
@@ -305,7 +329,7 @@ impl<T: Get<(Location, AssetFilter)>> ContainsPair<Location, Asset> for TrustedL
 impl pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
-	type XcmRouter = XcmRouter;
+	type XcmRouter = WithDeliveryFee<XcmRouter>;
 	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type XcmExecuteFilter = Everything;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -15,8 +15,77 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{Balances, Runtime, RuntimeCall, RuntimeEvent};
-use crate::parachain::RuntimeHoldReason;
-use frame_support::derive_impl;
+use crate::{
+	parachain::RuntimeHoldReason,
+	primitives::{AccountId, AssetIdForAssets},
+};
+use frame_support::{
+	derive_impl, ensure, parameter_types,
+	traits::{
+		fungible, fungibles,
+		tokens::{Fortitude, Precision, Preservation},
+	},
+	weights::Weight,
+};
+use pallet_revive::{
+	precompiles::{Erc20, InlineAssetId, XcmPrecompile},
+	xcm_fees::XcmFeeSwap,
+};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::{Asset, AssetId, Assets, Fungible, Here};
+
+parameter_types! {
+	pub const AssetsErc20Prefix: u16 = 0x0120;
+}
+
+/// Pays delivery fees by burning two units of an asset of `pallet_assets` for every native token
+/// minted.
+pub struct BurnAssetsForFees;
+
+impl BurnAssetsForFees {
+	/// The amount of the asset burnt for the native `fees`.
+	fn amount_in(fees: &Assets) -> Option<u128> {
+		match fees.inner().as_slice() {
+			[Asset { id: AssetId(location), fun: Fungible(amount) }]
+				if *location == Here.into() =>
+				amount.checked_mul(2),
+			_ => None,
+		}
+	}
+}
+
+impl XcmFeeSwap<AccountId> for BurnAssetsForFees {
+	type AssetKind = AssetIdForAssets;
+
+	fn swap_weight() -> Weight {
+		Weight::from_parts(1_000_000, 0)
+	}
+
+	fn quote(_asset: &Self::AssetKind, fees: &Assets) -> Option<u128> {
+		Self::amount_in(fees)
+	}
+
+	fn swap(
+		who: &AccountId,
+		asset: Self::AssetKind,
+		fees: &Assets,
+		max_amount_in: u128,
+	) -> Result<u128, DispatchError> {
+		let amount_in =
+			Self::amount_in(fees).ok_or(DispatchError::Other("Unsupported delivery fees"))?;
+		ensure!(amount_in <= max_amount_in, DispatchError::Other("Exceeds max amount in"));
+		<super::Assets as fungibles::Mutate<_>>::burn_from(
+			asset,
+			who,
+			amount_in,
+			Preservation::Expendable,
+			Precision::Exact,
+			Fortitude::Polite,
+		)?;
+		<Balances as fungible::Mutate<_>>::mint_into(who, amount_in / 2)?;
+		Ok(amount_in)
+	}
+}
 
 #[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
 impl pallet_revive::Config for Runtime {
@@ -24,4 +93,6 @@ impl pallet_revive::Config for Runtime {
 	type Currency = Balances;
 	type Time = super::Timestamp;
 	type Xcm = pallet_xcm::Pallet<Self>;
+	type Precompiles = (XcmPrecompile<Self>, Erc20<AssetsErc20Prefix, (), InlineAssetId>);
+	type XcmFeeSwap = BurnAssetsForFees;
 }
@@ -16,18 +16,30 @@
 // limitations under the License.
 
 use crate::{
-	parachain, parachain_account_sovereign_account_id, primitives::CENTS, relay_chain, MockNet,
-	ParaA, ParachainBalances, Relay, ALICE, BOB, INITIAL_BALANCE,
+	parachain, parachain_account_sovereign_account_id,
+	primitives::{AccountId, AssetIdForAssets, CENTS},
+	relay_chain, MockNet, ParaA, ParachainBalances, Relay, ALICE, BOB, INITIAL_BALANCE,
+};
+use alloy_core::{
+	primitives::{Address, U256},
+	sol_types::SolCall,
 };
 use codec::{Decode, Encode};
-use frame_support::traits::{fungibles::Mutate, Currency};
+use frame_support::{
+	assert_ok,
+	traits::{
+		fungibles::{approvals::Inspect as _, Mutate},
+		Currency,
+	},
+};
 use frame_system::RawOrigin;
 use pallet_revive::{
+	precompiles::{erc20_address, IXcm, IERC20, XCM_PRECOMPILE_ADDRESS},
 	test_utils::{self, builder::*},
-	Code, DepositLimit,
+	AccountId32Mapper, AddressMapper, Code, DepositLimit, ExecReturnValue,
 };
 use pallet_revive_fixtures::compile_module;
-use pallet_revive_uapi::ReturnErrorCode;
+use pallet_revive_uapi::{ReturnErrorCode, ReturnFlags};
 use sp_core::H160;
 use xcm::{v4::prelude::*, VersionedLocation, VersionedXcm};
 use xcm_simulator::TestExt;
@@ -42,6 +54,12 @@ fn bare_call(dest: H160) -> BareCallBuilder<parachain::Runtime> {
 	BareCallBuilder::<parachain::Runtime>::bare_call(RawOrigin::Signed(ALICE).into(), dest)
 }
 
+/// Call the `precompile` with `input` through the `call_and_return` contract at `addr`.
+fn call_precompile(addr: H160, precompile: H160, input: Vec<u8>) -> ExecReturnValue {
+	let data = (precompile, 0u64).encode().into_iter().chain(input).collect::<Vec<_>>();
+	bare_call(addr).data(data).build_and_unwrap_result()
+}
+
 /// Instantiate the tests contract, and fund it with some balance and assets.
 fn instantiate_test_contract(name: &str) -> Contract<parachain::Runtime> {
 	let (wasm, _) = compile_module(name).unwrap();
@@ -202,3 +220,425 @@ fn test_xcm_send() {
 		assert_eq!(INITIAL_BALANCE + amount - fee, relay_chain::Balances::free_balance(ALICE));
 	});
 }
+
+#[test]
+fn test_xcm_precompile_send() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+	let amount = 1_000 * CENTS;
+	let fee = parachain::estimate_message_fee(4); // Accounts for the `DescendOrigin` instruction added by `send_xcm`
+
+	// Send XCM instructions through the precompile, to transfer some funds from the contract
+	// derivative account to Alice on the relay chain.
+	ParaA::execute_with(|| {
+		let dest = VersionedLocation::V4(Parent.into());
+		let assets: Asset = (Here, amount).into();
+		let beneficiary = AccountId32 { network: None, id: ALICE.clone().into() };
+
+		let message: Xcm<()> = Xcm::builder()
+			.withdraw_asset(assets.clone())
+			.buy_execution((Here, fee), Unlimited)
+			.deposit_asset(assets, beneficiary)
+			.build();
+
+		let input = IXcm::sendCall {
+			destination: dest.encode().into(),
+			message: VersionedXcm::V4(message).encode().into(),
+		}
+		.abi_encode();
+		let result = call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input);
+
+		assert_eq!(result.flags, ReturnFlags::empty());
+		IXcm::sendCall::abi_decode_returns(&result.data, true)
+			.expect("Failed to decode send message_id");
+	});
+
+	Relay::execute_with(|| {
+		let derived_contract_addr = &parachain_account_sovereign_account_id(1, account_id);
+		assert_eq!(
+			INITIAL_BALANCE - amount,
+			relay_chain::Balances::free_balance(derived_contract_addr)
+		);
+		assert_eq!(INITIAL_BALANCE + amount - fee, relay_chain::Balances::free_balance(ALICE));
+	});
+}
+
+#[test]
+fn test_xcm_precompile_weigh_and_execute() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+
+	ParaA::execute_with(|| {
+		let amount: u128 = 10 * CENTS;
+		let assets: Asset = (Here, amount).into();
+		let beneficiary = AccountId32 { network: None, id: BOB.clone().into() };
+
+		// The XCM used to transfer funds to Bob.
+		let message: Xcm<()> = Xcm::builder_unsafe()
+			.withdraw_asset(assets.clone())
+			.deposit_asset(assets, beneficiary)
+			.build();
+		let message = VersionedXcm::V4(message).encode();
+
+		let input = IXcm::weighMessageCall { message: message.clone().into() }.abi_encode();
+		let result = call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		let weight = IXcm::weighMessageCall::abi_decode_returns(&result.data, true)
+			.expect("Failed to decode weighMessage result")
+			.weight;
+		assert_eq!(weight.refTime, parachain::estimate_weight(2).ref_time());
+		assert_eq!(weight.proofSize, parachain::estimate_weight(2).proof_size());
+
+		let input = IXcm::executeCall { message: message.into(), weight }.abi_encode();
+		let result = call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input);
+		assert_eq!(result.flags, ReturnFlags::empty());
+
+		assert_eq!(ParachainBalances::free_balance(BOB), INITIAL_BALANCE + amount);
+		assert_eq!(ParachainBalances::free_balance(&account_id), INITIAL_BALANCE - amount);
+	});
+}
+
+#[test]
+fn test_xcm_precompile_execute_incomplete_reverts() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+
+	ParaA::execute_with(|| {
+		let amount: u128 = 10 * CENTS;
+		let assets: Asset = (Here, amount).into();
+		let beneficiary = AccountId32 { network: None, id: BOB.clone().into() };
+
+		let message: Xcm<()> = Xcm::builder_unsafe()
+			.withdraw_asset(assets.clone())
+			// This will fail as the contract does not have enough balance to complete both
+			// withdrawals.
+			.withdraw_asset((Here, INITIAL_BALANCE))
+			.deposit_asset(assets, beneficiary)
+			.build();
+
+		let weight = parachain::estimate_weight(3);
+		let input = IXcm::executeCall {
+			message: VersionedXcm::V4(message).encode().into(),
+			weight: IXcm::Weight { refTime: weight.ref_time(), proofSize: weight.proof_size() },
+		}
+		.abi_encode();
+		let result = call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+
+		// Unlike `xcm_execute`, the precompile reverts all the effects of the message.
+		assert_eq!(ParachainBalances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(ParachainBalances::free_balance(&account_id), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn test_xcm_precompile_rejects_value() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+
+	ParaA::execute_with(|| {
+		let message = VersionedXcm::V4(Xcm::<()>::builder_unsafe().clear_origin().build());
+		let input = IXcm::weighMessageCall { message: message.encode().into() }.abi_encode();
+		let data = (XCM_PRECOMPILE_ADDRESS, 1u64).encode().into_iter().chain(input).collect();
+		let result = bare_call(addr).data(data).build_and_unwrap_result();
+
+		// The value is not kept by the precompile.
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(ParachainBalances::free_balance(&account_id), INITIAL_BALANCE);
+	});
+}
+
+/// A message transferring `amount` from the contract derivative account on the relay chain to
+/// Alice, paying `fee` for its execution.
+fn transfer_to_alice_message(amount: u128, fee: u128) -> VersionedXcm<()> {
+	let assets: Asset = (Here, amount).into();
+	let beneficiary = AccountId32 { network: None, id: ALICE.clone().into() };
+	let message: Xcm<()> = Xcm::builder()
+		.withdraw_asset(assets.clone())
+		.buy_execution((Here, fee), Unlimited)
+		.deposit_asset(assets, beneficiary)
+		.build();
+	VersionedXcm::V4(message)
+}
+
+/// Check that `amount` was transferred from the contract derivative account to Alice.
+fn assert_transferred_to_alice(account_id: AccountId, amount: u128, fee: u128) {
+	Relay::execute_with(|| {
+		let derived_contract_addr = &parachain_account_sovereign_account_id(1, account_id);
+		assert_eq!(
+			INITIAL_BALANCE - amount,
+			relay_chain::Balances::free_balance(derived_contract_addr)
+		);
+		assert_eq!(INITIAL_BALANCE + amount - fee, relay_chain::Balances::free_balance(ALICE));
+	});
+}
+
+#[test]
+fn test_xcm_send_with_fee_asset() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("xcm_send_with_fee_asset");
+	let amount = 1_000 * CENTS;
+	let fee = parachain::estimate_message_fee(4); // Accounts for the `DescendOrigin` instruction added by `send_xcm`
+	let fee_asset_amount = 2 * parachain::DeliveryFee::get();
+
+	ParaA::execute_with(|| {
+		let dest = VersionedLocation::V4(Parent.into());
+		let fee_asset = (0 as AssetIdForAssets, fee_asset_amount);
+
+		let result = bare_call(addr)
+			.data((dest, fee_asset, transfer_to_alice_message(amount, fee)).encode())
+			.build_and_unwrap_result();
+
+		let mut data = &result.data[..];
+		XcmHash::decode(&mut data).expect("Failed to decode xcm_send_with_fee_asset message_id");
+
+		// The delivery fees were paid by swapping the fee asset.
+		assert_eq!(parachain::Assets::balance(0, &account_id), INITIAL_BALANCE - fee_asset_amount);
+		assert_eq!(ParachainBalances::free_balance(&account_id), INITIAL_BALANCE);
+	});
+
+	assert_transferred_to_alice(account_id, amount, fee);
+}
+
+#[test]
+fn test_xcm_precompile_send_with_fee_asset() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+	let amount = 1_000 * CENTS;
+	let fee = parachain::estimate_message_fee(4); // Accounts for the `DescendOrigin` instruction added by `send_xcm`
+
+	ParaA::execute_with(|| {
+		let destination = VersionedLocation::V4(Parent.into()).encode();
+		let message = transfer_to_alice_message(amount, fee).encode();
+		let fee_asset = (0 as AssetIdForAssets).encode();
+
+		// The delivery fees are quoted in the fee asset.
+		let input = IXcm::quoteSendFeeCall {
+			destination: destination.clone().into(),
+			message: message.clone().into(),
+			feeAsset: fee_asset.clone().into(),
+		}
+		.abi_encode();
+		let result = call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		let quote = IXcm::quoteSendFeeCall::abi_decode_returns(&result.data, true)
+			.expect("Failed to decode quoteSendFee result")
+			.amount;
+		assert_eq!(quote, U256::from(2 * parachain::DeliveryFee::get()));
+
+		let send_with_fee_asset = |max_fee_amount: U256| {
+			let input = IXcm::sendWithFeeAssetCall {
+				destination: destination.clone().into(),
+				message: message.clone().into(),
+				feeAsset: fee_asset.clone().into(),
+				maxFeeAmount: max_fee_amount,
+			}
+			.abi_encode();
+			call_precompile(addr, XCM_PRECOMPILE_ADDRESS, input)
+		};
+
+		// Nothing is swapped or sent if the quote exceeds the maximum.
+		let result = send_with_fee_asset(quote - U256::from(1));
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(parachain::Assets::balance(0, &account_id), INITIAL_BALANCE);
+
+		let result = send_with_fee_asset(quote);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		IXcm::sendWithFeeAssetCall::abi_decode_returns(&result.data, true)
+			.expect("Failed to decode sendWithFeeAsset message_id");
+
+		// The delivery fees were paid by swapping the quoted amount of the fee asset.
+		assert_eq!(
+			parachain::Assets::balance(0, &account_id),
+			INITIAL_BALANCE - quote.to::<u128>()
+		);
+		assert_eq!(ParachainBalances::free_balance(&account_id), INITIAL_BALANCE);
+	});
+
+	assert_transferred_to_alice(account_id, amount, fee);
+}
+
+/// The ERC-20 precompile of asset `0`, see `parachain::contracts_config`.
+fn erc20_token() -> H160 {
+	erc20_address(0x0120, 0)
+}
+
+fn erc20_balance_of(addr: H160, account: H160) -> u128 {
+	let input = IERC20::balanceOfCall { account: Address::from(account.0) }.abi_encode();
+	let result = call_precompile(addr, erc20_token(), input);
+	assert_eq!(result.flags, ReturnFlags::empty());
+	let balance = IERC20::balanceOfCall::abi_decode_returns(&result.data, true).unwrap()._0;
+	balance.to()
+}
+
+fn erc20_emitted(topic: [u8; 32]) -> bool {
+	parachain::System::events().iter().any(|record| {
+		matches!(
+			&record.event,
+			parachain::RuntimeEvent::Contracts(pallet_revive::Event::ContractEmitted {
+				contract,
+				topics,
+				..
+			}) if *contract == erc20_token() && topics[0].0 == topic
+		)
+	})
+}
+
+#[test]
+fn test_erc20_precompile_transfer() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+	let dest = H160::repeat_byte(0x42);
+
+	ParaA::execute_with(|| {
+		assert_eq!(erc20_balance_of(addr, addr), INITIAL_BALANCE);
+
+		let input = IERC20::totalSupplyCall {}.abi_encode();
+		let result = call_precompile(addr, erc20_token(), input);
+		let supply = IERC20::totalSupplyCall::abi_decode_returns(&result.data, true).unwrap()._0;
+		assert_eq!(supply, U256::from(3 * INITIAL_BALANCE));
+
+		let input =
+			IERC20::transferCall { to: Address::from(dest.0), value: U256::from(1_000u128) }
+				.abi_encode();
+		let result = call_precompile(addr, erc20_token(), input);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert!(IERC20::transferCall::abi_decode_returns(&result.data, true).unwrap()._0);
+
+		assert_eq!(parachain::Assets::balance(0, &account_id), INITIAL_BALANCE - 1_000);
+		assert_eq!(
+			parachain::Assets::balance(
+				0,
+				AccountId32Mapper::<parachain::Runtime>::to_account_id(&dest)
+			),
+			1_000
+		);
+		assert_eq!(erc20_balance_of(addr, dest), 1_000);
+		assert!(erc20_emitted(IERC20::Transfer::SIGNATURE_HASH.0));
+
+		// Transferring more than the balance reverts.
+		let input =
+			IERC20::transferCall { to: Address::from(dest.0), value: U256::from(INITIAL_BALANCE) }
+				.abi_encode();
+		let result = call_precompile(addr, erc20_token(), input);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(parachain::Assets::balance(0, &account_id), INITIAL_BALANCE - 1_000);
+	});
+}
+
+#[test]
+fn test_erc20_precompile_approve_and_transfer_from() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+	let alice = AccountId32Mapper::<parachain::Runtime>::to_address(&ALICE);
+	let spender = H160::repeat_byte(0x42);
+	let dest = H160::repeat_byte(0x43);
+
+	ParaA::execute_with(|| {
+		// The contract approves `spender`.
+		let input =
+			IERC20::approveCall { spender: Address::from(spender.0), value: U256::from(500u128) }
+				.abi_encode();
+		let result = call_precompile(addr, erc20_token(), input);
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert!(erc20_emitted(IERC20::Approval::SIGNATURE_HASH.0));
+
+		let allowance = || {
+			let input = IERC20::allowanceCall {
+				owner: Address::from(addr.0),
+				spender: Address::from(spender.0),
+			}
+			.abi_encode();
+			let result = call_precompile(addr, erc20_token(), input);
+			IERC20::allowanceCall::abi_decode_returns(&result.data, true).unwrap()._0
+		};
+		assert_eq!(allowance(), U256::from(500u128));
+
+		// The allowance can be lowered, and reset to zero.
+		for value in [100u128, 0] {
+			let input =
+				IERC20::approveCall { spender: Address::from(spender.0), value: U256::from(value) }
+					.abi_encode();
+			let result = call_precompile(addr, erc20_token(), input);
+			assert_eq!(result.flags, ReturnFlags::empty());
+			assert_eq!(allowance(), U256::from(value));
+		}
+
+		// Alice approves the contract, which then spends part of her allowance.
+		assert_ok!(parachain::Assets::approve_transfer(
+			parachain::RuntimeOrigin::signed(ALICE),
+			0,
+			account_id.clone(),
+			300,
+		));
+		let transfer_from = IERC20::transferFromCall {
+			from: Address::from(alice.0),
+			to: Address::from(dest.0),
+			value: U256::from(200u128),
+		}
+		.abi_encode();
+		let result = call_precompile(addr, erc20_token(), transfer_from.clone());
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(erc20_balance_of(addr, dest), 200);
+		assert_eq!(parachain::Assets::allowance(0, &ALICE, &account_id), 100);
+
+		// Spending more than the remaining allowance reverts.
+		let result = call_precompile(addr, erc20_token(), transfer_from);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(erc20_balance_of(addr, dest), 200);
+	});
+}
+
+#[test]
+fn test_erc20_precompile_approve_from_account() {
+	MockNet::reset();
+	let spender = H160::repeat_byte(0x42);
+	let spender_id = AccountId32Mapper::<parachain::Runtime>::to_account_id(&spender);
+
+	ParaA::execute_with(|| {
+		// Alice calls the precompile directly, without a contract in between.
+		let input =
+			IERC20::approveCall { spender: Address::from(spender.0), value: U256::from(500u128) }
+				.abi_encode();
+		let result = bare_call(erc20_token()).data(input).build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert!(IERC20::approveCall::abi_decode_returns(&result.data, true).unwrap()._0);
+		assert!(erc20_emitted(IERC20::Approval::SIGNATURE_HASH.0));
+		assert_eq!(parachain::Assets::allowance(0, &ALICE, &spender_id), 500);
+	});
+}
+
+#[test]
+fn test_erc20_precompile_account_approves_contract() {
+	MockNet::reset();
+	let Contract { addr, account_id } = instantiate_test_contract("call_and_return");
+	let alice = AccountId32Mapper::<parachain::Runtime>::to_address(&ALICE);
+	let dest = H160::repeat_byte(0x43);
+
+	ParaA::execute_with(|| {
+		// Alice approves the contract through the precompile.
+		let input =
+			IERC20::approveCall { spender: Address::from(addr.0), value: U256::from(300u128) }
+				.abi_encode();
+		let result = bare_call(erc20_token()).data(input).build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(parachain::Assets::allowance(0, &ALICE, &account_id), 300);
+
+		// The contract spends part of her allowance.
+		let transfer_from = IERC20::transferFromCall {
+			from: Address::from(alice.0),
+			to: Address::from(dest.0),
+			value: U256::from(200u128),
+		}
+		.abi_encode();
+		let result = call_precompile(addr, erc20_token(), transfer_from.clone());
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(erc20_balance_of(addr, dest), 200);
+		assert_eq!(parachain::Assets::allowance(0, &ALICE, &account_id), 100);
+
+		// Spending more than the remaining allowance reverts.
+		let result = call_precompile(addr, erc20_token(), transfer_from);
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(erc20_balance_of(addr, dest), 200);
+	});
+}
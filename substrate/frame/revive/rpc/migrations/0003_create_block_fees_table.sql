@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS block_fees (
+	block_hash BLOB NOT NULL PRIMARY KEY,
+	block_number INTEGER NOT NULL,
+	base_fee_per_gas BLOB NOT NULL,
+	gas_limit BLOB NOT NULL,
+	gas_used BLOB NOT NULL,
+	rewards BLOB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_block_fees_block_number ON block_fees (
+	block_number
+);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS calls (
+	block_hash BLOB NOT NULL,
+	block_number INTEGER NOT NULL,
+	transaction_index INTEGER NOT NULL,
+	from_address BLOB NOT NULL,
+	to_address BLOB NOT NULL,
+	PRIMARY KEY (block_hash, transaction_index, from_address, to_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_calls_from_address ON calls (
+	from_address,
+	block_number
+);
+
+CREATE INDEX IF NOT EXISTS idx_calls_to_address ON calls (
+	to_address,
+	block_number
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS block_stats (
+	block_hash BLOB NOT NULL PRIMARY KEY,
+	transaction_count INTEGER NOT NULL,
+	contract_call_count INTEGER,
+	min_effective_gas_price BLOB NOT NULL,
+	avg_effective_gas_price BLOB NOT NULL,
+	max_effective_gas_price BLOB NOT NULL,
+	total_fees BLOB NOT NULL
+);
@@ -22,3 +22,12 @@ pub use execution_apis::*;
 
 mod health_api;
 pub use health_api::*;
+
+mod pubsub_apis;
+pub use pubsub_apis::*;
+
+mod revive_apis;
+pub use revive_apis::*;
+
+mod trace_apis;
+pub use trace_apis::*;
@@ -20,6 +20,18 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 /// Debug Ethererum JSON-RPC apis.
 #[rpc(server, client)]
 pub trait DebugRpc {
+	/// Returns the tracing of the execution of a specific block using its hash.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#debugtraceblockbyhash>
+	#[method(name = "debug_traceBlockByHash")]
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Vec<TransactionTrace>>;
+
 	/// Returns the tracing of the execution of a specific block using its number.
 	///
 	/// ## References
@@ -42,7 +54,7 @@ pub trait DebugRpc {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace>;
+	) -> RpcResult<Trace>;
 
 	/// Dry run a call and returns the transaction's traces.
 	///
@@ -55,7 +67,7 @@ pub trait DebugRpc {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace>;
+	) -> RpcResult<Trace>;
 }
 
 pub struct DebugRpcServerImpl {
@@ -70,6 +82,16 @@ impl DebugRpcServerImpl {
 
 #[async_trait]
 impl DebugRpcServer for DebugRpcServerImpl {
+	async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> RpcResult<Vec<TransactionTrace>> {
+		log::debug!(target: crate::LOG_TARGET, "trace_block_by_hash: {block_hash:?} config: {tracer_config:?}");
+		let traces = self.client.trace_block_by_hash(block_hash, tracer_config).await?;
+		Ok(traces)
+	}
+
 	async fn trace_block_by_number(
 		&self,
 		block: BlockNumberOrTag,
@@ -84,7 +106,7 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace> {
+	) -> RpcResult<Trace> {
 		let trace = self.client.trace_transaction(transaction_hash, tracer_config).await?;
 		Ok(trace)
 	}
@@ -94,7 +116,7 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> RpcResult<CallTrace> {
+	) -> RpcResult<Trace> {
 		log::debug!(target: crate::LOG_TARGET, "trace_call: {transaction:?} block: {block:?} config: {tracer_config:?}");
 		let trace = self.client.trace_call(transaction, block, tracer_config).await?;
 		Ok(trace)
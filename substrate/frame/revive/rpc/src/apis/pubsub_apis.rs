@@ -0,0 +1,169 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum publish/subscribe JSON-RPC methods, served over WebSocket.
+
+use crate::*;
+use jsonrpsee::{
+	core::SubscriptionResult, proc_macros::rpc, types::ErrorObjectOwned, PendingSubscriptionSink,
+	SubscriptionMessage, SubscriptionSink,
+};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::sync::broadcast;
+
+/// The kind of events an `eth_subscribe` subscription is notified of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// The header of every new block.
+	NewHeads,
+	/// The logs of new blocks matching the filter passed to the subscription.
+	Logs,
+	/// The hashes of the transactions submitted through this server.
+	NewPendingTransactions,
+}
+
+/// An item sent to `eth_subscribe` subscribers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// A transaction hash, sent to [`SubscriptionKind::NewPendingTransactions`] subscribers.
+	Hash(H256),
+	/// A log, sent to [`SubscriptionKind::Logs`] subscribers.
+	Log(Log),
+	/// A block, sent to [`SubscriptionKind::NewHeads`] subscribers.
+	Block(Box<Block>),
+}
+
+/// Ethereum publish/subscribe JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSub {
+	/// Subscribe to the events of the given `kind`.
+	///
+	/// `filter` is only accepted by [`SubscriptionKind::Logs`] subscriptions, and only its
+	/// `address` and `topics` are taken into account.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>)
+		-> SubscriptionResult;
+}
+
+pub struct EthPubSubServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl EthPubSubServer for EthPubSubServerImpl {
+	async fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		if filter.is_some() && kind != SubscriptionKind::Logs {
+			let err: ErrorObjectOwned = EthRpcError::InvalidFilter(format!(
+				"{kind:?} subscriptions do not accept a filter"
+			))
+			.into();
+			pending.reject(err).await;
+			return Ok(());
+		}
+
+		let sink = pending.accept().await?;
+		log::debug!(target: LOG_TARGET, "eth_subscribe: {kind:?} filter: {filter:?}");
+		match kind {
+			SubscriptionKind::NewHeads =>
+				forward(sink, self.client.block_notifications(), |notification| async move {
+					let Some(block) = self.client.block_by_hash(&notification.hash).await? else {
+						return Ok(vec![]);
+					};
+					let block = self.client.evm_block(block, false).await;
+					Ok(vec![SubscriptionItem::Block(Box::new(block))])
+				})
+				.await,
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				forward(sink, self.client.block_notifications(), |notification| {
+					let logs = notification
+						.receipts
+						.iter()
+						.flat_map(|receipt| receipt.logs.iter())
+						.filter(|log| matches_filter(&filter, log))
+						.cloned()
+						.map(SubscriptionItem::Log)
+						.collect();
+					async move { Ok(logs) }
+				})
+				.await
+			},
+			SubscriptionKind::NewPendingTransactions =>
+				forward(sink, self.client.pending_transaction_notifications(), |hash| async move {
+					Ok(vec![SubscriptionItem::Hash(hash)])
+				})
+				.await,
+		}
+	}
+}
+
+/// Send the items produced by `to_items` for every notification to `sink`, until either side is
+/// closed.
+async fn forward<N, F, Fut>(
+	sink: SubscriptionSink,
+	mut notifications: broadcast::Receiver<N>,
+	to_items: F,
+) -> SubscriptionResult
+where
+	N: Clone,
+	F: Fn(N) -> Fut,
+	Fut: Future<Output = Result<Vec<SubscriptionItem>, ClientError>>,
+{
+	loop {
+		let notification = tokio::select! {
+			_ = sink.closed() => return Ok(()),
+			notification = notifications.recv() => notification,
+		};
+
+		let notification = match notification {
+			Ok(notification) => notification,
+			Err(broadcast::error::RecvError::Lagged(skipped)) => {
+				log::warn!(target: LOG_TARGET, "Subscription {:?} lagged behind, skipped {skipped} notifications", sink.subscription_id());
+				continue;
+			},
+			Err(broadcast::error::RecvError::Closed) => return Ok(()),
+		};
+
+		for item in to_items(notification).await? {
+			let message = SubscriptionMessage::from_json(&item)?;
+			if sink.send(message).await.is_err() {
+				return Ok(());
+			}
+		}
+	}
+}
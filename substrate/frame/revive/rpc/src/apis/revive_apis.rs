@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Revive specific JSON-RPC apis.
+#[rpc(server, client)]
+pub trait ReviveRpc {
+	/// Returns the gas usage and fee aggregates of the indexed blocks in the given range.
+	///
+	/// The `baseFeePerGas` and `gasUsedRatio` of every block match the values reported by
+	/// `eth_feeHistory`. Blocks that were not indexed by this server are skipped.
+	#[method(name = "revive_blockStats")]
+	async fn block_stats(
+		&self,
+		from_block: BlockNumberOrTag,
+		to_block: BlockNumberOrTag,
+	) -> RpcResult<Vec<BlockStats>>;
+}
+
+pub struct ReviveRpcServerImpl {
+	client: client::Client,
+}
+
+impl ReviveRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl ReviveRpcServer for ReviveRpcServerImpl {
+	async fn block_stats(
+		&self,
+		from_block: BlockNumberOrTag,
+		to_block: BlockNumberOrTag,
+	) -> RpcResult<Vec<BlockStats>> {
+		log::debug!(target: crate::LOG_TARGET, "block_stats: {from_block:?}..={to_block:?}");
+		let stats = self.client.block_stats(from_block, to_block).await?;
+		Ok(stats)
+	}
+}
@@ -0,0 +1,49 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// Parity-style trace JSON-RPC apis.
+#[rpc(server, client)]
+pub trait TraceRpc {
+	/// Returns the call traces matching the given filter.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_filter>
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>>;
+}
+
+pub struct TraceRpcServerImpl {
+	client: client::Client,
+}
+
+impl TraceRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl TraceRpcServer for TraceRpcServerImpl {
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>> {
+		log::debug!(target: crate::LOG_TARGET, "trace_filter: {filter:?}");
+		let traces = self.client.trace_filter(filter).await?;
+		Ok(traces)
+	}
+}
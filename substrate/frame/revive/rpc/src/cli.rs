@@ -18,8 +18,10 @@
 use crate::{
 	client::{connect, native_to_eth_ratio, Client, SubscriptionType, SubstrateBlockNumber},
 	BlockInfoProvider, BlockInfoProviderImpl, CacheReceiptProvider, DBReceiptProvider,
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, ReceiptExtractor,
-	ReceiptProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubServer, EthPubSubServerImpl, EthRpcServer,
+	EthRpcServerImpl, ReceiptExtractor, ReceiptProvider, ReviveRpcServer, ReviveRpcServerImpl,
+	SystemHealthRpcServer, SystemHealthRpcServerImpl, TraceRpcServer, TraceRpcServerImpl,
+	LOG_TARGET,
 };
 use clap::Parser;
 use futures::{pin_mut, FutureExt};
@@ -65,6 +67,12 @@ pub struct CliCommand {
 	#[clap(long)]
 	pub index_until_block: Option<SubstrateBlockNumber>,
 
+	/// Index the calls of the Ethereum transactions, as needed by `trace_filter`.
+	///
+	/// Every indexed block is replayed with the call tracer to find its calls.
+	#[clap(long)]
+	pub index_calls: bool,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -105,6 +113,7 @@ fn build_client(
 	earliest_receipt_block: Option<SubstrateBlockNumber>,
 	node_rpc_url: &str,
 	database_url: &str,
+	index_calls: bool,
 	abort_signal: Signals,
 ) -> anyhow::Result<Client> {
 	let fut = async {
@@ -134,7 +143,8 @@ fn build_client(
 
 		let client =
 			Client::new(api, rpc_client, rpc, block_provider, receipt_provider, receipt_extractor)
-				.await?;
+				.await?
+				.with_call_indexing(index_calls);
 
 		Ok(client)
 	}
@@ -158,6 +168,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		database_url,
 		earliest_receipt_block,
 		index_until_block,
+		index_calls,
 		shared_params,
 		..
 	} = cmd;
@@ -200,6 +211,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		earliest_receipt_block,
 		&node_rpc_url,
 		&database_url,
+		index_calls,
 		tokio_runtime.block_on(async { Signals::capture() })?,
 	)?;
 
@@ -245,11 +257,17 @@ fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service:
 		.into_rpc();
 
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
-	let debug_api = DebugRpcServerImpl::new(client).into_rpc();
+	let pubsub_api = EthPubSubServerImpl::new(client.clone()).into_rpc();
+	let debug_api = DebugRpcServerImpl::new(client.clone()).into_rpc();
+	let trace_api = TraceRpcServerImpl::new(client.clone()).into_rpc();
+	let revive_api = ReviveRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(trace_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(revive_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	Ok(module)
 }
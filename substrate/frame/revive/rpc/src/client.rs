@@ -17,18 +17,21 @@
 //! The client connects to the source substrate chain
 //! and is used by the rpc server to query and send transactions to the substrate chain.
 use crate::{
+	localized_traces,
 	subxt_client::{
 		revive::calls::types::EthTransact, runtime_types::pallet_revive::storage::ContractInfo,
 	},
-	BlockInfoProvider, ReceiptExtractor, ReceiptProvider, TransactionInfo, LOG_TARGET,
+	BlockFees, BlockInfoProvider, BlockStats, FeeHistoryResult, IndexedCall, LocalizedTrace,
+	ReceiptExtractor, ReceiptProvider, TraceFilter, TransactionInfo, LOG_TARGET,
+	MAX_BLOCK_STATS_BLOCKS, MAX_FEE_HISTORY_BLOCKS, MAX_TRACE_FILTER_BLOCKS,
 };
 use codec::{Decode, Encode};
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, CallTrace, Filter,
-		GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, TracerConfig,
-		TransactionSigned, TransactionTrace, H160, H256, U256,
+		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag, CallTrace,
+		Filter, GenericTransaction, Log, ReceiptInfo, SyncingProgress, SyncingStatus, Trace,
+		TracerConfig, TransactionSigned, TransactionTrace, H160, H256, U256,
 	},
 	EthTransactError, EthTransactInfo,
 };
@@ -49,7 +52,7 @@ use subxt::{
 	Config, OnlineClient,
 };
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 use crate::subxt_client::{self, SrcChainConfig};
 
@@ -76,6 +79,20 @@ pub enum SubscriptionType {
 	FinalizedBlocks,
 }
 
+/// The number of notifications buffered for slow subscribers before they start lagging.
+const NOTIFICATION_BUFFER_SIZE: usize = 256;
+
+/// A notification sent for every new block ingested by the client.
+#[derive(Clone, Debug)]
+pub struct BlockNotification {
+	/// The hash of the block.
+	pub hash: SubstrateBlockHash,
+	/// The number of the block.
+	pub number: SubstrateBlockNumber,
+	/// The receipts of the Ethereum transactions included in the block.
+	pub receipts: Arc<Vec<ReceiptInfo>>,
+}
+
 /// Unwrap the original `jsonrpsee::core::client::Error::Call` error.
 fn unwrap_call_err(err: &subxt::error::RpcError) -> Option<ErrorObjectOwned> {
 	use subxt::backend::rpc::reconnecting_rpc_client;
@@ -137,6 +154,18 @@ pub enum ClientError {
 	/// Failed to filter logs.
 	#[error("Failed to filter logs")]
 	LogFilterFailed(#[from] anyhow::Error),
+	/// The trace filter is invalid or could not be applied.
+	#[error("Failed to filter traces: {0}")]
+	TraceFilterFailed(String),
+	/// The block stats range is invalid or could not be queried.
+	#[error("Failed to get block stats: {0}")]
+	BlockStatsFailed(String),
+	/// The runtime does not implement the `ReviveApi`.
+	#[error("ReviveApi not found in the runtime")]
+	ReviveApiNotFound,
+	/// The tracer is not supported by the runtime.
+	#[error("The runtime only supports the call tracer")]
+	TracerNotSupported,
 }
 
 const REVERT_CODE: i32 = 3;
@@ -181,6 +210,9 @@ pub struct Client {
 	receipt_extractor: ReceiptExtractor,
 	chain_id: u64,
 	max_block_weight: Weight,
+	block_notifier: broadcast::Sender<BlockNotification>,
+	pending_transaction_notifier: broadcast::Sender<H256>,
+	call_indexing: bool,
 }
 
 /// Fetch the chain ID from the substrate chain.
@@ -255,9 +287,19 @@ impl Client {
 			receipt_extractor,
 			chain_id,
 			max_block_weight,
+			block_notifier: broadcast::channel(NOTIFICATION_BUFFER_SIZE).0,
+			pending_transaction_notifier: broadcast::channel(NOTIFICATION_BUFFER_SIZE).0,
+			call_indexing: false,
 		})
 	}
 
+	/// Index the calls of the Ethereum transactions of every new block, as needed by
+	/// `trace_filter`. This replays every block with the call tracer.
+	pub fn with_call_indexing(mut self, call_indexing: bool) -> Self {
+		self.call_indexing = call_indexing;
+		self
+	}
+
 	/// Subscribe to past blocks executing the callback for each block.
 	/// The subscription continues iterating past blocks until the closure returns
 	/// `ControlFlow::Break`. Blocks are iterated starting from the latest block and moving
@@ -353,11 +395,26 @@ impl Client {
 			.subscribe_new_blocks(subscription_type, |block| async {
 				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
 
-				self.receipt_provider.insert(&block.hash(), &receipts).await;
+				let (hash, number) = (block.hash(), block.number());
+				self.receipt_provider.insert(&hash, &receipts).await;
+				let call_count = self.index_calls(&block, &receipts).await;
+				if let Some(fees) = self.try_block_fees(&block, &receipts).await {
+					self.receipt_provider.insert_block_fees(&hash, &fees).await;
+					let stats = BlockStats::new(hash, &fees, &receipts, call_count);
+					self.receipt_provider.insert_block_stats(&stats).await;
+				}
 				if let Some(pruned) = self.block_provider.cache_block(block).await {
 					self.receipt_provider.remove(&pruned).await;
 				}
 
+				// Sending only fails when nobody is listening.
+				let receipts = receipts.into_iter().map(|(_, receipt)| receipt).collect();
+				let _ = self.block_notifier.send(BlockNotification {
+					hash,
+					number,
+					receipts: Arc::new(receipts),
+				});
+
 				Ok(())
 			})
 			.await;
@@ -373,6 +430,12 @@ impl Client {
 			.subscribe_past_blocks(|block| async move {
 				let receipts = self.receipt_extractor.extract_from_block(&block).await?;
 				self.receipt_provider.archive(&block.hash(), &receipts).await;
+				let call_count = self.index_calls(&block, &receipts).await;
+				if let Some(fees) = self.try_block_fees(&block, &receipts).await {
+					self.receipt_provider.archive_block_fees(&block.hash(), &fees).await;
+					let stats = BlockStats::new(block.hash(), &fees, &receipts, call_count);
+					self.receipt_provider.insert_block_stats(&stats).await;
+				}
 				if block.number() <= oldest_block {
 					Ok(ControlFlow::Break(()))
 				} else {
@@ -454,6 +517,21 @@ impl Client {
 		Ok(hash)
 	}
 
+	/// Subscribe to the notifications of new blocks ingested by the client.
+	pub fn block_notifications(&self) -> broadcast::Receiver<BlockNotification> {
+		self.block_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of the Ethereum transactions submitted through this client.
+	pub fn pending_transaction_notifications(&self) -> broadcast::Receiver<H256> {
+		self.pending_transaction_notifier.subscribe()
+	}
+
+	/// Notify subscribers that the Ethereum transaction with the given hash was submitted.
+	pub fn notify_pending_transaction(&self, hash: H256) {
+		let _ = self.pending_transaction_notifier.send(hash);
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -603,6 +681,48 @@ impl Client {
 		Ok(latest_block.number())
 	}
 
+	/// Get the number of the block identified by `block`.
+	///
+	/// `finalized` and `safe` resolve to the last finalized block, `latest` and `pending` to the
+	/// latest block.
+	pub async fn block_number_for(
+		&self,
+		block: &BlockNumberOrTag,
+	) -> Result<SubstrateBlockNumber, ClientError> {
+		match block {
+			BlockNumberOrTag::U256(n) => (*n).try_into().map_err(|_| ClientError::ConversionFailed),
+			BlockNumberOrTag::BlockTag(BlockTag::Earliest) => Ok(0),
+			BlockNumberOrTag::BlockTag(BlockTag::Finalized | BlockTag::Safe) => {
+				let hash = self.rpc.chain_get_finalized_head().await?;
+				let header = self
+					.rpc
+					.chain_get_header(Some(hash))
+					.await?
+					.ok_or(ClientError::BlockNotFound)?;
+				Ok(header.number())
+			},
+			BlockNumberOrTag::BlockTag(BlockTag::Latest | BlockTag::Pending) =>
+				self.block_number().await,
+		}
+	}
+
+	/// Get the hash of the block identified by `block`.
+	pub async fn block_hash_for(
+		&self,
+		block: &BlockNumberOrTag,
+	) -> Result<Option<H256>, ClientError> {
+		match block {
+			BlockNumberOrTag::BlockTag(BlockTag::Finalized | BlockTag::Safe) =>
+				Ok(Some(self.rpc.chain_get_finalized_head().await?)),
+			BlockNumberOrTag::BlockTag(BlockTag::Latest | BlockTag::Pending) =>
+				Ok(self.latest_block().await.map(|block| block.hash())),
+			_ => {
+				let number = self.block_number_for(block).await?;
+				self.get_block_hash(number).await
+			},
+		}
+	}
+
 	/// Get a block hash for the given block number.
 	pub async fn get_block_hash(
 		&self,
@@ -657,16 +777,76 @@ impl Client {
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
 	) -> Result<Vec<TransactionTrace>, ClientError> {
-		let block_hash = match block {
-			BlockNumberOrTag::U256(n) => {
-				let block_number: SubstrateBlockNumber =
-					n.try_into().map_err(|_| ClientError::ConversionFailed)?;
-				self.get_block_hash(block_number).await?
-			},
-			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
+		let block_hash = self.block_hash_for(&block).await?.ok_or(ClientError::BlockNotFound)?;
+
+		self.trace_block_by_hash(block_hash, tracer_config).await
+	}
+
+	/// Get the transaction traces for the block with the given hash.
+	pub async fn trace_block_by_hash(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> Result<Vec<TransactionTrace>, ClientError> {
+		let traces = self.trace_block(block_hash, tracer_config).await?;
+
+		let mut hashes = self
+			.receipt_provider
+			.block_transaction_hashes(&block_hash)
+			.await
+			.ok_or(ClientError::EthExtrinsicNotFound)?;
+
+		let traces = traces
+			.into_iter()
+			.filter_map(|(index, trace)| {
+				Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
+			})
+			.collect();
+
+		Ok(traces)
+	}
+
+	/// Get the version of the `ReviveApi` implemented by the runtime at the given block, or at the
+	/// latest block if `None`.
+	async fn revive_api_version(&self, at: Option<H256>) -> Result<u32, ClientError> {
+		let id = format!("0x{}", hex::encode(sp_crypto_hashing::blake2_64(b"ReviveApi")));
+		let version = self.rpc.state_get_runtime_version(at).await?;
+		version
+			.other
+			.get("apis")
+			.and_then(|apis| apis.as_array())
+			.into_iter()
+			.flatten()
+			.filter_map(|api| api.as_array())
+			.find(|api| api.first().and_then(|api_id| api_id.as_str()) == Some(id.as_str()))
+			.and_then(|api| api.get(1)?.as_u64()?.try_into().ok())
+			.ok_or(ClientError::ReviveApiNotFound)
+	}
+
+	/// Check that the runtime at the given block can run `tracer_config`.
+	///
+	/// Returns `false` if the runtime implements version 1 of the `ReviveApi`, which only
+	/// supports the call tracer and returns [`CallTrace`]s instead of [`Trace`]s.
+	async fn returns_traces(
+		&self,
+		at: Option<H256>,
+		tracer_config: &TracerConfig,
+	) -> Result<bool, ClientError> {
+		if self.revive_api_version(at).await? >= 2 {
+			return Ok(true);
+		}
+		match tracer_config {
+			TracerConfig::CallTracer { .. } => Ok(false),
+			_ => Err(ClientError::TracerNotSupported),
 		}
-		.ok_or(ClientError::BlockNotFound)?;
+	}
 
+	/// Replay the block with the given hash, and return the traces of its extrinsics by index.
+	async fn trace_block(
+		&self,
+		block_hash: H256,
+		tracer_config: TracerConfig,
+	) -> Result<Vec<(u32, Trace)>, ClientError> {
 		let block = self
 			.rpc
 			.chain_get_block(Some(block_hash))
@@ -682,6 +862,7 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let returns_traces = self.returns_traces(Some(parent_hash), &tracer_config).await?;
 		let params = ((header, exts), tracer_config).encode();
 
 		let bytes = self
@@ -692,30 +873,173 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
+		if returns_traces {
+			return Ok(Vec::<(u32, Trace)>::decode(&mut &bytes[..])?);
+		}
 		let traces = Vec::<(u32, CallTrace)>::decode(&mut &bytes[..])?;
+		Ok(traces.into_iter().map(|(index, trace)| (index, Trace::Call(trace))).collect())
+	}
 
-		let mut hashes = self
+	/// Index the calls executed by the Ethereum transactions of the given block, so that they
+	/// can be found by `trace_filter`, and return the number of indexed calls.
+	///
+	/// Does nothing and returns `None` unless call indexing is enabled. Failing to trace the
+	/// block is logged and otherwise ignored.
+	async fn index_calls(
+		&self,
+		block: &SubstrateBlock,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Option<usize> {
+		if !self.call_indexing {
+			return None;
+		}
+		if receipts.is_empty() {
+			return Some(0);
+		}
+
+		let tracer_config = TracerConfig::CallTracer { with_logs: false };
+		let traces = match self.trace_block(block.hash(), tracer_config).await {
+			Ok(traces) => traces,
+			Err(err) => {
+				log::warn!(target: LOG_TARGET, "Failed to index the calls of block {:?}: {err:?}", block.hash());
+				return None;
+			},
+		};
+
+		let calls = traces
+			.iter()
+			.flat_map(|(index, trace)| match trace {
+				Trace::Call(trace) => IndexedCall::from_trace(*index, trace),
+				_ => Vec::new(),
+			})
+			.collect::<Vec<_>>();
+		self.receipt_provider.insert_calls(&block.hash(), block.number(), &calls).await;
+		Some(calls.len())
+	}
+
+	/// Get the traces of the calls matching the given filter.
+	pub async fn trace_filter(
+		&self,
+		filter: TraceFilter,
+	) -> Result<Vec<LocalizedTrace>, ClientError> {
+		if !self.call_indexing {
+			return Err(ClientError::TraceFilterFailed(
+				"call indexing is disabled, start the server with --index-calls".into(),
+			));
+		}
+
+		let latest = self.block_number().await?;
+		let from_block = match &filter.from_block {
+			Some(block) => self.block_number_for(block).await?,
+			None => latest,
+		};
+		let to_block = match &filter.to_block {
+			Some(block) => self.block_number_for(block).await?.min(latest),
+			None => latest,
+		};
+		if from_block > to_block || to_block - from_block >= MAX_TRACE_FILTER_BLOCKS {
+			return Err(ClientError::TraceFilterFailed(format!(
+				"invalid block range, at most {MAX_TRACE_FILTER_BLOCKS} blocks up to the latest block can be searched"
+			)));
+		}
+
+		let after = filter.after.unwrap_or_default().try_into().unwrap_or(usize::MAX);
+		let count = filter.count.map_or(usize::MAX, |count| count.try_into().unwrap_or(usize::MAX));
+		if count == 0 {
+			return Ok(Vec::new());
+		}
+
+		let transactions = self
 			.receipt_provider
-			.block_transaction_hashes(&block_hash)
+			.transactions_by_call(
+				from_block,
+				to_block,
+				filter.from_addresses(),
+				filter.to_addresses(),
+			)
 			.await
-			.ok_or(ClientError::EthExtrinsicNotFound)?;
+			.map_err(|err| ClientError::TraceFilterFailed(err.to_string()))?;
+
+		// Group the transactions by block, so that every block is only replayed once.
+		let mut blocks: Vec<(H256, SubstrateBlockNumber, Vec<u32>)> = Vec::new();
+		for (block_hash, block_number, index) in transactions {
+			match blocks.last_mut() {
+				Some((hash, _, indices)) if *hash == block_hash => indices.push(index),
+				_ => blocks.push((block_hash, block_number, vec![index])),
+			}
+		}
 
-		let traces = traces
-			.into_iter()
-			.filter_map(|(index, trace)| {
-				Some(TransactionTrace { tx_hash: hashes.remove(&(index as usize))?, trace })
-			})
-			.collect();
+		let tracer_config = TracerConfig::CallTracer { with_logs: false };
+		let mut traces = Vec::new();
+		let mut skipped = 0;
+		for (block_hash, block_number, indices) in blocks {
+			let hashes = self
+				.receipt_provider
+				.block_transaction_hashes(&block_hash)
+				.await
+				.unwrap_or_default();
+			for (index, trace) in self.trace_block(block_hash, tracer_config.clone()).await? {
+				let (Trace::Call(trace), Some(transaction_hash)) =
+					(trace, hashes.get(&(index as usize)))
+				else {
+					continue;
+				};
+				if !indices.contains(&index) {
+					continue;
+				}
+
+				for trace in
+					localized_traces(&trace, block_hash, block_number, *transaction_hash, index)
+				{
+					if !filter.matches(&trace.action.from, &trace.action.to) {
+						continue;
+					}
+					if skipped < after {
+						skipped += 1;
+						continue;
+					}
+					traces.push(trace);
+					if traces.len() >= count {
+						return Ok(traces);
+					}
+				}
+			}
+		}
 
 		Ok(traces)
 	}
 
+	/// Get the receipts of the given block, ordered by transaction index.
+	pub async fn receipts_by_block(
+		&self,
+		block: &BlockNumberOrTagOrHash,
+	) -> Result<Option<Vec<ReceiptInfo>>, ClientError> {
+		let block_hash = match block {
+			BlockNumberOrTagOrHash::H256(hash) => *hash,
+			BlockNumberOrTagOrHash::U256(n) => {
+				let n = (*n).try_into().map_err(|_| ClientError::ConversionFailed)?;
+				let Some(hash) = self.get_block_hash(n).await? else { return Ok(None) };
+				hash
+			},
+			BlockNumberOrTagOrHash::BlockTag(tag) => {
+				let Some(hash) =
+					self.block_hash_for(&BlockNumberOrTag::BlockTag(tag.clone())).await?
+				else {
+					return Ok(None)
+				};
+				hash
+			},
+		};
+
+		Ok(self.receipt_provider.receipts_by_block_hash(&block_hash).await)
+	}
+
 	/// Get the transaction traces for the given transaction.
 	pub async fn trace_transaction(
 		&self,
 		transaction_hash: H256,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
+	) -> Result<Trace, ClientError> {
 		let ReceiptInfo { block_hash, transaction_index, .. } = self
 			.receipt_provider
 			.receipt_by_hash(&transaction_hash)
@@ -740,6 +1064,7 @@ impl Client {
 			.filter_map(|e| OpaqueExtrinsic::decode(&mut &e[..]).ok())
 			.collect::<Vec<_>>();
 
+		let returns_traces = self.returns_traces(Some(parent_hash), &tracer_config).await?;
 		let params = ((header, exts), transaction_index.as_u32(), tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -749,7 +1074,11 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
-		let trace = Option::<CallTrace>::decode(&mut &bytes[..])?;
+		let trace = if returns_traces {
+			Option::<Trace>::decode(&mut &bytes[..])?
+		} else {
+			Option::<CallTrace>::decode(&mut &bytes[..])?.map(Trace::Call)
+		};
 		trace.ok_or(ClientError::EthExtrinsicNotFound)
 	}
 
@@ -759,16 +1088,10 @@ impl Client {
 		transaction: GenericTransaction,
 		block: BlockNumberOrTag,
 		tracer_config: TracerConfig,
-	) -> Result<CallTrace, ClientError> {
-		let block_hash = match block {
-			BlockNumberOrTag::U256(n) => {
-				let block_number: SubstrateBlockNumber =
-					n.try_into().map_err(|_| ClientError::ConversionFailed)?;
-				self.get_block_hash(block_number).await?
-			},
-			BlockNumberOrTag::BlockTag(_) => self.latest_block().await.map(|b| b.hash()),
-		};
+	) -> Result<Trace, ClientError> {
+		let block_hash = self.block_hash_for(&block).await?;
 
+		let returns_traces = self.returns_traces(block_hash, &tracer_config).await?;
 		let params = (transaction, tracer_config).encode();
 		let bytes = self
 			.rpc
@@ -778,7 +1101,12 @@ impl Client {
 				log::error!(target: LOG_TARGET, "state_call failed with: {err:?}");
 			})?;
 
+		if returns_traces {
+			return Result::<Trace, EthTransactError>::decode(&mut &bytes[..])?
+				.map_err(ClientError::TransactError);
+		}
 		Result::<CallTrace, EthTransactError>::decode(&mut &bytes[..])?
+			.map(Trace::Call)
 			.map_err(ClientError::TransactError)
 	}
 	/// Get the EVM block for the given hash.
@@ -832,6 +1160,116 @@ impl Client {
 		}
 	}
 
+	/// Compute the fee data of the given block from the receipts of its transactions.
+	async fn block_fees(
+		&self,
+		block: &SubstrateBlock,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<BlockFees, ClientError> {
+		let runtime_api = self.api.runtime_api().at(block.hash());
+		let gas_limit = Self::block_gas_limit(&runtime_api).await?;
+		let base_fee_per_gas =
+			runtime_api.call(subxt_client::apis().revive_api().gas_price()).await?;
+		Ok(BlockFees::new(block.number(), *base_fee_per_gas, gas_limit, receipts))
+	}
+
+	/// Compute the fee data of the given block, logging failures.
+	async fn try_block_fees(
+		&self,
+		block: &SubstrateBlock,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Option<BlockFees> {
+		self.block_fees(block, receipts)
+			.await
+			.inspect_err(|err| {
+				log::warn!(target: LOG_TARGET, "Failed to compute fees of block #{}: {err:?}", block.number());
+			})
+			.ok()
+	}
+
+	/// Get the fee history of the `block_count` blocks up to and including `newest_block`.
+	///
+	/// At most [`MAX_FEE_HISTORY_BLOCKS`] blocks are returned, `newest_block` is capped to the
+	/// latest block. `reward_percentiles` must be sorted in increasing order.
+	pub async fn fee_history(
+		&self,
+		block_count: u64,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistoryResult, ClientError> {
+		let latest = self.block_number().await?;
+		let newest_block = self.block_number_for(&newest_block).await?.min(latest);
+		let block_count = block_count.min(MAX_FEE_HISTORY_BLOCKS) as SubstrateBlockNumber;
+		let oldest_block = (newest_block + 1).saturating_sub(block_count);
+
+		let mut result = FeeHistoryResult {
+			oldest_block: oldest_block.into(),
+			reward: reward_percentiles.as_ref().map(|_| Vec::new()),
+			..Default::default()
+		};
+		if block_count == 0 {
+			return Ok(result);
+		}
+
+		for block_number in oldest_block..=newest_block {
+			let block =
+				self.block_by_number(block_number).await?.ok_or(ClientError::BlockNotFound)?;
+			let fees = match self.receipt_provider.block_fees(&block.hash()).await {
+				Some(fees) => fees,
+				None => {
+					let receipts = self.receipt_extractor.extract_from_block(&block).await?;
+					self.block_fees(&block, &receipts).await?
+				},
+			};
+
+			result.base_fee_per_gas.push(fees.base_fee_per_gas);
+			result.gas_used_ratio.push(fees.gas_used_ratio());
+			if let (Some(reward), Some(percentiles)) = (&mut result.reward, &reward_percentiles) {
+				reward.push(fees.reward_percentiles(percentiles));
+			}
+		}
+
+		// The base fee is set by the runtime and does not depend on the gas used, so the next
+		// block is expected to have the same base fee as the newest one.
+		if let Some(base_fee_per_gas) = result.base_fee_per_gas.last().copied() {
+			result.base_fee_per_gas.push(base_fee_per_gas);
+		}
+
+		Ok(result)
+	}
+
+	/// Get the stats of the indexed blocks `from_block..=to_block`.
+	///
+	/// At most [`MAX_BLOCK_STATS_BLOCKS`] blocks up to the latest block can be requested. Blocks
+	/// that are not indexed are skipped.
+	pub async fn block_stats(
+		&self,
+		from_block: BlockNumberOrTag,
+		to_block: BlockNumberOrTag,
+	) -> Result<Vec<BlockStats>, ClientError> {
+		let latest = self.block_number().await?;
+		let from_block = self.block_number_for(&from_block).await?;
+		let to_block = self.block_number_for(&to_block).await?.min(latest);
+		if from_block > to_block || to_block - from_block >= MAX_BLOCK_STATS_BLOCKS {
+			return Err(ClientError::BlockStatsFailed(format!(
+				"invalid block range, at most {MAX_BLOCK_STATS_BLOCKS} blocks up to the latest block can be requested"
+			)));
+		}
+
+		// Stats are stored for every imported block, only return those of the canonical chain.
+		let mut block_hashes = Vec::new();
+		for block_number in from_block..=to_block {
+			if let Some(hash) = self.rpc.chain_get_block_hash(Some(block_number.into())).await? {
+				block_hashes.push(hash);
+			}
+		}
+
+		self.receipt_provider
+			.block_stats(&block_hashes)
+			.await
+			.map_err(|err| ClientError::BlockStatsFailed(err.to_string()))
+	}
+
 	/// Convert a weight to a fee.
 	async fn block_gas_limit(
 		runtime_api: &subxt::runtime_api::RuntimeApi<SrcChainConfig, OnlineClient<SrcChainConfig>>,
@@ -0,0 +1,265 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types and helpers used to answer `eth_feeHistory` and `revive_blockStats`.
+use crate::client::SubstrateBlockNumber;
+use pallet_revive::evm::{ReceiptInfo, TransactionSigned, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be requested with `eth_feeHistory`.
+pub const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
+
+/// The maximum number of reward percentiles that can be requested with `eth_feeHistory`.
+pub const MAX_REWARD_PERCENTILES: usize = 100;
+
+/// The maximum number of blocks that can be requested with `revive_blockStats`.
+pub const MAX_BLOCK_STATS_BLOCKS: SubstrateBlockNumber = 1024;
+
+/// The fee data of a block, as stored by the [`crate::ReceiptProvider`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockFees {
+	/// The number of the block.
+	pub block_number: SubstrateBlockNumber,
+	/// The base fee per gas of the block, i.e. the runtime's `evm_gas_price`.
+	pub base_fee_per_gas: U256,
+	/// The gas limit of the block.
+	pub gas_limit: U256,
+	/// The gas used by the Ethereum transactions of the block.
+	pub gas_used: U256,
+	/// The gas used and priority fee per gas of every transaction, sorted by priority fee.
+	pub rewards: Vec<(U256, U256)>,
+}
+
+impl BlockFees {
+	/// Build the fee data of a block from the receipts of its transactions.
+	pub fn new(
+		block_number: SubstrateBlockNumber,
+		base_fee_per_gas: U256,
+		gas_limit: U256,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Self {
+		let mut rewards = receipts
+			.iter()
+			.map(|(_, receipt)| {
+				(receipt.gas_used, receipt.effective_gas_price.saturating_sub(base_fee_per_gas))
+			})
+			.collect::<Vec<_>>();
+		rewards.sort_by_key(|(_, reward)| *reward);
+
+		let gas_used = rewards.iter().fold(U256::zero(), |acc, (gas_used, _)| acc + gas_used);
+		Self { block_number, base_fee_per_gas, gas_limit, gas_used, rewards }
+	}
+
+	/// The ratio of gas used to the gas limit of the block.
+	pub fn gas_used_ratio(&self) -> f64 {
+		gas_used_ratio(self.gas_used, self.gas_limit)
+	}
+
+	/// The priority fees at the given `percentiles` of the gas used in the block.
+	///
+	/// Transactions are weighted by the gas they used, following the reference implementation.
+	/// `percentiles` must be sorted in increasing order.
+	pub fn reward_percentiles(&self, percentiles: &[f64]) -> Vec<U256> {
+		if self.rewards.is_empty() {
+			return vec![U256::zero(); percentiles.len()];
+		}
+
+		let gas_used = self.gas_used.low_u128() as f64;
+		let mut index = 0;
+		let mut cumulative_gas_used = self.rewards[0].0;
+		percentiles
+			.iter()
+			.map(|percentile| {
+				let threshold = U256::from((gas_used * percentile / 100.0) as u128);
+				while cumulative_gas_used < threshold && index < self.rewards.len() - 1 {
+					index += 1;
+					cumulative_gas_used += self.rewards[index].0;
+				}
+				self.rewards[index].1
+			})
+			.collect()
+	}
+}
+
+/// The result of `eth_feeHistory`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryResult {
+	/// The lowest block number of the returned range.
+	pub oldest_block: U256,
+	/// The base fee per gas of every block in the range, followed by the one of the next block.
+	pub base_fee_per_gas: Vec<U256>,
+	/// The ratio of gas used to the gas limit of every block in the range.
+	pub gas_used_ratio: Vec<f64>,
+	/// The requested priority fee percentiles of every block in the range.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reward: Option<Vec<Vec<U256>>>,
+}
+
+/// The gas usage and fee aggregates of a block, as stored by the [`crate::ReceiptProvider`] and
+/// returned by `revive_blockStats`.
+///
+/// `baseFeePerGas` and `gasUsedRatio` carry the same values as the per block entries of
+/// `eth_feeHistory`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStats {
+	/// The hash of the block.
+	pub block_hash: H256,
+	/// The number of the block.
+	pub block_number: U256,
+	/// The base fee per gas of the block.
+	pub base_fee_per_gas: U256,
+	/// The gas limit of the block.
+	pub gas_limit: U256,
+	/// The gas used by the Ethereum transactions of the block.
+	pub gas_used: U256,
+	/// The ratio of gas used to the gas limit of the block.
+	pub gas_used_ratio: f64,
+	/// The number of Ethereum transactions of the block.
+	pub transaction_count: U256,
+	/// The number of distinct calls executed by the transactions of the block, including nested
+	/// calls, or `None` if the block could not be traced.
+	pub contract_call_count: Option<U256>,
+	/// The lowest effective gas price paid in the block.
+	pub min_effective_gas_price: U256,
+	/// The average effective gas price paid in the block, weighted by gas used.
+	pub avg_effective_gas_price: U256,
+	/// The highest effective gas price paid in the block.
+	pub max_effective_gas_price: U256,
+	/// The sum of the fees paid by the transactions of the block.
+	pub total_fees: U256,
+}
+
+impl BlockStats {
+	/// Build the stats of a block from its fee data and the receipts of its transactions.
+	pub fn new(
+		block_hash: H256,
+		fees: &BlockFees,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+		contract_call_count: Option<usize>,
+	) -> Self {
+		let prices = receipts.iter().map(|(_, receipt)| receipt.effective_gas_price);
+		let total_fees = receipts.iter().fold(U256::zero(), |acc, (_, receipt)| {
+			acc.saturating_add(receipt.gas_used.saturating_mul(receipt.effective_gas_price))
+		});
+		let avg_effective_gas_price =
+			if fees.gas_used.is_zero() { U256::zero() } else { total_fees / fees.gas_used };
+
+		Self {
+			block_hash,
+			block_number: fees.block_number.into(),
+			base_fee_per_gas: fees.base_fee_per_gas,
+			gas_limit: fees.gas_limit,
+			gas_used: fees.gas_used,
+			gas_used_ratio: fees.gas_used_ratio(),
+			transaction_count: receipts.len().into(),
+			contract_call_count: contract_call_count.map(Into::into),
+			min_effective_gas_price: prices.clone().min().unwrap_or_default(),
+			avg_effective_gas_price,
+			max_effective_gas_price: prices.max().unwrap_or_default(),
+			total_fees,
+		}
+	}
+}
+
+/// The ratio of `gas_used` to `gas_limit`, or `0` if there is no gas limit.
+pub fn gas_used_ratio(gas_used: U256, gas_limit: U256) -> f64 {
+	if gas_limit.is_zero() {
+		return 0.0;
+	}
+	gas_used.low_u128() as f64 / gas_limit.low_u128() as f64
+}
+
+/// Whether `percentiles` are within `0..=100` and sorted in increasing order.
+pub fn valid_reward_percentiles(percentiles: &[f64]) -> bool {
+	percentiles.len() <= MAX_REWARD_PERCENTILES &&
+		percentiles.iter().all(|p| (0.0..=100.0).contains(p)) &&
+		percentiles.windows(2).all(|w| w[0] <= w[1])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn receipt(gas_used: u64, effective_gas_price: u64) -> (TransactionSigned, ReceiptInfo) {
+		let receipt = ReceiptInfo {
+			gas_used: gas_used.into(),
+			effective_gas_price: effective_gas_price.into(),
+			..Default::default()
+		};
+		(TransactionSigned::default(), receipt)
+	}
+
+	#[test]
+	fn reward_percentiles_works() {
+		let receipts = [receipt(50, 13), receipt(30, 11), receipt(20, 12)];
+		let fees = BlockFees::new(1, 10.into(), 200.into(), &receipts);
+
+		assert_eq!(fees.gas_used, U256::from(100));
+		assert_eq!(fees.gas_used_ratio(), 0.5);
+		assert_eq!(
+			fees.rewards,
+			vec![(30.into(), 1.into()), (20.into(), 2.into()), (50.into(), 3.into())]
+		);
+		assert_eq!(
+			fees.reward_percentiles(&[0.0, 30.0, 31.0, 50.0, 51.0, 100.0]),
+			[1u32, 1, 2, 2, 3, 3].map(U256::from).to_vec()
+		);
+	}
+
+	#[test]
+	fn reward_percentiles_of_empty_block_are_zero() {
+		let fees = BlockFees::new(1, 10.into(), 200.into(), &[]);
+		assert_eq!(fees.gas_used_ratio(), 0.0);
+		assert_eq!(fees.reward_percentiles(&[25.0, 75.0]), vec![U256::zero(); 2]);
+	}
+
+	#[test]
+	fn block_stats_works() {
+		let receipts = [receipt(50, 13), receipt(30, 11), receipt(20, 12)];
+		let fees = BlockFees::new(1, 10.into(), 200.into(), &receipts);
+		let stats = BlockStats::new(H256::repeat_byte(1), &fees, &receipts, Some(4));
+
+		assert_eq!(stats.gas_used_ratio, 0.5);
+		assert_eq!(stats.transaction_count, U256::from(3));
+		assert_eq!(stats.contract_call_count, Some(U256::from(4)));
+		assert_eq!(stats.min_effective_gas_price, U256::from(11));
+		assert_eq!(stats.max_effective_gas_price, U256::from(13));
+		assert_eq!(stats.total_fees, U256::from(50 * 13 + 30 * 11 + 20 * 12));
+		assert_eq!(stats.avg_effective_gas_price, U256::from(12));
+	}
+
+	#[test]
+	fn block_stats_of_empty_block_are_zero() {
+		let fees = BlockFees::new(1, 10.into(), 200.into(), &[]);
+		let stats = BlockStats::new(H256::zero(), &fees, &[], None);
+
+		assert_eq!(stats.base_fee_per_gas, U256::from(10));
+		assert_eq!(stats.transaction_count, U256::zero());
+		assert_eq!(stats.avg_effective_gas_price, U256::zero());
+		assert_eq!(stats.total_fees, U256::zero());
+	}
+
+	#[test]
+	fn valid_reward_percentiles_works() {
+		assert!(valid_reward_percentiles(&[]));
+		assert!(valid_reward_percentiles(&[0.0, 50.0, 50.0, 100.0]));
+		assert!(!valid_reward_percentiles(&[50.0, 20.0]));
+		assert!(!valid_reward_percentiles(&[-1.0]));
+		assert!(!valid_reward_percentiles(&[100.5]));
+	}
+}
@@ -0,0 +1,331 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Server side state of the filters installed with `eth_newFilter`, `eth_newBlockFilter` and
+//! `eth_newPendingTransactionFilter`, and polled with `eth_getFilterChanges`.
+use crate::{
+	client::{Client, SubstrateBlockNumber},
+	EthRpcError,
+};
+use pallet_revive::evm::{
+	AddressOrAddresses, BlockNumberOrTag, Filter, FilterResults, FilterTopic, Log, H256, U256,
+};
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::{broadcast, Mutex};
+
+/// Filters that are not polled within this duration are uninstalled.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters installed at the same time.
+const MAX_FILTERS: usize = 1024;
+
+/// The kind of an installed filter, along with the state needed to poll it.
+enum FilterKind {
+	/// Logs matching `filter` in the blocks after `last_block`.
+	Logs { filter: Filter, last_block: SubstrateBlockNumber },
+	/// Hashes of the blocks after `last_block`.
+	Blocks { last_block: SubstrateBlockNumber },
+	/// Hashes of the transactions submitted since the last poll.
+	PendingTransactions(broadcast::Receiver<H256>),
+}
+
+struct InstalledFilter {
+	kind: FilterKind,
+	last_poll: Instant,
+}
+
+#[derive(Default)]
+struct Filters {
+	next_id: u64,
+	installed: HashMap<U256, InstalledFilter>,
+}
+
+impl Filters {
+	/// Install a filter of the given kind and return its id.
+	///
+	/// Fails when [`MAX_FILTERS`] filters are already installed.
+	fn install(&mut self, kind: FilterKind) -> Result<U256, EthRpcError> {
+		self.prune();
+		if self.installed.len() >= MAX_FILTERS {
+			return Err(EthRpcError::TooManyFilters);
+		}
+		self.next_id += 1;
+		let id = U256::from(self.next_id);
+		self.installed.insert(id, InstalledFilter { kind, last_poll: Instant::now() });
+		Ok(id)
+	}
+
+	/// Uninstall the filters that were not polled in time.
+	fn prune(&mut self) {
+		self.installed.retain(|_, filter| filter.last_poll.elapsed() < FILTER_TIMEOUT);
+	}
+
+	/// Get the filter with the given id, and mark it as polled.
+	fn poll(&mut self, id: &U256) -> Result<&mut FilterKind, EthRpcError> {
+		self.prune();
+		let filter = self.installed.get_mut(id).ok_or(EthRpcError::FilterNotFound(*id))?;
+		filter.last_poll = Instant::now();
+		Ok(&mut filter.kind)
+	}
+
+	/// Mark the blocks up to `to` as reported by the filter with the given id, if still installed.
+	fn advance(&mut self, id: &U256, to: SubstrateBlockNumber) {
+		if let Some(FilterKind::Logs { last_block, .. } | FilterKind::Blocks { last_block }) =
+			self.installed.get_mut(id).map(|filter| &mut filter.kind)
+		{
+			*last_block = to.max(*last_block);
+		}
+	}
+}
+
+/// Keeps track of the filters installed on the server.
+///
+/// Filters only report changes that happened after they were installed. They are uninstalled
+/// automatically when they are not polled for [`FILTER_TIMEOUT`].
+#[derive(Clone, Default)]
+pub struct FilterManager {
+	filters: Arc<Mutex<Filters>>,
+}
+
+impl FilterManager {
+	/// Install a filter for the logs matching `filter`.
+	pub async fn new_filter(&self, client: &Client, filter: Filter) -> Result<U256, EthRpcError> {
+		if filter.block_hash.is_some() {
+			return Err(EthRpcError::InvalidFilter("blockHash is not supported".into()));
+		}
+		let last_block = client.block_number().await?;
+		self.filters.lock().await.install(FilterKind::Logs { filter, last_block })
+	}
+
+	/// Install a filter for the hashes of new blocks.
+	pub async fn new_block_filter(&self, client: &Client) -> Result<U256, EthRpcError> {
+		let last_block = client.block_number().await?;
+		self.filters.lock().await.install(FilterKind::Blocks { last_block })
+	}
+
+	/// Install a filter for the hashes of the transactions submitted through this server.
+	pub async fn new_pending_transaction_filter(
+		&self,
+		client: &Client,
+	) -> Result<U256, EthRpcError> {
+		let receiver = client.pending_transaction_notifications();
+		self.filters.lock().await.install(FilterKind::PendingTransactions(receiver))
+	}
+
+	/// Uninstall the filter with the given id. Return whether it was installed.
+	pub async fn uninstall_filter(&self, id: U256) -> bool {
+		self.filters.lock().await.installed.remove(&id).is_some()
+	}
+
+	/// Get the changes of the filter with the given id since it was last polled.
+	///
+	/// The filter only moves past the queried blocks once the query succeeded, so that a failed
+	/// poll can be retried without missing any changes.
+	pub async fn filter_changes(
+		&self,
+		client: &Client,
+		id: U256,
+	) -> Result<FilterResults, EthRpcError> {
+		let latest = client.block_number().await?;
+
+		// Don't hold the lock while querying the client.
+		let (filter, from, to) = {
+			let mut filters = self.filters.lock().await;
+			match filters.poll(&id)? {
+				FilterKind::PendingTransactions(receiver) => {
+					let mut hashes = Vec::new();
+					loop {
+						match receiver.try_recv() {
+							Ok(hash) => hashes.push(hash),
+							Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+								log::debug!(target: crate::LOG_TARGET, "Filter {id:?} missed {skipped} pending transactions");
+							},
+							Err(_) => break,
+						}
+					}
+					return Ok(FilterResults::Hashes(hashes));
+				},
+				FilterKind::Blocks { last_block } => (None, *last_block + 1, latest),
+				FilterKind::Logs { filter, last_block } =>
+					(Some(filter.clone()), *last_block + 1, latest),
+			}
+		};
+
+		let results = match filter {
+			None => {
+				let mut hashes = Vec::new();
+				for number in from..=to {
+					if let Some(hash) = client.get_block_hash(number).await? {
+						hashes.push(hash);
+					}
+				}
+				FilterResults::Hashes(hashes)
+			},
+			Some(filter) => match clamp_range(&filter, from, to) {
+				None => FilterResults::Logs(Vec::new()),
+				Some((from, to)) => {
+					let filter = Filter {
+						from_block: Some(U256::from(from).into()),
+						to_block: Some(U256::from(to).into()),
+						..filter
+					};
+					FilterResults::Logs(client.logs(Some(filter)).await?)
+				},
+			},
+		};
+
+		self.filters.lock().await.advance(&id, to);
+		Ok(results)
+	}
+
+	/// Get all the logs matching the log filter with the given id.
+	pub async fn filter_logs(&self, client: &Client, id: U256) -> Result<Vec<Log>, EthRpcError> {
+		let filter = match self.filters.lock().await.poll(&id)? {
+			FilterKind::Logs { filter, .. } => filter.clone(),
+			_ => return Err(EthRpcError::FilterNotFound(id)),
+		};
+		Ok(client.logs(Some(filter)).await?)
+	}
+}
+
+/// Restrict the block range `from..=to` to the block range of `filter`.
+fn clamp_range(
+	filter: &Filter,
+	from: SubstrateBlockNumber,
+	to: SubstrateBlockNumber,
+) -> Option<(SubstrateBlockNumber, SubstrateBlockNumber)> {
+	let as_number = |block: &Option<BlockNumberOrTag>| match block {
+		Some(BlockNumberOrTag::U256(n)) =>
+			Some((*n).try_into().unwrap_or(SubstrateBlockNumber::MAX)),
+		_ => None,
+	};
+	let from = as_number(&filter.from_block).map_or(from, |n| from.max(n));
+	let to = as_number(&filter.to_block).map_or(to, |n| to.min(n));
+	(from <= to).then_some((from, to))
+}
+
+/// Whether `log` matches the addresses and topics of `filter`.
+///
+/// The block range of the filter is ignored.
+pub fn matches_filter(filter: &Filter, log: &Log) -> bool {
+	let address_matches = match &filter.address {
+		None => true,
+		Some(AddressOrAddresses::Address(address)) => *address == log.address,
+		Some(AddressOrAddresses::Addresses(addresses)) => addresses.contains(&log.address),
+	};
+	let topics_match = filter.topics.iter().flatten().enumerate().all(|(i, topic)| {
+		match (topic, log.topics.get(i)) {
+			(FilterTopic::Multiple(hashes), _) if hashes.is_empty() => true,
+			(_, None) => false,
+			(FilterTopic::Single(hash), Some(topic)) => hash == topic,
+			(FilterTopic::Multiple(hashes), Some(topic)) => hashes.contains(topic),
+		}
+	});
+	address_matches && topics_match
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::H160;
+
+	fn log(address: H160, topics: Vec<H256>) -> Log {
+		Log { address, topics, ..Default::default() }
+	}
+
+	#[test]
+	fn matches_filter_works() {
+		let (a, b) = (H160::from([1; 20]), H160::from([2; 20]));
+		let (t1, t2) = (H256::from([1; 32]), H256::from([2; 32]));
+
+		let filter = Filter::default();
+		assert!(matches_filter(&filter, &log(a, vec![])));
+
+		let filter = Filter { address: Some(a.into()), ..Default::default() };
+		assert!(matches_filter(&filter, &log(a, vec![t1])));
+		assert!(!matches_filter(&filter, &log(b, vec![t1])));
+
+		let filter = Filter { address: Some(vec![a, b].into()), ..Default::default() };
+		assert!(matches_filter(&filter, &log(b, vec![])));
+
+		let filter = Filter {
+			topics: Some(vec![FilterTopic::Multiple(vec![]), t2.into()]),
+			..Default::default()
+		};
+		assert!(matches_filter(&filter, &log(a, vec![t1, t2])));
+		assert!(!matches_filter(&filter, &log(a, vec![t2, t1])));
+		assert!(!matches_filter(&filter, &log(a, vec![t1])));
+
+		let filter = Filter { topics: Some(vec![vec![t1, t2].into()]), ..Default::default() };
+		assert!(matches_filter(&filter, &log(a, vec![t2])));
+	}
+
+	#[test]
+	fn install_is_capped() {
+		let mut filters = Filters::default();
+		for _ in 0..MAX_FILTERS {
+			assert!(filters.install(FilterKind::Blocks { last_block: 0 }).is_ok());
+		}
+		assert!(matches!(
+			filters.install(FilterKind::Blocks { last_block: 0 }),
+			Err(EthRpcError::TooManyFilters)
+		));
+
+		filters.installed.remove(&U256::one());
+		assert!(filters.install(FilterKind::Blocks { last_block: 0 }).is_ok());
+	}
+
+	#[test]
+	fn advance_works() {
+		let mut filters = Filters::default();
+		let id = filters.install(FilterKind::Blocks { last_block: 5 }).unwrap();
+		let last_block = |filters: &mut Filters| match filters.poll(&id).unwrap() {
+			FilterKind::Blocks { last_block } => *last_block,
+			_ => unreachable!(),
+		};
+
+		filters.advance(&id, 7);
+		assert_eq!(last_block(&mut filters), 7);
+
+		// A poll of an older range does not move the filter back.
+		filters.advance(&id, 6);
+		assert_eq!(last_block(&mut filters), 7);
+
+		// Advancing an uninstalled filter is a no-op.
+		filters.installed.remove(&id);
+		filters.advance(&id, 8);
+		assert!(filters.poll(&id).is_err());
+	}
+
+	#[test]
+	fn clamp_range_works() {
+		let filter = Filter::default();
+		assert_eq!(clamp_range(&filter, 3, 5), Some((3, 5)));
+
+		let filter = Filter {
+			from_block: Some(U256::from(4).into()),
+			to_block: Some(U256::from(10).into()),
+			..Default::default()
+		};
+		assert_eq!(clamp_range(&filter, 3, 5), Some((4, 5)));
+		assert_eq!(clamp_range(&filter, 8, 12), Some((8, 10)));
+		assert_eq!(clamp_range(&filter, 11, 12), None);
+	}
+}
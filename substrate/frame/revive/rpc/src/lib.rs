@@ -41,6 +41,15 @@ pub use block_info_provider::*;
 mod receipt_provider;
 pub use receipt_provider::*;
 
+mod filters;
+pub use filters::*;
+
+mod fee_history;
+pub use fee_history::*;
+
+mod trace_filter;
+pub use trace_filter::*;
+
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
@@ -56,12 +65,15 @@ pub struct EthRpcServerImpl {
 
 	/// The accounts managed by the server.
 	accounts: Vec<Account>,
+
+	/// The filters installed on the server.
+	filters: FilterManager,
 }
 
 impl EthRpcServerImpl {
 	/// Creates a new [`EthRpcServerImpl`].
 	pub fn new(client: client::Client) -> Self {
-		Self { client, accounts: vec![] }
+		Self { client, accounts: vec![], filters: Default::default() }
 	}
 
 	/// Sets the accounts managed by the server.
@@ -95,6 +107,18 @@ pub enum EthRpcError {
 	/// Received an invalid transaction
 	#[error("Invalid transaction {0:?}")]
 	TransactionTypeNotSupported(Byte),
+	/// The filter was not found, or has expired.
+	#[error("Filter {0:?} not found")]
+	FilterNotFound(U256),
+	/// The filter parameters are not supported.
+	#[error("Invalid filter: {0}")]
+	InvalidFilter(String),
+	/// The maximum number of installed filters was reached.
+	#[error("Too many filters installed")]
+	TooManyFilters,
+	/// The reward percentiles are out of range or not sorted.
+	#[error("Invalid reward percentiles")]
+	InvalidRewardPercentiles,
 }
 
 // TODO use https://eips.ethereum.org/EIPS/eip-1474#error-codes
@@ -166,6 +190,7 @@ impl EthRpcServer for EthRpcServerImpl {
 		})?;
 
 		log::debug!(target: LOG_TARGET, "send_raw_transaction hash: {hash:?}");
+		self.client.notify_pending_transaction(hash);
 		Ok(hash)
 	}
 
@@ -227,6 +252,24 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.chain_id().into())
 	}
 
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> RpcResult<FeeHistoryResult> {
+		if let Some(percentiles) = &reward_percentiles {
+			if !valid_reward_percentiles(percentiles) {
+				return Err(EthRpcError::InvalidRewardPercentiles.into());
+			}
+		}
+
+		let block_count = block_count.try_into().unwrap_or(u64::MAX);
+		let fee_history =
+			self.client.fee_history(block_count, newest_block, reward_percentiles).await?;
+		Ok(fee_history)
+	}
+
 	async fn gas_price(&self) -> RpcResult<U256> {
 		Ok(self.client.gas_price(&BlockTag::Latest.into()).await?)
 	}
@@ -258,6 +301,14 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(Some(block))
 	}
 
+	async fn get_block_receipts(
+		&self,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<Option<Vec<ReceiptInfo>>> {
+		let receipts = self.client.receipts_by_block(&block).await?;
+		Ok(receipts)
+	}
+
 	async fn get_block_transaction_count_by_hash(
 		&self,
 		block_hash: Option<H256>,
@@ -289,6 +340,31 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.filters.filter_changes(&self.client, filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		let logs = self.filters.filter_logs(&self.client, filter_id).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		Ok(self.filters.new_filter(&self.client, filter).await?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		Ok(self.filters.new_block_filter(&self.client).await?)
+	}
+
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+		Ok(self.filters.new_pending_transaction_filter(&self.client).await?)
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.filters.uninstall_filter(filter_id).await)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
@@ -15,8 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{client::SubstrateBlockNumber, BlockFees, BlockStats, IndexedCall};
 use jsonrpsee::core::async_trait;
-use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H256};
+use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H160, H256};
 use std::collections::HashMap;
 use tokio::join;
 
@@ -57,8 +58,49 @@ pub trait ReceiptProvider: Send + Sync {
 	/// Get the receipt for the given transaction hash.
 	async fn receipt_by_hash(&self, transaction_hash: &H256) -> Option<ReceiptInfo>;
 
+	/// Get all the receipts of the given block hash, ordered by transaction index.
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>>;
+
 	/// Get the signed transaction for the given transaction hash.
 	async fn signed_tx_by_hash(&self, transaction_hash: &H256) -> Option<TransactionSigned>;
+
+	/// Insert the fee data of the given block.
+	async fn insert_block_fees(&self, block_hash: &H256, fees: &BlockFees);
+
+	/// Similar to `insert_block_fees`, but intended for archiving the fees of historical blocks.
+	async fn archive_block_fees(&self, block_hash: &H256, fees: &BlockFees);
+
+	/// Get the fee data of the given block.
+	async fn block_fees(&self, block_hash: &H256) -> Option<BlockFees>;
+
+	/// Index the calls executed by the transactions of the given block.
+	async fn insert_calls(
+		&self,
+		block_hash: &H256,
+		block_number: SubstrateBlockNumber,
+		calls: &[IndexedCall],
+	);
+
+	/// Get the transactions of the blocks `from_block..=to_block` that executed a call from one
+	/// of `from_addresses` to one of `to_addresses`, as `(block_hash, block_number,
+	/// transaction_index)`, ordered by block number and transaction index.
+	///
+	/// An empty address list matches every address.
+	async fn transactions_by_call(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		from_addresses: &[H160],
+		to_addresses: &[H160],
+	) -> anyhow::Result<Vec<(H256, SubstrateBlockNumber, u32)>>;
+
+	/// Insert the gas usage and fee aggregates of a block.
+	async fn insert_block_stats(&self, stats: &BlockStats);
+
+	/// Get the stats of the blocks with the given hashes, ordered by block number.
+	///
+	/// Blocks without stats are skipped.
+	async fn block_stats(&self, block_hashes: &[H256]) -> anyhow::Result<Vec<BlockStats>>;
 }
 
 #[async_trait]
@@ -110,6 +152,13 @@ impl<Cache: ReceiptProvider, Archive: ReceiptProvider> ReceiptProvider for (Cach
 		self.1.receipt_by_hash(hash).await
 	}
 
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>> {
+		if let Some(receipts) = self.0.receipts_by_block_hash(block_hash).await {
+			return Some(receipts);
+		}
+		self.1.receipts_by_block_hash(block_hash).await
+	}
+
 	async fn signed_tx_by_hash(&self, hash: &H256) -> Option<TransactionSigned> {
 		if let Some(tx) = self.0.signed_tx_by_hash(hash).await {
 			return Some(tx);
@@ -120,4 +169,64 @@ impl<Cache: ReceiptProvider, Archive: ReceiptProvider> ReceiptProvider for (Cach
 	async fn logs(&self, filter: Option<Filter>) -> anyhow::Result<Vec<Log>> {
 		self.1.logs(filter).await
 	}
+
+	async fn insert_block_fees(&self, block_hash: &H256, fees: &BlockFees) {
+		join!(
+			self.0.insert_block_fees(block_hash, fees),
+			self.1.insert_block_fees(block_hash, fees)
+		);
+	}
+
+	async fn archive_block_fees(&self, block_hash: &H256, fees: &BlockFees) {
+		self.1.insert_block_fees(block_hash, fees).await;
+	}
+
+	async fn block_fees(&self, block_hash: &H256) -> Option<BlockFees> {
+		if let Some(fees) = self.0.block_fees(block_hash).await {
+			return Some(fees);
+		}
+		self.1.block_fees(block_hash).await
+	}
+
+	async fn insert_calls(
+		&self,
+		block_hash: &H256,
+		block_number: SubstrateBlockNumber,
+		calls: &[IndexedCall],
+	) {
+		join!(
+			self.0.insert_calls(block_hash, block_number, calls),
+			self.1.insert_calls(block_hash, block_number, calls)
+		);
+	}
+
+	async fn transactions_by_call(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		from_addresses: &[H160],
+		to_addresses: &[H160],
+	) -> anyhow::Result<Vec<(H256, SubstrateBlockNumber, u32)>> {
+		match self
+			.1
+			.transactions_by_call(from_block, to_block, from_addresses, to_addresses)
+			.await
+		{
+			Ok(transactions) => Ok(transactions),
+			Err(err) => {
+				log::debug!(target: crate::LOG_TARGET, "Failed to query the indexed calls: {err:?}, using the cache");
+				self.0
+					.transactions_by_call(from_block, to_block, from_addresses, to_addresses)
+					.await
+			},
+		}
+	}
+
+	async fn insert_block_stats(&self, stats: &BlockStats) {
+		self.1.insert_block_stats(stats).await;
+	}
+
+	async fn block_stats(&self, block_hashes: &[H256]) -> anyhow::Result<Vec<BlockStats>> {
+		self.1.block_stats(block_hashes).await
+	}
 }
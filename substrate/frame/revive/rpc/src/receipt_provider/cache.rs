@@ -15,8 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use super::ReceiptProvider;
+use crate::{client::SubstrateBlockNumber, BlockFees, BlockStats, IndexedCall};
 use jsonrpsee::core::async_trait;
-use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H256};
+use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned, H160, H256};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
 
@@ -80,10 +81,63 @@ impl ReceiptProvider for CacheReceiptProvider {
 		cache.receipts_by_hash.get(hash).cloned()
 	}
 
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>> {
+		let cache = self.cache().await;
+		let mut hashes = cache
+			.transaction_hashes_by_block_and_index
+			.get(block_hash)?
+			.iter()
+			.collect::<Vec<_>>();
+		hashes.sort_by_key(|(index, _)| **index);
+		hashes
+			.into_iter()
+			.map(|(_, hash)| cache.receipts_by_hash.get(hash).cloned())
+			.collect()
+	}
+
 	async fn signed_tx_by_hash(&self, hash: &H256) -> Option<TransactionSigned> {
 		let cache = self.cache().await;
 		cache.signed_tx_by_hash.get(hash).cloned()
 	}
+
+	async fn insert_block_fees(&self, block_hash: &H256, fees: &BlockFees) {
+		let mut cache = self.cache.write().await;
+		cache.block_fees.insert(*block_hash, fees.clone());
+	}
+
+	async fn archive_block_fees(&self, _block_hash: &H256, _fees: &BlockFees) {}
+
+	async fn block_fees(&self, block_hash: &H256) -> Option<BlockFees> {
+		let cache = self.cache().await;
+		cache.block_fees.get(block_hash).cloned()
+	}
+
+	async fn insert_calls(
+		&self,
+		block_hash: &H256,
+		block_number: SubstrateBlockNumber,
+		calls: &[IndexedCall],
+	) {
+		let mut cache = self.cache.write().await;
+		cache.calls_by_block.insert(*block_hash, (block_number, calls.to_vec()));
+	}
+
+	async fn transactions_by_call(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		from_addresses: &[H160],
+		to_addresses: &[H160],
+	) -> anyhow::Result<Vec<(H256, SubstrateBlockNumber, u32)>> {
+		let cache = self.cache().await;
+		Ok(cache.transactions_by_call(from_block, to_block, from_addresses, to_addresses))
+	}
+
+	async fn insert_block_stats(&self, _stats: &BlockStats) {}
+
+	async fn block_stats(&self, _block_hashes: &[H256]) -> anyhow::Result<Vec<BlockStats>> {
+		anyhow::bail!("Not implemented")
+	}
 }
 
 #[derive(Default)]
@@ -96,6 +150,12 @@ struct ReceiptCache {
 
 	/// A map of receipt hashes by block hash.
 	transaction_hashes_by_block_and_index: HashMap<H256, HashMap<usize, H256>>,
+
+	/// A map of fee data by block hash.
+	block_fees: HashMap<H256, BlockFees>,
+
+	/// A map of the block number and indexed calls by block hash.
+	calls_by_block: HashMap<H256, (SubstrateBlockNumber, Vec<IndexedCall>)>,
 }
 
 impl ReceiptCache {
@@ -123,8 +183,41 @@ impl ReceiptCache {
 		}
 	}
 
+	/// Get the transactions of the blocks `from_block..=to_block` with a call from one of
+	/// `from_addresses` to one of `to_addresses`, ordered by block number and transaction index.
+	///
+	/// Empty address lists match any address.
+	pub fn transactions_by_call(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		from_addresses: &[H160],
+		to_addresses: &[H160],
+	) -> Vec<(H256, SubstrateBlockNumber, u32)> {
+		let matches =
+			|addresses: &[H160], address| addresses.is_empty() || addresses.contains(address);
+		let mut transactions = self
+			.calls_by_block
+			.iter()
+			.filter(|(_, (number, _))| (from_block..=to_block).contains(number))
+			.flat_map(|(hash, (number, calls))| {
+				calls
+					.iter()
+					.filter(|call| {
+						matches(from_addresses, &call.from) && matches(to_addresses, &call.to)
+					})
+					.map(move |call| (*hash, *number, call.transaction_index))
+			})
+			.collect::<Vec<_>>();
+		transactions.sort_by_key(|(hash, number, index)| (*number, *index, *hash));
+		transactions.dedup();
+		transactions
+	}
+
 	/// Remove entry from the cache.
 	pub fn remove(&mut self, hash: &H256) {
+		self.block_fees.remove(hash);
+		self.calls_by_block.remove(hash);
 		if let Some(entries) = self.transaction_hashes_by_block_and_index.remove(hash) {
 			for hash in entries.values() {
 				self.receipts_by_hash.remove(hash);
@@ -153,9 +246,41 @@ mod test {
 			);
 		}
 
+		cache.block_fees.insert(H256::from([1u8; 32]), Default::default());
+		cache.calls_by_block.insert(H256::from([1u8; 32]), (1, Vec::new()));
 		cache.remove(&H256::from([1u8; 32]));
+		assert!(cache.block_fees.is_empty());
+		assert!(cache.calls_by_block.is_empty());
 		assert_eq!(cache.transaction_hashes_by_block_and_index.len(), 2);
 		assert_eq!(cache.receipts_by_hash.len(), 2);
 		assert_eq!(cache.signed_tx_by_hash.len(), 2);
 	}
+
+	#[test]
+	fn transactions_by_call_works() {
+		let mut cache = ReceiptCache::default();
+		let (block1, block2) = (H256::from([1u8; 32]), H256::from([2u8; 32]));
+		let (a, b, c) = (H160::from([1u8; 20]), H160::from([2u8; 20]), H160::from([3u8; 20]));
+		cache.calls_by_block.insert(
+			block1,
+			(
+				1,
+				vec![
+					IndexedCall { transaction_index: 0, from: a, to: b },
+					IndexedCall { transaction_index: 0, from: b, to: c },
+				],
+			),
+		);
+		cache
+			.calls_by_block
+			.insert(block2, (2, vec![IndexedCall { transaction_index: 3, from: a, to: c }]));
+
+		assert_eq!(
+			cache.transactions_by_call(1, 2, &[], &[]),
+			vec![(block1, 1, 0), (block2, 2, 3)]
+		);
+		assert_eq!(cache.transactions_by_call(1, 2, &[b], &[]), vec![(block1, 1, 0)]);
+		assert_eq!(cache.transactions_by_call(1, 2, &[a], &[c]), vec![(block2, 2, 3)]);
+		assert_eq!(cache.transactions_by_call(2, 2, &[], &[b]), vec![]);
+	}
 }
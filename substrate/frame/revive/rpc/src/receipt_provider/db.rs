@@ -17,12 +17,14 @@
 
 use super::*;
 use crate::{
-	Address, AddressOrAddresses, BlockInfoProvider, BlockNumberOrTag, BlockTag, Bytes, FilterTopic,
+	client::SubstrateBlockNumber, gas_used_ratio, Address, AddressOrAddresses, BlockFees,
+	BlockInfoProvider, BlockNumberOrTag, BlockStats, BlockTag, Bytes, FilterTopic, IndexedCall,
 	ReceiptExtractor, LOG_TARGET,
 };
+use codec::{Decode, Encode};
 use jsonrpsee::core::async_trait;
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
 use sqlx::{query, QueryBuilder, Row, Sqlite, SqlitePool};
 use std::{collections::HashMap, sync::Arc};
 
@@ -99,7 +101,40 @@ impl ReceiptProvider for DBReceiptProvider {
 		)
 		.execute(&self.pool);
 
-		let (tx_result, logs_result) = tokio::join!(delete_transaction_hashes, delete_logs);
+		let delete_block_fees = query!(
+			r#"
+        DELETE FROM block_fees
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let delete_calls = query!(
+			r#"
+        DELETE FROM calls
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let delete_block_stats = query!(
+			r#"
+        DELETE FROM block_stats
+        WHERE block_hash = $1
+        "#,
+			block_hash
+		)
+		.execute(&self.pool);
+
+		let (tx_result, logs_result, fees_result, calls_result, stats_result) = tokio::join!(
+			delete_transaction_hashes,
+			delete_logs,
+			delete_block_fees,
+			delete_calls,
+			delete_block_stats
+		);
 
 		if let Err(err) = tx_result {
 			log::error!(target: LOG_TARGET, "Error removing transaction hashes for block hash {block_hash:?}: {err:?}");
@@ -108,6 +143,18 @@ impl ReceiptProvider for DBReceiptProvider {
 		if let Err(err) = logs_result {
 			log::error!(target: LOG_TARGET, "Error removing logs for block hash {block_hash:?}: {err:?}");
 		}
+
+		if let Err(err) = fees_result {
+			log::error!(target: LOG_TARGET, "Error removing block fees for block hash {block_hash:?}: {err:?}");
+		}
+
+		if let Err(err) = calls_result {
+			log::error!(target: LOG_TARGET, "Error removing calls for block hash {block_hash:?}: {err:?}");
+		}
+
+		if let Err(err) = stats_result {
+			log::error!(target: LOG_TARGET, "Error removing block stats for block hash {block_hash:?}: {err:?}");
+		}
 	}
 
 	async fn archive(&self, block_hash: &H256, receipts: &[(TransactionSigned, ReceiptInfo)]) {
@@ -380,6 +427,12 @@ impl ReceiptProvider for DBReceiptProvider {
 		Some(receipt)
 	}
 
+	async fn receipts_by_block_hash(&self, block_hash: &H256) -> Option<Vec<ReceiptInfo>> {
+		let block = self.block_provider.block_by_hash(block_hash).await.ok()??;
+		let receipts = self.receipt_extractor.extract_from_block(&block).await.ok()?;
+		Some(receipts.into_iter().map(|(_, receipt)| receipt).collect())
+	}
+
 	async fn signed_tx_by_hash(&self, transaction_hash: &H256) -> Option<TransactionSigned> {
 		let transaction_hash = transaction_hash.as_ref();
 		let result = query!(
@@ -405,6 +458,229 @@ impl ReceiptProvider for DBReceiptProvider {
 			.ok()?;
 		Some(signed_tx)
 	}
+
+	async fn insert_block_fees(&self, block_hash: &H256, fees: &BlockFees) {
+		let block_hash = block_hash.as_ref();
+		let block_number = fees.block_number as i64;
+		let base_fee_per_gas = fees.base_fee_per_gas.to_big_endian().to_vec();
+		let gas_limit = fees.gas_limit.to_big_endian().to_vec();
+		let gas_used = fees.gas_used.to_big_endian().to_vec();
+		let rewards = fees.rewards.encode();
+
+		let result = query!(
+			r#"
+			INSERT OR REPLACE INTO block_fees (block_hash, block_number, base_fee_per_gas, gas_limit, gas_used, rewards)
+			VALUES ($1, $2, $3, $4, $5, $6)
+			"#,
+			block_hash,
+			block_number,
+			base_fee_per_gas,
+			gas_limit,
+			gas_used,
+			rewards
+		)
+		.execute(&self.pool)
+		.await;
+
+		if let Err(err) = result {
+			log::error!(target: LOG_TARGET, "Error inserting block fees for block hash {block_hash:?}: {err:?}");
+		}
+	}
+
+	async fn archive_block_fees(&self, block_hash: &H256, fees: &BlockFees) {
+		self.insert_block_fees(block_hash, fees).await;
+	}
+
+	async fn block_fees(&self, block_hash: &H256) -> Option<BlockFees> {
+		let block_hash = block_hash.as_ref();
+		let row = query!(
+			r#"
+			SELECT block_number, base_fee_per_gas, gas_limit, gas_used, rewards
+			FROM block_fees
+			WHERE block_hash = $1
+			"#,
+			block_hash
+		)
+		.fetch_optional(&self.pool)
+		.await
+		.ok()??;
+
+		Some(BlockFees {
+			block_number: row.block_number.try_into().ok()?,
+			base_fee_per_gas: U256::from_big_endian(&row.base_fee_per_gas),
+			gas_limit: U256::from_big_endian(&row.gas_limit),
+			gas_used: U256::from_big_endian(&row.gas_used),
+			rewards: Decode::decode(&mut &row.rewards[..]).ok()?,
+		})
+	}
+
+	async fn insert_calls(
+		&self,
+		block_hash: &H256,
+		block_number: SubstrateBlockNumber,
+		calls: &[IndexedCall],
+	) {
+		let block_hash = block_hash.as_ref();
+		let block_number = block_number as i64;
+		for call in calls {
+			let transaction_index = call.transaction_index as i64;
+			let from_address = call.from.as_ref();
+			let to_address = call.to.as_ref();
+
+			let result = query!(
+				r#"
+				INSERT OR REPLACE INTO calls (block_hash, block_number, transaction_index, from_address, to_address)
+				VALUES ($1, $2, $3, $4, $5)
+				"#,
+				block_hash,
+				block_number,
+				transaction_index,
+				from_address,
+				to_address
+			)
+			.execute(&self.pool)
+			.await;
+
+			if let Err(err) = result {
+				log::error!(target: LOG_TARGET, "Error inserting calls for block hash {block_hash:?}: {err:?}");
+			}
+		}
+	}
+
+	async fn transactions_by_call(
+		&self,
+		from_block: SubstrateBlockNumber,
+		to_block: SubstrateBlockNumber,
+		from_addresses: &[H160],
+		to_addresses: &[H160],
+	) -> anyhow::Result<Vec<(H256, SubstrateBlockNumber, u32)>> {
+		let mut qb = QueryBuilder::<Sqlite>::new(
+			"SELECT DISTINCT block_hash, block_number, transaction_index FROM calls WHERE block_number BETWEEN ",
+		);
+		qb.push_bind(from_block as i64).push(" AND ").push_bind(to_block as i64);
+
+		for (column, addresses) in [("from_address", from_addresses), ("to_address", to_addresses)]
+		{
+			if addresses.is_empty() {
+				continue;
+			}
+			qb.push(format_args!(" AND {column} IN ("));
+			let mut separated = qb.separated(", ");
+			for address in addresses {
+				separated.push_bind(address.0.to_vec());
+			}
+			separated.push_unseparated(")");
+		}
+
+		qb.push(" ORDER BY block_number, transaction_index");
+
+		let transactions = qb
+			.build()
+			.try_map(|row| {
+				let block_hash: Vec<u8> = row.try_get("block_hash")?;
+				let block_number: i64 = row.try_get("block_number")?;
+				let transaction_index: i64 = row.try_get("transaction_index")?;
+				Ok((
+					H256::from_slice(&block_hash),
+					block_number as SubstrateBlockNumber,
+					transaction_index as u32,
+				))
+			})
+			.fetch_all(&self.pool)
+			.await?;
+
+		Ok(transactions)
+	}
+
+	async fn insert_block_stats(&self, stats: &BlockStats) {
+		let block_hash = stats.block_hash.as_ref();
+		let transaction_count = stats.transaction_count.as_u64() as i64;
+		let contract_call_count = stats.contract_call_count.map(|count| count.as_u64() as i64);
+		let min_effective_gas_price = stats.min_effective_gas_price.to_big_endian().to_vec();
+		let avg_effective_gas_price = stats.avg_effective_gas_price.to_big_endian().to_vec();
+		let max_effective_gas_price = stats.max_effective_gas_price.to_big_endian().to_vec();
+		let total_fees = stats.total_fees.to_big_endian().to_vec();
+
+		let result = query!(
+			r#"
+			INSERT OR REPLACE INTO block_stats (
+				block_hash,
+				transaction_count,
+				contract_call_count,
+				min_effective_gas_price,
+				avg_effective_gas_price,
+				max_effective_gas_price,
+				total_fees)
+			VALUES ($1, $2, $3, $4, $5, $6, $7)
+			"#,
+			block_hash,
+			transaction_count,
+			contract_call_count,
+			min_effective_gas_price,
+			avg_effective_gas_price,
+			max_effective_gas_price,
+			total_fees
+		)
+		.execute(&self.pool)
+		.await;
+
+		if let Err(err) = result {
+			log::error!(target: LOG_TARGET, "Error inserting block stats for block hash {block_hash:?}: {err:?}");
+		}
+	}
+
+	async fn block_stats(&self, block_hashes: &[H256]) -> anyhow::Result<Vec<BlockStats>> {
+		if block_hashes.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		// The gas and base fee data of a block is stored in `block_fees`.
+		let mut qb = QueryBuilder::<Sqlite>::new(
+			"SELECT block_stats.*, block_fees.block_number, block_fees.base_fee_per_gas, \
+			block_fees.gas_limit, block_fees.gas_used FROM block_stats \
+			JOIN block_fees ON block_fees.block_hash = block_stats.block_hash \
+			WHERE block_stats.block_hash IN (",
+		);
+		let mut separated = qb.separated(", ");
+		for block_hash in block_hashes {
+			separated.push_bind(block_hash.as_bytes().to_vec());
+		}
+		separated.push_unseparated(")");
+		qb.push(" ORDER BY block_fees.block_number");
+
+		let stats = qb
+			.build()
+			.try_map(|row| {
+				let u256 = |column: &str| -> Result<U256, sqlx::Error> {
+					let bytes: Vec<u8> = row.try_get(column)?;
+					Ok(U256::from_big_endian(&bytes))
+				};
+				let block_hash: Vec<u8> = row.try_get("block_hash")?;
+				let block_number: i64 = row.try_get("block_number")?;
+				let transaction_count: i64 = row.try_get("transaction_count")?;
+				let contract_call_count: Option<i64> = row.try_get("contract_call_count")?;
+				let gas_limit = u256("gas_limit")?;
+				let gas_used = u256("gas_used")?;
+				Ok(BlockStats {
+					block_hash: H256::from_slice(&block_hash),
+					block_number: U256::from(block_number as u64),
+					base_fee_per_gas: u256("base_fee_per_gas")?,
+					gas_limit,
+					gas_used,
+					gas_used_ratio: gas_used_ratio(gas_used, gas_limit),
+					transaction_count: U256::from(transaction_count as u64),
+					contract_call_count: contract_call_count.map(|count| U256::from(count as u64)),
+					min_effective_gas_price: u256("min_effective_gas_price")?,
+					avg_effective_gas_price: u256("avg_effective_gas_price")?,
+					max_effective_gas_price: u256("max_effective_gas_price")?,
+					total_fees: u256("total_fees")?,
+				})
+			})
+			.fetch_all(&self.pool)
+			.await?;
+
+		Ok(stats)
+	}
 }
 
 #[cfg(test)]
@@ -470,6 +746,129 @@ mod tests {
 		assert_eq!(logs_count, 0);
 	}
 
+	#[sqlx::test]
+	async fn test_insert_remove_block_fees(pool: SqlitePool) {
+		let provider = setup_sqlite_provider(pool).await;
+		let block_hash = H256::from([1u8; 32]);
+		let fees = BlockFees {
+			block_number: 1,
+			base_fee_per_gas: U256::from(1_000),
+			gas_limit: U256::from(1_000_000),
+			gas_used: U256::from(50_000),
+			rewards: vec![(U256::from(21_000), U256::from(1)), (U256::from(29_000), U256::from(2))],
+		};
+
+		provider.insert_block_fees(&block_hash, &fees).await;
+		assert_eq!(provider.block_fees(&block_hash).await, Some(fees));
+
+		provider.remove(&block_hash).await;
+		assert_eq!(provider.block_fees(&block_hash).await, None);
+	}
+
+	#[sqlx::test]
+	async fn test_insert_remove_calls(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let (a, b, c) = (H160::from([1u8; 20]), H160::from([2u8; 20]), H160::from([3u8; 20]));
+		let block1 = H256::from([1u8; 32]);
+		let block2 = H256::from([2u8; 32]);
+
+		provider
+			.insert_calls(
+				&block1,
+				1,
+				&[
+					IndexedCall { transaction_index: 0, from: a, to: b },
+					IndexedCall { transaction_index: 0, from: b, to: c },
+					IndexedCall { transaction_index: 1, from: c, to: b },
+				],
+			)
+			.await;
+		provider
+			.insert_calls(&block2, 2, &[IndexedCall { transaction_index: 3, from: a, to: c }])
+			.await;
+
+		// All calls of the range.
+		assert_eq!(
+			provider.transactions_by_call(1, 2, &[], &[]).await?,
+			vec![(block1, 1, 0), (block1, 1, 1), (block2, 2, 3)]
+		);
+
+		// Calls by caller and callee.
+		assert_eq!(
+			provider.transactions_by_call(1, 2, &[a], &[]).await?,
+			vec![(block1, 1, 0), (block2, 2, 3)]
+		);
+		assert_eq!(
+			provider.transactions_by_call(1, 1, &[], &[b]).await?,
+			vec![(block1, 1, 0), (block1, 1, 1)]
+		);
+		assert_eq!(provider.transactions_by_call(1, 2, &[a], &[c]).await?, vec![(block2, 2, 3)]);
+
+		// Removing a block removes its calls.
+		provider.remove(&block1).await;
+		assert_eq!(provider.transactions_by_call(1, 2, &[], &[]).await?, vec![(block2, 2, 3)]);
+		Ok(())
+	}
+
+	#[sqlx::test]
+	async fn test_insert_remove_block_stats(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let stats =
+			|block_hash: H256, block_number: u64, contract_call_count: Option<u64>| BlockStats {
+				block_hash,
+				block_number: block_number.into(),
+				base_fee_per_gas: U256::from(1_000),
+				gas_limit: U256::from(1_000_000),
+				gas_used: U256::from(50_000 * block_number),
+				gas_used_ratio: gas_used_ratio(
+					U256::from(50_000 * block_number),
+					U256::from(1_000_000),
+				),
+				transaction_count: U256::from(2),
+				contract_call_count: contract_call_count.map(Into::into),
+				min_effective_gas_price: U256::from(1_001),
+				avg_effective_gas_price: U256::from(1_002),
+				max_effective_gas_price: U256::from(1_003),
+				total_fees: U256::from(50_100_000),
+			};
+		let canonical = |block_number: u64| H256::repeat_byte(block_number as u8);
+		let (stats1, stats2, stats3) = (
+			stats(canonical(1), 1, Some(3)),
+			stats(canonical(2), 2, None),
+			stats(canonical(3), 3, Some(0)),
+		);
+		let fork2 = stats(H256::repeat_byte(0xf2), 2, Some(1));
+
+		// Inserted out of order, along with the fee data of the blocks.
+		for stats in [&stats3, &fork2, &stats1, &stats2] {
+			let fees = BlockFees {
+				block_number: stats.block_number.as_u32(),
+				base_fee_per_gas: stats.base_fee_per_gas,
+				gas_limit: stats.gas_limit,
+				gas_used: stats.gas_used,
+				rewards: vec![],
+			};
+			provider.insert_block_fees(&stats.block_hash, &fees).await;
+			provider.insert_block_stats(stats).await;
+		}
+
+		// Only the requested blocks are returned, ordered by block number.
+		let hashes = [canonical(3), canonical(1), canonical(2)];
+		assert_eq!(
+			provider.block_stats(&hashes).await?,
+			vec![stats1.clone(), stats2.clone(), stats3]
+		);
+		assert_eq!(provider.block_stats(&[canonical(2)]).await?, vec![stats2.clone()]);
+		assert_eq!(provider.block_stats(&[fork2.block_hash]).await?, vec![fork2]);
+		assert_eq!(provider.block_stats(&[canonical(4)]).await?, vec![]);
+		assert_eq!(provider.block_stats(&[]).await?, vec![]);
+
+		// Removing a block removes its stats.
+		provider.remove(&stats1.block_hash).await;
+		assert_eq!(provider.block_stats(&hashes[1..]).await?, vec![stats2]);
+		Ok(())
+	}
+
 	#[sqlx::test]
 	async fn test_receipts_count_per_block(pool: SqlitePool) {
 		let provider = setup_sqlite_provider(pool).await;
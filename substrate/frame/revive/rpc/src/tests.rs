@@ -75,6 +75,7 @@ impl SharedResources {
 			"--rpc-port=45788",
 			"--node-rpc-url=ws://localhost:45789",
 			"--no-prometheus",
+			"--index-calls",
 			"-linfo,eth-rpc=debug",
 		]);
 
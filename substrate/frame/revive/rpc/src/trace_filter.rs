@@ -0,0 +1,280 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types and helpers used to answer `trace_filter`.
+use crate::client::SubstrateBlockNumber;
+use pallet_revive::evm::{BlockNumberOrTag, Bytes, CallTrace, CallType, H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be searched with `trace_filter`.
+pub const MAX_TRACE_FILTER_BLOCKS: SubstrateBlockNumber = 1024;
+
+/// A call between two addresses, as indexed by the [`crate::ReceiptProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexedCall {
+	/// The index of the transaction that executed the call.
+	pub transaction_index: u32,
+	/// The caller.
+	pub from: H160,
+	/// The callee.
+	pub to: H160,
+}
+
+impl IndexedCall {
+	/// The distinct calls of `trace` and all its sub calls.
+	pub fn from_trace(transaction_index: u32, trace: &CallTrace) -> Vec<Self> {
+		let mut calls = Vec::new();
+		let mut stack = vec![trace];
+		while let Some(trace) = stack.pop() {
+			let call = Self { transaction_index, from: trace.from, to: trace.to };
+			if !calls.contains(&call) {
+				calls.push(call);
+			}
+			stack.extend(trace.calls.iter());
+		}
+		calls
+	}
+}
+
+/// The filter of `trace_filter`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// The first block to search, defaults to the latest block.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to search, defaults to the latest block.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only report the calls made by one of these addresses.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub from_address: Option<Vec<H160>>,
+	/// Only report the calls made to one of these addresses.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to_address: Option<Vec<H160>>,
+	/// The number of matching traces to skip.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub after: Option<u64>,
+	/// The maximum number of traces to report.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub count: Option<u64>,
+}
+
+impl TraceFilter {
+	/// The addresses the callers must be one of, or an empty slice to match every caller.
+	pub fn from_addresses(&self) -> &[H160] {
+		self.from_address.as_deref().unwrap_or_default()
+	}
+
+	/// The addresses the callees must be one of, or an empty slice to match every callee.
+	pub fn to_addresses(&self) -> &[H160] {
+		self.to_address.as_deref().unwrap_or_default()
+	}
+
+	/// Whether a call from `from` to `to` matches the addresses of the filter.
+	pub fn matches(&self, from: &H160, to: &H160) -> bool {
+		let matches =
+			|addresses: &[H160], address| addresses.is_empty() || addresses.contains(address);
+		matches(self.from_addresses(), from) && matches(self.to_addresses(), to)
+	}
+}
+
+/// The type of a call reported by `trace_filter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceCallType {
+	/// A regular call.
+	Call,
+	/// A read-only call.
+	StaticCall,
+	/// A delegate call.
+	DelegateCall,
+}
+
+impl From<&CallType> for TraceCallType {
+	fn from(call_type: &CallType) -> Self {
+		match call_type {
+			CallType::Call => Self::Call,
+			CallType::StaticCall => Self::StaticCall,
+			CallType::DelegateCall => Self::DelegateCall,
+		}
+	}
+}
+
+/// The call reported by a [`LocalizedTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+	/// The type of the call.
+	pub call_type: TraceCallType,
+	/// The caller.
+	pub from: H160,
+	/// The gas provided to the call.
+	pub gas: U256,
+	/// The input of the call.
+	pub input: Bytes,
+	/// The callee.
+	pub to: H160,
+	/// The value transferred by the call.
+	pub value: U256,
+}
+
+/// The outcome of a successful call reported by a [`LocalizedTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOutcome {
+	/// The gas used by the call.
+	pub gas_used: U256,
+	/// The data returned by the call.
+	pub output: Bytes,
+}
+
+/// A call reported by `trace_filter`, along with its position in the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+	/// The call.
+	pub action: CallAction,
+	/// The hash of the block of the transaction.
+	pub block_hash: H256,
+	/// The number of the block of the transaction.
+	pub block_number: SubstrateBlockNumber,
+	/// The outcome of the call, if it succeeded.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub result: Option<CallOutcome>,
+	/// The error of the call, if it failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The number of sub calls of the call.
+	pub subtraces: usize,
+	/// The position of the call in the call tree of the transaction.
+	pub trace_address: Vec<usize>,
+	/// The hash of the transaction.
+	pub transaction_hash: H256,
+	/// The index of the transaction in its block.
+	pub transaction_position: u32,
+	/// The type of the trace, always `call`.
+	#[serde(rename = "type")]
+	pub trace_type: String,
+}
+
+/// Flatten the call tree of a transaction, parent calls first, the way `trace_filter` reports
+/// it.
+pub fn localized_traces(
+	trace: &CallTrace,
+	block_hash: H256,
+	block_number: SubstrateBlockNumber,
+	transaction_hash: H256,
+	transaction_position: u32,
+) -> Vec<LocalizedTrace> {
+	fn flatten(
+		trace: &CallTrace,
+		trace_address: Vec<usize>,
+		localize: &impl Fn(&CallTrace, Vec<usize>) -> LocalizedTrace,
+		traces: &mut Vec<LocalizedTrace>,
+	) {
+		traces.push(localize(trace, trace_address.clone()));
+		for (i, call) in trace.calls.iter().enumerate() {
+			let mut address = trace_address.clone();
+			address.push(i);
+			flatten(call, address, localize, traces);
+		}
+	}
+
+	let localize = |trace: &CallTrace, trace_address| LocalizedTrace {
+		action: CallAction {
+			call_type: (&trace.call_type).into(),
+			from: trace.from,
+			gas: trace.gas,
+			input: trace.input.clone(),
+			to: trace.to,
+			value: trace.value.unwrap_or_default(),
+		},
+		block_hash,
+		block_number,
+		result: trace
+			.error
+			.is_none()
+			.then(|| CallOutcome { gas_used: trace.gas_used, output: trace.output.clone() }),
+		error: trace.error.clone(),
+		subtraces: trace.calls.len(),
+		trace_address,
+		transaction_hash,
+		transaction_position,
+		trace_type: "call".into(),
+	};
+
+	let mut traces = Vec::new();
+	flatten(trace, Vec::new(), &localize, &mut traces);
+	traces
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn call(from: u8, to: u8, calls: Vec<CallTrace>) -> CallTrace {
+		CallTrace {
+			from: H160::from([from; 20]),
+			to: H160::from([to; 20]),
+			calls,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn localized_traces_works() {
+		let trace = call(1, 2, vec![call(2, 3, vec![call(3, 4, vec![])]), call(2, 4, vec![])]);
+		let traces = localized_traces(&trace, H256::zero(), 1, H256::zero(), 0);
+
+		assert_eq!(
+			traces
+				.iter()
+				.map(|t| (t.action.to, t.trace_address.clone(), t.subtraces))
+				.collect::<Vec<_>>(),
+			vec![
+				(H160::from([2; 20]), vec![], 2),
+				(H160::from([3; 20]), vec![0], 1),
+				(H160::from([4; 20]), vec![0, 0], 0),
+				(H160::from([4; 20]), vec![1], 0),
+			]
+		);
+	}
+
+	#[test]
+	fn indexed_calls_are_distinct() {
+		let trace = call(1, 2, vec![call(2, 3, vec![]), call(2, 3, vec![])]);
+		assert_eq!(IndexedCall::from_trace(7, &trace).len(), 2);
+	}
+
+	#[test]
+	fn trace_filter_matches_addresses() {
+		let (a, b, c) = (H160::from([1; 20]), H160::from([2; 20]), H160::from([3; 20]));
+		assert!(TraceFilter::default().matches(&a, &b));
+
+		let filter = TraceFilter { from_address: Some(vec![a]), ..Default::default() };
+		assert!(filter.matches(&a, &c));
+		assert!(!filter.matches(&b, &c));
+
+		let filter = TraceFilter {
+			from_address: Some(vec![a]),
+			to_address: Some(vec![b, c]),
+			..Default::default()
+		};
+		assert!(filter.matches(&a, &c));
+		assert!(!filter.matches(&a, &a));
+	}
+}
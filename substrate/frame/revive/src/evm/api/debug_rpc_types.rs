@@ -15,8 +15,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::{Bytes, CallTracer};
-use alloc::{fmt, string::String, vec::Vec};
+use crate::{
+	evm::{Bytes, CallTracer, EvmTracer, PrestateTracer, StructLogger},
+	Config,
+};
+use alloc::{collections::BTreeMap, fmt, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{
@@ -25,6 +28,9 @@ use serde::{
 };
 use sp_core::{H160, H256, U256};
 
+/// The number of instructions captured by [`TracerConfig::StructLogger`] when no limit is given.
+pub const DEFAULT_STRUCT_LOG_LIMIT: u32 = 10_000;
+
 /// Tracer configuration used to trace calls.
 #[derive(TypeInfo, Debug, Clone, Encode, Decode, Serialize, PartialEq)]
 #[serde(tag = "tracer", content = "tracerConfig")]
@@ -36,13 +42,51 @@ pub enum TracerConfig {
 		#[serde(rename = "withLog")]
 		with_logs: bool,
 	},
+
+	/// A tracer that captures the state of the accounts touched by a transaction.
+	#[serde(rename = "prestateTracer")]
+	PrestateTracer {
+		/// Whether to capture the state before and after the transaction instead of only the
+		/// state before it.
+		#[serde(rename = "diffMode")]
+		diff_mode: bool,
+		/// Whether to omit the code of the accounts.
+		#[serde(rename = "disableCode")]
+		disable_code: bool,
+		/// Whether to omit the storage of the accounts.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+	},
+
+	/// A tracer that captures every executed PolkaVM instruction.
+	///
+	/// This is the tracer used when no tracer is specified.
+	#[serde(rename = "structLogger")]
+	StructLogger {
+		/// Whether to omit the storage accessed by the instructions.
+		#[serde(rename = "disableStorage")]
+		disable_storage: bool,
+		/// The maximum number of instructions to capture, `0` meaning no limit.
+		///
+		/// Defaults to [`DEFAULT_STRUCT_LOG_LIMIT`] when deserialized.
+		limit: u32,
+	},
 }
 
 impl TracerConfig {
 	/// Build the tracer associated to this config.
-	pub fn build<G>(self, gas_mapper: G) -> CallTracer<U256, G> {
+	pub fn build<T: Config, G>(self, gas_mapper: G) -> EvmTracer<T, G> {
 		match self {
-			Self::CallTracer { with_logs } => CallTracer::new(with_logs, gas_mapper),
+			Self::CallTracer { with_logs } =>
+				EvmTracer::CallTracer(CallTracer::new(with_logs, gas_mapper)),
+			Self::PrestateTracer { diff_mode, disable_code, disable_storage } =>
+				EvmTracer::PrestateTracer(PrestateTracer::new(
+					diff_mode,
+					disable_code,
+					disable_storage,
+				)),
+			Self::StructLogger { disable_storage, limit } =>
+				EvmTracer::StructLogger(StructLogger::new(disable_storage, limit, gas_mapper)),
 		}
 	}
 }
@@ -56,6 +100,16 @@ impl TracerConfig {
 /// ```json
 /// { "tracer": "callTracer" }
 /// ```
+///
+/// ```json
+/// { "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }
+/// ```
+///
+/// When no tracer is specified, the struct logger is used, configured with the top level fields:
+///
+/// ```json
+/// { "disableStorage": true, "limit": 100 }
+/// ```
 impl<'de> Deserialize<'de> for TracerConfig {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
@@ -67,15 +121,27 @@ impl<'de> Deserialize<'de> for TracerConfig {
 			type Value = TracerConfig;
 
 			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-				formatter.write_str("a map with tracer and optional tracerConfig")
+				formatter.write_str("a map with optional tracer and tracerConfig")
 			}
 
 			fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
 			where
 				M: MapAccess<'de>,
 			{
+				#[derive(Deserialize, Default)]
+				#[serde(rename_all = "camelCase")]
+				struct InnerTracerConfig {
+					with_logs: Option<bool>,
+					diff_mode: Option<bool>,
+					disable_code: Option<bool>,
+					disable_storage: Option<bool>,
+					limit: Option<u32>,
+				}
+
 				let mut tracer_type: Option<String> = None;
-				let mut with_logs = None;
+				let mut inner = InnerTracerConfig::default();
+				let mut disable_storage = None;
+				let mut limit = None;
 
 				while let Some(key) = map.next_key::<String>()? {
 					match key.as_str() {
@@ -83,22 +149,35 @@ impl<'de> Deserialize<'de> for TracerConfig {
 							tracer_type = map.next_value()?;
 						},
 						"tracerConfig" => {
-							#[derive(Deserialize)]
-							struct CallTracerConfig {
-								#[serde(rename = "withLogs")]
-								with_logs: Option<bool>,
-							}
-							let inner: CallTracerConfig = map.next_value()?;
-							with_logs = inner.with_logs;
+							inner = map.next_value()?;
+						},
+						"disableStorage" => {
+							disable_storage = map.next_value()?;
+						},
+						"limit" => {
+							limit = map.next_value()?;
+						},
+						_ => {
+							map.next_value::<de::IgnoredAny>()?;
 						},
-						_ => {},
 					}
 				}
 
 				match tracer_type.as_deref() {
 					Some("callTracer") =>
-						Ok(TracerConfig::CallTracer { with_logs: with_logs.unwrap_or(true) }),
-					_ => Err(de::Error::custom("Unsupported or missing tracer type")),
+						Ok(TracerConfig::CallTracer { with_logs: inner.with_logs.unwrap_or(true) }),
+					Some("prestateTracer") => Ok(TracerConfig::PrestateTracer {
+						diff_mode: inner.diff_mode.unwrap_or_default(),
+						disable_code: inner.disable_code.unwrap_or_default(),
+						disable_storage: inner.disable_storage.unwrap_or_default(),
+					}),
+					None | Some("structLogger") => Ok(TracerConfig::StructLogger {
+						disable_storage: disable_storage
+							.or(inner.disable_storage)
+							.unwrap_or_default(),
+						limit: limit.or(inner.limit).unwrap_or(DEFAULT_STRUCT_LOG_LIMIT),
+					}),
+					_ => Err(de::Error::custom("Unsupported tracer type")),
 				}
 			}
 		}
@@ -119,6 +198,35 @@ fn test_tracer_config_serialization() {
 			r#"{"tracer": "callTracer", "tracerConfig": { "withLogs": false }}"#,
 			TracerConfig::CallTracer { with_logs: false },
 		),
+		(
+			r#"{"tracer": "prestateTracer"}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: false,
+				disable_code: false,
+				disable_storage: false,
+			},
+		),
+		(
+			r#"{"tracer": "prestateTracer", "tracerConfig": { "diffMode": true, "disableCode": true }}"#,
+			TracerConfig::PrestateTracer {
+				diff_mode: true,
+				disable_code: true,
+				disable_storage: false,
+			},
+		),
+		(
+			r#"{}"#,
+			TracerConfig::StructLogger { disable_storage: false, limit: DEFAULT_STRUCT_LOG_LIMIT },
+		),
+		(r#"{"limit": 0}"#, TracerConfig::StructLogger { disable_storage: false, limit: 0 }),
+		(
+			r#"{"tracer": "structLogger", "tracerConfig": { "limit": 5 }}"#,
+			TracerConfig::StructLogger { disable_storage: false, limit: 5 },
+		),
+		(
+			r#"{"disableStorage": true, "enableMemory": false, "limit": 10}"#,
+			TracerConfig::StructLogger { disable_storage: true, limit: 10 },
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -126,6 +234,8 @@ fn test_tracer_config_serialization() {
 			serde_json::from_str(json_data).expect("Deserialization should succeed");
 		assert_eq!(result, expected);
 	}
+
+	assert!(serde_json::from_str::<TracerConfig>(r#"{"tracer": "4byteTracer"}"#).is_err());
 }
 
 impl Default for TracerConfig {
@@ -134,6 +244,24 @@ impl Default for TracerConfig {
 	}
 }
 
+/// The result of a trace, depending on the tracer that produced it.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Trace {
+	/// A call trace, produced by [`TracerConfig::CallTracer`].
+	Call(CallTrace),
+	/// A struct log trace, produced by [`TracerConfig::StructLogger`].
+	StructLog(StructLogTrace),
+	/// A prestate trace, produced by [`TracerConfig::PrestateTracer`].
+	Prestate(PrestateTrace),
+}
+
+impl Default for Trace {
+	fn default() -> Self {
+		Trace::Call(CallTrace::default())
+	}
+}
+
 /// The type of call that was executed.
 #[derive(
 	Default, TypeInfo, Encode, Decode, Serialize, Deserialize, Eq, PartialEq, Clone, Debug,
@@ -214,5 +342,84 @@ pub struct TransactionTrace {
 	pub tx_hash: H256,
 	/// The trace of the transaction.
 	#[serde(rename = "result")]
-	pub trace: CallTrace,
+	pub trace: Trace,
+}
+
+/// The state of the accounts touched by a transaction.
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+	/// The state of the accounts before the transaction.
+	Prestate(BTreeMap<H160, PrestateTraceInfo>),
+	/// The state of the accounts modified by the transaction, before and after it.
+	///
+	/// Only the modified fields are included in `post`.
+	DiffMode {
+		/// The state before the transaction.
+		pre: BTreeMap<H160, PrestateTraceInfo>,
+		/// The state after the transaction.
+		post: BTreeMap<H160, PrestateTraceInfo>,
+	},
+}
+
+/// The state of an account, as captured by the prestate tracer.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct PrestateTraceInfo {
+	/// The balance of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// The nonce of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	/// The code of the account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// The storage items accessed by the transaction, by unhashed key.
+	///
+	/// A `None` value denotes an empty storage item.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<Bytes, Option<Bytes>>,
+}
+
+/// The instructions executed by a transaction, as captured by the struct logger.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogTrace {
+	/// The gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// The data returned by the transaction.
+	pub return_value: Bytes,
+	/// The executed instructions.
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// A PolkaVM instruction executed by a transaction.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// The program counter of the instruction.
+	pub pc: u32,
+	/// The name of the instruction.
+	pub op: String,
+	/// The gas left before the instruction is executed.
+	pub gas: u64,
+	/// The gas consumed by the instruction, including the host functions it called.
+	pub gas_cost: u64,
+	/// The call depth at which the instruction is executed, starting at 1.
+	pub depth: u32,
+	/// The error that aborted the call after this instruction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The storage items of the executing contract accessed so far, when this instruction
+	/// accessed the storage.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<Bytes, Bytes>>,
 }
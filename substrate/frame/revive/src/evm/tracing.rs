@@ -14,41 +14,69 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+//! Tracers for the EVM compatible `debug_trace*` RPC methods.
+mod call_tracing;
+pub use call_tracing::*;
+mod prestate_tracing;
+pub use prestate_tracing::*;
+mod struct_logging;
+pub use struct_logging::*;
+
 use crate::{
-	evm::{decode_revert_reason, CallLog, CallTrace, CallType},
-	primitives::ExecReturnValue,
-	tracing::Tracer,
-	DispatchError, Weight,
+	evm::Trace, exec::Key, primitives::ExecReturnValue, tracing::Tracer, BalanceOf, Config,
+	DispatchError, MomentOf, Weight,
 };
-use alloc::{format, string::ToString, vec::Vec};
 use sp_core::{H160, H256, U256};
-
-/// A Tracer that reports logs and nested call traces transactions.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
-pub struct CallTracer<Gas, GasMapper> {
-	/// Map Weight to Gas equivalent.
-	gas_mapper: GasMapper,
-	/// Store all in-progress CallTrace instances.
-	traces: Vec<CallTrace<Gas>>,
-	/// Stack of indices to the current active traces.
-	current_stack: Vec<usize>,
-	/// whether or not to capture logs.
-	with_log: bool,
+use sp_runtime::traits::Bounded;
+
+/// A tracer built from a [`crate::evm::TracerConfig`].
+pub enum EvmTracer<T: Config, GasMapper> {
+	/// See [`CallTracer`].
+	CallTracer(CallTracer<U256, GasMapper>),
+	/// See [`PrestateTracer`].
+	PrestateTracer(PrestateTracer<T>),
+	/// See [`StructLogger`].
+	StructLogger(StructLogger<GasMapper>),
 }
 
-impl<Gas, GasMapper> CallTracer<Gas, GasMapper> {
-	/// Create a new [`CallTracer`] instance.
-	pub fn new(with_log: bool, gas_mapper: GasMapper) -> Self {
-		Self { gas_mapper, traces: Vec::new(), current_stack: Vec::new(), with_log }
+impl<T, GasMapper> EvmTracer<T, GasMapper>
+where
+	T: Config,
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+	GasMapper: Fn(Weight) -> U256,
+{
+	/// Collect the trace of the last traced transaction.
+	///
+	/// Returns `None` if no contract was called since the last collection.
+	pub fn collect_trace(&mut self) -> Option<Trace> {
+		match self {
+			Self::CallTracer(tracer) => tracer.collect_traces().pop().map(Trace::Call),
+			Self::PrestateTracer(tracer) => tracer.collect_trace().map(Trace::Prestate),
+			Self::StructLogger(tracer) => tracer.collect_trace().map(Trace::StructLog),
+		}
 	}
 
-	/// Collect the traces and return them.
-	pub fn collect_traces(&mut self) -> Vec<CallTrace<Gas>> {
-		core::mem::take(&mut self.traces)
+	/// The inner tracer.
+	fn tracer(&mut self) -> &mut dyn Tracer {
+		match self {
+			Self::CallTracer(tracer) => tracer,
+			Self::PrestateTracer(tracer) => tracer,
+			Self::StructLogger(tracer) => tracer,
+		}
 	}
 }
 
-impl<Gas: Default, GasMapper: Fn(Weight) -> Gas> Tracer for CallTracer<Gas, GasMapper> {
+impl<T, GasMapper> Tracer for EvmTracer<T, GasMapper>
+where
+	T: Config,
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+	GasMapper: Fn(Weight) -> U256,
+{
 	fn enter_child_span(
 		&mut self,
 		from: H160,
@@ -57,78 +85,57 @@ impl<Gas: Default, GasMapper: Fn(Weight) -> Gas> Tracer for CallTracer<Gas, GasM
 		is_read_only: bool,
 		value: U256,
 		input: &[u8],
-		gas_left: Weight,
+		gas: Weight,
 	) {
-		let call_type = if is_read_only {
-			CallType::StaticCall
-		} else if is_delegate_call {
-			CallType::DelegateCall
-		} else {
-			CallType::Call
-		};
-
-		self.traces.push(CallTrace {
-			from,
-			to,
-			value: if is_read_only { None } else { Some(value) },
-			call_type,
-			input: input.to_vec().into(),
-			gas: (self.gas_mapper)(gas_left),
-			..Default::default()
-		});
-
-		// Push the index onto the stack of the current active trace
-		self.current_stack.push(self.traces.len() - 1);
+		self.tracer()
+			.enter_child_span(from, to, is_delegate_call, is_read_only, value, input, gas)
 	}
 
 	fn log_event(&mut self, address: H160, topics: &[H256], data: &[u8]) {
-		if !self.with_log {
-			return;
-		}
+		self.tracer().log_event(address, topics, data)
+	}
 
-		let current_index = self.current_stack.last().unwrap();
-		let position = self.traces[*current_index].calls.len() as u32;
-		let log =
-			CallLog { address, topics: topics.to_vec(), data: data.to_vec().into(), position };
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.tracer().exit_child_span(output, gas_used)
+	}
 
-		let current_index = *self.current_stack.last().unwrap();
-		self.traces[current_index].logs.push(log);
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		self.tracer().exit_child_span_with_error(error, gas_used)
 	}
 
-	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
-		// Set the output of the current trace
-		let current_index = self.current_stack.pop().unwrap();
-		let trace = &mut self.traces[current_index];
-		trace.output = output.data.clone().into();
-		trace.gas_used = (self.gas_mapper)(gas_used);
-
-		if output.did_revert() {
-			trace.revert_reason = decode_revert_reason(&output.data);
-			trace.error = Some("execution reverted".to_string());
-		}
+	fn storage_read(&mut self, address: &H160, key: &Key, value: Option<&[u8]>) {
+		self.tracer().storage_read(address, key, value)
+	}
 
-		//  Move the current trace into its parent
-		if let Some(parent_index) = self.current_stack.last() {
-			let child_trace = self.traces.remove(current_index);
-			self.traces[*parent_index].calls.push(child_trace);
-		}
+	fn storage_write(
+		&mut self,
+		address: &H160,
+		key: &Key,
+		old_value: Option<&[u8]>,
+		new_value: Option<&[u8]>,
+	) {
+		self.tracer().storage_write(address, key, old_value, new_value)
 	}
-	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
-		// Set the output of the current trace
-		let current_index = self.current_stack.pop().unwrap();
-		let trace = &mut self.traces[current_index];
-		trace.gas_used = (self.gas_mapper)(gas_used);
-
-		trace.error = match error {
-			DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
-				Some(message.unwrap_or_default().to_string()),
-			_ => Some(format!("{:?}", error)),
-		};
-
-		//  Move the current trace into its parent
-		if let Some(parent_index) = self.current_stack.last() {
-			let child_trace = self.traces.remove(current_index);
-			self.traces[*parent_index].calls.push(child_trace);
-		}
+
+	fn balance_change(&mut self, address: &H160, old_balance: U256, new_balance: U256) {
+		self.tracer().balance_change(address, old_balance, new_balance)
+	}
+
+	fn trace_steps(&self) -> bool {
+		matches!(self, Self::StructLogger(_))
+	}
+
+	fn step(&mut self, pc: u32, opcode: &str, gas_left: Weight) {
+		self.tracer().step(pc, opcode, gas_left)
+	}
+}
+
+/// The message reported by the tracers for a call that failed with `error`.
+fn error_message(error: DispatchError) -> alloc::string::String {
+	use alloc::string::ToString;
+	match error {
+		DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+			message.unwrap_or_default().to_string(),
+		_ => alloc::format!("{:?}", error),
 	}
 }
@@ -0,0 +1,209 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, PrestateTrace, PrestateTraceInfo},
+	exec::Key,
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	AddressMapper, BalanceOf, Config, ContractInfoOf, DispatchError, MomentOf, Pallet,
+	PristineCode, Weight,
+};
+use alloc::collections::BTreeMap;
+use core::marker::PhantomData;
+use frame_system::Pallet as System;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Bounded, SaturatedConversion};
+
+/// A tracer that reports the state of the accounts touched by a transaction.
+///
+/// The state of an account is captured the first time it is touched, which is after the
+/// transaction fees were withdrawn. In diff mode, the state after the transaction is read when
+/// the trace is collected.
+pub struct PrestateTracer<T> {
+	/// Whether to report the state after the transaction as well.
+	diff_mode: bool,
+	/// Whether to omit the code of the accounts.
+	disable_code: bool,
+	/// Whether to omit the storage of the accounts.
+	disable_storage: bool,
+	/// The state of the touched accounts before the transaction.
+	pre: BTreeMap<H160, PrestateTraceInfo>,
+	/// The storage keys accessed in every account, used to read their final values.
+	keys: BTreeMap<H160, BTreeMap<Bytes, Key>>,
+	_phantom: PhantomData<T>,
+}
+
+impl<T: Config> PrestateTracer<T> {
+	/// Create a new [`PrestateTracer`] instance.
+	pub fn new(diff_mode: bool, disable_code: bool, disable_storage: bool) -> Self {
+		Self {
+			diff_mode,
+			disable_code,
+			disable_storage,
+			pre: BTreeMap::new(),
+			keys: BTreeMap::new(),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<T: Config> PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+{
+	/// Collect the trace of the last traced transaction.
+	pub fn collect_trace(&mut self) -> Option<PrestateTrace> {
+		let pre = core::mem::take(&mut self.pre);
+		let keys = core::mem::take(&mut self.keys);
+		if pre.is_empty() {
+			return None;
+		}
+		if !self.diff_mode {
+			return Some(PrestateTrace::Prestate(pre));
+		}
+
+		let mut modified = BTreeMap::new();
+		let mut post = BTreeMap::new();
+		for (address, mut pre_info) in pre {
+			let mut post_info = self.account_info(&address);
+			let contract = ContractInfoOf::<T>::get(&address);
+			for (raw_key, key) in keys.get(&address).into_iter().flatten() {
+				let value = contract.as_ref().and_then(|info| info.read(key)).map(Bytes::from);
+				if pre_info.storage.get(raw_key) == Some(&value) {
+					pre_info.storage.remove(raw_key);
+				} else {
+					post_info.storage.insert(raw_key.clone(), value);
+				}
+			}
+
+			// Only report the modified fields in the post state.
+			if post_info.balance == pre_info.balance {
+				post_info.balance = None;
+			}
+			if post_info.nonce == pre_info.nonce {
+				post_info.nonce = None;
+			}
+			if post_info.code == pre_info.code {
+				post_info.code = None;
+			}
+
+			if post_info != PrestateTraceInfo::default() {
+				modified.insert(address, pre_info);
+				post.insert(address, post_info);
+			}
+		}
+
+		Some(PrestateTrace::DiffMode { pre: modified, post })
+	}
+
+	/// The current balance, nonce and code of the account at `address`.
+	fn account_info(&self, address: &H160) -> PrestateTraceInfo {
+		let account_id = T::AddressMapper::to_account_id(address);
+		let code = if self.disable_code {
+			None
+		} else {
+			ContractInfoOf::<T>::get(address)
+				.and_then(|info| PristineCode::<T>::get(info.code_hash))
+				.map(|code| code.into_inner().into())
+		};
+
+		PrestateTraceInfo {
+			balance: Some(Pallet::<T>::evm_balance(address)),
+			nonce: Some(System::<T>::account_nonce(&account_id).saturated_into()),
+			code,
+			storage: BTreeMap::new(),
+		}
+	}
+
+	/// Capture the state of the account at `address` if it was not touched before.
+	fn touch(&mut self, address: &H160) -> &mut PrestateTraceInfo {
+		if !self.pre.contains_key(address) {
+			let info = self.account_info(address);
+			self.pre.insert(*address, info);
+		}
+		self.pre.get_mut(address).expect("inserted above; qed")
+	}
+
+	/// Capture the value of the storage item at `key` if it was not accessed before.
+	fn touch_storage(&mut self, address: &H160, key: &Key, value: Option<&[u8]>) {
+		if self.disable_storage {
+			self.touch(address);
+			return;
+		}
+		let raw_key = Bytes::from(key.unhashed().to_vec());
+		self.touch(address)
+			.storage
+			.entry(raw_key.clone())
+			.or_insert_with(|| value.map(|value| value.to_vec().into()));
+		self.keys
+			.entry(*address)
+			.or_default()
+			.entry(raw_key)
+			.or_insert_with(|| key.clone());
+	}
+}
+
+impl<T: Config> Tracer for PrestateTracer<T>
+where
+	BalanceOf<T>: Into<U256> + TryFrom<U256> + Bounded,
+	MomentOf<T>: Into<U256>,
+	T::Hash: frame_support::traits::IsType<H256>,
+{
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.touch(&from);
+		self.touch(&to);
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: Weight) {}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: Weight) {}
+
+	fn storage_read(&mut self, address: &H160, key: &Key, value: Option<&[u8]>) {
+		self.touch_storage(address, key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		address: &H160,
+		key: &Key,
+		old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+		self.touch_storage(address, key, old_value);
+	}
+
+	fn balance_change(&mut self, address: &H160, old_balance: U256, _new_balance: U256) {
+		if !self.pre.contains_key(address) {
+			let mut info = self.account_info(address);
+			info.balance = Some(old_balance);
+			self.pre.insert(*address, info);
+		}
+	}
+}
@@ -0,0 +1,171 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, StructLog, StructLogTrace},
+	exec::Key,
+	primitives::ExecReturnValue,
+	tracing::Tracer,
+	DispatchError, Weight,
+};
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that reports every PolkaVM instruction executed by a transaction.
+pub struct StructLogger<GasMapper> {
+	/// Map Weight to Gas equivalent.
+	gas_mapper: GasMapper,
+	/// Whether to omit the storage accessed by the instructions.
+	disable_storage: bool,
+	/// The maximum number of instructions to capture, `0` meaning no limit.
+	limit: u32,
+	/// The captured instructions.
+	struct_logs: Vec<StructLog>,
+	/// The index of the last captured instruction of every active call.
+	last_steps: Vec<Option<usize>>,
+	/// The storage items accessed by every active call.
+	storage: Vec<BTreeMap<Bytes, Bytes>>,
+	/// The result of the outermost call, once it returned.
+	result: Option<(U256, bool, Bytes)>,
+}
+
+impl<GasMapper> StructLogger<GasMapper> {
+	/// Create a new [`StructLogger`] instance.
+	pub fn new(disable_storage: bool, limit: u32, gas_mapper: GasMapper) -> Self {
+		Self {
+			gas_mapper,
+			disable_storage,
+			limit,
+			struct_logs: Vec::new(),
+			last_steps: Vec::new(),
+			storage: Vec::new(),
+			result: None,
+		}
+	}
+}
+
+impl<GasMapper: Fn(Weight) -> U256> StructLogger<GasMapper> {
+	/// Collect the trace of the last traced transaction.
+	pub fn collect_trace(&mut self) -> Option<StructLogTrace> {
+		let (gas, failed, return_value) = self.result.take()?;
+		Some(StructLogTrace {
+			gas: to_u64(gas),
+			failed,
+			return_value,
+			struct_logs: core::mem::take(&mut self.struct_logs),
+		})
+	}
+
+	/// Record that the current call accessed the storage item at `key`.
+	fn record_storage(&mut self, key: &Key, value: Option<&[u8]>) {
+		if self.disable_storage {
+			return;
+		}
+		let (Some(storage), Some(Some(index))) = (self.storage.last_mut(), self.last_steps.last())
+		else {
+			return;
+		};
+		storage.insert(key.unhashed().to_vec().into(), value.unwrap_or_default().to_vec().into());
+		self.struct_logs[*index].storage = Some(storage.clone());
+	}
+
+	/// Record that the current call returned.
+	fn exit(&mut self, gas_used: Weight, failed: bool, return_value: Bytes) {
+		self.last_steps.pop();
+		self.storage.pop();
+		if self.last_steps.is_empty() {
+			self.result = Some(((self.gas_mapper)(gas_used), failed, return_value));
+		}
+	}
+}
+
+impl<GasMapper: Fn(Weight) -> U256> Tracer for StructLogger<GasMapper> {
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_is_delegate_call: bool,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas: Weight,
+	) {
+		self.last_steps.push(None);
+		self.storage.push(BTreeMap::new());
+	}
+
+	fn log_event(&mut self, _address: H160, _topics: &[H256], _data: &[u8]) {}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: Weight) {
+		self.exit(gas_used, output.did_revert(), output.data.clone().into());
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: Weight) {
+		if let Some(Some(index)) = self.last_steps.last() {
+			self.struct_logs[*index].error = Some(super::error_message(error));
+		}
+		self.exit(gas_used, true, Bytes::default());
+	}
+
+	fn storage_read(&mut self, _address: &H160, key: &Key, value: Option<&[u8]>) {
+		self.record_storage(key, value);
+	}
+
+	fn storage_write(
+		&mut self,
+		_address: &H160,
+		key: &Key,
+		_old_value: Option<&[u8]>,
+		new_value: Option<&[u8]>,
+	) {
+		self.record_storage(key, new_value);
+	}
+
+	fn trace_steps(&self) -> bool {
+		true
+	}
+
+	fn step(&mut self, pc: u32, opcode: &str, gas_left: Weight) {
+		if self.limit != 0 && self.struct_logs.len() >= self.limit as usize {
+			return;
+		}
+		let gas = to_u64((self.gas_mapper)(gas_left));
+
+		// The cost of the previous instruction of this call is only known now, as it includes
+		// the host functions and the calls it executed.
+		let depth = self.last_steps.len() as u32;
+		let Some(last_step) = self.last_steps.last_mut() else { return };
+		if let Some(index) = last_step {
+			let previous = &mut self.struct_logs[*index];
+			previous.gas_cost = previous.gas.saturating_sub(gas);
+		}
+		*last_step = Some(self.struct_logs.len());
+
+		self.struct_logs.push(StructLog {
+			pc,
+			op: opcode.to_string(),
+			gas,
+			gas_cost: 0,
+			depth,
+			..Default::default()
+		});
+	}
+}
+
+/// Convert a gas value to `u64`, saturating on overflow.
+fn to_u64(gas: U256) -> u64 {
+	gas.try_into().unwrap_or(u64::MAX)
+}
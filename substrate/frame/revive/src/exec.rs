@@ -19,8 +19,8 @@ use crate::{
 	address::{self, AddressMapper},
 	gas::GasMeter,
 	limits,
+	precompiles::{self, is_precompile},
 	primitives::{ExecReturnValue, StorageDeposit},
-	pure_precompiles::{self, is_precompile},
 	runtime_decl_for_revive_api::{Decode, Encode, RuntimeDebugNoBound, TypeInfo},
 	storage::{self, meter::Diff, WriteOutcome},
 	tracing::if_tracing,
@@ -74,6 +74,7 @@ pub const EMPTY_CODE_HASH: H256 =
 	H256(sp_core::hex2array!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
 
 /// Combined key type for both fixed and variable sized storage keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Key {
 	/// Variant for fixed sized keys.
 	Fix([u8; 32]),
@@ -86,8 +87,8 @@ impl Key {
 	///
 	/// # Note
 	///
-	/// Only used by benchmarking in order to generate storage collisions on purpose.
-	#[cfg(feature = "runtime-benchmarks")]
+	/// Used by tracers to report the accessed keys and by benchmarking in order to generate
+	/// storage collisions on purpose.
 	pub fn unhashed(&self) -> &[u8] {
 		match self {
 			Key::Fix(v) => v.as_ref(),
@@ -607,6 +608,10 @@ enum CachedContract<T: Config> {
 	/// In this case a reload is neither allowed nor possible. Please note that recursive
 	/// calls cannot remove a contract as this is checked and denied.
 	Terminated,
+	/// The frame belongs to a plain account calling into a precompile.
+	///
+	/// There is no contract info to load or persist.
+	None,
 }
 
 impl<T: Config> Frame<T> {
@@ -729,6 +734,21 @@ where
 		input_data: Vec<u8>,
 		skip_transfer: bool,
 	) -> ExecResult {
+		if is_precompile::<T>(&dest) {
+			let mut stack = Self::new_precompile_caller(
+				origin,
+				gas_meter,
+				storage_meter,
+				value,
+				skip_transfer,
+			)?;
+			let result = stack.run_precompile(dest, false, false, value, &input_data);
+			let success =
+				matches!(result, Ok(()) if !stack.first_frame.last_frame_output.did_revert());
+			stack.pop_frame(success);
+			return result.map(|_| stack.first_frame.last_frame_output)
+		}
+
 		let dest = T::AddressMapper::to_account_id(&dest);
 		if let Some((mut stack, executable)) = Self::new(
 			FrameArgs::Call { dest: dest.clone(), cached_info: None, delegated_call: None },
@@ -863,6 +883,45 @@ where
 		Ok(Some((stack, executable)))
 	}
 
+	/// Create a new call stack for a precompile called by `origin` directly.
+	///
+	/// Its only frame belongs to the account of `origin`, so that the precompile acts on its
+	/// behalf in the same way it acts on behalf of a calling contract.
+	fn new_precompile_caller(
+		origin: Origin<T>,
+		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut storage::meter::Meter<T>,
+		value: U256,
+		skip_transfer: bool,
+	) -> Result<Self, ExecError> {
+		origin.ensure_mapped()?;
+		let first_frame = Frame {
+			account_id: origin.account_id()?.clone(),
+			contract_info: CachedContract::None,
+			value_transferred: value,
+			entry_point: ExportedFunction::Call,
+			nested_gas: gas_meter.nested(Weight::max_value()),
+			nested_storage: storage_meter.nested(BalanceOf::<T>::max_value()),
+			allows_reentry: true,
+			read_only: false,
+			delegate: None,
+			last_frame_output: Default::default(),
+		};
+
+		Ok(Self {
+			origin,
+			gas_meter,
+			storage_meter,
+			timestamp: T::Time::now(),
+			block_number: <frame_system::Pallet<T>>::block_number(),
+			first_frame,
+			frames: Default::default(),
+			transient_storage: TransientStorage::new(limits::TRANSIENT_STORAGE_BYTES),
+			skip_transfer,
+			_phantom: Default::default(),
+		})
+	}
+
 	/// Construct a new frame.
 	///
 	/// This does not take `self` because when constructing the first frame `self` is
@@ -1272,6 +1331,39 @@ where
 			return Ok(Default::default());
 		}
 
+		// Record the balances before the transfer so that tracers can observe the change.
+		let mut balances_before = Vec::new();
+		if_tracing(|_| {
+			let origin = origin.account_id().ok();
+			for account in [Some(from), Some(to), origin].into_iter().flatten() {
+				let address = T::AddressMapper::to_address(account);
+				if !balances_before.iter().any(|(a, _)| *a == address) {
+					balances_before.push((address, crate::Pallet::<T>::evm_balance(&address)));
+				}
+			}
+		});
+
+		let result = Self::transfer_native(origin, from, to, value);
+
+		if_tracing(|tracer| {
+			for (address, old_balance) in balances_before {
+				let new_balance = crate::Pallet::<T>::evm_balance(&address);
+				if new_balance != old_balance {
+					tracer.balance_change(&address, old_balance, new_balance);
+				}
+			}
+		});
+
+		result
+	}
+
+	/// Transfer `value` in native decimals, see [`Self::transfer`].
+	fn transfer_native(
+		origin: &Origin<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		value: BalanceOf<T>,
+	) -> ExecResult {
 		if <System<T>>::account_exists(to) {
 			return T::Currency::transfer(from, to, value, Preservation::Preserve)
 				.map(|_| Default::default())
@@ -1404,9 +1496,11 @@ where
 				)?;
 			}
 
-			pure_precompiles::Precompiles::<T>::execute(
+			precompiles::execute(
 				precompile_address,
-				self.gas_meter_mut(),
+				self,
+				value_transferred,
+				is_read_only,
 				input_data,
 			)
 			.map_err(|e| ExecError { error: e.error, origin: ErrorOrigin::Callee })
@@ -1482,7 +1576,7 @@ where
 			// Enable read-only access if requested; cannot disable it if already set.
 			let is_read_only = read_only || self.is_read_only();
 
-			if is_precompile(dest_addr) {
+			if is_precompile::<T>(dest_addr) {
 				return self.run_precompile(*dest_addr, false, is_read_only, value, &input_data);
 			}
 
@@ -1561,7 +1655,7 @@ where
 		address: H160,
 		input_data: Vec<u8>,
 	) -> Result<(), ExecError> {
-		if is_precompile(&address) {
+		if is_precompile::<T>(&address) {
 			return self.run_precompile(
 				address,
 				true,
@@ -1654,7 +1748,12 @@ where
 	}
 
 	fn get_storage(&mut self, key: &Key) -> Option<Vec<u8>> {
-		self.top_frame_mut().contract_info().read(key)
+		let value = self.top_frame_mut().contract_info().read(key);
+		if_tracing(|tracer| {
+			let address = T::AddressMapper::to_address(&self.top_frame().account_id);
+			tracer.storage_read(&address, key, value.as_deref());
+		});
+		value
 	}
 
 	fn get_storage_size(&mut self, key: &Key) -> Option<u32> {
@@ -1668,6 +1767,11 @@ where
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
 		let frame = self.top_frame_mut();
+		if_tracing(|tracer| {
+			let address = T::AddressMapper::to_address(&frame.account_id);
+			let old_value = frame.contract_info().read(key);
+			tracer.storage_write(&address, key, old_value.as_deref(), value.as_deref());
+		});
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
 			value,
@@ -280,6 +280,19 @@ impl<T: Config> GasMeter<T> {
 		self.gas_left
 	}
 
+	/// The gas left when the executor has `engine_fuel` left, without syncing with it.
+	///
+	/// Used to report the gas left while the executor is still running.
+	pub fn gas_left_from_executor(&self, engine_fuel: polkavm::Gas) -> Weight {
+		let fuel = u64::try_from(engine_fuel).unwrap_or_default();
+		let consumed = self
+			.engine_meter
+			.fuel
+			.saturating_sub(fuel)
+			.saturating_mul(EngineMeter::<T>::ref_time_per_fuel());
+		self.gas_left.saturating_sub(Weight::from_parts(consumed, 0))
+	}
+
 	/// The amount of gas in terms of engine gas.
 	pub fn engine_fuel_left(&self) -> Result<polkavm::Gas, DispatchError> {
 		self.engine_meter.fuel.try_into().map_err(|_| <Error<T>>::OutOfGas.into())
@@ -37,13 +37,18 @@ mod tests;
 
 pub mod chain_extension;
 pub mod evm;
+pub mod precompiles;
 pub mod test_utils;
 pub mod tracing;
 pub mod weights;
+pub mod xcm_fees;
 
 use crate::{
-	evm::{runtime::GAS_PRICE, CallTrace, GasEncoder, GenericTransaction, TracerConfig},
-	exec::{AccountIdOf, ExecError, Executable, Key, Stack as ExecStack},
+	evm::{
+		runtime::GAS_PRICE, CallTrace, EvmTracer, GasEncoder, GenericTransaction, Trace,
+		TracerConfig,
+	},
+	exec::{AccountIdOf, ExecError, Executable, Stack as ExecStack},
 	gas::GasMeter,
 	storage::{meter::Meter as StorageMeter, ContractInfo, DeletionQueueManager},
 	wasm::{CodeInfo, RuntimeCosts, WasmBlob},
@@ -80,7 +85,7 @@ use sp_runtime::{
 
 pub use crate::{
 	address::{create1, create2, is_eth_derived, AccountId32Mapper, AddressMapper},
-	exec::{MomentOf, Origin},
+	exec::{Key, MomentOf, Origin},
 	pallet::*,
 };
 pub use primitives::*;
@@ -261,6 +266,20 @@ pub mod pallet {
 			BlockNumberFor<Self>,
 		>;
 
+		/// The precompiles available to contracts in addition to the Ethereum ones.
+		///
+		/// Use a tuple to declare several precompiles, or `()` to declare none. See the
+		/// [`precompiles`] module for how to write one.
+		#[pallet::no_default_bounds]
+		type Precompiles: precompiles::Precompile<Self>;
+
+		/// Swaps assets of contracts for the native tokens needed to pay the delivery fees of the
+		/// XCM programs they send with a fee asset.
+		///
+		/// Use `()` to only accept the native token. See [`xcm_fees`] for details.
+		#[pallet::no_default_bounds]
+		type XcmFeeSwap: xcm_fees::XcmFeeSwap<Self::AccountId>;
+
 		/// The amount of memory in bytes that parachain nodes a lot to the runtime.
 		///
 		/// This is used in [`Pallet::integrity_test`] to make sure that the runtime has enough
@@ -359,6 +378,8 @@ pub mod pallet {
 			type WeightInfo = ();
 			type WeightPrice = Self;
 			type Xcm = ();
+			type Precompiles = ();
+			type XcmFeeSwap = ();
 			type RuntimeMemory = ConstU32<{ 128 * 1024 * 1024 }>;
 			type PVFMemory = ConstU32<{ 512 * 1024 * 1024 }>;
 			type ChainId = ConstU64<0>;
@@ -499,6 +520,8 @@ pub mod pallet {
 		UnsupportedPrecompileAddress = 0x2F,
 		/// Precompile Error
 		PrecompileFailure = 0x30,
+		/// The delivery fees of an XCM program can't be paid with the requested asset.
+		XcmFeeAssetNotSupported = 0x31,
 	}
 
 	/// A reason for the pallet contracts placing a hold on funds.
@@ -579,6 +602,19 @@ pub mod pallet {
 		fn integrity_test() {
 			use limits::code::STATIC_MEMORY_BYTES;
 
+			// The addresses `0x01..=0xff` are reserved for the Ethereum precompiles.
+			for address in (1..=0xffu64).map(H160::from_low_u64_be) {
+				assert!(
+					!<T::Precompiles as precompiles::Precompile<T>>::matches(&address),
+					"Precompile configured at {address:?}, which is reserved for Ethereum precompiles.",
+				);
+			}
+			if let Some(address) =
+				<T::Precompiles as precompiles::Precompile<T>>::overlapping_address()
+			{
+				panic!("More than one precompile configured at {address:?}.");
+			}
+
 			// The memory available in the block building runtime
 			let max_runtime_mem: u32 = T::RuntimeMemory::get();
 			// The root frame is not accounted in CALL_STACK_DEPTH
@@ -1360,6 +1396,11 @@ where
 		Self::evm_fee_to_gas(fee)
 	}
 
+	/// Build the tracer for the given `config`, reporting gas with [`Self::evm_gas_from_weight`].
+	pub fn evm_tracer(config: TracerConfig) -> EvmTracer<T, fn(Weight) -> U256> {
+		config.build(Self::evm_gas_from_weight as fn(Weight) -> U256)
+	}
+
 	/// Get the block gas limit.
 	pub fn evm_block_gas_limit() -> U256 {
 		let max_block_weight = T::BlockWeights::get()
@@ -1375,6 +1416,22 @@ where
 		GAS_PRICE.into()
 	}
 
+	/// The amount of `fee_asset` the contract at `address` has to swap to pay the delivery fees
+	/// of sending `message` to `dest`.
+	///
+	/// `fee_asset` is the SCALE encoded asset kind of [`Config::XcmFeeSwap`].
+	pub fn quote_xcm_send_fee(
+		address: H160,
+		dest: xcm::VersionedLocation,
+		message: xcm::VersionedXcm<()>,
+		fee_asset: Vec<u8>,
+	) -> Result<u128, DispatchError> {
+		let fee_asset =
+			Decode::decode(&mut &fee_asset[..]).map_err(|_| Error::<T>::XcmFeeAssetNotSupported)?;
+		let origin = T::AddressMapper::to_account_id(&address);
+		xcm_fees::quote::<T>(&origin, dest, message, &fee_asset)
+	}
+
 	/// A generalized version of [`Self::upload_code`].
 	///
 	/// It is identical to [`Self::upload_code`] and only differs in the information it returns.
@@ -1472,7 +1529,7 @@ environmental!(executing_contract: bool);
 
 sp_api::decl_runtime_apis! {
 	/// The API used to dry-run contract interactions.
-	#[api_version(1)]
+	#[api_version(3)]
 	pub trait ReviveApi<AccountId, Balance, Nonce, BlockNumber> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1543,6 +1600,13 @@ sp_api::decl_runtime_apis! {
 
 
 		/// Traces the execution of an entire block and returns call traces.
+		#[changed_in(2)]
+		fn trace_block(
+			block: Block,
+			config: TracerConfig
+		) -> Vec<(u32, CallTrace)>;
+
+		/// Traces the execution of an entire block and returns the traces of its transactions.
 		///
 		/// This is intended to be called through `state_call` to replay the block from the
 		/// parent block.
@@ -1551,7 +1615,15 @@ sp_api::decl_runtime_apis! {
 		fn trace_block(
 			block: Block,
 			config: TracerConfig
-		) -> Vec<(u32, CallTrace)>;
+		) -> Vec<(u32, Trace)>;
+
+		/// Traces the execution of a specific transaction within a block.
+		#[changed_in(2)]
+		fn trace_tx(
+			block: Block,
+			tx_index: u32,
+			config: TracerConfig
+		) -> Option<CallTrace>;
 
 		/// Traces the execution of a specific transaction within a block.
 		///
@@ -1563,12 +1635,28 @@ sp_api::decl_runtime_apis! {
 			block: Block,
 			tx_index: u32,
 			config: TracerConfig
-		) -> Option<CallTrace>;
+		) -> Option<Trace>;
+
+		/// Dry run and return the call trace of the given call.
+		#[changed_in(2)]
+		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
 
 		/// Dry run and return the trace of the given call.
 		///
 		/// See eth-rpc `debug_traceCall` for usage.
-		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<CallTrace, EthTransactError>;
+		fn trace_call(tx: GenericTransaction, config: TracerConfig) -> Result<Trace, EthTransactError>;
+
+		/// Quote the amount of `fee_asset` the contract at `address` has to swap to pay the
+		/// delivery fees of sending `message` to `dest` with a fee asset.
+		///
+		/// See [`crate::Pallet::quote_xcm_send_fee`].
+		#[api_version(3)]
+		fn quote_xcm_send_fee(
+			address: H160,
+			dest: xcm::VersionedLocation,
+			message: xcm::VersionedXcm<()>,
+			fee_asset: Vec<u8>,
+		) -> Result<u128, DispatchError>;
 
 	}
 }
@@ -0,0 +1,264 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mechanism for runtime authors to expose runtime functionality to contracts as precompiles.
+//!
+//! Unlike the pure Ethereum precompiles at `0x01..=0xff`, precompiles defined here have access to
+//! the execution environment of the calling contract. They can act on behalf of the contract, e.g.
+//! to dispatch XCM programs using the contract's account as origin, or to move its assets.
+//! Precompiles can also be called by a transaction directly, in which case they act on behalf of
+//! its signer.
+//!
+//! In order to add a precompile the runtime author implements the [`Precompile`] trait and
+//! declares it in [`Config::Precompiles`]. Multiple precompiles are declared as a tuple, e.g.
+//! `type Precompiles = (XcmPrecompile<Self>, Erc20<Prefix, AssetsInstance, InlineAssetId>)`.
+//! When a contract calls an address, the first precompile in the tuple
+//! [matching](Precompile::matches) it is executed. The addresses reserved for the pure precompiles
+//! can't be claimed, and no two precompiles may claim the same address. Both are checked by
+//! [`Pallet::integrity_test`](crate::Pallet).
+//!
+//! Precompiles are called with a Solidity ABI encoded input. The [`sol!`](alloy_core::sol) macro
+//! can be used to define their interface, together with [`decode_call`], [`success`] and
+//! [`revert`] to decode the input and encode the output.
+//!
+//! # Security
+//!
+//! As with [chain extensions](crate::chain_extension), the runtime author alone is responsible for
+//! the security of the precompiles. The input is charged for by the pallet, any further work must
+//! be charged for using [`Environment::charge_weight`] **before** it is carried out. State
+//! changes must be refused when [`Environment::is_read_only`] is set.
+
+use crate::{
+	exec::{AccountIdOf, ExecResult},
+	pure_precompiles,
+	tracing::if_tracing,
+	wasm::RuntimeCosts,
+	AddressMapper, Error, Event, ExecReturnValue, Pallet, H160, H256, U256,
+};
+use alloc::vec::Vec;
+use alloy_core::sol_types::{Revert, SolError, SolInterface};
+use frame_support::weights::Weight;
+use sp_runtime::DispatchError;
+
+pub use crate::{exec::Ext, gas::ChargedAmount, Config};
+pub use pallet_revive_uapi::ReturnFlags;
+
+/// Result that returns a [`DispatchError`] on error.
+pub type Result<T> = core::result::Result<T, DispatchError>;
+
+mod erc20;
+mod xcm;
+pub use self::{erc20::*, xcm::*};
+
+/// A precompile that can be declared in [`Config::Precompiles`].
+///
+/// Consult the [module documentation](self) for a general explanation of precompiles.
+pub trait Precompile<T: Config> {
+	/// Whether this precompile lives at `address`.
+	///
+	/// A precompile may claim several addresses, e.g. one per asset.
+	fn matches(address: &H160) -> bool;
+
+	/// Addresses representative of those this precompile lives at.
+	///
+	/// Used by [`Pallet::integrity_test`](crate::Pallet) to check that no other precompile
+	/// [matches](Self::matches) them. A precompile claiming a range of addresses should return at
+	/// least one address of the range.
+	fn addresses() -> Vec<H160> {
+		Vec::new()
+	}
+
+	/// Return an address claimed by more than one of the precompiles, if any.
+	///
+	/// Only checks the [`addresses`](Self::addresses) declared by the precompiles.
+	fn overlapping_address() -> Option<H160> {
+		None
+	}
+
+	/// Execute the precompile with the ABI encoded `input`.
+	///
+	/// Reverting is signaled by returning [`ReturnFlags::REVERT`], in which case all state changes
+	/// made by the precompile are rolled back. Returning `Err` traps the calling contract.
+	fn call<E: Ext<T = T>>(env: &mut Environment<E>, input: &[u8]) -> Result<ExecReturnValue>;
+}
+
+impl<T: Config> Precompile<T> for () {
+	fn matches(_address: &H160) -> bool {
+		false
+	}
+
+	fn call<E: Ext<T = T>>(_env: &mut Environment<E>, _input: &[u8]) -> Result<ExecReturnValue> {
+		Err(Error::<T>::UnsupportedPrecompileAddress.into())
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 16)]
+#[tuple_types_custom_trait_bound(Precompile<T>)]
+impl<T: Config> Precompile<T> for Tuple {
+	fn matches(address: &H160) -> bool {
+		for_tuples!( #( if Tuple::matches(address) { return true } )* );
+		false
+	}
+
+	fn addresses() -> Vec<H160> {
+		let mut addresses = Vec::new();
+		for_tuples!( #( addresses.extend(Tuple::addresses()); )* );
+		addresses
+	}
+
+	fn overlapping_address() -> Option<H160> {
+		for_tuples!( #(
+			if let Some(address) = Tuple::overlapping_address() { return Some(address) }
+		)* );
+		Self::addresses().into_iter().find(|address| {
+			let mut matching = 0;
+			for_tuples!( #( if Tuple::matches(address) { matching += 1 } )* );
+			matching > 1
+		})
+	}
+
+	fn call<E: Ext<T = T>>(env: &mut Environment<E>, input: &[u8]) -> Result<ExecReturnValue> {
+		for_tuples!( #( if Tuple::matches(&env.address()) { return Tuple::call(env, input) } )* );
+		Err(Error::<T>::UnsupportedPrecompileAddress.into())
+	}
+}
+
+/// Grants a precompile access to its call context and the execution environment.
+pub struct Environment<'a, E: Ext> {
+	/// The execution environment of the calling contract.
+	ext: &'a mut E,
+	/// The address of the precompile being called.
+	address: H160,
+	/// The value transferred to the precompile.
+	value: U256,
+	/// Whether the precompile was called in a read-only context.
+	read_only: bool,
+}
+
+impl<'a, E: Ext> Environment<'a, E> {
+	/// The address of the precompile being called.
+	pub fn address(&self) -> H160 {
+		self.address
+	}
+
+	/// The account calling the precompile.
+	///
+	/// This is the calling contract, or the signer when called by a transaction directly.
+	pub fn caller(&self) -> &AccountIdOf<E::T> {
+		self.ext.account_id()
+	}
+
+	/// The address of the account calling the precompile, see [`Self::caller`].
+	pub fn caller_address(&self) -> H160 {
+		<E::T as Config>::AddressMapper::to_address(self.ext.account_id())
+	}
+
+	/// The value transferred to the precompile.
+	pub fn value_transferred(&self) -> U256 {
+		self.value
+	}
+
+	/// Whether the precompile was called in a read-only context, e.g. through `STATICCALL`.
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+
+	/// Return [`Error::StateChangeDenied`] when called in a read-only context.
+	pub fn ensure_mutable(&self) -> Result<()> {
+		if self.read_only {
+			return Err(Error::<E::T>::StateChangeDenied.into())
+		}
+		Ok(())
+	}
+
+	/// Charge the passed `amount` of weight from the overall limit.
+	///
+	/// The returned value can be used with [`Self::adjust_weight`].
+	pub fn charge_weight(&mut self, amount: Weight) -> Result<ChargedAmount> {
+		self.ext.gas_meter_mut().charge(RuntimeCosts::Precompile(amount))
+	}
+
+	/// Adjust a previously charged amount down to its actual amount.
+	pub fn adjust_weight(&mut self, charged: ChargedAmount, actual_weight: Weight) {
+		self.ext
+			.gas_meter_mut()
+			.adjust_gas(charged, RuntimeCosts::Precompile(actual_weight))
+	}
+
+	/// Deposit an event with the given `topics` and `data`, emitted by the precompile.
+	pub fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>) -> Result<()> {
+		self.ext.gas_meter_mut().charge(RuntimeCosts::DepositEvent {
+			num_topic: topics.len() as u32,
+			len: data.len() as u32,
+		})?;
+
+		let contract = self.address;
+		if_tracing(|tracer| {
+			tracer.log_event(contract, &topics, &data);
+		});
+		Pallet::<E::T>::deposit_event(Event::ContractEmitted { contract, data, topics });
+		Ok(())
+	}
+
+	/// Grants access to the execution environment of the calling contract.
+	///
+	/// Consult the functions on this type before re-implementing those functions. When called by
+	/// a transaction directly there is no calling contract: functions accessing its storage, code
+	/// or immutable data must not be used then.
+	pub fn ext(&mut self) -> &mut E {
+		self.ext
+	}
+}
+
+/// Decode the ABI encoded `input` into a call of the interface `C`.
+///
+/// Returns `None` if the selector is unknown or the arguments are malformed.
+pub fn decode_call<C: SolInterface>(input: &[u8]) -> Option<C> {
+	C::abi_decode(input, true).ok()
+}
+
+/// Return the ABI encoded `data` to the caller.
+pub fn success(data: Vec<u8>) -> ExecReturnValue {
+	ExecReturnValue { data, flags: ReturnFlags::empty() }
+}
+
+/// Revert with the given `reason`, encoded as a Solidity `Error(string)`.
+pub fn revert(reason: &str) -> ExecReturnValue {
+	ExecReturnValue { data: Revert::from(reason).abi_encode(), flags: ReturnFlags::REVERT }
+}
+
+/// Determine if the given address is a precompile.
+pub fn is_precompile<T: Config>(address: &H160) -> bool {
+	pure_precompiles::is_precompile(address) || T::Precompiles::matches(address)
+}
+
+/// Execute the precompile at `address` on behalf of the account currently executing in `ext`.
+pub(crate) fn execute<E: Ext>(
+	address: H160,
+	ext: &mut E,
+	value: U256,
+	read_only: bool,
+	input: &[u8],
+) -> ExecResult {
+	if pure_precompiles::is_precompile(&address) {
+		return pure_precompiles::Precompiles::<E::T>::execute(address, ext.gas_meter_mut(), input)
+	}
+
+	ext.gas_meter_mut().charge(RuntimeCosts::CopyFromContract(input.len() as u32))?;
+	let mut env = Environment { ext, address, value, read_only };
+	Ok(<E::T as Config>::Precompiles::call(&mut env, input)?)
+}
@@ -0,0 +1,305 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompiles exposing the assets of [`pallet_assets`] instances to contracts.
+//!
+//! Every asset gets its own precompile address, derived from a per-instance prefix and a `u32`
+//! index identifying the asset within the instance:
+//!
+//! ```text
+//! | index (4 bytes, BE) | 0x00 * 12 | prefix (2 bytes, BE) | 0x00 * 2 |
+//! ```
+//!
+//! A runtime exposes an asset instance by adding an [`Erc20`] precompile with its own prefix to
+//! [`Config::Precompiles`].
+
+use super::{decode_call, revert, success, Environment, Ext, Precompile, Result};
+use crate::{AddressMapper, Config, ExecReturnValue, H160, H256, LOG_TARGET, U256};
+use alloc::{vec, vec::Vec};
+use alloy_core::{
+	primitives::{Address, U256 as AbiU256},
+	sol,
+	sol_types::{SolCall, SolEvent},
+};
+use core::marker::PhantomData;
+use frame_support::{
+	traits::{
+		fungibles::{
+			approvals::{Inspect as _, Mutate as _},
+			Inspect as _, Mutate,
+		},
+		tokens::Preservation,
+		Get, StorageInfoTrait,
+	},
+	weights::Weight,
+};
+use pallet_assets::WeightInfo as _;
+use sp_runtime::traits::{MaybeEquivalence, Zero};
+
+type AssetsOf<T, I> = pallet_assets::Pallet<T, I>;
+type AssetIdOf<T, I> = <T as pallet_assets::Config<I>>::AssetId;
+type AssetBalanceOf<T, I> = <T as pallet_assets::Config<I>>::Balance;
+type AssetWeightsOf<T, I> = <T as pallet_assets::Config<I>>::WeightInfo;
+
+sol! {
+	/// The Solidity interface of the ERC-20 precompiles.
+	interface IERC20 {
+		/// Emitted when `value` tokens are moved from `from` to `to`.
+		event Transfer(address indexed from, address indexed to, uint256 value);
+
+		/// Emitted when the allowance of `spender` for `owner` is set to `value`.
+		event Approval(address indexed owner, address indexed spender, uint256 value);
+
+		/// Return the total amount of tokens in existence.
+		function totalSupply() external view returns (uint256);
+
+		/// Return the amount of tokens owned by `account`.
+		function balanceOf(address account) external view returns (uint256);
+
+		/// Move `value` tokens from the caller to `to`.
+		function transfer(address to, uint256 value) external returns (bool);
+
+		/// Return the remaining amount `spender` is allowed to spend on behalf of `owner`.
+		function allowance(address owner, address spender) external view returns (uint256);
+
+		/// Set the allowance of `spender` over the caller's tokens to `value`.
+		function approve(address spender, uint256 value) external returns (bool);
+
+		/// Move `value` tokens from `from` to `to` using the caller's allowance.
+		function transferFrom(address from, address to, uint256 value) external returns (bool);
+	}
+}
+
+/// Maps the `u32` index encoded in the precompile address to the id of an asset, and back.
+///
+/// Use this for instances whose asset id already is a small integer. Instances with other ids, e.g.
+/// XCM locations, need to provide their own index.
+pub struct InlineAssetId;
+
+impl<AssetId: From<u32> + TryInto<u32> + Clone> MaybeEquivalence<u32, AssetId> for InlineAssetId {
+	fn convert(index: &u32) -> Option<AssetId> {
+		Some((*index).into())
+	}
+
+	fn convert_back(id: &AssetId) -> Option<u32> {
+		id.clone().try_into().ok()
+	}
+}
+
+/// Return the precompile address of the asset with the given `index` in the instance using
+/// `prefix`.
+pub fn erc20_address(prefix: u16, index: u32) -> H160 {
+	let mut address = [0u8; 20];
+	address[..4].copy_from_slice(&index.to_be_bytes());
+	address[16..18].copy_from_slice(&prefix.to_be_bytes());
+	H160(address)
+}
+
+/// Return the asset index encoded in `address` if it belongs to the instance using `prefix`.
+fn erc20_index(prefix: u16, address: &H160) -> Option<u32> {
+	let bytes = address.as_bytes();
+	if bytes[4..16].iter().any(|b| *b != 0) ||
+		bytes[16..18] != prefix.to_be_bytes() ||
+		bytes[18..] != [0, 0]
+	{
+		return None
+	}
+	Some(u32::from_be_bytes(bytes[..4].try_into().expect("slice has 4 bytes; qed")))
+}
+
+/// An ERC-20 precompile for every asset of the `pallet_assets` instance `Instance`.
+///
+/// - `Prefix` identifies the instance in the precompile address, see the [module docs](self).
+/// - `AssetIndex` maps the index encoded in the address to an asset id of the instance.
+///
+/// State changing functions are charged the benchmarked weight of the equivalent
+/// `pallet_assets` dispatchable. `approve` sets the allowance to the given value: lowering it
+/// cancels the existing approval before approving the new value, if any.
+pub struct Erc20<Prefix, Instance, AssetIndex>(PhantomData<(Prefix, Instance, AssetIndex)>);
+
+impl<T, Prefix, I, AssetIndex> Precompile<T> for Erc20<Prefix, I, AssetIndex>
+where
+	T: Config + pallet_assets::Config<I>,
+	I: 'static,
+	Prefix: Get<u16>,
+	AssetBalanceOf<T, I>: TryFrom<U256> + Into<U256>,
+	AssetIndex: MaybeEquivalence<u32, AssetIdOf<T, I>>,
+{
+	fn matches(address: &H160) -> bool {
+		erc20_index(Prefix::get(), address).is_some()
+	}
+
+	fn addresses() -> Vec<H160> {
+		vec![erc20_address(Prefix::get(), 0), erc20_address(Prefix::get(), u32::MAX)]
+	}
+
+	fn call<E: Ext<T = T>>(env: &mut Environment<E>, input: &[u8]) -> Result<ExecReturnValue> {
+		use IERC20::IERC20Calls;
+
+		let Some(call) = decode_call::<IERC20Calls>(input) else {
+			return Ok(revert("invalid input"))
+		};
+		let asset = erc20_index(Prefix::get(), &env.address())
+			.and_then(|index| AssetIndex::convert(&index));
+		let Some(asset) = asset else { return Ok(revert("unknown asset")) };
+		// None of the functions is payable, the value would be stuck at the precompile.
+		if !env.value_transferred().is_zero() {
+			return Ok(revert("value transfer not supported"))
+		}
+
+		if matches!(
+			call,
+			IERC20Calls::transfer(_) | IERC20Calls::approve(_) | IERC20Calls::transferFrom(_)
+		) {
+			env.ensure_mutable()?;
+		}
+
+		let caller = env.caller().clone();
+		match call {
+			IERC20Calls::totalSupply(_) => {
+				env.charge_weight(read_weight::<T, pallet_assets::Asset<T, I>>())?;
+				let supply = AssetsOf::<T, I>::total_issuance(asset);
+				Ok(success(IERC20::totalSupplyCall::abi_encode_returns(&(to_abi(supply),))))
+			},
+			IERC20Calls::balanceOf(IERC20::balanceOfCall { account }) => {
+				env.charge_weight(read_weight::<T, pallet_assets::Account<T, I>>())?;
+				let balance = AssetsOf::<T, I>::balance(asset, &to_account::<T>(account));
+				Ok(success(IERC20::balanceOfCall::abi_encode_returns(&(to_abi(balance),))))
+			},
+			IERC20Calls::allowance(IERC20::allowanceCall { owner, spender }) => {
+				env.charge_weight(read_weight::<T, pallet_assets::Approvals<T, I>>())?;
+				let allowance = AssetsOf::<T, I>::allowance(
+					asset,
+					&to_account::<T>(owner),
+					&to_account::<T>(spender),
+				);
+				Ok(success(IERC20::allowanceCall::abi_encode_returns(&(to_abi(allowance),))))
+			},
+			IERC20Calls::transfer(IERC20::transferCall { to, value }) => {
+				env.charge_weight(AssetWeightsOf::<T, I>::transfer())?;
+				let Some(amount) = from_abi::<AssetBalanceOf<T, I>>(value) else {
+					return Ok(revert("value out of range"))
+				};
+				let dest = to_account::<T>(to);
+				if let Err(error) = <AssetsOf<T, I> as Mutate<_>>::transfer(
+					asset,
+					&caller,
+					&dest,
+					amount,
+					Preservation::Expendable,
+				) {
+					log::debug!(target: LOG_TARGET, "ERC-20 transfer failed with: {error:?}");
+					return Ok(revert(error.into()))
+				}
+				let from = env.caller_address();
+				emit(env, IERC20::Transfer::SIGNATURE_HASH.0, [from, to_h160(to)], value)?;
+				Ok(success(IERC20::transferCall::abi_encode_returns(&(true,))))
+			},
+			IERC20Calls::approve(IERC20::approveCall { spender, value }) => {
+				let cancel_weight = AssetWeightsOf::<T, I>::cancel_approval();
+				let approve_weight = AssetWeightsOf::<T, I>::approve_transfer();
+				let charged = env.charge_weight(cancel_weight.saturating_add(approve_weight))?;
+				let Some(amount) = from_abi::<AssetBalanceOf<T, I>>(value) else {
+					return Ok(revert("value out of range"))
+				};
+				let delegate = to_account::<T>(spender);
+				let current = AssetsOf::<T, I>::allowance(asset.clone(), &caller, &delegate);
+				// `pallet_assets` approvals can only be increased, lowering one means replacing it.
+				let (to_approve, cancel) =
+					if amount < current { (amount, true) } else { (amount - current, false) };
+				let mut actual_weight = read_weight::<T, pallet_assets::Approvals<T, I>>();
+				let mut result = Ok(());
+				if cancel {
+					actual_weight.saturating_accrue(cancel_weight);
+					result =
+						AssetsOf::<T, I>::do_cancel_approval(asset.clone(), &caller, &delegate);
+				}
+				if result.is_ok() && !to_approve.is_zero() {
+					actual_weight.saturating_accrue(approve_weight);
+					result = AssetsOf::<T, I>::approve(asset, &caller, &delegate, to_approve);
+				}
+				env.adjust_weight(charged, actual_weight);
+				if let Err(error) = result {
+					log::debug!(target: LOG_TARGET, "ERC-20 approve failed with: {error:?}");
+					return Ok(revert(error.into()))
+				}
+				let owner = env.caller_address();
+				emit(env, IERC20::Approval::SIGNATURE_HASH.0, [owner, to_h160(spender)], value)?;
+				Ok(success(IERC20::approveCall::abi_encode_returns(&(true,))))
+			},
+			IERC20Calls::transferFrom(IERC20::transferFromCall { from, to, value }) => {
+				env.charge_weight(AssetWeightsOf::<T, I>::transfer_approved())?;
+				let Some(amount) = from_abi::<AssetBalanceOf<T, I>>(value) else {
+					return Ok(revert("value out of range"))
+				};
+				let owner = to_account::<T>(from);
+				let dest = to_account::<T>(to);
+				if let Err(error) =
+					AssetsOf::<T, I>::transfer_from(asset, &owner, &caller, &dest, amount)
+				{
+					log::debug!(target: LOG_TARGET, "ERC-20 transferFrom failed with: {error:?}");
+					return Ok(revert(error.into()))
+				}
+				emit(env, IERC20::Transfer::SIGNATURE_HASH.0, [to_h160(from), to_h160(to)], value)?;
+				Ok(success(IERC20::transferFromCall::abi_encode_returns(&(true,))))
+			},
+		}
+	}
+}
+
+/// The weight of reading a single entry of the storage item `S`, including its maximum proof size.
+fn read_weight<T: Config, S: StorageInfoTrait>() -> Weight {
+	let proof_size = S::storage_info().first().and_then(|info| info.max_size).unwrap_or_default();
+	T::DbWeight::get()
+		.reads(1)
+		.saturating_add(Weight::from_parts(0, proof_size.into()))
+}
+
+/// Deposit an ERC-20 event with two indexed addresses on behalf of the token.
+fn emit<E: Ext>(
+	env: &mut Environment<E>,
+	signature: [u8; 32],
+	addresses: [H160; 2],
+	value: AbiU256,
+) -> Result<()> {
+	let topics =
+		[H256(signature), address_topic(addresses[0]), address_topic(addresses[1])].to_vec();
+	env.deposit_event(topics, value.to_be_bytes::<32>().to_vec())
+}
+
+/// Left-pad `address` to a 32 byte event topic.
+fn address_topic(address: H160) -> H256 {
+	let mut topic = H256::zero();
+	topic.0[12..].copy_from_slice(address.as_bytes());
+	topic
+}
+
+fn to_h160(address: Address) -> H160 {
+	H160(address.0 .0)
+}
+
+fn to_account<T: Config>(address: Address) -> T::AccountId {
+	T::AddressMapper::to_account_id(&to_h160(address))
+}
+
+fn to_abi<B: Into<U256>>(balance: B) -> AbiU256 {
+	AbiU256::from_be_bytes(balance.into().to_big_endian())
+}
+
+fn from_abi<B: TryFrom<U256>>(value: AbiU256) -> Option<B> {
+	B::try_from(U256::from_big_endian(&value.to_be_bytes::<32>())).ok()
+}
@@ -0,0 +1,285 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The XCM precompile, exposing [`Config::Xcm`] to contracts through a Solidity ABI.
+
+use super::{decode_call, revert, success, Environment, Ext, Precompile, Result};
+use crate::{
+	exec::AccountIdOf,
+	xcm_fees::{self, XcmFeeSwap},
+	Config, ExecReturnValue, RawOrigin, H160, LOG_TARGET,
+};
+use ::xcm::{VersionedLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+use alloc::{boxed::Box, vec, vec::Vec};
+use alloy_core::{
+	primitives::{FixedBytes, U256},
+	sol,
+	sol_types::SolCall,
+};
+use codec::{DecodeAll, DecodeLimit};
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{extract_actual_weight, DispatchInfo, DispatchResultWithPostInfo},
+	weights::Weight,
+};
+use xcm_builder::{
+	ExecuteController, ExecuteControllerWeightInfo, SendController, SendControllerWeightInfo,
+};
+
+type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+type FeeAssetOf<T> = <<T as Config>::XcmFeeSwap as XcmFeeSwap<AccountIdOf<T>>>::AssetKind;
+
+/// The address of the XCM precompile.
+pub const XCM_PRECOMPILE_ADDRESS: H160 =
+	H160(hex_literal::hex!("00000000000000000000000000000000000a0000"));
+
+sol! {
+	/// The Solidity interface of the XCM precompile.
+	///
+	/// Locations and messages are passed as SCALE encoded `VersionedLocation` and `VersionedXcm`.
+	interface IXcm {
+		/// The weight of an XCM program.
+		struct Weight {
+			uint64 refTime;
+			uint64 proofSize;
+		}
+
+		/// Send `message` to `destination`, using the calling contract as origin.
+		function send(bytes calldata destination, bytes calldata message)
+			external
+			returns (bytes32 messageId);
+
+		/// Send `message` to `destination`, using the calling contract as origin, and pay the
+		/// delivery fees by swapping at most `maxFeeAmount` of the SCALE encoded `feeAsset`.
+		function sendWithFeeAsset(
+			bytes calldata destination,
+			bytes calldata message,
+			bytes calldata feeAsset,
+			uint256 maxFeeAmount
+		) external returns (bytes32 messageId);
+
+		/// Return the amount of `feeAsset` swapped to pay the delivery fees of `sendWithFeeAsset`.
+		function quoteSendFee(bytes calldata destination, bytes calldata message, bytes calldata feeAsset)
+			external
+			view
+			returns (uint256 amount);
+
+		/// Execute `message` locally, using the calling contract as origin.
+		function execute(bytes calldata message, Weight calldata weight) external;
+
+		/// Return the weight required to execute `message` locally.
+		function weighMessage(bytes calldata message) external view returns (Weight memory weight);
+	}
+}
+
+/// The XCM precompile.
+///
+/// Dispatches through [`Config::Xcm`] with the account of the calling contract as origin. Failures
+/// are reported to the caller by reverting with a Solidity `Error(string)`.
+pub struct XcmPrecompile<T>(PhantomData<T>);
+
+impl<T: Config> Precompile<T> for XcmPrecompile<T> {
+	fn matches(address: &H160) -> bool {
+		*address == XCM_PRECOMPILE_ADDRESS
+	}
+
+	fn addresses() -> Vec<H160> {
+		vec![XCM_PRECOMPILE_ADDRESS]
+	}
+
+	fn call<E: Ext<T = T>>(env: &mut Environment<E>, input: &[u8]) -> Result<ExecReturnValue> {
+		let Some(call) = decode_call::<IXcm::IXcmCalls>(input) else {
+			return Ok(revert("invalid input"))
+		};
+		// None of the functions is payable, the value would be stuck at the precompile.
+		if !env.value_transferred().is_zero() {
+			return Ok(revert("value transfer not supported"))
+		}
+
+		match call {
+			IXcm::IXcmCalls::send(IXcm::sendCall { destination, message }) =>
+				Self::send(env, &destination, &message),
+			IXcm::IXcmCalls::sendWithFeeAsset(IXcm::sendWithFeeAssetCall {
+				destination,
+				message,
+				feeAsset,
+				maxFeeAmount,
+			}) => Self::send_with_fee_asset(env, &destination, &message, &feeAsset, maxFeeAmount),
+			IXcm::IXcmCalls::quoteSendFee(IXcm::quoteSendFeeCall {
+				destination,
+				message,
+				feeAsset,
+			}) => Self::quote_send_fee(env, &destination, &message, &feeAsset),
+			IXcm::IXcmCalls::execute(IXcm::executeCall { message, weight }) =>
+				Self::execute(env, &message, weight),
+			IXcm::IXcmCalls::weighMessage(IXcm::weighMessageCall { message }) =>
+				Self::weigh_message(env, &message),
+		}
+	}
+}
+
+impl<T: Config> XcmPrecompile<T> {
+	fn send<E: Ext<T = T>>(
+		env: &mut Environment<E>,
+		destination: &[u8],
+		message: &[u8],
+	) -> Result<ExecReturnValue> {
+		env.ensure_mutable()?;
+
+		let Ok(destination) = decode::<VersionedLocation>(destination) else {
+			return Ok(revert("invalid destination"))
+		};
+		let Ok(message) = decode::<VersionedXcm<()>>(message) else {
+			return Ok(revert("invalid message"))
+		};
+
+		env.charge_weight(<T::Xcm as SendController<_>>::WeightInfo::send())?;
+
+		let origin = RawOrigin::Signed(env.caller().clone()).into();
+		match <T::Xcm>::send(origin, Box::new(destination), Box::new(message)) {
+			Ok(message_id) =>
+				Ok(success(IXcm::sendCall::abi_encode_returns(&(FixedBytes(message_id),)))),
+			Err(error) => {
+				log::debug!(target: LOG_TARGET, "XCM precompile send failed with: {error:?}");
+				Ok(revert(error.into()))
+			},
+		}
+	}
+
+	fn send_with_fee_asset<E: Ext<T = T>>(
+		env: &mut Environment<E>,
+		destination: &[u8],
+		message: &[u8],
+		fee_asset: &[u8],
+		max_fee_amount: U256,
+	) -> Result<ExecReturnValue> {
+		env.ensure_mutable()?;
+
+		let Ok(destination) = decode::<VersionedLocation>(destination) else {
+			return Ok(revert("invalid destination"))
+		};
+		let Ok(message) = decode::<VersionedXcm<()>>(message) else {
+			return Ok(revert("invalid message"))
+		};
+		let Ok(fee_asset) = FeeAssetOf::<T>::decode_all(&mut &fee_asset[..]) else {
+			return Ok(revert("invalid fee asset"))
+		};
+
+		env.charge_weight(xcm_fees::send_weight::<T>())?;
+
+		let max_amount_in = max_fee_amount.saturating_to::<u128>();
+		match xcm_fees::send::<T>(env.caller(), destination, message, fee_asset, max_amount_in) {
+			Ok(message_id) => Ok(success(IXcm::sendWithFeeAssetCall::abi_encode_returns(&(
+				FixedBytes(message_id),
+			)))),
+			Err(error) => {
+				log::debug!(target: LOG_TARGET, "XCM precompile sendWithFeeAsset failed with: {error:?}");
+				Ok(revert(error.into()))
+			},
+		}
+	}
+
+	fn quote_send_fee<E: Ext<T = T>>(
+		env: &mut Environment<E>,
+		destination: &[u8],
+		message: &[u8],
+		fee_asset: &[u8],
+	) -> Result<ExecReturnValue> {
+		let Ok(destination) = decode::<VersionedLocation>(destination) else {
+			return Ok(revert("invalid destination"))
+		};
+		let Ok(message) = decode::<VersionedXcm<()>>(message) else {
+			return Ok(revert("invalid message"))
+		};
+		let Ok(fee_asset) = FeeAssetOf::<T>::decode_all(&mut &fee_asset[..]) else {
+			return Ok(revert("invalid fee asset"))
+		};
+
+		env.charge_weight(xcm_fees::send_weight::<T>())?;
+
+		match xcm_fees::quote::<T>(env.caller(), destination, message, &fee_asset) {
+			Ok(amount) =>
+				Ok(success(IXcm::quoteSendFeeCall::abi_encode_returns(&(U256::from(amount),)))),
+			Err(error) => {
+				log::debug!(target: LOG_TARGET, "XCM precompile quoteSendFee failed with: {error:?}");
+				Ok(revert(error.into()))
+			},
+		}
+	}
+
+	fn execute<E: Ext<T = T>>(
+		env: &mut Environment<E>,
+		message: &[u8],
+		weight: IXcm::Weight,
+	) -> Result<ExecReturnValue> {
+		env.ensure_mutable()?;
+
+		let Ok(message) = decode::<VersionedXcm<CallOf<T>>>(message) else {
+			return Ok(revert("invalid message"))
+		};
+
+		let max_weight = Weight::from_parts(weight.refTime, weight.proofSize);
+		let execute_weight = <T::Xcm as ExecuteController<_, _>>::WeightInfo::execute();
+		let dispatch_info = DispatchInfo {
+			call_weight: max_weight.saturating_add(execute_weight),
+			..Default::default()
+		};
+		let charged = env.charge_weight(dispatch_info.call_weight)?;
+
+		let origin = RawOrigin::Signed(env.caller().clone()).into();
+		let result: DispatchResultWithPostInfo =
+			<T::Xcm>::execute(origin, Box::new(message), max_weight)
+				.map(|weight_used| Some(weight_used.saturating_add(execute_weight)).into());
+		env.adjust_weight(charged, extract_actual_weight(&result, &dispatch_info));
+
+		match result {
+			Ok(_) => Ok(success(IXcm::executeCall::abi_encode_returns(&()))),
+			Err(error) => {
+				log::debug!(target: LOG_TARGET, "XCM precompile execute failed with: {error:?}");
+				Ok(revert(error.error.into()))
+			},
+		}
+	}
+
+	fn weigh_message<E: Ext<T = T>>(
+		env: &mut Environment<E>,
+		message: &[u8],
+	) -> Result<ExecReturnValue> {
+		let Ok(message) = decode::<VersionedXcm<CallOf<T>>>(message) else {
+			return Ok(revert("invalid message"))
+		};
+
+		env.charge_weight(<T::Xcm as ExecuteController<_, _>>::WeightInfo::weigh())?;
+
+		match <T::Xcm as ExecuteController<_, _>>::weigh(Box::new(message)) {
+			Ok(weight) => {
+				let weight =
+					IXcm::Weight { refTime: weight.ref_time(), proofSize: weight.proof_size() };
+				Ok(success(IXcm::weighMessageCall::abi_encode_returns(&(weight,))))
+			},
+			Err(error) => {
+				log::debug!(target: LOG_TARGET, "XCM precompile weighMessage failed with: {error:?}");
+				Ok(revert(error.into()))
+			},
+		}
+	}
+}
+
+/// Decode a SCALE encoded XCM type, rejecting trailing bytes.
+fn decode<V: DecodeLimit>(mut data: &[u8]) -> core::result::Result<V, codec::Error> {
+	V::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut data)
+}
@@ -40,6 +40,8 @@ pub use blake2f::*;
 
 /// Determine if the given address is a precompile.
 /// For now, we consider that all addresses between 0x1 and 0xff are reserved for precompiles.
+///
+/// Precompiles configured by the runtime must live outside of this range.
 pub fn is_precompile(address: &H160) -> bool {
 	let bytes = address.as_bytes();
 	bytes.starts_with(&[0u8; 19]) && bytes[19] != 0
@@ -28,6 +28,7 @@ use crate::{
 	evm::{runtime::GAS_PRICE, CallTrace, CallTracer, CallType, GenericTransaction},
 	exec::Key,
 	limits,
+	precompiles::{self, Environment as PrecompileEnvironment, Precompile},
 	storage::DeletionQueueManager,
 	test_utils::*,
 	tests::test_utils::{get_contract, get_contract_checked},
@@ -35,11 +36,12 @@ use crate::{
 	wasm::Memory,
 	weights::WeightInfo,
 	AccountId32Mapper, BalanceOf, Code, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
-	DeletionQueueCounter, DepositLimit, Error, EthTransactError, HoldReason, Origin, Pallet,
-	PristineCode, H160,
+	DeletionQueueCounter, DepositLimit, Error, EthTransactError, ExecReturnValue, HoldReason,
+	Origin, Pallet, PristineCode, H160,
 };
 
 use crate::test_utils::builder::Contract;
+use alloc::collections::BTreeMap;
 use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use frame_support::{
@@ -520,6 +522,39 @@ impl FindAuthor<<Test as frame_system::Config>::AccountId> for Test {
 	}
 }
 
+/// A precompile at the first address after the range reserved for the Ethereum precompiles.
+///
+/// Returns the address of its caller, the value it received and whether it was called read-only,
+/// and emits its input as an event. Reverts on empty input.
+pub struct TestPrecompile;
+
+impl TestPrecompile {
+	const ADDRESS: H160 = H160(hex_literal::hex!("0000000000000000000000000000000000000100"));
+}
+
+impl Precompile<Test> for TestPrecompile {
+	fn matches(address: &H160) -> bool {
+		*address == Self::ADDRESS
+	}
+
+	fn addresses() -> Vec<H160> {
+		vec![Self::ADDRESS]
+	}
+
+	fn call<E: Ext<T = Test>>(
+		env: &mut PrecompileEnvironment<E>,
+		input: &[u8],
+	) -> ExtensionResult<ExecReturnValue> {
+		if input.is_empty() {
+			return Ok(precompiles::revert("empty input"))
+		}
+		env.charge_weight(Weight::from_parts(1_000, 0))?;
+		env.deposit_event(vec![], input.to_vec())?;
+		let output = (env.caller_address(), env.value_transferred(), env.is_read_only()).encode();
+		Ok(precompiles::success(output))
+	}
+}
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
 impl Config for Test {
 	type Time = Timestamp;
@@ -528,6 +563,7 @@ impl Config for Test {
 	type CallFilter = TestFilter;
 	type ChainExtension =
 		(TestExtension, DisabledExtension, RevertingExtension, TempStorageExtension);
+	type Precompiles = (TestPrecompile,);
 	type DepositPerByte = DepositPerByte;
 	type DepositPerItem = DepositPerItem;
 	type UnsafeUnstableInterface = UnstableInterface;
@@ -4578,6 +4614,153 @@ fn tracing_works() {
 	});
 }
 
+#[test]
+fn prestate_tracing_works_for_transfers() {
+	use crate::evm::*;
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let _ = <Test as Config>::Currency::set_balance(&BOB, 1_000_000);
+		let alice_balance = Pallet::<Test>::evm_balance(&ALICE_ADDR);
+		let bob_balance = Pallet::<Test>::evm_balance(&BOB_ADDR);
+
+		for diff_mode in [false, true] {
+			let mut tracer = PrestateTracer::<Test>::new(diff_mode, false, false);
+			trace(&mut tracer, || {
+				builder::bare_call(BOB_ADDR).value(10_000_000).build_and_unwrap_result();
+			});
+
+			let pre = BTreeMap::from([
+				(
+					ALICE_ADDR,
+					PrestateTraceInfo {
+						balance: Some(alice_balance),
+						nonce: Some(0),
+						..Default::default()
+					},
+				),
+				(
+					BOB_ADDR,
+					PrestateTraceInfo {
+						balance: Some(bob_balance),
+						nonce: Some(0),
+						..Default::default()
+					},
+				),
+			]);
+			let alice_balance_after = Pallet::<Test>::evm_balance(&ALICE_ADDR);
+			let bob_balance_after = Pallet::<Test>::evm_balance(&BOB_ADDR);
+			assert!(alice_balance_after < alice_balance);
+			assert_eq!(alice_balance - alice_balance_after, bob_balance_after - bob_balance);
+
+			let expected = if diff_mode {
+				let post = BTreeMap::from([
+					(
+						ALICE_ADDR,
+						PrestateTraceInfo {
+							balance: Some(alice_balance_after),
+							..Default::default()
+						},
+					),
+					(
+						BOB_ADDR,
+						PrestateTraceInfo {
+							balance: Some(bob_balance_after),
+							..Default::default()
+						},
+					),
+				]);
+				PrestateTrace::DiffMode { pre, post }
+			} else {
+				PrestateTrace::Prestate(pre)
+			};
+			assert_eq!(tracer.collect_trace(), Some(expected));
+
+			// Undo the transfer for the next iteration.
+			<Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+			<Test as Config>::Currency::set_balance(&BOB, 1_000_000);
+		}
+	});
+}
+
+#[test]
+fn prestate_tracing_works_for_storage() {
+	use crate::evm::*;
+	let (code, _) = compile_module("multi_store").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut tracer = PrestateTracer::<Test>::new(true, true, false);
+		trace(&mut tracer, || {
+			builder::bare_call(addr).data((2u32, 3u32).encode()).build_and_unwrap_result();
+		});
+
+		let Some(PrestateTrace::DiffMode { pre, post }) = tracer.collect_trace() else {
+			panic!("expected a diff mode trace");
+		};
+		// The caller pays the storage deposit.
+		assert_eq!(pre.keys().collect::<Vec<_>>(), vec![&ALICE_ADDR, &addr]);
+		assert_eq!(
+			pre[&addr].storage,
+			BTreeMap::from([(Bytes(vec![1u8; 32]), None), (Bytes(vec![2u8; 32]), None)])
+		);
+		assert_eq!(pre[&addr].code, None);
+		assert_eq!(
+			post[&addr],
+			PrestateTraceInfo {
+				storage: BTreeMap::from([
+					(Bytes(vec![1u8; 32]), Some(Bytes(vec![0u8; 2]))),
+					(Bytes(vec![2u8; 32]), Some(Bytes(vec![0u8; 3]))),
+				]),
+				..Default::default()
+			}
+		);
+	});
+}
+
+#[test]
+fn struct_logging_works() {
+	use crate::evm::*;
+	let (code, _) = compile_module("multi_store").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut tracer = StructLogger::new(false, 0, |w: Weight| U256::from(w.ref_time()));
+		let gas_consumed = trace(&mut tracer, || {
+			builder::bare_call(addr).data((2u32, 3u32).encode()).build().gas_consumed
+		});
+
+		let struct_trace = tracer.collect_trace().unwrap();
+		assert!(!struct_trace.failed);
+		assert_eq!(struct_trace.gas, gas_consumed.ref_time());
+		assert!(!struct_trace.struct_logs.is_empty());
+		assert!(struct_trace.struct_logs.iter().all(|log| log.depth == 1));
+		assert!(struct_trace.struct_logs.windows(2).all(|logs| logs[0].gas >= logs[1].gas));
+
+		// Both storage writes are reported on the instructions that executed them.
+		let storage = struct_trace.struct_logs.iter().rev().find_map(|log| log.storage.clone());
+		assert_eq!(
+			storage,
+			Some(BTreeMap::from([
+				(Bytes(vec![1u8; 32]), Bytes(vec![0u8; 2])),
+				(Bytes(vec![2u8; 32]), Bytes(vec![0u8; 3])),
+			]))
+		);
+
+		// The number of captured instructions can be limited.
+		let mut tracer = StructLogger::new(true, 3, |w: Weight| U256::from(w.ref_time()));
+		trace(&mut tracer, || {
+			builder::bare_call(addr).data((2u32, 3u32).encode()).build_and_unwrap_result();
+		});
+		let struct_trace = tracer.collect_trace().unwrap();
+		assert_eq!(struct_trace.struct_logs.len(), 3);
+		assert!(struct_trace.struct_logs.iter().all(|log| log.storage.is_none()));
+	});
+}
+
 #[test]
 fn unknown_precompiles_revert() {
 	let (code, _code_hash) = compile_module("read_only_call").unwrap();
@@ -4706,3 +4889,82 @@ fn pure_precompile_works() {
 		});
 	}
 }
+
+#[test]
+fn runtime_precompile_works() {
+	let (code, _code_hash) = compile_module("call_and_return").unwrap();
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.value(1000)
+			.build_and_unwrap_contract();
+		assert!(precompiles::is_precompile::<Test>(&TestPrecompile::ADDRESS));
+		assert!(!precompiles::is_precompile::<Test>(&H160::from_low_u64_be(0x101)));
+
+		let result = builder::bare_call(addr)
+			.data((&TestPrecompile::ADDRESS, 100u64, [42u8]).encode())
+			.build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(result.data, (addr, U256::from(100), false).encode());
+		assert!(System::events().iter().any(|record| record.event ==
+			RuntimeEvent::Contracts(crate::Event::ContractEmitted {
+				contract: TestPrecompile::ADDRESS,
+				data: vec![42],
+				topics: vec![],
+			})));
+
+		// Reverting rolls back the value transfer.
+		let precompile_account =
+			<Test as Config>::AddressMapper::to_account_id(&TestPrecompile::ADDRESS);
+		let balance = test_utils::get_balance(&precompile_account);
+		let result = builder::bare_call(addr)
+			.data((&TestPrecompile::ADDRESS, 100u64).encode())
+			.build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(test_utils::get_balance(&precompile_account), balance);
+	});
+}
+
+#[test]
+fn runtime_precompile_can_be_called_by_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let precompile_account =
+			<Test as Config>::AddressMapper::to_account_id(&TestPrecompile::ADDRESS);
+
+		// The precompile acts on behalf of the signer.
+		let result = builder::bare_call(TestPrecompile::ADDRESS)
+			.value(100)
+			.data(vec![42])
+			.build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::empty());
+		assert_eq!(result.data, (ALICE_ADDR, U256::from(100), false).encode());
+		assert_eq!(test_utils::get_balance(&precompile_account), 101);
+
+		// Calling it through an extrinsic runs it, too.
+		System::reset_events();
+		assert_ok!(builder::call(TestPrecompile::ADDRESS).data(vec![43]).build());
+		assert!(System::events().iter().any(|record| record.event ==
+			RuntimeEvent::Contracts(crate::Event::ContractEmitted {
+				contract: TestPrecompile::ADDRESS,
+				data: vec![43],
+				topics: vec![],
+			})));
+
+		// Reverting rolls back the value transfer.
+		let result =
+			builder::bare_call(TestPrecompile::ADDRESS).value(100).build_and_unwrap_result();
+		assert_eq!(result.flags, ReturnFlags::REVERT);
+		assert_eq!(test_utils::get_balance(&precompile_account), 101);
+	});
+}
+
+#[test]
+fn overlapping_precompiles_are_detected() {
+	use precompiles::Precompile;
+	assert_eq!(<(TestPrecompile,) as Precompile<Test>>::overlapping_address(), None);
+	assert_eq!(
+		<(TestPrecompile, (TestPrecompile,)) as Precompile<Test>>::overlapping_address(),
+		Some(TestPrecompile::ADDRESS),
+	);
+}
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{primitives::ExecReturnValue, DispatchError, Weight};
+use crate::{exec::Key, primitives::ExecReturnValue, DispatchError, Weight};
 use environmental::environmental;
 use sp_core::{H160, H256, U256};
 
@@ -40,6 +40,9 @@ pub(crate) fn if_tracing<F: FnOnce(&mut (dyn Tracer + 'static))>(f: F) {
 }
 
 /// Defines methods to trace contract interactions.
+///
+/// Only the call span and event methods need to be implemented. The remaining hooks default to
+/// no-ops so that tracers only pay for the information they record.
 pub trait Tracer {
 	/// Called before a contract call is executed
 	fn enter_child_span(
@@ -61,4 +64,35 @@ pub trait Tracer {
 
 	/// Called when a contract call terminates with an error
 	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_left: Weight);
+
+	/// Called when a contract reads the storage item stored under `key`.
+	///
+	/// `key` is the unhashed key as passed by the contract.
+	fn storage_read(&mut self, _address: &H160, _key: &Key, _value: Option<&[u8]>) {}
+
+	/// Called before a contract writes `new_value` to the storage item stored under `key`.
+	///
+	/// `old_value` is the value stored under `key` before the write.
+	fn storage_write(
+		&mut self,
+		_address: &H160,
+		_key: &Key,
+		_old_value: Option<&[u8]>,
+		_new_value: Option<&[u8]>,
+	) {
+	}
+
+	/// Called after the balance of `address` changed due to a value transfer.
+	fn balance_change(&mut self, _address: &H160, _old_balance: U256, _new_balance: U256) {}
+
+	/// Whether [`Self::step`] should be called for every executed instruction.
+	///
+	/// Stepping through the instructions slows down execution considerably, hence it is only
+	/// enabled for the tracers that need it.
+	fn trace_steps(&self) -> bool {
+		false
+	}
+
+	/// Called before the PolkaVM instruction `opcode` at program counter `pc` is executed.
+	fn step(&mut self, _pc: u32, _opcode: &str, _gas_left: Weight) {}
 }
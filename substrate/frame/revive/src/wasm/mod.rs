@@ -33,11 +33,12 @@ use crate::{
 	gas::{GasMeter, Token},
 	limits,
 	storage::meter::Diff,
+	tracing::if_tracing,
 	weights::WeightInfo,
 	AccountIdOf, BadOrigin, BalanceOf, CodeInfoOf, CodeVec, Config, Error, ExecError, HoldReason,
 	PristineCode, Weight, LOG_TARGET,
 };
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
@@ -285,6 +286,7 @@ impl<T: Config> CodeInfo<T> {
 }
 
 pub struct PreparedCall<'a, E: Ext> {
+	blob: polkavm::ProgramBlob,
 	module: polkavm::Module,
 	instance: polkavm::RawInstance,
 	runtime: Runtime<'a, E, polkavm::RawInstance>,
@@ -298,6 +300,9 @@ where
 	pub fn call(mut self) -> ExecResult {
 		let exec_result = loop {
 			let interrupt = self.instance.run();
+			if let Ok(polkavm::InterruptKind::Step) = interrupt {
+				self.trace_step();
+			}
 			if let Some(exec_result) =
 				self.runtime.handle_interrupt(interrupt, &self.module, &mut self.instance)
 			{
@@ -308,6 +313,21 @@ where
 		exec_result
 	}
 
+	/// Report the instruction that is about to be executed to the tracer.
+	fn trace_step(&mut self) {
+		let Some(pc) = self.instance.program_counter() else { return };
+		let Some(instruction) =
+			self.blob.instructions_bounded_at(polkavm::program::ISA64_V1, pc).next()
+		else {
+			return
+		};
+		// The name of the instruction without its operands.
+		let instruction = format!("{:?}", instruction.kind);
+		let opcode = instruction.split(['(', ' ']).next().unwrap_or_default();
+		let gas_left = self.runtime.ext().gas_meter().gas_left_from_executor(self.instance.gas());
+		if_tracing(|tracer| tracer.step(pc.0, opcode, gas_left));
+	}
+
 	/// The guest memory address at which the aux data is located.
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn aux_data_base(&self) -> u32 {
@@ -363,11 +383,18 @@ impl<T: Config> WasmBlob<T> {
 		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
 		module_config.set_allow_sbrk(false);
 		module_config.set_aux_data_size(aux_data_size);
-		let module = polkavm::Module::new(&engine, &module_config, self.code.into_inner().into())
-			.map_err(|err| {
-			log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
+		if_tracing(|tracer| {
+			module_config.set_step_tracing(tracer.trace_steps());
+		});
+		let blob = polkavm::ProgramBlob::parse(self.code.into_inner().into()).map_err(|err| {
+			log::debug!(target: LOG_TARGET, "failed to parse polkavm blob: {err:?}");
 			Error::<T>::CodeRejected
 		})?;
+		let module =
+			polkavm::Module::from_blob(&engine, &module_config, blob.clone()).map_err(|err| {
+				log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
+				Error::<T>::CodeRejected
+			})?;
 
 		let entry_program_counter = module
 			.exports()
@@ -385,7 +412,7 @@ impl<T: Config> WasmBlob<T> {
 		instance.set_gas(gas_limit_polkavm);
 		instance.prepare_call_untyped(entry_program_counter, &[]);
 
-		Ok(PreparedCall { module, instance, runtime })
+		Ok(PreparedCall { blob, module, instance, runtime })
 	}
 }
 
@@ -23,8 +23,8 @@ use crate::{
 	exec::{ExecError, ExecResult, Ext, Key},
 	gas::{ChargedAmount, Token},
 	limits,
+	precompiles::is_precompile,
 	primitives::ExecReturnValue,
-	pure_precompiles::is_precompile,
 	weights::WeightInfo,
 	Config, Error, LOG_TARGET, SENTINEL,
 };
@@ -394,6 +394,8 @@ pub enum RuntimeCosts {
 	CallRuntime(Weight),
 	/// Weight charged for calling xcm_execute.
 	CallXcmExecute(Weight),
+	/// Weight charged by a precompile configured in [`Config::Precompiles`].
+	Precompile(Weight),
 	/// Weight of calling `seal_set_code_hash`
 	SetCodeHash,
 	/// Weight of calling `ecdsa_to_eth_address`
@@ -538,7 +540,10 @@ impl<T: Config> Token<T> for RuntimeCosts {
 			HashBlake128(len) => T::WeightInfo::seal_hash_blake2_128(len),
 			EcdsaRecovery => T::WeightInfo::ecdsa_recover(),
 			Sr25519Verify(len) => T::WeightInfo::seal_sr25519_verify(len),
-			ChainExtension(weight) | CallRuntime(weight) | CallXcmExecute(weight) => weight,
+			ChainExtension(weight) |
+			CallRuntime(weight) |
+			CallXcmExecute(weight) |
+			Precompile(weight) => weight,
 			SetCodeHash => T::WeightInfo::seal_set_code_hash(),
 			EcdsaToEthAddress => T::WeightInfo::seal_ecdsa_to_eth_address(),
 			GetImmutableData(len) => T::WeightInfo::seal_get_immutable_data(len),
@@ -1048,7 +1053,7 @@ impl<'a, E: Ext, M: ?Sized + Memory<E::T>> Runtime<'a, E, M> {
 		output_len_ptr: u32,
 	) -> Result<ReturnErrorCode, TrapReason> {
 		let callee = match memory.read_h160(callee_ptr) {
-			Ok(callee) if is_precompile(&callee) => callee,
+			Ok(callee) if is_precompile::<E::T>(&callee) => callee,
 			Ok(callee) => {
 				self.charge_gas(call_type.cost())?;
 				callee
@@ -2151,6 +2156,52 @@ pub mod env {
 		}
 	}
 
+	/// Send an XCM program from the contract to the specified destination, paying the delivery
+	/// fees with another asset.
+	/// See [`pallet_revive_uapi::HostFn::xcm_send_with_fee_asset`].
+	#[mutating]
+	fn xcm_send_with_fee_asset(
+		&mut self,
+		memory: &mut M,
+		dest_ptr: u32,
+		dest_len: u32,
+		msg_ptr: u32,
+		msg_len: u32,
+		fee_asset: u64,
+		output_ptr: u32,
+	) -> Result<ReturnErrorCode, TrapReason> {
+		use crate::xcm_fees::{self, XcmFeeSwap};
+		use xcm::{VersionedLocation, VersionedXcm};
+
+		let (fee_asset_len, fee_asset_ptr) = extract_hi_lo(fee_asset);
+
+		self.charge_gas(RuntimeCosts::CopyFromContract(dest_len))?;
+		let dest: VersionedLocation = memory.read_as_unbounded(dest_ptr, dest_len)?;
+
+		self.charge_gas(RuntimeCosts::CopyFromContract(msg_len))?;
+		let message: VersionedXcm<()> = memory.read_as_unbounded(msg_ptr, msg_len)?;
+
+		self.charge_gas(RuntimeCosts::CopyFromContract(fee_asset_len))?;
+		let (fee_asset, max_amount_in): (
+			<<E::T as Config>::XcmFeeSwap as XcmFeeSwap<_>>::AssetKind,
+			u128,
+		) = memory.read_as_unbounded(fee_asset_ptr, fee_asset_len)?;
+
+		self.charge_gas(RuntimeCosts::CallRuntime(xcm_fees::send_weight::<E::T>()))?;
+
+		match xcm_fees::send::<E::T>(self.ext.account_id(), dest, message, fee_asset, max_amount_in)
+		{
+			Ok(message_id) => {
+				memory.write(output_ptr, &message_id.encode())?;
+				Ok(ReturnErrorCode::Success)
+			},
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "seal0::xcm_send_with_fee_asset failed with: {e:?}");
+				Ok(ReturnErrorCode::XcmSendFailed)
+			},
+		}
+	}
+
 	/// Retrieves the account id for a specified contract address.
 	///
 	/// See [`pallet_revive_uapi::HostFn::to_account_id`].
@@ -0,0 +1,359 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Paying the delivery fees of XCM programs sent by contracts with assets other than the native
+//! token.
+//!
+//! [`Config::Xcm`] charges delivery fees in the native token. When a contract sends a program
+//! with a fee asset, the native tokens needed are first acquired by swapping the fee asset through
+//! [`Config::XcmFeeSwap`], the same way `pallet_asset_conversion_tx_payment` pays transaction fees.
+//! With the `asset-conversion` feature, `SwapXcmFees` implements it on top of
+//! `pallet_asset_conversion`.
+
+use crate::{exec::AccountIdOf, Config, Error, RawOrigin};
+use alloc::boxed::Box;
+use codec::Decode;
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use sp_runtime::DispatchError;
+#[cfg(feature = "asset-conversion")]
+use {
+	alloc::vec,
+	core::marker::PhantomData,
+	frame_support::traits::Get,
+	pallet_asset_conversion::{QuotePrice, Swap},
+	sp_runtime::ArithmeticError,
+};
+use xcm::{prelude::*, VersionedLocation, VersionedXcm};
+use xcm_builder::{SendController, SendControllerWeightInfo};
+
+/// Swaps assets of a contract for the native tokens needed to pay XCM delivery fees.
+pub trait XcmFeeSwap<AccountId> {
+	/// The kind of assets the fees can be paid with. Contracts pass it SCALE encoded.
+	type AssetKind: Decode;
+
+	/// The weight of [`Self::swap`], charged before the swap is attempted.
+	fn swap_weight() -> Weight;
+
+	/// The amount of `asset` that has to be swapped to pay the native `fees`.
+	///
+	/// Returns `None` if the fees can't be paid with `asset`.
+	fn quote(asset: &Self::AssetKind, fees: &Assets) -> Option<u128>;
+
+	/// Swap at most `max_amount_in` of the `asset` held by `who` for the native `fees`, deposited
+	/// into the account of `who`.
+	///
+	/// Returns the amount of `asset` that was swapped.
+	fn swap(
+		who: &AccountId,
+		asset: Self::AssetKind,
+		fees: &Assets,
+		max_amount_in: u128,
+	) -> Result<u128, DispatchError>;
+}
+
+/// Delivery fees can only be paid with the native token.
+impl<AccountId> XcmFeeSwap<AccountId> for () {
+	type AssetKind = ();
+
+	fn swap_weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn quote(_asset: &Self::AssetKind, _fees: &Assets) -> Option<u128> {
+		None
+	}
+
+	fn swap(
+		_who: &AccountId,
+		_asset: Self::AssetKind,
+		_fees: &Assets,
+		_max_amount_in: u128,
+	) -> Result<u128, DispatchError> {
+		Err(DispatchError::Other("XcmFeeSwap::swap not implemented"))
+	}
+}
+
+/// An [`XcmFeeSwap`] swapping through `S`, usually `pallet_asset_conversion`.
+///
+/// - `Native`: the asset kind of the native token in `S`.
+/// - `NativeLocation`: the location of the native token in the delivery fees.
+/// - `SwapWeight`: the weight of swapping for an exact amount of the native token with `S`.
+///
+/// Only fees that are charged in the native token are supported.
+#[cfg(feature = "asset-conversion")]
+pub struct SwapXcmFees<S, Native, NativeLocation, SwapWeight>(
+	PhantomData<(S, Native, NativeLocation, SwapWeight)>,
+);
+
+#[cfg(feature = "asset-conversion")]
+impl<S, Native, NativeLocation, SwapWeight> SwapXcmFees<S, Native, NativeLocation, SwapWeight>
+where
+	NativeLocation: Get<Location>,
+{
+	/// The amount of native tokens in `fees`, or `None` if they contain any other asset.
+	fn native_fees(fees: &Assets) -> Option<u128> {
+		let native = AssetId(NativeLocation::get());
+		fees.inner().iter().try_fold(0u128, |total, asset| match asset {
+			Asset { id, fun: Fungible(amount) } if *id == native => total.checked_add(*amount),
+			_ => None,
+		})
+	}
+}
+
+#[cfg(feature = "asset-conversion")]
+impl<AccountId, S, Native, NativeLocation, SwapWeight> XcmFeeSwap<AccountId>
+	for SwapXcmFees<S, Native, NativeLocation, SwapWeight>
+where
+	AccountId: Clone,
+	S: Swap<AccountId>
+		+ QuotePrice<
+			Balance = <S as Swap<AccountId>>::Balance,
+			AssetKind = <S as Swap<AccountId>>::AssetKind,
+		>,
+	<S as Swap<AccountId>>::AssetKind: Decode + Clone + PartialEq,
+	<S as Swap<AccountId>>::Balance: TryFrom<u128> + Into<u128>,
+	Native: Get<<S as Swap<AccountId>>::AssetKind>,
+	NativeLocation: Get<Location>,
+	SwapWeight: Get<Weight>,
+{
+	type AssetKind = <S as Swap<AccountId>>::AssetKind;
+
+	fn swap_weight() -> Weight {
+		SwapWeight::get()
+	}
+
+	fn quote(asset: &Self::AssetKind, fees: &Assets) -> Option<u128> {
+		let amount_out = Self::native_fees(fees)?;
+		if *asset == Native::get() {
+			return Some(amount_out)
+		}
+		S::quote_price_tokens_for_exact_tokens(
+			asset.clone(),
+			Native::get(),
+			amount_out.try_into().ok()?,
+			true,
+		)
+		.map(Into::into)
+	}
+
+	fn swap(
+		who: &AccountId,
+		asset: Self::AssetKind,
+		fees: &Assets,
+		max_amount_in: u128,
+	) -> Result<u128, DispatchError> {
+		let amount_out = Self::native_fees(fees)
+			.ok_or(DispatchError::Other("Delivery fees are not charged in the native token"))?;
+		if asset == Native::get() {
+			return Ok(amount_out)
+		}
+
+		let amount_in = S::swap_tokens_for_exact_tokens(
+			who.clone(),
+			vec![asset, Native::get()],
+			amount_out.try_into().map_err(|_| ArithmeticError::Overflow)?,
+			// A limit exceeding the balance type does not limit the swap.
+			max_amount_in.try_into().ok(),
+			who.clone(),
+			true,
+		)?;
+		Ok(amount_in.into())
+	}
+}
+
+/// The weight of [`send`].
+///
+/// Also charged for [`quote`], as it validates the message with the router and quotes the swap.
+pub(crate) fn send_weight<T: Config>() -> Weight {
+	<T::Xcm as SendController<_>>::WeightInfo::send().saturating_add(T::XcmFeeSwap::swap_weight())
+}
+
+/// The delivery fees charged to `origin` for sending `message` to `dest`.
+fn delivery_fees<T: Config>(
+	origin: &AccountIdOf<T>,
+	dest: VersionedLocation,
+	message: VersionedXcm<()>,
+) -> Result<Assets, DispatchError> {
+	let origin = RawOrigin::Signed(origin.clone()).into();
+	let fees =
+		<T::Xcm as SendController<_>>::delivery_fees(origin, Box::new(dest), Box::new(message))?;
+	fees.try_into().map_err(|()| Error::<T>::XcmFeeAssetNotSupported.into())
+}
+
+/// The amount of `fee_asset` that `origin` has to swap to pay the delivery fees of sending
+/// `message` to `dest`.
+pub(crate) fn quote<T: Config>(
+	origin: &AccountIdOf<T>,
+	dest: VersionedLocation,
+	message: VersionedXcm<()>,
+	fee_asset: &<T::XcmFeeSwap as XcmFeeSwap<AccountIdOf<T>>>::AssetKind,
+) -> Result<u128, DispatchError> {
+	let fees = delivery_fees::<T>(origin, dest, message)?;
+	if fees.is_none() {
+		return Ok(0)
+	}
+	T::XcmFeeSwap::quote(fee_asset, &fees).ok_or_else(|| Error::<T>::XcmFeeAssetNotSupported.into())
+}
+
+/// Send `message` to `dest` from `origin`, swapping at most `max_amount_in` of `fee_asset` to
+/// pay the delivery fees.
+///
+/// The swap is reverted if sending fails.
+pub(crate) fn send<T: Config>(
+	origin: &AccountIdOf<T>,
+	dest: VersionedLocation,
+	message: VersionedXcm<()>,
+	fee_asset: <T::XcmFeeSwap as XcmFeeSwap<AccountIdOf<T>>>::AssetKind,
+	max_amount_in: u128,
+) -> Result<XcmHash, DispatchError> {
+	with_transaction(|| {
+		let result = (|| {
+			let fees = delivery_fees::<T>(origin, dest.clone(), message.clone())?;
+			if !fees.is_none() {
+				T::XcmFeeSwap::swap(origin, fee_asset, &fees, max_amount_in)?;
+			}
+			let send_origin = RawOrigin::Signed(origin.clone()).into();
+			<T::Xcm>::send(send_origin, Box::new(dest), Box::new(message))
+		})();
+		match result {
+			Ok(message_id) => TransactionOutcome::Commit(Ok(message_id)),
+			Err(error) => TransactionOutcome::Rollback(Err(error)),
+		}
+	})
+}
+
+#[cfg(all(test, feature = "asset-conversion"))]
+mod tests {
+	use super::*;
+	use alloc::vec::Vec;
+	use core::cell::RefCell;
+	use frame_support::parameter_types;
+
+	type AccountId = u64;
+
+	thread_local! {
+		/// The swaps executed by [`MockSwap`], as `(path, amount_out, amount_in_max)`.
+		static SWAPS: RefCell<Vec<(Vec<u32>, u128, Option<u128>)>> = RefCell::new(Vec::new());
+	}
+
+	/// Swaps at a price of two units of any asset for one unit of the native token.
+	struct MockSwap;
+
+	impl Swap<AccountId> for MockSwap {
+		type Balance = u128;
+		type AssetKind = u32;
+
+		fn max_path_len() -> u32 {
+			2
+		}
+
+		fn swap_exact_tokens_for_tokens(
+			_sender: AccountId,
+			_path: Vec<u32>,
+			amount_in: u128,
+			amount_out_min: Option<u128>,
+			_send_to: AccountId,
+			_keep_alive: bool,
+		) -> Result<u128, DispatchError> {
+			let amount_out = amount_in / 2;
+			if amount_out_min.is_some_and(|min| amount_out < min) {
+				return Err(DispatchError::Other("slippage"))
+			}
+			Ok(amount_out)
+		}
+
+		fn swap_tokens_for_exact_tokens(
+			_sender: AccountId,
+			path: Vec<u32>,
+			amount_out: u128,
+			amount_in_max: Option<u128>,
+			_send_to: AccountId,
+			_keep_alive: bool,
+		) -> Result<u128, DispatchError> {
+			let amount_in = amount_out * 2;
+			if amount_in_max.is_some_and(|max| amount_in > max) {
+				return Err(DispatchError::Other("slippage"))
+			}
+			SWAPS.with(|swaps| swaps.borrow_mut().push((path, amount_out, amount_in_max)));
+			Ok(amount_in)
+		}
+	}
+
+	impl QuotePrice for MockSwap {
+		type Balance = u128;
+		type AssetKind = u32;
+
+		fn quote_price_tokens_for_exact_tokens(
+			_asset1: u32,
+			_asset2: u32,
+			amount: u128,
+			_include_fee: bool,
+		) -> Option<u128> {
+			Some(amount * 2)
+		}
+
+		fn quote_price_exact_tokens_for_tokens(
+			_asset1: u32,
+			_asset2: u32,
+			amount: u128,
+			_include_fee: bool,
+		) -> Option<u128> {
+			Some(amount / 2)
+		}
+	}
+
+	parameter_types! {
+		pub const NativeAsset: u32 = 0;
+		pub NativeLocation: Location = Location::here();
+		pub const SwapWeight: Weight = Weight::from_parts(1_000, 0);
+	}
+
+	type Fees = SwapXcmFees<MockSwap, NativeAsset, NativeLocation, SwapWeight>;
+
+	fn native(amount: u128) -> Assets {
+		(Here, amount).into()
+	}
+
+	#[test]
+	fn quote_works() {
+		assert_eq!(<Fees as XcmFeeSwap<AccountId>>::quote(&1, &native(100)), Some(200));
+		assert_eq!(<Fees as XcmFeeSwap<AccountId>>::quote(&0, &native(100)), Some(100));
+
+		// Fees in other assets can't be swapped for.
+		let foreign: Assets = (Parent, 100).into();
+		assert_eq!(<Fees as XcmFeeSwap<AccountId>>::quote(&1, &foreign), None);
+	}
+
+	#[test]
+	fn swap_works() {
+		assert_eq!(<Fees as XcmFeeSwap<AccountId>>::swap(&1, 1, &native(100), 200), Ok(200));
+		assert_eq!(SWAPS.with(|swaps| swaps.take()), vec![(vec![1, 0], 100, Some(200))]);
+
+		// Paying with the native token does not swap.
+		assert_eq!(<Fees as XcmFeeSwap<AccountId>>::swap(&1, 0, &native(100), 0), Ok(100));
+		assert!(SWAPS.with(|swaps| swaps.borrow().is_empty()));
+	}
+
+	#[test]
+	fn swap_respects_max_amount_in() {
+		assert!(<Fees as XcmFeeSwap<AccountId>>::swap(&1, 1, &native(100), 199).is_err());
+		assert!(SWAPS.with(|swaps| swaps.borrow().is_empty()));
+	}
+}
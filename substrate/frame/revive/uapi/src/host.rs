@@ -721,6 +721,33 @@ pub trait HostFn: private::Sealed {
 	/// execution fails, `ReturnErrorCode::XcmSendFailed` is returned.
 	#[unstable_hostfn]
 	fn xcm_send(dest: &[u8], msg: &[u8], output: &mut [u8; 32]) -> Result;
+
+	/// Send an XCM program from the contract to the specified destination, paying the delivery
+	/// fees with an asset other than the native token.
+	///
+	/// The required native tokens are acquired by swapping the fee asset of the contract, which
+	/// is reverted if the message can't be sent.
+	///
+	/// # Parameters
+	///
+	/// - `dest`: The XCM destination, should be decodable as [VersionedLocation](https://paritytech.github.io/polkadot-sdk/master/staging_xcm/enum.VersionedLocation.html),
+	///   traps otherwise.
+	/// - `msg`: The message, should be decodable as a [VersionedXcm](https://paritytech.github.io/polkadot-sdk/master/staging_xcm/enum.VersionedXcm.html),
+	///   traps otherwise.
+	/// - `fee_asset`: The SCALE encoded tuple of the asset kind accepted by the runtime and the
+	///   maximum amount (`u128`) of it to swap, traps otherwise.
+	///
+	/// # Return
+	///
+	/// Returns `ReturnCode::Success` when the message was successfully sent. When the swap or
+	/// sending fails, `ReturnErrorCode::XcmSendFailed` is returned.
+	#[unstable_hostfn]
+	fn xcm_send_with_fee_asset(
+		dest: &[u8],
+		msg: &[u8],
+		fee_asset: &[u8],
+		output: &mut [u8; 32],
+	) -> Result;
 }
 
 mod private {
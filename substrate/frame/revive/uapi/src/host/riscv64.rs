@@ -149,6 +149,14 @@ mod sys {
 			msg_len: u32,
 			out_ptr: *mut u8,
 		) -> ReturnCode;
+		pub fn xcm_send_with_fee_asset(
+			dest_ptr: *const u8,
+			dest_len: u32,
+			msg_ptr: *const u8,
+			msg_len: u32,
+			fee_asset: u64,
+			out_ptr: *mut u8,
+		) -> ReturnCode;
 		pub fn return_data_size() -> u64;
 		pub fn return_data_copy(out_ptr: *mut u8, out_len_ptr: *mut u32, offset: u32);
 	}
@@ -604,4 +612,25 @@ impl HostFn for HostFnImpl {
 		};
 		ret_code.into()
 	}
+
+	#[unstable_hostfn]
+	fn xcm_send_with_fee_asset(
+		dest: &[u8],
+		msg: &[u8],
+		fee_asset: &[u8],
+		output: &mut [u8; 32],
+	) -> Result {
+		let fee_asset_data = pack_hi_lo(fee_asset.len() as _, fee_asset.as_ptr() as _);
+		let ret_code = unsafe {
+			sys::xcm_send_with_fee_asset(
+				dest.as_ptr(),
+				dest.len() as _,
+				msg.as_ptr(),
+				msg.len() as _,
+				fee_asset_data,
+				output.as_mut_ptr(),
+			)
+		};
+		ret_code.into()
+	}
 }
@@ -0,0 +1,229 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strategy Vaults pallet benchmarking.
+
+use super::*;
+use crate::Pallet as StrategyVaults;
+use alloc::vec;
+use frame_benchmarking::{v2::*, whitelisted_caller, BenchmarkError};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+
+/// Benchmark Helper
+pub trait BenchmarkHelper<AccountId, AssetKind, Balance> {
+	/// Returns `n` distinct assets and an amount of the first of them, after endowing `who` with
+	/// the amount of each asset, and enough funds to pay any deposit.
+	///
+	/// The assets other than the first must be swappable to and from the first, with enough
+	/// liquidity to swap the amount. The helper may be called several times with the same `n`.
+	fn setup_assets(who: &AccountId, n: u32) -> (Vec<AssetKind>, Balance);
+}
+
+/// Creates a vault of `n` assets, with a deposit of the depositor swapped into every asset.
+///
+/// Returns the vault's manager and id, and the depositor with an amount left to deposit while
+/// staying alive.
+fn create_funded_vault<T: Config>(
+	n: u32,
+) -> Result<(T::AccountId, VaultId, T::AccountId, T::Balance), BenchmarkError> {
+	let origin =
+		T::CreateVaultOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let manager = T::CreateVaultOrigin::ensure_origin(origin.clone()).unwrap();
+	let (assets, _) = T::BenchmarkHelper::setup_assets(&manager, n);
+	T::Consideration::ensure_successful(&manager, StrategyVaults::<T>::vault_creation_footprint());
+
+	let mut assets = assets.into_iter().map(Box::new);
+	let denomination = assets.next().unwrap();
+	StrategyVaults::<T>::create_vault(
+		origin,
+		denomination,
+		assets.collect(),
+		Permill::from_percent(2),
+		Permill::from_percent(20),
+	)?;
+	let vault_id = NextVaultId::<T>::get() - 1;
+
+	let depositor: T::AccountId = whitelisted_caller();
+	let (_, amount) = T::BenchmarkHelper::setup_assets(&depositor, n);
+	let deposit = amount / 4u32.into();
+	StrategyVaults::<T>::deposit(
+		RawOrigin::Signed(depositor.clone()).into(),
+		vault_id,
+		deposit,
+		One::one(),
+	)?;
+
+	// Value the holdings through quotes of every asset.
+	rebalance_all::<T>(&manager, vault_id, deposit / (n + 1).into())?;
+
+	Ok((manager, vault_id, depositor, deposit))
+}
+
+/// Swap `amount` of the denomination asset of a vault into each of its other assets.
+fn rebalance_all<T: Config>(
+	manager: &T::AccountId,
+	vault_id: VaultId,
+	amount: T::Balance,
+) -> Result<(), BenchmarkError> {
+	let assets = Vaults::<T>::get(vault_id).unwrap().assets;
+	for asset in assets.iter().skip(1) {
+		StrategyVaults::<T>::rebalance(
+			RawOrigin::Signed(manager.clone()).into(),
+			vault_id,
+			vec![Box::new(assets[0].clone()), Box::new(asset.clone())],
+			amount,
+			One::one(),
+		)?;
+	}
+	Ok(())
+}
+
+fn advance_blocks<T: Config>(blocks: BlockNumberFor<T>) {
+	let now = frame_system::Pallet::<T>::block_number();
+	frame_system::Pallet::<T>::set_block_number(now.saturating_add(blocks));
+}
+
+/// Advance a year for the fees of a vault to be due, and move the prices of its pools within the
+/// last [`Config::PriceWindow`], for their time-weighted prices to be searched in their
+/// observations.
+fn advance_and_move_prices<T: Config>(
+	manager: &T::AccountId,
+	vault_id: VaultId,
+) -> Result<(), BenchmarkError> {
+	advance_blocks::<T>(T::BlocksPerYear::get());
+	let vault = Vaults::<T>::get(vault_id).unwrap();
+	let amount = StrategyVaults::<T>::holding(&vault, vault.denomination()) /
+		(vault.assets.len() as u32 + 1).into();
+	rebalance_all::<T>(manager, vault_id, amount)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_vault(n: Linear<1, { T::MaxAssets::get() }>) -> Result<(), BenchmarkError> {
+		let origin = T::CreateVaultOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let manager = T::CreateVaultOrigin::ensure_origin(origin.clone()).unwrap();
+		let (assets, _) = T::BenchmarkHelper::setup_assets(&manager, n);
+		T::Consideration::ensure_successful(
+			&manager,
+			StrategyVaults::<T>::vault_creation_footprint(),
+		);
+		let mut assets = assets.into_iter().map(Box::new);
+		let denomination = assets.next().unwrap();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			denomination,
+			assets.collect(),
+			Permill::from_percent(2),
+			Permill::from_percent(20),
+		);
+
+		assert!(Vaults::<T>::contains_key(NextVaultId::<T>::get() - 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deposit(n: Linear<1, { T::MaxAssets::get() }>) -> Result<(), BenchmarkError> {
+		let (manager, vault_id, depositor, amount) = create_funded_vault::<T>(n)?;
+		advance_and_move_prices::<T>(&manager, vault_id)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(depositor), vault_id, amount, One::one());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn request_withdrawal() -> Result<(), BenchmarkError> {
+		let (_, vault_id, depositor, _) = create_funded_vault::<T>(1)?;
+		let vault = Vaults::<T>::get(vault_id).unwrap();
+		let shares = T::ShareAssets::balance(vault.share_asset, &depositor) / 2u32.into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(depositor), vault_id, shares);
+
+		assert!(WithdrawalRequests::<T>::contains_key(vault_id, 0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_withdrawal(n: Linear<1, { T::MaxAssets::get() }>) -> Result<(), BenchmarkError> {
+		let (_, vault_id, depositor, _) = create_funded_vault::<T>(n)?;
+		let vault = Vaults::<T>::get(vault_id).unwrap();
+		let shares = T::ShareAssets::balance(vault.share_asset, &depositor) / 2u32.into();
+		StrategyVaults::<T>::request_withdrawal(
+			RawOrigin::Signed(depositor.clone()).into(),
+			vault_id,
+			shares,
+		)?;
+		advance_blocks::<T>(T::WithdrawalDelay::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(depositor), vault_id, 0);
+
+		assert!(!WithdrawalRequests::<T>::contains_key(vault_id, 0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn rebalance() -> Result<(), BenchmarkError> {
+		let (manager, vault_id, _, _) = create_funded_vault::<T>(2)?;
+		let vault = Vaults::<T>::get(vault_id).unwrap();
+		let amount = StrategyVaults::<T>::holding(&vault, vault.denomination()) / 2u32.into();
+		let path = vec![Box::new(vault.assets[0].clone()), Box::new(vault.assets[1].clone())];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(manager), vault_id, path, amount, One::one());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accrue_fees(n: Linear<1, { T::MaxAssets::get() }>) -> Result<(), BenchmarkError> {
+		let (manager, vault_id, depositor, _) = create_funded_vault::<T>(n)?;
+		advance_and_move_prices::<T>(&manager, vault_id)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(depositor), vault_id);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_fees(n: Linear<1, { T::MaxAssets::get() }>) -> Result<(), BenchmarkError> {
+		let (manager, vault_id, _, _) = create_funded_vault::<T>(n)?;
+		advance_and_move_prices::<T>(&manager, vault_id)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(manager),
+			vault_id,
+			Permill::from_percent(1),
+			Permill::from_percent(10),
+		);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(StrategyVaults, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,881 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Strategy Vaults Pallet
+//!
+//! Pooled deposits managed by a strategy, with share-based accounting of the vault's net asset
+//! value.
+//!
+//! ## Overview
+//!
+//! A vault holds a set of assets in its own account and is denominated in one of them. A
+//! manager [creates the vault](`Pallet::create_vault`), after which accounts can:
+//!
+//! - [deposit](`Pallet::deposit`) the denomination asset, receiving newly minted shares of the
+//!   vault in proportion to the vault's net asset value. The first deposit to a vault also mints
+//!   [`Config::MintMinShares`] shares to the vault's account, which are never redeemed;
+//! - [request a withdrawal](`Pallet::request_withdrawal`) of their shares, which are put on hold
+//!   with [`HoldReason::Withdrawal`] and can be [claimed](`Pallet::claim_withdrawal`) once
+//!   [`Config::WithdrawalDelay`] has passed, paying out the same fraction of every asset held by
+//!   the vault.
+//!
+//! The manager runs the vault's strategy by [swapping its holdings](`Pallet::rebalance`) through
+//! [`Config::Swap`], and is paid in shares:
+//!
+//! - a management fee, a yearly fraction of the shares accrued over time;
+//! - a performance fee, a fraction of the gain of the net asset value per share above its
+//!   high-water mark, the highest value on which the performance fee was paid.
+//!
+//! Fees are accrued before every deposit and withdrawal, and can be
+//! [accrued](`Pallet::accrue_fees`) by anyone at any time.
+//!
+//! ### Valuation
+//!
+//! The net asset value of a vault is the amount of its holdings in the denomination asset, plus
+//! the value of the other assets at the time-weighted average prices quoted by [`Config::Swap`]
+//! over the last [`Config::PriceWindow`] blocks. These prices can't be moved within a block, so
+//! depositors can't mint shares at a price they manipulated. The holdings of a vault can't be
+//! valued, and deposits fail, until the prices of its pools are known over the whole window.
+//!
+//! The shares minted to the vault's account on its first deposit keep the value of a share from
+//! being inflated by a donation to a vault of a few shares. The delay of withdrawals should
+//! cover the unwinding of the strategy.
+//!
+//! Note that depositors trust the manager with the vault's holdings: the swaps of
+//! [`Pallet::rebalance`] are only bounded by the minimum amount set by the manager.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{
+		fungibles::{Create, Inspect, Mutate, MutateHold},
+		tokens::{AssetId, Balance, Fortitude, Precision, Preservation, Provenance},
+		AccountTouch, Consideration, Footprint, Incrementable,
+	},
+	BoundedVec, CloneNoBound, EqNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_asset_conversion::{QuoteTimeWeightedPrice, Swap};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, EnsureAdd, Get, One, Saturating, Zero},
+	ArithmeticError, DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, Permill,
+	Rounding, SaturatedConversion,
+};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
+
+/// Unique identifier of a vault.
+pub type VaultId = u32;
+
+/// Unique identifier of a withdrawal request, within its vault.
+pub type RequestId = u32;
+
+/// A strategy vault.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Vault<T: Config> {
+	/// The account running the vault's strategy and receiving its fees.
+	pub manager: T::AccountId,
+	/// The account holding the vault's assets.
+	pub account: T::AccountId,
+	/// The assets the vault may hold. The first one is the denomination asset, in which
+	/// deposits are made and the net asset value is measured.
+	pub assets: BoundedVec<T::AssetKind, T::MaxAssets>,
+	/// The asset of the vault's shares.
+	pub share_asset: T::ShareAssetId,
+	/// The yearly fraction of the shares paid to the manager.
+	pub management_fee: Permill,
+	/// The fraction of the gain above the high-water mark paid to the manager.
+	pub performance_fee: Permill,
+	/// The highest net asset value per share on which the performance fee was paid.
+	pub high_water_mark: FixedU128,
+	/// The block up to which the management fee was accrued.
+	pub last_fee_accrual: BlockNumberFor<T>,
+}
+
+impl<T: Config> Vault<T> {
+	/// The asset in which deposits are made and the net asset value is measured.
+	pub fn denomination(&self) -> &T::AssetKind {
+		&self.assets[0]
+	}
+}
+
+/// A request to withdraw shares from a vault.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct WithdrawalRequest<T: Config> {
+	/// The account withdrawing, whose shares are on hold.
+	pub owner: T::AccountId,
+	/// The amount of shares to redeem.
+	pub shares: T::Balance,
+	/// The block from which the withdrawal can be claimed.
+	pub claimable_at: BlockNumberFor<T>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_arithmetic::MultiplyRational;
+	use sp_runtime::traits::BadOrigin;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Cost associated with storing a vault on-chain.
+		#[codec(index = 0)]
+		VaultCreation,
+		/// Shares of a withdrawal request, burnt when the withdrawal is claimed.
+		#[codec(index = 1)]
+		Withdrawal,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The pallet's unique identifier, used to derive the vaults' accounts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The type in which the assets and the shares are measured.
+		type Balance: Balance + FixedPointOperand;
+
+		/// Type of asset class held by the vaults.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// Registry of the assets held by the vaults.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>
+			+ AccountTouch<Self::AssetKind, Self::AccountId, Balance = Self::Balance>;
+
+		/// Type of the assets of the vaults' shares.
+		type ShareAssetId: AssetId + Incrementable;
+
+		/// Registry for the vaults' shares.
+		type ShareAssets: Create<Self::AccountId>
+			+ Inspect<Self::AccountId, AssetId = Self::ShareAssetId, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>
+			+ AccountTouch<Self::ShareAssetId, Self::AccountId, Balance = Self::Balance>;
+
+		/// Holder of the vaults' shares, for the shares of the withdrawal requests.
+		type ShareAssetsHolder: MutateHold<
+			Self::AccountId,
+			AssetId = Self::ShareAssetId,
+			Balance = Self::Balance,
+			Reason = Self::RuntimeHoldReason,
+		>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The swap and price quoting implementation, typically [`pallet_asset_conversion`].
+		type Swap: Swap<Self::AccountId, Balance = Self::Balance, AssetKind = Self::AssetKind>
+			+ QuoteTimeWeightedPrice<
+				Balance = Self::Balance,
+				AssetKind = Self::AssetKind,
+				BlockNumber = BlockNumberFor<Self>,
+			>;
+
+		/// The number of blocks over which the prices valuing the holdings of the vaults are
+		/// averaged.
+		#[pallet::constant]
+		type PriceWindow: Get<BlockNumberFor<Self>>;
+
+		/// The amount of shares minted to the account of a vault on its first deposit, deducted
+		/// from the shares of the depositor.
+		///
+		/// These shares are never redeemed, and make the value of a share too costly to inflate.
+		#[pallet::constant]
+		type MintMinShares: Get<Self::Balance>;
+
+		/// The origin with permission to create vaults, returning the vault's manager.
+		type CreateVaultOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// Means for associating a cost with the on-chain storage of a vault, which is incurred
+		/// by the vault's creator.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+
+		/// The maximum number of assets held by a vault, including its denomination asset.
+		#[pallet::constant]
+		type MaxAssets: Get<u32>;
+
+		/// The highest yearly management fee of a vault.
+		#[pallet::constant]
+		type MaxManagementFee: Get<Permill>;
+
+		/// The highest performance fee of a vault.
+		#[pallet::constant]
+		type MaxPerformanceFee: Get<Permill>;
+
+		/// The number of blocks per year, over which the management fee is accrued.
+		#[pallet::constant]
+		type BlocksPerYear: Get<BlockNumberFor<Self>>;
+
+		/// The number of blocks between a withdrawal request and its claim.
+		#[pallet::constant]
+		type WithdrawalDelay: Get<BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::AssetKind, Self::Balance>;
+	}
+
+	/// The existing vaults.
+	#[pallet::storage]
+	pub type Vaults<T: Config> = StorageMap<_, Blake2_128Concat, VaultId, Vault<T>>;
+
+	/// The cost associated with storing a vault, and the account that incurred it.
+	///
+	/// This storage is separate from [`Vaults`] to maintain the vault's `MaxEncodedLen` and
+	/// `TypeInfo` independent of the [`Config::Consideration`] implementation.
+	#[pallet::storage]
+	pub type VaultCost<T: Config> =
+		StorageMap<_, Blake2_128Concat, VaultId, (T::AccountId, T::Consideration)>;
+
+	/// The id of the next vault created.
+	#[pallet::storage]
+	pub type NextVaultId<T: Config> = StorageValue<_, VaultId, ValueQuery>;
+
+	/// The id of the share asset of the next vault created.
+	#[pallet::storage]
+	pub type NextShareAssetId<T: Config> = StorageValue<_, T::ShareAssetId, OptionQuery>;
+
+	/// The pending withdrawal requests of each vault.
+	#[pallet::storage]
+	pub type WithdrawalRequests<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VaultId,
+		Twox64Concat,
+		RequestId,
+		WithdrawalRequest<T>,
+	>;
+
+	/// The id of the next withdrawal request of each vault.
+	#[pallet::storage]
+	pub type NextRequestId<T: Config> =
+		StorageMap<_, Blake2_128Concat, VaultId, RequestId, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A vault was created.
+		VaultCreated {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The vault's manager.
+			manager: T::AccountId,
+			/// The account holding the vault's assets.
+			account: T::AccountId,
+			/// The assets the vault may hold, starting with its denomination asset.
+			assets: BoundedVec<T::AssetKind, T::MaxAssets>,
+			/// The asset of the vault's shares.
+			share_asset: T::ShareAssetId,
+		},
+		/// The denomination asset was deposited to a vault.
+		Deposited {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The depositing account.
+			who: T::AccountId,
+			/// The amount of the denomination asset deposited.
+			amount: T::Balance,
+			/// The amount of shares minted for the deposit.
+			shares: T::Balance,
+		},
+		/// A withdrawal was requested.
+		WithdrawalRequested {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The request's id.
+			request_id: RequestId,
+			/// The withdrawing account.
+			who: T::AccountId,
+			/// The amount of shares put on hold.
+			shares: T::Balance,
+			/// The block from which the withdrawal can be claimed.
+			claimable_at: BlockNumberFor<T>,
+		},
+		/// A withdrawal was claimed.
+		WithdrawalClaimed {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The request's id.
+			request_id: RequestId,
+			/// The withdrawing account.
+			who: T::AccountId,
+			/// The amount of shares redeemed.
+			shares: T::Balance,
+			/// The amount of each asset paid out.
+			amounts: Vec<(T::AssetKind, T::Balance)>,
+		},
+		/// The holdings of a vault were swapped by its manager.
+		Rebalanced {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The swap path.
+			path: Vec<T::AssetKind>,
+			/// The amount of `path[0]` swapped.
+			amount_in: T::Balance,
+			/// The amount of `path[last]` received.
+			amount_out: T::Balance,
+		},
+		/// Fees were paid to the manager of a vault.
+		FeesAccrued {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The amount of shares minted for the management fee.
+			management_shares: T::Balance,
+			/// The amount of shares minted for the performance fee.
+			performance_shares: T::Balance,
+		},
+		/// The fees of a vault were changed.
+		FeesSet {
+			/// The vault's id.
+			vault_id: VaultId,
+			/// The new yearly management fee.
+			management_fee: Permill,
+			/// The new performance fee.
+			performance_fee: Permill,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The vault does not exist.
+		UnknownVault,
+		/// The assets of the vault are duplicated or too many.
+		InvalidAssets,
+		/// A fee is above its maximum.
+		FeeTooHigh,
+		/// The amount is zero.
+		ZeroAmount,
+		/// The deposit is too small to mint any share.
+		ZeroShares,
+		/// The deposit would mint fewer shares than the minimum set.
+		SlippageExceeded,
+		/// The holdings of the vault can not be valued in its denomination asset.
+		ValuationFailed,
+		/// The withdrawal request does not exist.
+		UnknownRequest,
+		/// The withdrawal can not be claimed yet.
+		WithdrawalNotReady,
+		/// The swap path does not start and end with assets of the vault.
+		InvalidPath,
+		/// The id of the next share asset is not available.
+		IncorrectShareAssetId,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a vault denominated in `denomination`, which may also hold `assets`, with the
+		/// caller as its manager.
+		///
+		/// A new share asset is created for the vault, and the storage of the vault is paid for
+		/// by the caller with [`Config::Consideration`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create_vault(assets.len() as u32 + 1))]
+		pub fn create_vault(
+			origin: OriginFor<T>,
+			denomination: Box<T::AssetKind>,
+			assets: Vec<Box<T::AssetKind>>,
+			management_fee: Permill,
+			performance_fee: Permill,
+		) -> DispatchResult {
+			let manager = T::CreateVaultOrigin::ensure_origin(origin)?;
+			Self::ensure_fees(management_fee, performance_fee)?;
+
+			let mut all_assets = Vec::with_capacity(assets.len() + 1);
+			for asset in core::iter::once(denomination).chain(assets).map(|asset| *asset) {
+				ensure!(!all_assets.contains(&asset), Error::<T>::InvalidAssets);
+				all_assets.push(asset);
+			}
+			let assets: BoundedVec<_, T::MaxAssets> =
+				all_assets.try_into().map_err(|_| Error::<T>::InvalidAssets)?;
+
+			let vault_id = NextVaultId::<T>::get();
+			let account = Self::vault_account_id(vault_id);
+			for asset in assets.iter() {
+				if T::Assets::should_touch(asset.clone(), &account) {
+					T::Assets::touch(asset.clone(), &account, &manager)?;
+				}
+			}
+
+			let share_asset = NextShareAssetId::<T>::get()
+				.or(T::ShareAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectShareAssetId)?;
+			let next_share_asset =
+				share_asset.increment().ok_or(Error::<T>::IncorrectShareAssetId)?;
+			NextShareAssetId::<T>::set(Some(next_share_asset));
+
+			T::ShareAssets::create(share_asset.clone(), account.clone(), false, One::one())?;
+			// The vault's account receives the shares minted on the first deposit, and the manager
+			// the fees.
+			for who in [&account, &manager] {
+				if T::ShareAssets::should_touch(share_asset.clone(), who) {
+					T::ShareAssets::touch(share_asset.clone(), who, &manager)?;
+				}
+			}
+
+			let footprint = Self::vault_creation_footprint();
+			let cost = T::Consideration::new(&manager, footprint)?;
+			VaultCost::<T>::insert(vault_id, (manager.clone(), cost));
+			NextVaultId::<T>::put(vault_id.ensure_add(1)?);
+
+			Self::deposit_event(Event::VaultCreated {
+				vault_id,
+				manager: manager.clone(),
+				account: account.clone(),
+				assets: assets.clone(),
+				share_asset: share_asset.clone(),
+			});
+			Vaults::<T>::insert(
+				vault_id,
+				Vault {
+					manager,
+					account,
+					assets,
+					share_asset,
+					management_fee,
+					performance_fee,
+					high_water_mark: FixedU128::one(),
+					last_fee_accrual: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Ok(())
+		}
+
+		/// Deposit `amount` of the denomination asset of a vault, minting at least `min_shares`
+		/// shares of the vault to the caller.
+		///
+		/// The first deposit mints one share per unit deposited, of which
+		/// [`Config::MintMinShares`] are minted to the vault's account. Other deposits mint shares
+		/// in proportion to the vault's net asset value.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::deposit(T::MaxAssets::get()))]
+		pub fn deposit(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			amount: T::Balance,
+			min_shares: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let mut vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+			Self::do_accrue_fees(vault_id, &mut vault)?;
+
+			let supply = T::ShareAssets::total_issuance(vault.share_asset.clone());
+			let shares = if supply.is_zero() {
+				vault.high_water_mark = FixedU128::one();
+				let dead_shares = T::MintMinShares::get();
+				if !dead_shares.is_zero() {
+					T::ShareAssets::mint_into(
+						vault.share_asset.clone(),
+						&vault.account,
+						dead_shares,
+					)?;
+				}
+				amount.saturating_sub(dead_shares)
+			} else {
+				let nav = Self::net_asset_value(&vault)?;
+				// Shares of a vault without value can't be priced.
+				ensure!(!nav.is_zero(), Error::<T>::ValuationFailed);
+				amount
+					.multiply_rational(supply, nav, Rounding::Down)
+					.ok_or(ArithmeticError::Overflow)?
+			};
+			ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+			ensure!(shares >= min_shares, Error::<T>::SlippageExceeded);
+
+			T::Assets::transfer(
+				vault.denomination().clone(),
+				&who,
+				&vault.account,
+				amount,
+				Preservation::Preserve,
+			)?;
+			T::ShareAssets::mint_into(vault.share_asset.clone(), &who, shares)?;
+			Vaults::<T>::insert(vault_id, vault);
+
+			Self::deposit_event(Event::Deposited { vault_id, who, amount, shares });
+			Ok(())
+		}
+
+		/// Request the withdrawal of `shares` of a vault, putting them on hold until the
+		/// withdrawal is claimed.
+		///
+		/// The withdrawal can be claimed after [`Config::WithdrawalDelay`] blocks. The minimum
+		/// balance of the share asset can't be put on hold.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::request_withdrawal())]
+		pub fn request_withdrawal(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			shares: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!shares.is_zero(), Error::<T>::ZeroAmount);
+			let vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+
+			T::ShareAssetsHolder::hold(
+				vault.share_asset,
+				&HoldReason::Withdrawal.into(),
+				&who,
+				shares,
+			)?;
+
+			let request_id = NextRequestId::<T>::get(vault_id);
+			NextRequestId::<T>::insert(vault_id, request_id.ensure_add(1)?);
+			let claimable_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::WithdrawalDelay::get());
+			WithdrawalRequests::<T>::insert(
+				vault_id,
+				request_id,
+				WithdrawalRequest { owner: who.clone(), shares, claimable_at },
+			);
+
+			Self::deposit_event(Event::WithdrawalRequested {
+				vault_id,
+				request_id,
+				who,
+				shares,
+				claimable_at,
+			});
+			Ok(())
+		}
+
+		/// Claim a withdrawal request on behalf of its owner, redeeming its shares for the same
+		/// fraction of every asset held by the vault.
+		///
+		/// Fails if an amount can not be deposited to the owner, such as an amount below the
+		/// minimum balance of an asset the owner doesn't hold, in which case the request remains
+		/// claimable.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::claim_withdrawal(T::MaxAssets::get()))]
+		pub fn claim_withdrawal(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			request_id: RequestId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let request = WithdrawalRequests::<T>::get(vault_id, request_id)
+				.ok_or(Error::<T>::UnknownRequest)?;
+			ensure!(
+				request.claimable_at <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::WithdrawalNotReady
+			);
+			let mut vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+			Self::do_accrue_fees(vault_id, &mut vault)?;
+
+			let supply = T::ShareAssets::total_issuance(vault.share_asset.clone());
+			let mut amounts = Vec::with_capacity(vault.assets.len());
+			for asset in vault.assets.iter() {
+				let amount = Self::holding(&vault, asset)
+					.multiply_rational(request.shares, supply, Rounding::Down)
+					.ok_or(ArithmeticError::Overflow)?;
+				if !amount.is_zero() {
+					T::Assets::can_deposit(
+						asset.clone(),
+						&request.owner,
+						amount,
+						Provenance::Extant,
+					)
+					.into_result()?;
+					T::Assets::transfer(
+						asset.clone(),
+						&vault.account,
+						&request.owner,
+						amount,
+						Preservation::Preserve,
+					)?;
+				}
+				amounts.push((asset.clone(), amount));
+			}
+
+			T::ShareAssetsHolder::burn_held(
+				vault.share_asset.clone(),
+				&HoldReason::Withdrawal.into(),
+				&request.owner,
+				request.shares,
+				Precision::Exact,
+				Fortitude::Polite,
+			)?;
+			WithdrawalRequests::<T>::remove(vault_id, request_id);
+			Vaults::<T>::insert(vault_id, vault);
+
+			Self::deposit_event(Event::WithdrawalClaimed {
+				vault_id,
+				request_id,
+				who: request.owner,
+				shares: request.shares,
+				amounts,
+			});
+			Ok(())
+		}
+
+		/// Swap `amount_in` of `path[0]` held by a vault for at least `amount_out_min` of
+		/// `path[last]`.
+		///
+		/// Only callable by the manager of the vault. Both ends of the path must be assets of the
+		/// vault.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::rebalance())]
+		pub fn rebalance(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			path: Vec<Box<T::AssetKind>>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+			ensure!(who == vault.manager, BadOrigin);
+			ensure!(!amount_out_min.is_zero(), Error::<T>::ZeroAmount);
+
+			let path: Vec<_> = path.into_iter().map(|asset| *asset).collect();
+			ensure!(
+				path.len() >= 2 &&
+					path.first().is_some_and(|asset| vault.assets.contains(asset)) &&
+					path.last().is_some_and(|asset| vault.assets.contains(asset)),
+				Error::<T>::InvalidPath
+			);
+
+			let amount_out = T::Swap::swap_exact_tokens_for_tokens(
+				vault.account.clone(),
+				path.clone(),
+				amount_in,
+				Some(amount_out_min),
+				vault.account,
+				true,
+			)?;
+
+			Self::deposit_event(Event::Rebalanced { vault_id, path, amount_in, amount_out });
+			Ok(())
+		}
+
+		/// Accrue the fees of a vault, minting shares to its manager.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::accrue_fees(T::MaxAssets::get()))]
+		pub fn accrue_fees(origin: OriginFor<T>, vault_id: VaultId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+			Self::do_accrue_fees(vault_id, &mut vault)?;
+			Vaults::<T>::insert(vault_id, vault);
+			Ok(())
+		}
+
+		/// Set the fees of a vault, after accruing the fees due so far.
+		///
+		/// Only callable by the manager of the vault.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_fees(T::MaxAssets::get()))]
+		pub fn set_fees(
+			origin: OriginFor<T>,
+			vault_id: VaultId,
+			management_fee: Permill,
+			performance_fee: Permill,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut vault = Vaults::<T>::get(vault_id).ok_or(Error::<T>::UnknownVault)?;
+			ensure!(who == vault.manager, BadOrigin);
+			Self::ensure_fees(management_fee, performance_fee)?;
+
+			Self::do_accrue_fees(vault_id, &mut vault)?;
+			vault.management_fee = management_fee;
+			vault.performance_fee = performance_fee;
+			Vaults::<T>::insert(vault_id, vault);
+
+			Self::deposit_event(Event::FeesSet { vault_id, management_fee, performance_fee });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the assets of a vault.
+		pub fn vault_account_id(vault_id: VaultId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(vault_id)
+		}
+
+		/// The storage footprint of a vault.
+		pub fn vault_creation_footprint() -> Footprint {
+			Footprint::from_mel::<(VaultId, Vault<T>)>()
+		}
+
+		/// The amount of `asset` held by `vault`, which can be withdrawn from its account.
+		pub fn holding(vault: &Vault<T>, asset: &T::AssetKind) -> T::Balance {
+			T::Assets::reducible_balance(
+				asset.clone(),
+				&vault.account,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			)
+		}
+
+		/// The net asset value of `vault`, in its denomination asset.
+		///
+		/// Holdings in other assets are valued in the denomination asset at the time-weighted
+		/// average price of their pool over the last [`Config::PriceWindow`] blocks.
+		pub fn net_asset_value(vault: &Vault<T>) -> Result<T::Balance, DispatchError> {
+			vault.assets.iter().try_fold(Zero::zero(), |nav: T::Balance, asset| {
+				let holding = Self::holding(vault, asset);
+				let value = if holding.is_zero() || asset == vault.denomination() {
+					holding
+				} else {
+					T::Swap::quote_price_time_weighted(
+						asset.clone(),
+						vault.denomination().clone(),
+						holding,
+						T::PriceWindow::get(),
+					)
+					.ok_or(Error::<T>::ValuationFailed)?
+				};
+				Ok(nav.ensure_add(value)?)
+			})
+		}
+
+		/// The net asset value of a vault, and its amount of shares.
+		pub fn vault_value(vault_id: VaultId) -> Option<(T::Balance, T::Balance)> {
+			let vault = Vaults::<T>::get(vault_id)?;
+			let nav = Self::net_asset_value(&vault).ok()?;
+			Some((nav, T::ShareAssets::total_issuance(vault.share_asset)))
+		}
+
+		fn ensure_fees(management_fee: Permill, performance_fee: Permill) -> DispatchResult {
+			ensure!(
+				management_fee <= T::MaxManagementFee::get() &&
+					performance_fee <= T::MaxPerformanceFee::get(),
+				Error::<T>::FeeTooHigh
+			);
+			Ok(())
+		}
+
+		/// Mint the fees due since the last accrual to the manager of `vault`.
+		///
+		/// The performance fee is deferred while the holdings of the vault can not be valued.
+		fn do_accrue_fees(vault_id: VaultId, vault: &mut Vault<T>) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			let elapsed = now.saturating_sub(vault.last_fee_accrual);
+			vault.last_fee_accrual = now;
+
+			let mut supply = T::ShareAssets::total_issuance(vault.share_asset.clone());
+			if supply.is_zero() {
+				return Ok(());
+			}
+
+			let management_shares = vault
+				.management_fee
+				.mul_floor(supply)
+				.multiply_rational(
+					elapsed.saturated_into::<u32>().into(),
+					T::BlocksPerYear::get().saturated_into::<u32>().into(),
+					Rounding::Down,
+				)
+				.unwrap_or_default();
+			if !management_shares.is_zero() {
+				T::ShareAssets::mint_into(
+					vault.share_asset.clone(),
+					&vault.manager,
+					management_shares,
+				)?;
+				supply = supply.ensure_add(management_shares)?;
+			}
+
+			let mut performance_shares = Zero::zero();
+			if let Ok(nav) = Self::net_asset_value(vault) {
+				let nav_per_share = FixedU128::checked_from_rational(nav, supply)
+					.ok_or(ArithmeticError::Overflow)?;
+				if nav_per_share > vault.high_water_mark {
+					let gain = (nav_per_share - vault.high_water_mark).saturating_mul_int(supply);
+					let fee = vault.performance_fee.mul_floor(gain);
+					// The shares worth `fee` once minted.
+					performance_shares = fee
+						.multiply_rational(supply, nav.saturating_sub(fee), Rounding::Down)
+						.unwrap_or_default();
+					if !performance_shares.is_zero() {
+						T::ShareAssets::mint_into(
+							vault.share_asset.clone(),
+							&vault.manager,
+							performance_shares,
+						)?;
+						supply = supply.ensure_add(performance_shares)?;
+					}
+					vault.high_water_mark =
+						FixedU128::checked_from_rational(nav, supply).unwrap_or(nav_per_share);
+				}
+			}
+
+			if !management_shares.is_zero() || !performance_shares.is_zero() {
+				Self::deposit_event(Event::FeesAccrued {
+					vault_id,
+					management_shares,
+					performance_shares,
+				});
+			}
+			Ok(())
+		}
+	}
+}
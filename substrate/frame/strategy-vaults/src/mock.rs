@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Test environment for Strategy Vaults pallet.
+
+use super::*;
+use crate as pallet_strategy_vaults;
+use core::default::Default;
+use frame_support::{
+	assert_ok, construct_runtime, derive_impl,
+	instances::{Instance1, Instance2, Instance3},
+	ord_parameter_types, parameter_types,
+	traits::{
+		tokens::{
+			fungible::{HoldConsideration, NativeFromLeft, NativeOrWithId, UnionOf},
+			imbalance::ResolveAssetTo,
+		},
+		AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, LinearStoragePrice,
+	},
+};
+use frame_system::{EnsureSigned, EnsureSignedBy};
+use pallet_asset_conversion::{AccountIdConverter, Ascending, Chain, WithFirstAsset};
+use sp_runtime::{
+	traits::{AccountIdConversion, IdentityLookup},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets::<Instance1>,
+		PoolAssets: pallet_assets::<Instance2>,
+		VaultShares: pallet_assets::<Instance3>,
+		VaultSharesHolder: pallet_assets_holder::<Instance3>,
+		AssetConversion: pallet_asset_conversion,
+		StrategyVaults: pallet_strategy_vaults,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountId = u128;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = u128;
+	type ExistentialDeposit = ConstU128<100>;
+	type AccountStore = System;
+}
+
+impl pallet_assets::Config<Instance1> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<10>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Holder = ();
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type CallbackHandle = ();
+	pallet_assets::runtime_benchmarks_enabled! {
+		type BenchmarkHelper = ();
+	}
+}
+
+impl pallet_assets::Config<Instance2> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin =
+		AsEnsureOriginWithArg<EnsureSignedBy<AssetConversionOrigin, Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Holder = ();
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type CallbackHandle = ();
+	pallet_assets::runtime_benchmarks_enabled! {
+		type BenchmarkHelper = ();
+	}
+}
+
+impl pallet_assets::Config<Instance3> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u128;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSignedBy<VaultsOrigin, Self::AccountId>>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Holder = VaultSharesHolder;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+	type CallbackHandle = ();
+	pallet_assets::runtime_benchmarks_enabled! {
+		type BenchmarkHelper = ();
+	}
+}
+
+impl pallet_assets_holder::Config<Instance3> for Test {
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	pub const AssetConversionPalletId: PalletId = PalletId(*b"py/ascon");
+	pub const StrategyVaultsPalletId: PalletId = PalletId(*b"py/vault");
+	pub const Native: NativeOrWithId<u32> = NativeOrWithId::Native;
+	pub storage LiquidityWithdrawalFee: Permill = Permill::from_percent(0);
+	pub const MaxManagementFee: Permill = Permill::from_percent(5);
+	pub const MaxPerformanceFee: Permill = Permill::from_percent(30);
+	pub const VaultCreationHoldReason: RuntimeHoldReason =
+		RuntimeHoldReason::StrategyVaults(pallet_strategy_vaults::HoldReason::VaultCreation);
+}
+
+ord_parameter_types! {
+	pub const AssetConversionOrigin: u128 = AccountIdConversion::<u128>::into_account_truncating(&AssetConversionPalletId::get());
+	pub const VaultsOrigin: u128 = AccountIdConversion::<u128>::into_account_truncating(&StrategyVaultsPalletId::get());
+}
+
+pub type NativeAndAssets = UnionOf<Balances, Assets, NativeFromLeft, NativeOrWithId<u32>, u128>;
+pub type PoolIdToAccountId =
+	AccountIdConverter<AssetConversionPalletId, (NativeOrWithId<u32>, NativeOrWithId<u32>)>;
+pub type AscendingLocator = Ascending<u128, NativeOrWithId<u32>, PoolIdToAccountId>;
+pub type WithFirstAssetLocator =
+	WithFirstAsset<Native, u128, NativeOrWithId<u32>, PoolIdToAccountId>;
+
+impl pallet_asset_conversion::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = <Self as pallet_balances::Config>::Balance;
+	type HigherPrecisionBalance = sp_core::U256;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type PoolId = (Self::AssetKind, Self::AssetKind);
+	type PoolLocator = Chain<WithFirstAssetLocator, AscendingLocator>;
+	type PoolAssetId = u32;
+	type PoolAssets = PoolAssets;
+	type PoolSetupFee = ConstU128<100>;
+	type PoolSetupFeeAsset = Native;
+	type PoolSetupFeeTarget = ResolveAssetTo<AssetConversionOrigin, Self::Assets>;
+	type PalletId = AssetConversionPalletId;
+	type WeightInfo = ();
+	type LPFee = ConstU32<3>;
+	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
+	type MaxSwapPathLength = ConstU32<4>;
+	type MaxPriceObservations = ConstU32<8>;
+	type MintMinLiquidity = ConstU128<100>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct VaultsBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<u128, NativeOrWithId<u32>, u128> for VaultsBenchmarkHelper {
+	fn setup_assets(who: &u128, n: u32) -> (Vec<NativeOrWithId<u32>>, u128) {
+		use frame_support::traits::{fungible::Mutate as _, fungibles::Mutate};
+		assert_ok!(Balances::mint_into(who, 10_000));
+		// The genesis pools may have been wiped by the benchmark tests along with the storage of
+		// a previous run.
+		for id in 2..=n {
+			let (asset1, asset2) = (NativeOrWithId::WithId(1), NativeOrWithId::WithId(id));
+			if AssetConversion::get_reserves(asset1.clone(), asset2.clone()).is_ok() {
+				continue
+			}
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				Box::new(asset1.clone()),
+				Box::new(asset2.clone()),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				Box::new(asset1),
+				Box::new(asset2),
+				100_000,
+				100_000,
+				1,
+				1,
+				LIQUIDITY_PROVIDER,
+			));
+		}
+		let assets = (1..=n).map(NativeOrWithId::WithId).collect::<Vec<_>>();
+		for asset in 1..=n {
+			assert_ok!(Assets::mint_into(asset, who, 10_000));
+		}
+		(assets, 10_000)
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type PalletId = StrategyVaultsPalletId;
+	type Balance = <Self as pallet_balances::Config>::Balance;
+	type AssetKind = NativeOrWithId<u32>;
+	type Assets = NativeAndAssets;
+	type ShareAssetId = u32;
+	type ShareAssets = VaultShares;
+	type ShareAssetsHolder = VaultSharesHolder;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Swap = AssetConversion;
+	type PriceWindow = ConstU64<1>;
+	type MintMinShares = ConstU128<100>;
+	type CreateVaultOrigin = EnsureSigned<u128>;
+	type Consideration = HoldConsideration<
+		u128,
+		Balances,
+		VaultCreationHoldReason,
+		LinearStoragePrice<ConstU128<100>, ConstU128<0>, u128>,
+	>;
+	type MaxAssets = ConstU32<4>;
+	type MaxManagementFee = MaxManagementFee;
+	type MaxPerformanceFee = MaxPerformanceFee;
+	type BlocksPerYear = ConstU64<1_000>;
+	type WithdrawalDelay = ConstU64<10>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = VaultsBenchmarkHelper;
+}
+
+/// The account providing the liquidity of the genesis pools.
+pub(crate) const LIQUIDITY_PROVIDER: u128 = 100;
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	pallet_assets::GenesisConfig::<Test, Instance1> {
+		assets: (1..=4).map(|id| (id, LIQUIDITY_PROVIDER, true, 1)).collect(),
+		metadata: vec![],
+		accounts: (1..=4)
+			.map(|id| (id, LIQUIDITY_PROVIDER, 1_000_000))
+			.chain([(1, 2, 10_000), (1, 3, 10_000)])
+			.collect(),
+		next_asset_id: None,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000), (2, 10_000), (3, 10_000), (LIQUIDITY_PROVIDER, 1_000_000)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		// Pools of asset `1` and each other asset, with a price of one since the genesis block.
+		for id in 2..=4 {
+			let (asset1, asset2) =
+				(Box::new(NativeOrWithId::WithId(1)), Box::new(NativeOrWithId::WithId(id)));
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				asset1.clone(),
+				asset2.clone(),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+				asset1,
+				asset2,
+				100_000,
+				100_000,
+				1,
+				1,
+				LIQUIDITY_PROVIDER,
+			));
+		}
+		System::set_block_number(1);
+		System::reset_events();
+	});
+	ext
+}
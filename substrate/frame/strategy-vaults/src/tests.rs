@@ -0,0 +1,523 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold, NativeOrWithId},
+		fungibles::{Inspect, InspectHold as _},
+		Get,
+	},
+};
+use sp_runtime::{traits::BadOrigin, TokenError};
+
+const DENOMINATION: NativeOrWithId<u32> = NativeOrWithId::WithId(1);
+const OTHER: NativeOrWithId<u32> = NativeOrWithId::WithId(2);
+const MANAGER: u128 = 1;
+
+fn events() -> Vec<Event<Test>> {
+	let result = System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			RuntimeEvent::StrategyVaults(inner) => Some(inner),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	result
+}
+
+/// Creates a vault of `DENOMINATION` and `OTHER`, with a 2% management fee and a 20%
+/// performance fee.
+fn create_vault() -> VaultId {
+	assert_ok!(StrategyVaults::create_vault(
+		RuntimeOrigin::signed(MANAGER),
+		Box::new(DENOMINATION),
+		vec![Box::new(OTHER)],
+		Permill::from_percent(2),
+		Permill::from_percent(20),
+	));
+	System::reset_events();
+	NextVaultId::<Test>::get() - 1
+}
+
+fn vault(vault_id: VaultId) -> Vault<Test> {
+	Vaults::<Test>::get(vault_id).unwrap()
+}
+
+fn shares(vault_id: VaultId, who: u128) -> u128 {
+	VaultShares::balance(vault(vault_id).share_asset, who)
+}
+
+fn supply(vault_id: VaultId) -> u128 {
+	VaultShares::total_issuance(vault(vault_id).share_asset)
+}
+
+fn held_shares(vault_id: VaultId, who: u128) -> u128 {
+	VaultSharesHolder::balance_on_hold(
+		vault(vault_id).share_asset,
+		&HoldReason::Withdrawal.into(),
+		&who,
+	)
+}
+
+fn nav(vault_id: VaultId) -> u128 {
+	StrategyVaults::net_asset_value(&vault(vault_id)).unwrap()
+}
+
+#[test]
+fn create_vault_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(StrategyVaults::create_vault(
+			RuntimeOrigin::signed(MANAGER),
+			Box::new(DENOMINATION),
+			vec![Box::new(OTHER)],
+			Permill::from_percent(2),
+			Permill::from_percent(20),
+		));
+
+		let account = StrategyVaults::vault_account_id(0);
+		let assets: BoundedVec<_, _> = vec![DENOMINATION, OTHER].try_into().unwrap();
+		assert_eq!(
+			Vaults::<Test>::get(0),
+			Some(Vault {
+				manager: MANAGER,
+				account,
+				assets: assets.clone(),
+				share_asset: 0,
+				management_fee: Permill::from_percent(2),
+				performance_fee: Permill::from_percent(20),
+				high_water_mark: FixedU128::one(),
+				last_fee_accrual: 1,
+			})
+		);
+		assert_eq!(
+			events(),
+			[Event::<Test>::VaultCreated {
+				vault_id: 0,
+				manager: MANAGER,
+				account,
+				assets,
+				share_asset: 0,
+			}]
+		);
+		assert_eq!(NextVaultId::<Test>::get(), 1);
+		assert_eq!(NextShareAssetId::<Test>::get(), Some(1));
+		assert!(VaultShares::asset_exists(0));
+		assert_eq!(Balances::balance_on_hold(&VaultCreationHoldReason::get(), &MANAGER), 100);
+	});
+}
+
+#[test]
+fn create_vault_fails_for_invalid_input() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(MANAGER);
+		let fee = Permill::from_percent(1);
+
+		assert_noop!(
+			StrategyVaults::create_vault(
+				origin.clone(),
+				Box::new(DENOMINATION),
+				vec![Box::new(OTHER), Box::new(DENOMINATION)],
+				fee,
+				fee,
+			),
+			Error::<Test>::InvalidAssets
+		);
+		assert_noop!(
+			StrategyVaults::create_vault(
+				origin.clone(),
+				Box::new(DENOMINATION),
+				(2..=5).map(|id| Box::new(NativeOrWithId::WithId(id))).collect(),
+				fee,
+				fee,
+			),
+			Error::<Test>::InvalidAssets
+		);
+		assert_noop!(
+			StrategyVaults::create_vault(
+				origin.clone(),
+				Box::new(DENOMINATION),
+				vec![],
+				Permill::from_percent(6),
+				fee,
+			),
+			Error::<Test>::FeeTooHigh
+		);
+		assert_noop!(
+			StrategyVaults::create_vault(
+				origin,
+				Box::new(DENOMINATION),
+				vec![],
+				fee,
+				Permill::from_percent(31),
+			),
+			Error::<Test>::FeeTooHigh
+		);
+	});
+}
+
+#[test]
+fn deposit_mints_shares_by_net_asset_value() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+
+		// The first deposit mints one share per unit, of which the minimum is kept by the vault.
+		let dead_shares = <Test as Config>::MintMinShares::get();
+		assert_noop!(
+			StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, dead_shares, 0),
+			Error::<Test>::ZeroShares
+		);
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 900));
+		assert_eq!(
+			events(),
+			[Event::<Test>::Deposited { vault_id, who: 2, amount: 1_000, shares: 900 }]
+		);
+		assert_eq!(shares(vault_id, 2), 900);
+		assert_eq!(shares(vault_id, StrategyVaults::vault_account_id(vault_id)), dead_shares);
+		assert_eq!(supply(vault_id), 1_000);
+		assert_eq!(Assets::balance(1, StrategyVaults::vault_account_id(vault_id)), 1_000);
+
+		// The next deposits mint shares in proportion to the net asset value.
+		let expected = 1_000 * supply(vault_id) / nav(vault_id);
+		assert_noop!(
+			StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 1_000, expected + 1),
+			Error::<Test>::SlippageExceeded
+		);
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 1_000, expected));
+		assert_eq!(shares(vault_id, 3), expected);
+
+		assert_noop!(
+			StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 0, 0),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			StrategyVaults::deposit(RuntimeOrigin::signed(3), 1, 1_000, 0),
+			Error::<Test>::UnknownVault
+		);
+	});
+}
+
+#[test]
+fn deposit_fails_without_net_asset_value() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+
+		// The holdings of a frozen vault account can't be withdrawn, and are worth nothing.
+		let account = StrategyVaults::vault_account_id(vault_id);
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 1, account));
+		assert_eq!(nav(vault_id), 0);
+		assert_noop!(
+			StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 1_000, 0),
+			Error::<Test>::ValuationFailed
+		);
+	});
+}
+
+#[test]
+fn rebalance_swaps_holdings_valued_by_quotes() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		events();
+
+		let path = || vec![Box::new(DENOMINATION), Box::new(OTHER)];
+		assert_noop!(
+			StrategyVaults::rebalance(RuntimeOrigin::signed(2), vault_id, path(), 500, 1),
+			BadOrigin
+		);
+		assert_noop!(
+			StrategyVaults::rebalance(
+				RuntimeOrigin::signed(MANAGER),
+				vault_id,
+				vec![Box::new(DENOMINATION), Box::new(NativeOrWithId::WithId(3))],
+				500,
+				1
+			),
+			Error::<Test>::InvalidPath
+		);
+
+		assert_ok!(StrategyVaults::rebalance(
+			RuntimeOrigin::signed(MANAGER),
+			vault_id,
+			path(),
+			500,
+			1
+		));
+		let account = StrategyVaults::vault_account_id(vault_id);
+		let amount_out = Assets::balance(2, account);
+		assert_eq!(
+			events(),
+			[Event::<Test>::Rebalanced {
+				vault_id,
+				path: vec![DENOMINATION, OTHER],
+				amount_in: 500,
+				amount_out,
+			}]
+		);
+
+		// The other asset is valued at its time-weighted price in the denomination asset.
+		let vault = vault(vault_id);
+		let other_value = AssetConversion::quote_price_time_weighted(
+			OTHER,
+			DENOMINATION,
+			StrategyVaults::holding(&vault, &OTHER),
+			<Test as Config>::PriceWindow::get(),
+		)
+		.unwrap();
+		assert_eq!(
+			StrategyVaults::net_asset_value(&vault),
+			Ok(StrategyVaults::holding(&vault, &DENOMINATION) + other_value)
+		);
+		assert_eq!(StrategyVaults::vault_value(vault_id), Some((nav(vault_id), 1_000)));
+	});
+}
+
+#[test]
+fn net_asset_value_ignores_price_moves_within_the_window() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		assert_ok!(StrategyVaults::rebalance(
+			RuntimeOrigin::signed(MANAGER),
+			vault_id,
+			vec![Box::new(DENOMINATION), Box::new(OTHER)],
+			500,
+			1
+		));
+		let before = nav(vault_id);
+
+		// Pumping the price of the other asset doesn't change the value of the vault in the
+		// same block.
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(LIQUIDITY_PROVIDER),
+			vec![Box::new(DENOMINATION), Box::new(OTHER)],
+			50_000,
+			1,
+			LIQUIDITY_PROVIDER,
+			true,
+		));
+		assert_eq!(nav(vault_id), before);
+		let expected = 1_000 * supply(vault_id) / before;
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 1_000, expected));
+		assert_eq!(shares(vault_id, 3), expected);
+
+		// Once the window has passed, the new price is used.
+		System::set_block_number(3);
+		assert!(nav(vault_id) > before + 1_000);
+	});
+}
+
+#[test]
+fn management_fee_accrues_over_time() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		events();
+
+		// Half of the 2% yearly fee.
+		System::set_block_number(501);
+		assert_ok!(StrategyVaults::accrue_fees(RuntimeOrigin::signed(3), vault_id));
+		assert_eq!(
+			events(),
+			[Event::<Test>::FeesAccrued { vault_id, management_shares: 10, performance_shares: 0 }]
+		);
+		assert_eq!(shares(vault_id, MANAGER), 10);
+		assert_eq!(vault(vault_id).last_fee_accrual, 501);
+
+		// Nothing more is due in the same block.
+		assert_ok!(StrategyVaults::accrue_fees(RuntimeOrigin::signed(3), vault_id));
+		assert!(events().is_empty());
+	});
+}
+
+#[test]
+fn performance_fee_is_paid_above_high_water_mark() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		assert_eq!(nav(vault_id), 999);
+		events();
+
+		// A gain of 1_000 raises the value per share from 0.999 to 1.999.
+		assert_ok!(Assets::transfer(
+			RuntimeOrigin::signed(3),
+			1,
+			StrategyVaults::vault_account_id(vault_id),
+			1_000
+		));
+		assert_ok!(StrategyVaults::accrue_fees(RuntimeOrigin::signed(3), vault_id));
+
+		// 20% of the gain of 0.999 per share above the high-water mark of one, worth 199, is
+		// paid in shares worth 199 once minted.
+		assert_eq!(
+			events(),
+			[Event::<Test>::FeesAccrued {
+				vault_id,
+				management_shares: 0,
+				performance_shares: 110
+			}]
+		);
+		assert_eq!(shares(vault_id, MANAGER), 110);
+		assert_eq!(
+			vault(vault_id).high_water_mark,
+			FixedU128::checked_from_rational(1_999, 1_110).unwrap()
+		);
+
+		// No fee is due until the high-water mark is exceeded again.
+		assert_ok!(StrategyVaults::accrue_fees(RuntimeOrigin::signed(3), vault_id));
+		assert!(events().is_empty());
+	});
+}
+
+#[test]
+fn withdrawal_queue_pays_out_pro_rata() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		let account = StrategyVaults::vault_account_id(vault_id);
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(3), vault_id, 1_000, 1));
+		assert_ok!(StrategyVaults::rebalance(
+			RuntimeOrigin::signed(MANAGER),
+			vault_id,
+			vec![Box::new(DENOMINATION), Box::new(OTHER)],
+			600,
+			1
+		));
+		events();
+
+		// The minimum balance of the share asset can't be put on hold.
+		let withdrawn = shares(vault_id, 2) - 1;
+		assert_ok!(StrategyVaults::request_withdrawal(
+			RuntimeOrigin::signed(2),
+			vault_id,
+			withdrawn
+		));
+		assert_eq!(
+			events(),
+			[Event::<Test>::WithdrawalRequested {
+				vault_id,
+				request_id: 0,
+				who: 2,
+				shares: withdrawn,
+				claimable_at: 11,
+			}]
+		);
+		// The shares are on hold.
+		assert_eq!(shares(vault_id, 2), 1);
+		assert_eq!(held_shares(vault_id, 2), withdrawn);
+
+		assert_noop!(
+			StrategyVaults::claim_withdrawal(RuntimeOrigin::signed(3), vault_id, 0),
+			Error::<Test>::WithdrawalNotReady
+		);
+		assert_noop!(
+			StrategyVaults::claim_withdrawal(RuntimeOrigin::signed(3), vault_id, 1),
+			Error::<Test>::UnknownRequest
+		);
+
+		System::set_block_number(11);
+		let supply = supply(vault_id);
+		let vault = vault(vault_id);
+		let expected = [DENOMINATION, OTHER].map(|asset| {
+			(asset.clone(), StrategyVaults::holding(&vault, &asset) * withdrawn / supply)
+		});
+		let balances = || (Assets::balance(1, 2), Assets::balance(2, 2));
+		let before = balances();
+
+		// Anyone can claim on behalf of the owner.
+		assert_ok!(StrategyVaults::claim_withdrawal(RuntimeOrigin::signed(3), vault_id, 0));
+		assert_eq!(
+			events(),
+			[Event::<Test>::WithdrawalClaimed {
+				vault_id,
+				request_id: 0,
+				who: 2,
+				shares: withdrawn,
+				amounts: expected.to_vec(),
+			}]
+		);
+		assert_eq!(balances(), (before.0 + expected[0].1, before.1 + expected[1].1));
+		assert_eq!(held_shares(vault_id, 2), 0);
+		assert_eq!(shares(vault_id, account), <Test as Config>::MintMinShares::get());
+		assert_eq!(self::supply(vault_id), supply - withdrawn);
+		assert!(!WithdrawalRequests::<Test>::contains_key(vault_id, 0));
+	});
+}
+
+#[test]
+fn claim_fails_if_an_amount_can_not_be_paid() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		assert_ok!(StrategyVaults::deposit(RuntimeOrigin::signed(2), vault_id, 1_000, 1));
+		assert_ok!(StrategyVaults::rebalance(
+			RuntimeOrigin::signed(MANAGER),
+			vault_id,
+			vec![Box::new(DENOMINATION), Box::new(OTHER)],
+			500,
+			1
+		));
+		assert_ok!(StrategyVaults::request_withdrawal(RuntimeOrigin::signed(2), vault_id, 500));
+		System::set_block_number(11);
+
+		// The owner can't receive the other asset, the shares stay on hold.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 2, 2, 10));
+		assert_ok!(Assets::block(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 2, 2));
+		assert_noop!(
+			StrategyVaults::claim_withdrawal(RuntimeOrigin::signed(3), vault_id, 0),
+			TokenError::Blocked
+		);
+		assert_eq!(held_shares(vault_id, 2), 500);
+
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(LIQUIDITY_PROVIDER), 2, 2));
+		assert_ok!(StrategyVaults::claim_withdrawal(RuntimeOrigin::signed(3), vault_id, 0));
+		assert_eq!(held_shares(vault_id, 2), 0);
+		assert!(!WithdrawalRequests::<Test>::contains_key(vault_id, 0));
+	});
+}
+
+#[test]
+fn set_fees_works() {
+	new_test_ext().execute_with(|| {
+		let vault_id = create_vault();
+		let fee = Permill::from_percent(1);
+
+		assert_noop!(
+			StrategyVaults::set_fees(RuntimeOrigin::signed(2), vault_id, fee, fee),
+			BadOrigin
+		);
+		assert_noop!(
+			StrategyVaults::set_fees(
+				RuntimeOrigin::signed(MANAGER),
+				vault_id,
+				fee,
+				Permill::from_percent(31)
+			),
+			Error::<Test>::FeeTooHigh
+		);
+
+		assert_ok!(StrategyVaults::set_fees(RuntimeOrigin::signed(MANAGER), vault_id, fee, fee));
+		assert_eq!(
+			events(),
+			[Event::<Test>::FeesSet { vault_id, management_fee: fee, performance_fee: fee }]
+		);
+		assert_eq!(vault(vault_id).management_fee, fee);
+		assert_eq!(vault(vault_id).performance_fee, fee);
+	});
+}
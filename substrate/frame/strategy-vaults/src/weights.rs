@@ -0,0 +1,299 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_strategy_vaults`
+//!
+//! These weights are estimated from the storage accesses of each call and the weights of
+//! `pallet_assets` and `pallet_asset_conversion`. They should be regenerated with the pallet's
+//! benchmarks:
+//!
+//! frame-omni-bencher v1 benchmark pallet
+//! --runtime=target/production/wbuild/kitchensink-runtime/kitchensink_runtime.wasm
+//! --pallet=pallet_strategy_vaults --extrinsic=*
+//! --template=substrate/.maintain/frame-weight-template.hbs
+//! --output=substrate/frame/strategy-vaults/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+#![allow(dead_code)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_strategy_vaults`.
+pub trait WeightInfo {
+	fn create_vault(n: u32, ) -> Weight;
+	fn deposit(n: u32, ) -> Weight;
+	fn request_withdrawal() -> Weight;
+	fn claim_withdrawal(n: u32, ) -> Weight;
+	fn rebalance() -> Weight;
+	fn accrue_fees(n: u32, ) -> Weight;
+	fn set_fees(n: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_strategy_vaults` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `StrategyVaults::NextVaultId` (r:1 w:1)
+	/// Storage: `StrategyVaults::NextShareAssetId` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:2 w:2)
+	/// Storage: `Assets::Asset` (r:n w:n)
+	/// Storage: `Assets::Account` (r:n w:n)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `StrategyVaults::VaultCost` (r:0 w:1)
+	/// Storage: `StrategyVaults::Vaults` (r:0 w:1)
+	/// The range of component `n` is `[1, 8]`.
+	fn create_vault(n: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 8000)
+			// Standard Error: 20_000
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5200).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:1)
+	/// Storage: `Assets::Account` (r:2n w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `n` is `[1, 8]`.
+	fn deposit(n: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 8000)
+			// Standard Error: 20_000
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:0)
+	/// Storage: `VaultShares::Asset` (r:1 w:0)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::Holds` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `StrategyVaults::NextRequestId` (r:1 w:1)
+	/// Storage: `StrategyVaults::WithdrawalRequests` (r:0 w:1)
+	fn request_withdrawal() -> Weight {
+		Weight::from_parts(45_000_000, 6200)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `StrategyVaults::WithdrawalRequests` (r:1 w:1)
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::Holds` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:n)
+	/// Storage: `Assets::Account` (r:2n w:2n)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `n` is `[1, 8]`.
+	fn claim_withdrawal(n: u32, ) -> Weight {
+		Weight::from_parts(85_000_000, 8000)
+			// Standard Error: 25_000
+			.saturating_add(Weight::from_parts(35_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn rebalance() -> Weight {
+		Weight::from_parts(220_000_000, 21000)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(20_u64))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:0)
+	/// Storage: `Assets::Account` (r:2n w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// The range of component `n` is `[1, 8]`.
+	fn accrue_fees(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6200)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:0)
+	/// Storage: `Assets::Account` (r:2n w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// The range of component `n` is `[1, 8]`.
+	fn set_fees(n: u32, ) -> Weight {
+		Weight::from_parts(42_000_000, 6200)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `StrategyVaults::NextVaultId` (r:1 w:1)
+	/// Storage: `StrategyVaults::NextShareAssetId` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:2 w:2)
+	/// Storage: `Assets::Asset` (r:n w:n)
+	/// Storage: `Assets::Account` (r:n w:n)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `StrategyVaults::VaultCost` (r:0 w:1)
+	/// Storage: `StrategyVaults::Vaults` (r:0 w:1)
+	/// The range of component `n` is `[1, 8]`.
+	fn create_vault(n: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 8000)
+			// Standard Error: 20_000
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5200).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:1)
+	/// Storage: `Assets::Account` (r:2n w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `n` is `[1, 8]`.
+	fn deposit(n: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 8000)
+			// Standard Error: 20_000
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:0)
+	/// Storage: `VaultShares::Asset` (r:1 w:0)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::Holds` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `StrategyVaults::NextRequestId` (r:1 w:1)
+	/// Storage: `StrategyVaults::WithdrawalRequests` (r:0 w:1)
+	fn request_withdrawal() -> Weight {
+		Weight::from_parts(45_000_000, 6200)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `StrategyVaults::WithdrawalRequests` (r:1 w:1)
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::Holds` (r:1 w:1)
+	/// Storage: `VaultSharesHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:n)
+	/// Storage: `Assets::Account` (r:2n w:2n)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// The range of component `n` is `[1, 8]`.
+	fn claim_withdrawal(n: u32, ) -> Weight {
+		Weight::from_parts(85_000_000, 8000)
+			// Standard Error: 25_000
+			.saturating_add(Weight::from_parts(35_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:0)
+	/// Storage: `AssetConversion::Pools` (r:3 w:0)
+	/// Storage: `AssetConversion::PriceOracles` (r:3 w:3)
+	/// Storage: `AssetConversion::PriceObservations` (r:0 w:3)
+	/// Storage: `Assets::Asset` (r:4 w:4)
+	/// Storage: `Assets::Account` (r:8 w:8)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn rebalance() -> Weight {
+		Weight::from_parts(220_000_000, 21000)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(20_u64))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:0)
+	/// Storage: `Assets::Account` (r:2n w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// The range of component `n` is `[1, 8]`.
+	fn accrue_fees(n: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6200)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+	/// Storage: `StrategyVaults::Vaults` (r:1 w:1)
+	/// Storage: `VaultShares::Asset` (r:1 w:1)
+	/// Storage: `VaultShares::Account` (r:1 w:1)
+	/// Storage: `AssetConversion::PriceOracles` (r:n w:0)
+	/// Storage: `AssetConversion::PriceObservations` (r:4n w:0)
+	/// Storage: `Assets::Asset` (r:n w:0)
+	/// Storage: `Assets::Account` (r:2n w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// The range of component `n` is `[1, 8]`.
+	fn set_fees(n: u32, ) -> Weight {
+		Weight::from_parts(42_000_000, 6200)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 7800).saturating_mul(n.into()))
+	}
+}
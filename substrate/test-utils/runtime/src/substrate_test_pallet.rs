@@ -217,6 +217,14 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::view_functions]
+	impl<T: Config> Pallet<T> {
+		/// The authority at `index`, if any.
+		pub fn authority(index: u32) -> Option<Public> {
+			Authorities::<T>::get().get(index as usize).cloned()
+		}
+	}
+
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T> {
 		type Call = Call<T>;
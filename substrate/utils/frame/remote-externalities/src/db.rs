@@ -0,0 +1,377 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loading the state from the database of a local node.
+
+use crate::{logging, Builder, RemoteExternalities, Result, Snapshot, SnapshotConfig, LOG_TARGET};
+use codec::Encode;
+use log::*;
+use sc_client_api::Backend as _;
+use sc_client_db::{BlocksPruning, DatabaseSettings};
+use sc_executor_common::runtime_blob::RuntimeBlob;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hexdisplay::HexDisplay,
+	storage::{
+		well_known_keys::{is_default_child_storage_key, CODE, DEFAULT_CHILD_STORAGE_KEY_PREFIX},
+		ChildInfo, ChildType, PrefixedStorageKey,
+	},
+};
+use sp_runtime::{traits::Block as BlockT, StateVersion};
+use sp_state_machine::{Backend as _, IterArgs, TestExternalities};
+
+pub use sc_client_db::DatabaseSource;
+
+/// Configuration of the offline execution from the database of a local node.
+///
+/// The database must already exist, e.g. the data directory of a synced node. It is opened
+/// read-only with the pruning mode that is stored in it, and is never created, migrated or
+/// upgraded.
+#[derive(Clone)]
+pub struct DbConfig<H> {
+	/// The database to read from.
+	pub source: DatabaseSource,
+	/// The block hash at which to get the runtime state. Will be the latest finalized block of the
+	/// database if not provided. The state of this block must not have been pruned.
+	pub at: Option<H>,
+	/// An optional state snapshot file to WRITE to, not for reading. Not written if set to `None`.
+	pub state_snapshot: Option<SnapshotConfig>,
+	/// The pallets to load. These values are hashed and added to `hashed_prefix`.
+	pub pallets: Vec<String>,
+	/// Lookout for child-keys, and load them as well if set to true.
+	pub child_trie: bool,
+	/// Storage entry key prefixes to be injected into the externalities. The *hashed* prefix must
+	/// be given.
+	pub hashed_prefixes: Vec<Vec<u8>>,
+	/// Storage entry keys to be injected into the externalities. The *hashed* key must be given.
+	pub hashed_keys: Vec<Vec<u8>>,
+}
+
+impl<H> DbConfig<H> {
+	/// Create a new config that loads the entire state of the latest finalized block in `source`.
+	pub fn new(source: DatabaseSource) -> Self {
+		Self {
+			source,
+			at: None,
+			state_snapshot: None,
+			pallets: Default::default(),
+			child_trie: true,
+			hashed_prefixes: Default::default(),
+			hashed_keys: Default::default(),
+		}
+	}
+}
+
+impl<H> From<DatabaseSource> for DbConfig<H> {
+	fn from(source: DatabaseSource) -> Self {
+		Self::new(source)
+	}
+}
+
+impl<B: BlockT> Builder<B> {
+	fn do_load_db(&self, config: DbConfig<B::Hash>) -> Result<RemoteExternalities<B>> {
+		if config.source.path().map_or(false, |path| !path.exists()) {
+			error!(target: LOG_TARGET, "no database found at {:?}", config.source.path());
+			return Err("Database does not exist")
+		}
+
+		info!(target: LOG_TARGET, "opening database {:?} read-only", config.source);
+		let backend = sc_client_db::Backend::<B>::new_read_only(DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: None,
+			source: config.source.clone(),
+			blocks_pruning: BlocksPruning::KeepFinalized,
+		})
+		.map_err(|e| {
+			error!(target: LOG_TARGET, "Error = {e:?}");
+			"failed to open database"
+		})?;
+
+		let at = config.at.unwrap_or_else(|| {
+			let at = backend.blockchain().info().finalized_hash;
+			info!(
+				target: LOG_TARGET,
+				"since no at is provided, setting it to latest finalized block, {at:?}",
+			);
+			at
+		});
+		let header = backend
+			.blockchain()
+			.header(at)
+			.map_err(|e| {
+				error!(target: LOG_TARGET, "Error = {e:?}");
+				"failed to read header from database"
+			})?
+			.ok_or("Database returned None block header")?;
+		let state = backend.state_at(at).map_err(|e| {
+			error!(target: LOG_TARGET, "Error = {e:?}");
+			"failed to read state from database, it may have been pruned"
+		})?;
+
+		// An older runtime without an embedded version can still be loaded with an explicit one.
+		let code = state.storage(CODE).map_err(|e| {
+			error!(target: LOG_TARGET, "Error = {e:?}");
+			"failed to read :code from database"
+		})?;
+		let state_version =
+			runtime_state_version(code).or_else(|e| self.overwrite_state_version.ok_or(e))?;
+		let mut pending_ext = TestExternalities::new_with_code_and_state(
+			Default::default(),
+			Default::default(),
+			self.overwrite_state_version.unwrap_or(state_version),
+		);
+
+		let mut hashed_prefixes = config.hashed_prefixes.clone();
+		hashed_prefixes.extend(
+			config
+				.pallets
+				.iter()
+				.map(|p| sp_crypto_hashing::twox_128(p.as_bytes()).to_vec()),
+		);
+		if hashed_prefixes.is_empty() {
+			info!(
+				target: LOG_TARGET,
+				"since no prefix is filtered, the data for all pallets will be loaded"
+			);
+			hashed_prefixes.push(vec![]);
+		}
+		if config.child_trie && !hashed_prefixes.iter().any(|p| p.is_empty()) {
+			hashed_prefixes.push(DEFAULT_CHILD_STORAGE_KEY_PREFIX.to_vec());
+		}
+
+		let (top_kv, child_kv) = logging::with_elapsed(
+			|| {
+				let mut top_kv = Vec::new();
+				for prefix in &hashed_prefixes {
+					let mut args = IterArgs::default();
+					args.prefix = Some(prefix.as_slice());
+					let pairs = state
+						.pairs(args)
+						.map_err(|e| {
+							error!(target: LOG_TARGET, "Error = {e:?}");
+							"failed to iterate database state"
+						})?
+						.collect::<std::result::Result<Vec<_>, _>>()
+						.map_err(|e| {
+							error!(target: LOG_TARGET, "Error = {e:?}");
+							"failed to read database state"
+						})?;
+					info!(
+						target: LOG_TARGET,
+						"adding {} key-pairs for hashed prefix: {:?}",
+						pairs.len(),
+						HexDisplay::from(prefix)
+					);
+					top_kv.extend(pairs);
+				}
+
+				for key in &config.hashed_keys {
+					match state.storage(key).map_err(|e| {
+						error!(target: LOG_TARGET, "Error = {e:?}");
+						"failed to read database state"
+					})? {
+						Some(value) => top_kv.push((key.clone(), value)),
+						None => warn!(
+							target: LOG_TARGET,
+							"no data found for hashed key: {:?}",
+							HexDisplay::from(key)
+						),
+					}
+				}
+
+				// The child roots are part of the top trie, but their data is inserted separately.
+				let mut child_kv = Vec::new();
+				for (prefixed_top_key, _) in top_kv
+					.iter()
+					.filter(|(k, _)| config.child_trie && is_default_child_storage_key(k))
+				{
+					let prefixed_top_key = PrefixedStorageKey::new(prefixed_top_key.clone());
+					let info = match ChildType::from_prefixed_key(&prefixed_top_key) {
+						Some((ChildType::ParentKeyId, storage_key)) =>
+							ChildInfo::new_default(storage_key),
+						None => {
+							error!(target: LOG_TARGET, "invalid key: {prefixed_top_key:?}");
+							return Err("Invalid child key")
+						},
+					};
+					let mut args = IterArgs::default();
+					args.child_info = Some(info.clone());
+					let pairs = state
+						.pairs(args)
+						.map_err(|e| {
+							error!(target: LOG_TARGET, "Error = {e:?}");
+							"failed to iterate database child state"
+						})?
+						.collect::<std::result::Result<Vec<_>, _>>()
+						.map_err(|e| {
+							error!(target: LOG_TARGET, "Error = {e:?}");
+							"failed to read database child state"
+						})?;
+					child_kv.push((info, pairs));
+				}
+
+				Ok((top_kv, child_kv))
+			},
+			"Loading state from database...",
+			|_| "Loaded state from database".into(),
+		)?;
+
+		pending_ext
+			.batch_insert(top_kv.into_iter().filter(|(k, _)| !is_default_child_storage_key(k)));
+		for (info, pairs) in child_kv {
+			for (k, v) in pairs {
+				pending_ext.insert_child(info.clone(), k, v);
+			}
+		}
+
+		// If we need to save a snapshot, save the raw storage and root hash to the snapshot.
+		let inner_ext = if let Some(path) = config.state_snapshot.map(|c| c.path) {
+			let (raw_storage, storage_root) = pending_ext.into_raw_snapshot();
+			let snapshot = Snapshot::<B>::new(
+				state_version,
+				raw_storage.clone(),
+				storage_root,
+				header.clone(),
+			);
+			let encoded = snapshot.encode();
+			info!(
+				target: LOG_TARGET,
+				"writing snapshot of {} bytes to {path:?}",
+				encoded.len(),
+			);
+			std::fs::write(path, encoded).map_err(|_| "fs::write failed")?;
+
+			// pending_ext was consumed when creating the snapshot, need to reinitailize it
+			TestExternalities::from_raw_snapshot(
+				raw_storage,
+				storage_root,
+				self.overwrite_state_version.unwrap_or(state_version),
+			)
+		} else {
+			pending_ext
+		};
+
+		Ok(RemoteExternalities { inner_ext, header })
+	}
+}
+
+/// Read the state version from the version embedded in the runtime `code`.
+fn runtime_state_version(code: Option<Vec<u8>>) -> Result<StateVersion> {
+	let code = code.ok_or("Database state has no :code")?;
+	let blob = RuntimeBlob::uncompress_if_needed(&code).map_err(|e| {
+		error!(target: LOG_TARGET, "Error = {e:?}");
+		"failed to decompress :code"
+	})?;
+	let version = sc_executor::read_embedded_version(&blob)
+		.map_err(|e| {
+			error!(target: LOG_TARGET, "Error = {e:?}");
+			"failed to read the runtime version from :code"
+		})?
+		.ok_or("runtime in :code has no embedded version")?;
+	Ok(version.state_version())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		test_prelude::{init_logger, Block},
+		Mode,
+	};
+	use std::sync::Arc;
+
+	#[tokio::test]
+	async fn can_load_from_database() {
+		use sc_client_db::PruningMode;
+		use sp_runtime::traits::Header as _;
+		use substrate_test_runtime_client::{
+			runtime::Block as TestBlock, TestClientBuilder, TestClientBuilderExt,
+		};
+
+		init_logger();
+		let tmp = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::ParityDb { path: tmp.path().into() };
+
+		// write the genesis state of the test runtime into a fresh database.
+		let genesis_hash = {
+			let backend = Arc::new(
+				sc_client_db::Backend::<TestBlock>::new(
+					DatabaseSettings {
+						trie_cache_maximum_size: None,
+						state_pruning: Some(PruningMode::ArchiveAll),
+						source: source.clone(),
+						blocks_pruning: BlocksPruning::KeepAll,
+					},
+					u64::MAX,
+				)
+				.unwrap(),
+			);
+			let client = TestClientBuilder::with_backend(backend).build();
+			client.chain_info().genesis_hash
+		};
+
+		// the entire state is loaded by default.
+		let mut ext = Builder::<TestBlock>::new()
+			.mode(Mode::OfflineDb(DbConfig::new(source.clone())))
+			.build()
+			.await
+			.unwrap();
+		assert_eq!(ext.header.hash(), genesis_hash);
+		assert_eq!(ext.state_version, StateVersion::V1);
+		ext.execute_with(|| assert!(sp_io::storage::get(b":code").is_some()));
+
+		// the state version read from the runtime can be overwritten.
+		let ext = Builder::<TestBlock>::new()
+			.mode(Mode::OfflineDb(DbConfig::new(source.clone())))
+			.overwrite_state_version(StateVersion::V0)
+			.build()
+			.await
+			.unwrap();
+		assert_eq!(ext.state_version, StateVersion::V0);
+
+		// only the given pallets are loaded.
+		Builder::<TestBlock>::new()
+			.mode(Mode::OfflineDb(DbConfig {
+				at: Some(genesis_hash),
+				pallets: vec!["System".to_owned()],
+				..DbConfig::new(source)
+			}))
+			.build()
+			.await
+			.unwrap()
+			.execute_with(|| {
+				let prefix = sp_crypto_hashing::twox_128(b"System");
+				let key = sp_io::storage::next_key(&[]).expect("some key must be loaded");
+				assert!(key.starts_with(&prefix));
+				assert!(sp_io::storage::get(b":code").is_none());
+			});
+	}
+
+	#[tokio::test]
+	async fn loading_from_missing_database_fails() {
+		init_logger();
+		let tmp = tempfile::tempdir().unwrap();
+
+		let result = Builder::<Block>::new()
+			.mode(Mode::OfflineDb(DbConfig::new(DatabaseSource::ParityDb {
+				path: tmp.path().join("missing"),
+			})))
+			.build()
+			.await;
+		assert!(matches!(result, Err("Database does not exist")));
+	}
+}
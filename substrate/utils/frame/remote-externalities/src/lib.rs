@@ -18,8 +18,10 @@
 //! # Remote Externalities
 //!
 //! An equivalent of `sp_io::TestExternalities` that can load its state from a remote substrate
-//! based chain, or a local state snapshot file.
+//! based chain, a local state snapshot file, or the database of a local node.
 
+#[cfg(feature = "db")]
+mod db;
 mod logging;
 
 use codec::{Compact, Decode, Encode};
@@ -50,6 +52,9 @@ use std::{
 use substrate_rpc_client::{rpc_params, BatchRequestBuilder, ChainApi, ClientT, StateApi};
 use tokio_retry::{strategy::FixedInterval, Retry};
 
+#[cfg(feature = "db")]
+pub use db::{DatabaseSource, DbConfig};
+
 type Result<T, E = &'static str> = std::result::Result<T, E>;
 
 type KeyValue = (StorageKey, StorageData);
@@ -136,6 +141,10 @@ pub enum Mode<H> {
 	Offline(OfflineConfig),
 	/// Prefer using a snapshot file if it exists, else use a remote server.
 	OfflineOrElseOnline(OfflineConfig, OnlineConfig<H>),
+	/// Offline. Reads the state directly from the database of a local node. Potentially writes to
+	/// a snapshot file.
+	#[cfg(feature = "db")]
+	OfflineDb(DbConfig<H>),
 }
 
 impl<H> Default for Mode<H> {
@@ -1151,6 +1160,8 @@ where
 	pub(crate) async fn pre_build(mut self) -> Result<RemoteExternalities<B>> {
 		let mut ext = match self.mode.clone() {
 			Mode::Offline(config) => self.do_load_offline(config)?,
+			#[cfg(feature = "db")]
+			Mode::OfflineDb(config) => self.do_load_db(config)?,
 			Mode::Online(_) => self.do_load_remote().await?,
 			Mode::OfflineOrElseOnline(offline_config, _) => {
 				match self.do_load_offline(offline_config) {
@@ -0,0 +1,87 @@
+//! Benchmarking setup for pallet-axen-strategy
+
+use super::*;
+use frame::{deps::frame_benchmarking::v2::*, prelude::*};
+
+/// Register `n` traders with a deposit each, returning the last one.
+fn setup_traders<T: Config>(n: u32) -> T::AccountId {
+	let origin = T::RegisterOrigin::try_successful_origin().expect("origin must be available");
+	let report = T::ReportOrigin::try_successful_origin().expect("origin must be available");
+	let mut trader = account("trader", 0, 0);
+	for i in 0..n {
+		trader = account("trader", i, 0);
+		assert!(Pallet::<T>::register_trader(origin.clone(), trader.clone()).is_ok());
+		let amount = T::Balance::from(1_000u32 + i);
+		let deposit = Activity::Deposit { amount };
+		assert!(Pallet::<T>::record_activity(report.clone(), trader.clone(), deposit).is_ok());
+	}
+	trader
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+	#[cfg(test)]
+	use crate::pallet::Pallet as AxenStrategy;
+
+	#[benchmark]
+	fn register_trader() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let trader: T::AccountId = account("trader", 0, 0);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, trader.clone());
+
+		assert!(Traders::<T>::contains_key(&trader));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_trader() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegisterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let trader = setup_traders::<T>(1);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, trader.clone());
+
+		assert!(!Traders::<T>::contains_key(&trader));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn record_activity() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ReportOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let trader = setup_traders::<T>(1);
+		// Fill the swap history so that the oldest return gets evicted.
+		for _ in 0..T::MaxReturnSamples::get() {
+			let swap = Activity::Swap { amount_in: 10u32.into(), amount_out: 11u32.into() };
+			Pallet::<T>::record_activity(origin.clone(), trader.clone(), swap)?;
+		}
+		let swap = Activity::Swap { amount_in: 100u32.into(), amount_out: 200u32.into() };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, trader.clone(), swap);
+
+		assert_eq!(TopTraders::<T>::get().first(), Some(&trader));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_ranking(n: Linear<1, { T::MaxTrackedTraders::get() }>) {
+		setup_traders::<T>(n);
+		// Make sure the ranking changes, so that it is written back.
+		TopTraders::<T>::kill();
+
+		#[block]
+		{
+			Pallet::<T>::update_ranking();
+		}
+
+		assert_eq!(TopTraders::<T>::get().len() as u32, n.min(T::MaxTopTraders::get()));
+	}
+
+	impl_benchmark_test_suite!(AxenStrategy, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,402 @@
+//! # Axen Strategy Pallet
+//!
+//! Tracks a set of trader accounts, records their activity and ranks them by return on investment
+//! and volatility, so that the Axen vault can mirror the best performing strategies.
+//!
+//! ## Overview
+//!
+//! The pallet is made of the components described in the Axen architecture:
+//!
+//! - **Top traders storage**: the set of tracked traders lives in [`Traders`], and the current
+//!   ranking of the best performing ones in [`TopTraders`]. Traders are added and removed through
+//!   [`Pallet::register_trader`] and [`Pallet::remove_trader`], gated by
+//!   [`Config::RegisterOrigin`].
+//! - **Activity recording**: [`Pallet::record_activity`] reports a deposit, a withdrawal or a swap
+//!   performed by a tracked trader. It is gated by [`Config::ReportOrigin`], which is expected to
+//!   be the account of the oracle observing the traders on their chains. All amounts are expressed
+//!   in a single reference unit (e.g. their USDT value at the time of the activity).
+//! - **Strategy analysis**: after every recorded activity the ROI and volatility of the trader are
+//!   recomputed and all tracked traders are re-ranked. [`Event::TopTradersChanged`] is emitted
+//!   whenever the ranking changes, so that off-chain mirroring logic can react to it.
+//!
+//! ### Metrics
+//!
+//! - **ROI** is `(value + withdrawn - deposited) / deposited`, where `value` is the current
+//!   position of the trader after applying all the recorded activity.
+//! - **Volatility** is the mean absolute deviation of the returns of the last
+//!   [`Config::MaxReturnSamples`] swaps of the trader. The return of a swap is `(amount_out -
+//!   amount_in) / amount_in`.
+//!
+//! Traders are ranked by ROI, highest first. Traders with an equal ROI are ordered by volatility,
+//! lowest first. Traders that never deposited anything are not ranked.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[frame::pallet]
+pub mod pallet {
+	use alloc::vec::Vec;
+	use frame::prelude::*;
+
+	use crate::weights::WeightInfo;
+
+	/// A single activity of a tracked trader.
+	#[derive(
+		Encode,
+		Decode,
+		DecodeWithMemTracking,
+		Clone,
+		Copy,
+		PartialEq,
+		Eq,
+		RuntimeDebug,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	pub enum Activity<Balance> {
+		/// The trader added `amount` to their position.
+		Deposit { amount: Balance },
+		/// The trader took `amount` out of their position.
+		Withdraw { amount: Balance },
+		/// The trader swapped assets worth `amount_in` for assets worth `amount_out`.
+		Swap { amount_in: Balance, amount_out: Balance },
+	}
+
+	/// Performance statistics of a tracked trader.
+	#[derive(
+		Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct TraderStats<Balance, BlockNumber> {
+		/// Total amount deposited by the trader.
+		pub deposited: Balance,
+		/// Total amount withdrawn by the trader.
+		pub withdrawn: Balance,
+		/// Current value of the trader's position.
+		pub value: Balance,
+		/// Total amount swapped by the trader, measured on the input side.
+		pub swap_volume: Balance,
+		/// Number of swaps performed by the trader.
+		pub swaps: u32,
+		/// Return on investment of the trader.
+		pub roi: FixedI64,
+		/// Mean absolute deviation of the trader's recent swap returns.
+		pub volatility: FixedI64,
+		/// Block at which the last activity was recorded.
+		pub last_activity: BlockNumber,
+	}
+
+	/// Statistics of a tracked trader for a given runtime.
+	pub type TraderStatsOf<T> = TraderStats<<T as Config>::Balance, BlockNumberFor<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The unit in which the value of trader activity is expressed.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ FixedPointOperand
+			+ Default
+			+ Copy
+			+ MaxEncodedLen;
+
+		/// Origin allowed to register and remove tracked traders.
+		type RegisterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to report the activity of tracked traders.
+		type ReportOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of traders that can be tracked at the same time.
+		#[pallet::constant]
+		type MaxTrackedTraders: Get<u32>;
+
+		/// Maximum number of traders kept in [`TopTraders`].
+		#[pallet::constant]
+		type MaxTopTraders: Get<u32>;
+
+		/// Number of recent swap returns used to compute the volatility of a trader.
+		#[pallet::constant]
+		type MaxReturnSamples: Get<u32>;
+
+		/// A type representing the weights required by the dispatchables of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Statistics of every tracked trader.
+	#[pallet::storage]
+	pub type Traders<T: Config> =
+		CountedStorageMap<_, Blake2_128Concat, T::AccountId, TraderStatsOf<T>>;
+
+	/// Returns of the most recent swaps of every tracked trader, oldest first.
+	#[pallet::storage]
+	pub type SwapReturns<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<FixedI64, T::MaxReturnSamples>,
+		ValueQuery,
+	>;
+
+	/// The best performing tracked traders, best first.
+	#[pallet::storage]
+	pub type TopTraders<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxTopTraders>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A trader is now tracked.
+		TraderRegistered { trader: T::AccountId },
+		/// A trader is no longer tracked.
+		TraderRemoved { trader: T::AccountId },
+		/// The activity of a tracked trader was recorded.
+		ActivityRecorded { trader: T::AccountId, activity: Activity<T::Balance> },
+		/// The metrics of a tracked trader were updated.
+		StrategyUpdated { trader: T::AccountId, roi: FixedI64, volatility: FixedI64 },
+		/// The ranking of the best performing traders changed.
+		TopTradersChanged { top_traders: BoundedVec<T::AccountId, T::MaxTopTraders> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The trader is already tracked.
+		AlreadyTracked,
+		/// The trader is not tracked.
+		NotTracked,
+		/// The maximum number of tracked traders has been reached.
+		TooManyTraders,
+		/// The activity has a zero amount.
+		ZeroAmount,
+		/// The activity spends more than the current value of the trader's position.
+		ExceedsPosition,
+		/// An arithmetic operation overflowed.
+		Overflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Start tracking `trader`.
+		///
+		/// The origin must be [`Config::RegisterOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_trader())]
+		pub fn register_trader(origin: OriginFor<T>, trader: T::AccountId) -> DispatchResult {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(!Traders::<T>::contains_key(&trader), Error::<T>::AlreadyTracked);
+			ensure!(
+				Traders::<T>::count() < T::MaxTrackedTraders::get(),
+				Error::<T>::TooManyTraders
+			);
+
+			let stats = TraderStats {
+				last_activity: frame_system::Pallet::<T>::block_number(),
+				..Default::default()
+			};
+			Traders::<T>::insert(&trader, stats);
+
+			Self::deposit_event(Event::TraderRegistered { trader });
+			Ok(())
+		}
+
+		/// Stop tracking `trader`, dropping all of their statistics.
+		///
+		/// The origin must be [`Config::RegisterOrigin`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(
+			T::WeightInfo::remove_trader()
+				.saturating_add(T::WeightInfo::update_ranking(T::MaxTrackedTraders::get()))
+		)]
+		pub fn remove_trader(
+			origin: OriginFor<T>,
+			trader: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			ensure!(Traders::<T>::contains_key(&trader), Error::<T>::NotTracked);
+			Traders::<T>::remove(&trader);
+			SwapReturns::<T>::remove(&trader);
+
+			Self::deposit_event(Event::TraderRemoved { trader });
+
+			let ranked = Self::update_ranking();
+			Ok(Some(
+				T::WeightInfo::remove_trader()
+					.saturating_add(T::WeightInfo::update_ranking(ranked)),
+			)
+			.into())
+		}
+
+		/// Record an `activity` performed by the tracked `trader`, then update their metrics and
+		/// the ranking of all tracked traders.
+		///
+		/// The origin must be [`Config::ReportOrigin`].
+		#[pallet::call_index(2)]
+		#[pallet::weight(
+			T::WeightInfo::record_activity()
+				.saturating_add(T::WeightInfo::update_ranking(T::MaxTrackedTraders::get()))
+		)]
+		pub fn record_activity(
+			origin: OriginFor<T>,
+			trader: T::AccountId,
+			activity: Activity<T::Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::ReportOrigin::ensure_origin(origin)?;
+
+			let mut stats = Traders::<T>::get(&trader).ok_or(Error::<T>::NotTracked)?;
+			Self::apply_activity(&trader, &mut stats, &activity)?;
+			stats.last_activity = frame_system::Pallet::<T>::block_number();
+			Traders::<T>::insert(&trader, &stats);
+
+			Self::deposit_event(Event::ActivityRecorded { trader: trader.clone(), activity });
+			Self::deposit_event(Event::StrategyUpdated {
+				trader,
+				roi: stats.roi,
+				volatility: stats.volatility,
+			});
+
+			let ranked = Self::update_ranking();
+			Ok(Some(
+				T::WeightInfo::record_activity()
+					.saturating_add(T::WeightInfo::update_ranking(ranked)),
+			)
+			.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The tracked traders ordered by ROI, highest first, and then by volatility, lowest
+		/// first, along with their ROI and volatility.
+		///
+		/// Traders that never deposited anything are left out.
+		pub fn ranked_traders() -> Vec<(T::AccountId, FixedI64, FixedI64)> {
+			let mut ranked: Vec<_> = Traders::<T>::iter()
+				.filter(|(_, stats)| !stats.deposited.is_zero())
+				.map(|(trader, stats)| (trader, stats.roi, stats.volatility))
+				.collect();
+			ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.2.cmp(&b.2)));
+			ranked
+		}
+
+		/// Recompute [`TopTraders`], emitting [`Event::TopTradersChanged`] if it changed.
+		///
+		/// This reads and sorts every tracked trader, so its cost is linear in their number and is
+		/// accounted for separately through [`WeightInfo::update_ranking`].
+		///
+		/// Returns the number of tracked traders that were read.
+		pub(crate) fn update_ranking() -> u32 {
+			let ranked = Self::ranked_traders();
+			let top_traders = BoundedVec::<_, T::MaxTopTraders>::truncate_from(
+				ranked.into_iter().map(|(trader, ..)| trader).collect(),
+			);
+
+			if top_traders != TopTraders::<T>::get() {
+				TopTraders::<T>::put(&top_traders);
+				Self::deposit_event(Event::TopTradersChanged { top_traders });
+			}
+			Traders::<T>::count()
+		}
+
+		/// Apply `activity` to the statistics of `trader` and recompute their metrics.
+		fn apply_activity(
+			trader: &T::AccountId,
+			stats: &mut TraderStatsOf<T>,
+			activity: &Activity<T::Balance>,
+		) -> DispatchResult {
+			match *activity {
+				Activity::Deposit { amount } => {
+					ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+					stats.deposited =
+						stats.deposited.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+					stats.value = stats.value.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+				},
+				Activity::Withdraw { amount } => {
+					ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+					stats.value =
+						stats.value.checked_sub(&amount).ok_or(Error::<T>::ExceedsPosition)?;
+					stats.withdrawn =
+						stats.withdrawn.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+				},
+				Activity::Swap { amount_in, amount_out } => {
+					ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+					stats.value = stats
+						.value
+						.checked_sub(&amount_in)
+						.ok_or(Error::<T>::ExceedsPosition)?
+						.checked_add(&amount_out)
+						.ok_or(Error::<T>::Overflow)?;
+					stats.swap_volume = stats.swap_volume.saturating_add(amount_in);
+					stats.swaps.saturating_inc();
+
+					let sample = Self::signed_ratio(amount_out, amount_in, amount_in);
+					let mut returns = SwapReturns::<T>::get(trader);
+					if returns.is_full() {
+						returns.remove(0);
+					}
+					// Cannot fail, there is room for at least one element.
+					let _ = returns.try_push(sample);
+					stats.volatility = Self::mean_absolute_deviation(&returns);
+					SwapReturns::<T>::insert(trader, returns);
+				},
+			}
+
+			stats.roi = Self::signed_ratio(
+				stats.value.saturating_add(stats.withdrawn),
+				stats.deposited,
+				stats.deposited,
+			);
+			Ok(())
+		}
+
+		/// `(plus - minus) / base`, or zero if `base` is zero.
+		fn signed_ratio(plus: T::Balance, minus: T::Balance, base: T::Balance) -> FixedI64 {
+			if base.is_zero() {
+				return FixedI64::zero()
+			}
+			if plus >= minus {
+				FixedI64::saturating_from_rational(plus - minus, base)
+			} else {
+				-FixedI64::saturating_from_rational(minus - plus, base)
+			}
+		}
+
+		/// The mean absolute deviation of `samples` from their mean.
+		fn mean_absolute_deviation(samples: &[FixedI64]) -> FixedI64 {
+			if samples.is_empty() {
+				return FixedI64::zero()
+			}
+			let count = FixedI64::saturating_from_integer(samples.len() as u32);
+			let mean = samples
+				.iter()
+				.fold(FixedI64::zero(), |acc, sample| acc.saturating_add(*sample))
+				.checked_div(&count)
+				.unwrap_or_default();
+			samples
+				.iter()
+				.fold(FixedI64::zero(), |acc, sample| {
+					acc.saturating_add(sample.saturating_sub(mean).saturating_abs())
+				})
+				.checked_div(&count)
+				.unwrap_or_default()
+		}
+	}
+}
@@ -0,0 +1,196 @@
+use crate::{mock::*, Activity, Error, Event, SwapReturns, TopTraders, Traders};
+use frame::testing_prelude::*;
+
+fn register(trader: u64) {
+	assert_ok!(AxenStrategy::register_trader(RuntimeOrigin::root(), trader));
+}
+
+fn record(trader: u64, activity: Activity<u128>) {
+	assert_ok!(AxenStrategy::record_activity(RuntimeOrigin::root(), trader, activity));
+}
+
+fn deposit(amount: u128) -> Activity<u128> {
+	Activity::Deposit { amount }
+}
+
+fn swap(amount_in: u128, amount_out: u128) -> Activity<u128> {
+	Activity::Swap { amount_in, amount_out }
+}
+
+#[test]
+fn register_trader_works() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		assert!(Traders::<Test>::contains_key(1));
+		System::assert_last_event(Event::TraderRegistered { trader: 1 }.into());
+
+		assert_noop!(
+			AxenStrategy::register_trader(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyTracked
+		);
+		assert_noop!(AxenStrategy::register_trader(RuntimeOrigin::signed(1), 2), BadOrigin);
+	});
+}
+
+#[test]
+fn register_trader_respects_max_tracked_traders() {
+	new_test_ext().execute_with(|| {
+		(1..=4).for_each(register);
+		assert_noop!(
+			AxenStrategy::register_trader(RuntimeOrigin::root(), 5),
+			Error::<Test>::TooManyTraders
+		);
+	});
+}
+
+#[test]
+fn remove_trader_works() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		record(1, deposit(100));
+		record(1, swap(50, 60));
+		assert_eq!(TopTraders::<Test>::get().into_inner(), vec![1]);
+
+		assert_ok!(AxenStrategy::remove_trader(RuntimeOrigin::root(), 1));
+		assert!(!Traders::<Test>::contains_key(1));
+		assert!(SwapReturns::<Test>::get(1).is_empty());
+		assert!(TopTraders::<Test>::get().is_empty());
+		System::assert_last_event(
+			Event::TopTradersChanged { top_traders: Default::default() }.into(),
+		);
+
+		assert_noop!(
+			AxenStrategy::remove_trader(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotTracked
+		);
+	});
+}
+
+#[test]
+fn record_activity_tracks_position_and_roi() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		record(1, deposit(100));
+		record(1, swap(100, 150));
+		record(1, Activity::Withdraw { amount: 50 });
+
+		let stats = Traders::<Test>::get(1).unwrap();
+		assert_eq!(stats.deposited, 100);
+		assert_eq!(stats.withdrawn, 50);
+		assert_eq!(stats.value, 100);
+		assert_eq!(stats.swap_volume, 100);
+		assert_eq!(stats.swaps, 1);
+		assert_eq!(stats.roi, FixedI64::from_rational(1, 2));
+		System::assert_has_event(
+			Event::StrategyUpdated {
+				trader: 1,
+				roi: FixedI64::from_rational(1, 2),
+				volatility: FixedI64::zero(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn record_activity_computes_negative_roi() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		record(1, deposit(200));
+		record(1, swap(100, 50));
+
+		assert_eq!(Traders::<Test>::get(1).unwrap().roi, -FixedI64::from_rational(1, 4));
+	});
+}
+
+#[test]
+fn record_activity_rejects_invalid_activity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AxenStrategy::record_activity(RuntimeOrigin::root(), 1, deposit(10)),
+			Error::<Test>::NotTracked
+		);
+
+		register(1);
+		assert_noop!(
+			AxenStrategy::record_activity(RuntimeOrigin::signed(1), 1, deposit(10)),
+			BadOrigin
+		);
+		assert_noop!(
+			AxenStrategy::record_activity(RuntimeOrigin::root(), 1, deposit(0)),
+			Error::<Test>::ZeroAmount
+		);
+
+		record(1, deposit(10));
+		assert_noop!(
+			AxenStrategy::record_activity(RuntimeOrigin::root(), 1, swap(11, 20)),
+			Error::<Test>::ExceedsPosition
+		);
+		assert_noop!(
+			AxenStrategy::record_activity(
+				RuntimeOrigin::root(),
+				1,
+				Activity::Withdraw { amount: 11 }
+			),
+			Error::<Test>::ExceedsPosition
+		);
+	});
+}
+
+#[test]
+fn volatility_uses_recent_swap_returns() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		record(1, deposit(1_000));
+
+		// Returns of +10% and -10% deviate by 10% from their mean.
+		record(1, swap(100, 110));
+		record(1, swap(100, 90));
+		assert_eq!(Traders::<Test>::get(1).unwrap().volatility, FixedI64::from_rational(1, 10));
+
+		// Only the last `MaxReturnSamples` returns are kept.
+		record(1, swap(100, 90));
+		record(1, swap(100, 90));
+		assert_eq!(SwapReturns::<Test>::get(1).len(), 3);
+		assert_eq!(Traders::<Test>::get(1).unwrap().volatility, FixedI64::zero());
+	});
+}
+
+#[test]
+fn traders_are_ranked_by_roi_then_volatility() {
+	new_test_ext().execute_with(|| {
+		(1..=4).for_each(register);
+		(1..=4).for_each(|trader| record(trader, deposit(1_000)));
+
+		// Trader 1 earns 10% in a single swap.
+		record(1, swap(100, 200));
+		// Trader 2 earns the same through two swaps with different returns.
+		record(2, swap(100, 180));
+		record(2, swap(100, 120));
+		// Trader 3 loses money.
+		record(3, swap(100, 50));
+
+		assert_eq!(
+			AxenStrategy::ranked_traders()
+				.into_iter()
+				.map(|(who, ..)| who)
+				.collect::<Vec<_>>(),
+			vec![1, 2, 4, 3]
+		);
+		assert_eq!(TopTraders::<Test>::get().into_inner(), vec![1, 2]);
+		System::assert_has_event(
+			Event::TopTradersChanged { top_traders: vec![1, 2].try_into().unwrap() }.into(),
+		);
+	});
+}
+
+#[test]
+fn traders_without_deposits_are_not_ranked() {
+	new_test_ext().execute_with(|| {
+		register(1);
+		register(2);
+		record(2, deposit(10));
+
+		assert_eq!(TopTraders::<Test>::get().into_inner(), vec![2]);
+	});
+}
@@ -0,0 +1,141 @@
+//! Placeholder weights for pallet_axen_strategy.
+//!
+//! THESE WEIGHTS WERE NOT GENERATED BY BENCHMARKING. They are rough estimates derived from the
+//! storage accessed by each dispatchable, so that the template can be used out of the box. Any
+//! chain using this pallet must regenerate them on its reference hardware with the command below.
+
+// Command to regenerate:
+// ../../target/release/parachain-template-node
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_axen_strategy
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --output
+// pallets/axen-strategy/src/weights.rs
+// --template
+// ../../.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_axen_strategy.
+pub trait WeightInfo {
+	fn register_trader() -> Weight;
+	fn remove_trader() -> Weight;
+	fn record_activity() -> Weight;
+	fn update_ranking(n: u32, ) -> Weight;
+}
+
+/// Placeholder weights for pallet_axen_strategy.
+#[cfg_attr(
+    not(feature = "std"),
+    deprecated(
+        note = "SubstrateWeight is a placeholder and should not be used in production. Replace it with runtime benchmarked weights."
+    )
+)]
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:1)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn register_trader() -> Weight {
+		Weight::from_parts(13_000_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:1)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy SwapReturns (r:0 w:1)
+	/// Proof: AxenStrategy SwapReturns (max_values: None, max_size: Some(130), added: 2605, mode: MaxEncodedLen)
+	fn remove_trader() -> Weight {
+		Weight::from_parts(15_000_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy SwapReturns (r:1 w:1)
+	/// Proof: AxenStrategy SwapReturns (max_values: None, max_size: Some(130), added: 2605, mode: MaxEncodedLen)
+	fn record_activity() -> Weight {
+		Weight::from_parts(18_000_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:100 w:0)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:0)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy TopTraders (r:1 w:1)
+	/// Proof: AxenStrategy TopTraders (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn update_ranking(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1806)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 2620).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:1)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn register_trader() -> Weight {
+		Weight::from_parts(13_000_000, 3610)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:1)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy SwapReturns (r:0 w:1)
+	/// Proof: AxenStrategy SwapReturns (max_values: None, max_size: Some(130), added: 2605, mode: MaxEncodedLen)
+	fn remove_trader() -> Weight {
+		Weight::from_parts(15_000_000, 3610)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:1 w:1)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy SwapReturns (r:1 w:1)
+	/// Proof: AxenStrategy SwapReturns (max_values: None, max_size: Some(130), added: 2605, mode: MaxEncodedLen)
+	fn record_activity() -> Weight {
+		Weight::from_parts(18_000_000, 3610)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: AxenStrategy Traders (r:100 w:0)
+	/// Proof: AxenStrategy Traders (max_values: None, max_size: Some(145), added: 2620, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy CounterForTraders (r:1 w:0)
+	/// Proof: AxenStrategy CounterForTraders (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: AxenStrategy TopTraders (r:1 w:1)
+	/// Proof: AxenStrategy TopTraders (max_values: Some(1), max_size: Some(321), added: 816, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 100]`.
+	fn update_ranking(n: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 1806)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(Weight::from_parts(0, 2620).saturating_mul(n.into()))
+	}
+}
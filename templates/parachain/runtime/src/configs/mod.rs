@@ -321,3 +321,15 @@ impl pallet_parachain_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_parachain_template::weights::SubstrateWeight<Runtime>;
 }
+
+/// Configure the Axen strategy pallet in pallets/axen-strategy.
+impl pallet_axen_strategy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type RegisterOrigin = EnsureRoot<AccountId>;
+	type ReportOrigin = EnsureRoot<AccountId>;
+	type MaxTrackedTraders = ConstU32<100>;
+	type MaxTopTraders = ConstU32<10>;
+	type MaxReturnSamples = ConstU32<16>;
+	type WeightInfo = pallet_axen_strategy::weights::SubstrateWeight<Runtime>;
+}
@@ -313,6 +313,8 @@ mod runtime {
 	// Template
 	#[runtime::pallet_index(50)]
 	pub type TemplatePallet = pallet_parachain_template;
+	#[runtime::pallet_index(51)]
+	pub type AxenStrategy = pallet_axen_strategy;
 }
 
 #[docify::export(register_validate_block)]
@@ -296,10 +296,18 @@ pub use pallet_alliance;
 #[cfg(feature = "pallet-asset-conversion")]
 pub use pallet_asset_conversion;
 
+/// FRAME asset conversion pallet's copy trading of leaders' swaps by their followers.
+#[cfg(feature = "pallet-asset-conversion-copy-trading")]
+pub use pallet_asset_conversion_copy_trading;
+
 /// FRAME asset conversion pallet's operations suite.
 #[cfg(feature = "pallet-asset-conversion-ops")]
 pub use pallet_asset_conversion_ops;
 
+/// FRAME asset conversion pallet's limit and recurring orders.
+#[cfg(feature = "pallet-asset-conversion-orders")]
+pub use pallet_asset_conversion_orders;
+
 /// Pallet to manage transaction payments in assets by converting them to native assets.
 #[cfg(feature = "pallet-asset-conversion-tx-payment")]
 pub use pallet_asset_conversion_tx_payment;
@@ -673,6 +681,10 @@ pub use pallet_state_trie_migration;
 #[cfg(feature = "pallet-statement")]
 pub use pallet_statement;
 
+/// FRAME pallet for share-based strategy vaults valued through asset conversion.
+#[cfg(feature = "pallet-strategy-vaults")]
+pub use pallet_strategy_vaults;
+
 /// FRAME pallet for sudo.
 #[cfg(feature = "pallet-sudo")]
 pub use pallet_sudo;